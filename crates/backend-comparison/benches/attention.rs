@@ -1,10 +1,13 @@
 use std::fmt::Display;
 
-use burn::tensor::{
-    Bool, Distribution, Element, Shape, Tensor,
-    backend::Backend,
-    module::{attention, attention_fallback},
-    ops::AttentionModuleOptions,
+use burn::{
+    backend::Autodiff,
+    tensor::{
+        Bool, Distribution, Element, Shape, Tensor,
+        backend::{AutodiffBackend, Backend},
+        module::{attention, attention_fallback},
+        ops::AttentionModuleOptions,
+    },
 };
 use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
 
@@ -42,6 +45,22 @@ impl AttentionProblem {
     fn mask_shape(&self) -> Shape {
         Shape::new([self.batch_size, self.num_heads, self.seq_q, self.seq_kv])
     }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            self.query_shape().to_vec(),
+            self.key_shape().to_vec(),
+            self.value_shape().to_vec(),
+        ]
+    }
+
+    fn causal_suffix(&self) -> &'static str {
+        if self.options.is_causal {
+            "-causal"
+        } else {
+            ""
+        }
+    }
 }
 
 enum AttentionKind {
@@ -88,7 +107,17 @@ impl<B: Backend> Benchmark for AttentionBenchmark<B> {
     type Output = Tensor<B, 4>;
 
     fn name(&self) -> String {
-        format!("attention_{}-{:?}", self.kind, B::FloatElem::dtype()).to_lowercase()
+        format!(
+            "attention_{}{}-{:?}",
+            self.kind,
+            self.problem.causal_suffix(),
+            B::FloatElem::dtype()
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.problem.shapes()
     }
 
     fn execute(&self, input: Self::Input) -> Self::Output {
@@ -121,38 +150,92 @@ impl<B: Backend> Benchmark for AttentionBenchmark<B> {
     }
 }
 
+pub struct AttentionBackwardBenchmark<B: AutodiffBackend> {
+    problem: AttentionProblem,
+    device: B::Device,
+}
+
+impl<B: AutodiffBackend> Benchmark for AttentionBackwardBenchmark<B> {
+    type Input = AttentionInput<B>;
+    type Output = B::Gradients;
+
+    fn name(&self) -> String {
+        format!(
+            "attention_backward{}-{:?}",
+            self.problem.causal_suffix(),
+            B::FloatElem::dtype()
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.problem.shapes()
+    }
+
+    fn execute(&self, input: Self::Input) -> Self::Output {
+        let output = attention(
+            input.query.require_grad(),
+            input.key.require_grad(),
+            input.value.require_grad(),
+            input.mask,
+            None,
+            self.problem.options,
+        );
+        output.sum().backward()
+    }
+
+    fn prepare(&self) -> Self::Input {
+        AttentionInput::new(&self.problem, &self.device)
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
 #[allow(dead_code)]
 fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
-    let small_problem = AttentionProblem {
-        batch_size: 1,
-        num_heads: 4,
-        seq_q: 2048,
-        head_dim: 128,
-        seq_kv: 2048,
-        val_dim: 128,
-        mask: false,
-        options: AttentionModuleOptions {
-            scale: None,
-            softcap: None,
-            is_causal: false,
-        },
-    };
-
-    let benchmark_flash = AttentionBenchmark::<B> {
-        device: device.clone(),
-        problem: small_problem.clone(),
-        kind: AttentionKind::Flash,
-    };
-    let benchmark_fallback = AttentionBenchmark::<B> {
-        device: device.clone(),
-        problem: small_problem,
-        kind: AttentionKind::Fallback,
-    };
-
-    vec![
-        run_benchmark(benchmark_flash),
-        run_benchmark(benchmark_fallback),
-    ]
+    // (batch_size, num_heads, seq_len, head_dim)
+    let sizes = [
+        (1, 4, 2048, 128),
+        (2, 16, 2048, 64),
+        (8, 12, 512, 64),
+        (16, 8, 256, 64),
+    ];
+
+    let mut results = Vec::new();
+    for (batch_size, num_heads, seq_len, head_dim) in sizes {
+        for is_causal in [false, true] {
+            let problem = AttentionProblem {
+                batch_size,
+                num_heads,
+                seq_q: seq_len,
+                head_dim,
+                seq_kv: seq_len,
+                val_dim: head_dim,
+                mask: false,
+                options: AttentionModuleOptions {
+                    scale: None,
+                    softcap: None,
+                    is_causal,
+                },
+            };
+
+            for kind in [AttentionKind::Flash, AttentionKind::Fallback] {
+                results.push(run_benchmark(AttentionBenchmark::<B> {
+                    device: device.clone(),
+                    problem: problem.clone(),
+                    kind,
+                }));
+            }
+            results.push(run_benchmark(AttentionBackwardBenchmark::<Autodiff<B>> {
+                device: device.clone(),
+                problem,
+            }));
+        }
+    }
+
+    results
 }
 
 fn main() {
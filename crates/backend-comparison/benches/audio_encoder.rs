@@ -0,0 +1,115 @@
+use burn::{
+    nn::{
+        Gelu, PaddingConfig1d,
+        conv::{Conv1d, Conv1dConfig},
+        transformer::{TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput},
+    },
+    prelude::*,
+    tensor::{Distribution, Element},
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+#[derive(Config, Debug)]
+pub struct AudioEncoderConfig {
+    transformer: TransformerEncoderConfig,
+    n_mels: usize,
+}
+
+/// Whisper-style audio encoder: a conv1d front-end over the mel spectrogram halving the number
+/// of frames, followed by transformer layers.
+#[derive(Module, Debug)]
+pub struct AudioEncoder<B: Backend> {
+    conv1: Conv1d<B>,
+    conv2: Conv1d<B>,
+    activation: Gelu,
+    transformer: TransformerEncoder<B>,
+}
+
+impl AudioEncoderConfig {
+    pub fn init<B: Backend>(&self, device: &B::Device) -> AudioEncoder<B> {
+        let d_model = self.transformer.d_model;
+
+        AudioEncoder {
+            conv1: Conv1dConfig::new(self.n_mels, d_model, 3)
+                .with_padding(PaddingConfig1d::Explicit(1))
+                .init(device),
+            conv2: Conv1dConfig::new(d_model, d_model, 3)
+                .with_stride(2)
+                .with_padding(PaddingConfig1d::Explicit(1))
+                .init(device),
+            activation: Gelu::new(),
+            transformer: self.transformer.init(device),
+        }
+    }
+}
+
+impl<B: Backend> AudioEncoder<B> {
+    /// Encode a mel spectrogram `[batch_size, n_mels, n_frames]`.
+    pub fn forward(&self, mels: Tensor<B, 3>) -> Tensor<B, 3> {
+        let x = self.activation.forward(self.conv1.forward(mels));
+        let x = self.activation.forward(self.conv2.forward(x));
+
+        self.transformer
+            .forward(TransformerEncoderInput::new(x.swap_dims(1, 2)))
+    }
+}
+
+pub struct AudioEncoderBenchmark<B: Backend> {
+    shape: Shape,
+    config: AudioEncoderConfig,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for AudioEncoderBenchmark<B> {
+    type Input = (AudioEncoder<B>, Tensor<B, 3>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("audio-encoder-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, (model, mels): Self::Input) -> Self::Output {
+        model.forward(mels)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        (
+            self.config.init(&self.device),
+            Tensor::random(self.shape.clone(), Distribution::Default, &self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    // Something similar to the Whisper-base encoder over 30 seconds of audio.
+    let n_mels = 80;
+    let n_frames = 3000;
+    let config = AudioEncoderConfig::new(
+        TransformerEncoderConfig::new(512, 2048, 8, 6).with_norm_first(true),
+        n_mels,
+    );
+
+    [1, 8]
+        .into_iter()
+        .map(|batch_size| {
+            run_benchmark(AudioEncoderBenchmark::<B> {
+                shape: [batch_size, n_mels, n_frames].into(),
+                config: config.clone(),
+                device: device.clone(),
+            })
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
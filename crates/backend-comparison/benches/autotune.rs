@@ -0,0 +1,114 @@
+use std::cell::Cell;
+
+use burn::tensor::{Distribution, Element, Tensor, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+/// Measure the latency of the first execution of a matmul, with or without autotuning.
+///
+/// Every execution uses a new problem size falling into a different autotune key, so that the
+/// cold variant always includes the tuning of the kernel while the warm variant executes the
+/// same problem once beforehand. The benchmark runs with an empty home directory, see
+/// [isolate_autotune_cache], so that the autotune results persisted on disk by the previous runs
+/// don't warm up the cold variant.
+struct AutotuneBenchmark<B: Backend> {
+    warm: bool,
+    execution: Cell<usize>,
+    device: B::Device,
+}
+
+impl<B: Backend> AutotuneBenchmark<B> {
+    fn new(warm: bool, device: B::Device) -> Self {
+        Self {
+            warm,
+            execution: Cell::new(0),
+            device,
+        }
+    }
+
+    /// Problem size `[m, k, n]` of the given execution, each one in a different autotune key.
+    fn problem(execution: usize) -> [usize; 3] {
+        let m = 1 << (6 + execution % 5);
+        let n = 1 << (6 + (execution / 5) % 5);
+        let k = 1 << (8 + execution / 25);
+        // Unaligned sizes, so that the problems don't share keys with the other benchmarks.
+        [m + 1, k + 1, n + 1]
+    }
+}
+
+impl<B: Backend> Benchmark for AutotuneBenchmark<B> {
+    type Input = (Tensor<B, 2>, Tensor<B, 2>);
+    type Output = Tensor<B, 2>;
+
+    fn name(&self) -> String {
+        let cache = if self.warm { "warm" } else { "cold" };
+        format!("autotune-matmul-{cache}-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        let [m, k, n] = Self::problem(0);
+        vec![vec![m, k], vec![k, n]]
+    }
+
+    fn num_samples(&self) -> usize {
+        10
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Self::Output {
+        lhs.matmul(rhs)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let execution = self.execution.get();
+        self.execution.set(execution + 1);
+
+        let [m, k, n] = Self::problem(execution);
+        let lhs = Tensor::random([m, k], Distribution::Default, &self.device);
+        let rhs = Tensor::random([k, n], Distribution::Default, &self.device);
+
+        if self.warm {
+            let _ = lhs.clone().matmul(rhs.clone());
+            self.sync();
+        }
+
+        (lhs, rhs)
+    }
+
+    fn prepare_cloned(&self) -> bool {
+        // Each execution needs a new problem size.
+        false
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    vec![
+        run_benchmark(AutotuneBenchmark::<B>::new(false, device.clone())),
+        run_benchmark(AutotuneBenchmark::<B>::new(true, device.clone())),
+    ]
+}
+
+/// Point the home and cache directories to a new temporary directory, hiding the autotune cache
+/// persisted by the previous runs, while the records are still saved to the usual directory.
+#[cfg(not(target_family = "wasm"))]
+fn isolate_autotune_cache() -> tempfile::TempDir {
+    let records_dir = burnbench::benchmarks_cache_dir();
+    let home = tempfile::tempdir().expect("Temporary directory should be created");
+    // SAFETY: no other thread is running yet, the backend is initialized afterwards.
+    unsafe {
+        std::env::set_var(burnbench::CACHE_DIR_ENV, records_dir);
+        std::env::set_var("HOME", home.path());
+        std::env::set_var("XDG_CACHE_HOME", home.path().join(".cache"));
+        std::env::set_var("LOCALAPPDATA", home.path());
+    }
+    home
+}
+
+fn main() {
+    #[cfg(not(target_family = "wasm"))]
+    let _home = isolate_autotune_cache();
+    burnbench::bench_on_backend!();
+}
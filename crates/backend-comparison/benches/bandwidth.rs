@@ -0,0 +1,141 @@
+use burn::tensor::{Distribution, Element, Tensor, TensorData, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transfer {
+    /// Host to device with `to_device`/`from_data`.
+    Upload,
+    /// Host to device, the data being moved to staging memory beforehand when supported.
+    UploadStaging,
+    /// Device to host with `to_data`.
+    Download,
+}
+
+struct BandwidthBenchmark<B: Backend> {
+    transfer: Transfer,
+    data: TensorData,
+    device: B::Device,
+}
+
+enum BandwidthInput<B: Backend> {
+    Host(TensorData),
+    Device(Tensor<B, 1>),
+}
+
+impl<B: Backend> Clone for BandwidthInput<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Host(data) => Self::Host(data.clone()),
+            Self::Device(tensor) => Self::Device(tensor.clone()),
+        }
+    }
+}
+
+enum BandwidthOutput<B: Backend> {
+    Host(TensorData),
+    Device(Tensor<B, 1>),
+}
+
+impl<B: Backend> BandwidthBenchmark<B> {
+    fn new(transfer: Transfer, num_elements: usize, device: B::Device) -> Self {
+        let data =
+            Tensor::<B, 1>::random([num_elements], Distribution::Default, &device).into_data();
+
+        Self {
+            transfer,
+            data,
+            device,
+        }
+    }
+
+    fn num_bytes(&self) -> usize {
+        self.data.bytes.len()
+    }
+}
+
+impl<B: Backend> Benchmark for BandwidthBenchmark<B> {
+    type Input = BandwidthInput<B>;
+    type Output = BandwidthOutput<B>;
+
+    fn name(&self) -> String {
+        let transfer = match self.transfer {
+            Transfer::Upload => "upload",
+            Transfer::UploadStaging => "upload-staging",
+            Transfer::Download => "download",
+        };
+        format!("bandwidth-{transfer}-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn options(&self) -> Option<String> {
+        Some(format!("bytes={}", self.num_bytes()))
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.data.shape.to_vec()]
+    }
+
+    fn work(&self) -> Option<(f64, String)> {
+        Some((self.num_bytes() as f64 / 1e9, "GB".to_string()))
+    }
+
+    fn execute(&self, input: Self::Input) -> Self::Output {
+        match input {
+            BandwidthInput::Host(data) => {
+                BandwidthOutput::Device(Tensor::from_data(data, &self.device))
+            }
+            BandwidthInput::Device(tensor) => BandwidthOutput::Host(tensor.into_data()),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        match self.transfer {
+            Transfer::Upload => BandwidthInput::Host(self.data.clone()),
+            Transfer::UploadStaging => {
+                let mut data = [self.data.clone()];
+                B::staging(data.iter_mut(), &self.device);
+                let [data] = data;
+                BandwidthInput::Host(data)
+            }
+            Transfer::Download => {
+                BandwidthInput::Device(Tensor::from_data(self.data.clone(), &self.device))
+            }
+        }
+    }
+
+    fn prepare_cloned(&self) -> bool {
+        // Staged data can only be uploaded once.
+        self.transfer != Transfer::UploadStaging
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    // From 4 KiB to 256 MiB with 32-bit elements.
+    let sizes: [usize; 5] = [1 << 10, 1 << 14, 1 << 18, 1 << 22, 1 << 26];
+
+    for transfer in [
+        Transfer::Upload,
+        Transfer::UploadStaging,
+        Transfer::Download,
+    ] {
+        for num_elements in sizes {
+            results.push(run_benchmark(BandwidthBenchmark::<B>::new(
+                transfer,
+                num_elements,
+                device.clone(),
+            )));
+        }
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,818 @@
+//! Workloads of the `matmul`, `conv2d` and `transformer-encoder` benchmarks run directly on the
+//! engines underlying the Burn backends, without going through Burn.
+//!
+//! The benchmarks have the same names and shapes as their Burn counterparts so that comparing a
+//! `baseline-*` backend with the matching Burn backend shows the overhead added by Burn.
+
+#[allow(unused_imports)]
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[cfg(feature = "f32")]
+const DTYPE: &str = "f32";
+#[cfg(feature = "f16")]
+const DTYPE: &str = "f16";
+#[cfg(feature = "bf16")]
+const DTYPE: &str = "bf16";
+#[cfg(feature = "flex32")]
+compile_error!("The baselines don't support flex32");
+
+/// General problems of the `matmul` benchmark, as `[b, m, n, k]`.
+#[allow(dead_code)]
+const MATMULS: [[usize; 4]; 3] = [
+    [4, 4096, 4096, 4096],
+    [8, 1024, 1024, 1024],
+    [32, 256, 256, 256],
+];
+
+/// Problem of the `conv2d` benchmark.
+#[allow(dead_code)]
+struct Conv2dProblem {
+    suffix: &'static str,
+    input: [usize; 4],
+    weight: [usize; 4],
+    stride: usize,
+}
+
+#[allow(dead_code)]
+const CONVS: [Conv2dProblem; 3] = [
+    Conv2dProblem {
+        suffix: "input_16x512x512_weight_16x3x3_stride_1",
+        input: [16, 16, 512, 512],
+        weight: [16, 16, 3, 3],
+        stride: 1,
+    },
+    Conv2dProblem {
+        suffix: "input_3x227x227_weight_96x11x11_stride_4",
+        input: [16, 3, 227, 227],
+        weight: [96, 3, 11, 11],
+        stride: 4,
+    },
+    Conv2dProblem {
+        suffix: "input_64x224x224_weight_64x7x7_stride_2",
+        input: [16, 64, 224, 224],
+        weight: [64, 64, 7, 7],
+        stride: 2,
+    },
+];
+
+/// Something similar to RoBERTa-base, as in the inference of the `transformer-encoder`
+/// benchmark: pre-norm layers, classification of the first token.
+#[allow(dead_code)]
+struct EncoderConfig {
+    d_model: usize,
+    d_ff: usize,
+    n_heads: usize,
+    n_layers: usize,
+    n_classes: usize,
+    vocab_size: usize,
+    max_seq_length: usize,
+}
+
+#[allow(dead_code)]
+const ENCODER: EncoderConfig = EncoderConfig {
+    d_model: 768,
+    d_ff: 3072,
+    n_heads: 12,
+    n_layers: 12,
+    n_classes: 10,
+    vocab_size: 50_265,
+    max_seq_length: 512,
+};
+
+/// Batch size and sequence length of the tokens.
+#[allow(dead_code)]
+const ENCODER_SHAPE: [usize; 2] = [2, 256];
+
+#[allow(dead_code)]
+fn matmul_shapes([b, m, n, k]: [usize; 4]) -> Vec<Vec<usize>> {
+    let (lhs, rhs) = (vec![b, m, k], vec![b, k, n]);
+    if lhs == rhs {
+        vec![lhs]
+    } else {
+        vec![lhs, rhs]
+    }
+}
+
+#[allow(dead_code)]
+fn conv2d_shapes(problem: &Conv2dProblem) -> Vec<Vec<usize>> {
+    vec![
+        problem.input.to_vec(),
+        problem.weight.to_vec(),
+        vec![problem.weight[0]],
+    ]
+}
+
+/// Tokens wrapping around the vocabulary, as in the `transformer-encoder` benchmark.
+#[allow(dead_code)]
+fn encoder_tokens() -> Vec<usize> {
+    let [batch_size, seq_length] = ENCODER_SHAPE;
+    (0..batch_size * seq_length)
+        .map(|i| i % ENCODER.vocab_size)
+        .collect()
+}
+
+#[cfg(any(feature = "baseline-tch-cpu", feature = "baseline-tch-cuda"))]
+mod libtorch {
+    use super::*;
+    use tch::{Device, Kind, Tensor};
+
+    fn kind() -> Kind {
+        match DTYPE {
+            "f16" => Kind::Half,
+            "bf16" => Kind::BFloat16,
+            _ => Kind::Float,
+        }
+    }
+
+    fn random(shape: &[usize], device: Device) -> Tensor {
+        let shape: Vec<i64> = shape.iter().map(|&d| d as i64).collect();
+        Tensor::randn(shape, (kind(), device))
+    }
+
+    fn sync(device: Device) {
+        if let Device::Cuda(index) = device {
+            tch::Cuda::synchronize(index as i64);
+        }
+    }
+
+    struct MatmulBenchmark {
+        problem: [usize; 4],
+        device: Device,
+    }
+
+    impl Benchmark for MatmulBenchmark {
+        type Input = Arc<(Tensor, Tensor)>;
+        type Output = Tensor;
+
+        fn name(&self) -> String {
+            format!("matmul-general-{DTYPE}")
+        }
+
+        fn shapes(&self) -> Vec<Vec<usize>> {
+            matmul_shapes(self.problem)
+        }
+
+        fn execute(&self, input: Self::Input) -> Self::Output {
+            let (lhs, rhs) = &*input;
+            lhs.matmul(rhs)
+        }
+
+        fn prepare(&self) -> Self::Input {
+            let [b, m, n, k] = self.problem;
+            Arc::new((
+                random(&[b, m, k], self.device),
+                random(&[b, k, n], self.device),
+            ))
+        }
+
+        fn sync(&self) {
+            sync(self.device);
+        }
+    }
+
+    struct Conv2dBenchmark {
+        problem: &'static Conv2dProblem,
+        device: Device,
+    }
+
+    impl Benchmark for Conv2dBenchmark {
+        type Input = Arc<(Tensor, Tensor, Tensor)>;
+        type Output = Tensor;
+
+        fn name(&self) -> String {
+            format!("conv2d-{}-{DTYPE}", self.problem.suffix)
+        }
+
+        fn shapes(&self) -> Vec<Vec<usize>> {
+            conv2d_shapes(self.problem)
+        }
+
+        fn execute(&self, input: Self::Input) -> Self::Output {
+            let (x, weight, bias) = &*input;
+            let stride = self.problem.stride as i64;
+            x.conv2d(weight, Some(bias), [stride, stride], [0, 0], [1, 1], 1)
+        }
+
+        fn prepare(&self) -> Self::Input {
+            Arc::new((
+                random(&self.problem.input, self.device),
+                random(&self.problem.weight, self.device),
+                random(&[self.problem.weight[0]], self.device),
+            ))
+        }
+
+        fn sync(&self) {
+            sync(self.device);
+        }
+
+        fn num_samples(&self) -> usize {
+            40
+        }
+    }
+
+    struct Linear {
+        weight: Tensor,
+        bias: Tensor,
+    }
+
+    impl Linear {
+        fn new(d_input: usize, d_output: usize, device: Device) -> Self {
+            Self {
+                weight: random(&[d_output, d_input], device),
+                bias: random(&[d_output], device),
+            }
+        }
+
+        fn forward(&self, x: &Tensor) -> Tensor {
+            x.linear(&self.weight, Some(&self.bias))
+        }
+    }
+
+    struct LayerNorm {
+        gamma: Tensor,
+        beta: Tensor,
+    }
+
+    impl LayerNorm {
+        fn new(device: Device) -> Self {
+            Self {
+                gamma: Tensor::ones([ENCODER.d_model as i64], (kind(), device)),
+                beta: Tensor::zeros([ENCODER.d_model as i64], (kind(), device)),
+            }
+        }
+
+        fn forward(&self, x: &Tensor) -> Tensor {
+            x.layer_norm(
+                [ENCODER.d_model as i64],
+                Some(&self.gamma),
+                Some(&self.beta),
+                1e-5,
+                false,
+            )
+        }
+    }
+
+    struct EncoderLayer {
+        norm_attention: LayerNorm,
+        query: Linear,
+        key: Linear,
+        value: Linear,
+        output: Linear,
+        norm_ff: LayerNorm,
+        ff_input: Linear,
+        ff_output: Linear,
+    }
+
+    impl EncoderLayer {
+        fn new(device: Device) -> Self {
+            let d_model = ENCODER.d_model;
+            Self {
+                norm_attention: LayerNorm::new(device),
+                query: Linear::new(d_model, d_model, device),
+                key: Linear::new(d_model, d_model, device),
+                value: Linear::new(d_model, d_model, device),
+                output: Linear::new(d_model, d_model, device),
+                norm_ff: LayerNorm::new(device),
+                ff_input: Linear::new(d_model, ENCODER.d_ff, device),
+                ff_output: Linear::new(ENCODER.d_ff, d_model, device),
+            }
+        }
+
+        fn attention(&self, x: &Tensor) -> Tensor {
+            let (batch_size, seq_length, d_model) = x.size3().unwrap();
+            let n_heads = ENCODER.n_heads as i64;
+            let d_k = d_model / n_heads;
+            let heads = |x: Tensor| {
+                x.reshape([batch_size, seq_length, n_heads, d_k])
+                    .transpose(1, 2)
+            };
+            let query = heads(self.query.forward(x));
+            let key = heads(self.key.forward(x));
+            let value = heads(self.value.forward(x));
+
+            let scores = query.matmul(&key.transpose(-2, -1)) / (d_k as f64).sqrt();
+            let context = scores.softmax(-1, kind()).matmul(&value);
+            let context = context
+                .transpose(1, 2)
+                .reshape([batch_size, seq_length, d_model]);
+            self.output.forward(&context)
+        }
+
+        fn forward(&self, x: Tensor) -> Tensor {
+            let x = &x + self.attention(&self.norm_attention.forward(&x));
+            let ff = self
+                .ff_input
+                .forward(&self.norm_ff.forward(&x))
+                .gelu("none");
+            &x + self.ff_output.forward(&ff)
+        }
+    }
+
+    struct Encoder {
+        token_embedding: Tensor,
+        position_embedding: Tensor,
+        layers: Vec<EncoderLayer>,
+        output: Linear,
+    }
+
+    impl Encoder {
+        fn new(device: Device) -> Self {
+            Self {
+                token_embedding: random(&[ENCODER.vocab_size, ENCODER.d_model], device),
+                position_embedding: random(&[ENCODER.max_seq_length, ENCODER.d_model], device),
+                layers: (0..ENCODER.n_layers)
+                    .map(|_| EncoderLayer::new(device))
+                    .collect(),
+                output: Linear::new(ENCODER.d_model, ENCODER.n_classes, device),
+            }
+        }
+
+        fn infer(&self, tokens: &Tensor) -> Tensor {
+            let seq_length = tokens.size()[1];
+            let positions = Tensor::arange(seq_length, (Kind::Int64, tokens.device()));
+            let mut x = Tensor::embedding(&self.token_embedding, tokens, -1, false, false)
+                + Tensor::embedding(&self.position_embedding, &positions, -1, false, false);
+            for layer in &self.layers {
+                x = layer.forward(x);
+            }
+            self.output.forward(&x).select(1, 0).softmax(1, kind())
+        }
+    }
+
+    struct EncoderBenchmark {
+        device: Device,
+    }
+
+    impl Benchmark for EncoderBenchmark {
+        type Input = Arc<(Encoder, Tensor)>;
+        type Output = Tensor;
+
+        fn name(&self) -> String {
+            format!("transformer-encoder-inference-{DTYPE}")
+        }
+
+        fn shapes(&self) -> Vec<Vec<usize>> {
+            vec![ENCODER_SHAPE.to_vec()]
+        }
+
+        fn execute(&self, input: Self::Input) -> Self::Output {
+            let (model, tokens) = &*input;
+            tch::no_grad(|| model.infer(tokens))
+        }
+
+        fn prepare(&self) -> Self::Input {
+            let tokens: Vec<i64> = encoder_tokens().into_iter().map(|t| t as i64).collect();
+            let [batch_size, seq_length] = ENCODER_SHAPE;
+            let tokens = Tensor::from_slice(&tokens)
+                .reshape([batch_size as i64, seq_length as i64])
+                .to(self.device);
+            Arc::new((Encoder::new(self.device), tokens))
+        }
+
+        fn sync(&self) {
+            sync(self.device);
+        }
+    }
+
+    pub fn bench(device: Device) -> Vec<BenchmarkResult> {
+        let mut results: Vec<BenchmarkResult> = MATMULS
+            .into_iter()
+            .map(|problem| run_benchmark(MatmulBenchmark { problem, device }))
+            .collect();
+        results.extend(
+            CONVS
+                .iter()
+                .map(|problem| run_benchmark(Conv2dBenchmark { problem, device })),
+        );
+        results.push(run_benchmark(EncoderBenchmark { device }));
+        results
+    }
+}
+
+#[cfg(any(feature = "baseline-candle-cpu", feature = "baseline-candle-cuda"))]
+mod candle {
+    use super::*;
+    use candle_core::{D, DType, Device, Tensor};
+
+    fn dtype() -> DType {
+        match DTYPE {
+            "f16" => DType::F16,
+            "bf16" => DType::BF16,
+            _ => DType::F32,
+        }
+    }
+
+    fn random(shape: &[usize], device: &Device) -> Tensor {
+        Tensor::randn(0f32, 1f32, shape, device)
+            .and_then(|tensor| tensor.to_dtype(dtype()))
+            .unwrap()
+    }
+
+    fn softmax(x: &Tensor) -> candle_core::Result<Tensor> {
+        let max = x.max_keepdim(D::Minus1)?;
+        let exp = x.broadcast_sub(&max)?.exp()?;
+        exp.broadcast_div(&exp.sum_keepdim(D::Minus1)?)
+    }
+
+    struct MatmulBenchmark {
+        problem: [usize; 4],
+        device: Device,
+    }
+
+    impl Benchmark for MatmulBenchmark {
+        type Input = (Tensor, Tensor);
+        type Output = Tensor;
+
+        fn name(&self) -> String {
+            format!("matmul-general-{DTYPE}")
+        }
+
+        fn shapes(&self) -> Vec<Vec<usize>> {
+            matmul_shapes(self.problem)
+        }
+
+        fn execute(&self, (lhs, rhs): Self::Input) -> Self::Output {
+            lhs.matmul(&rhs).unwrap()
+        }
+
+        fn prepare(&self) -> Self::Input {
+            let [b, m, n, k] = self.problem;
+            (
+                random(&[b, m, k], &self.device),
+                random(&[b, k, n], &self.device),
+            )
+        }
+
+        fn sync(&self) {
+            self.device.synchronize().unwrap();
+        }
+    }
+
+    struct Conv2dBenchmark {
+        problem: &'static Conv2dProblem,
+        device: Device,
+    }
+
+    impl Benchmark for Conv2dBenchmark {
+        type Input = (Tensor, Tensor, Tensor);
+        type Output = Tensor;
+
+        fn name(&self) -> String {
+            format!("conv2d-{}-{DTYPE}", self.problem.suffix)
+        }
+
+        fn shapes(&self) -> Vec<Vec<usize>> {
+            conv2d_shapes(self.problem)
+        }
+
+        fn execute(&self, (x, weight, bias): Self::Input) -> Self::Output {
+            let channels_out = self.problem.weight[0];
+            x.conv2d(&weight, 0, self.problem.stride, 1, 1)
+                .and_then(|x| x.broadcast_add(&bias.reshape((1, channels_out, 1, 1))?))
+                .unwrap()
+        }
+
+        fn prepare(&self) -> Self::Input {
+            (
+                random(&self.problem.input, &self.device),
+                random(&self.problem.weight, &self.device),
+                random(&[self.problem.weight[0]], &self.device),
+            )
+        }
+
+        fn sync(&self) {
+            self.device.synchronize().unwrap();
+        }
+
+        fn num_samples(&self) -> usize {
+            40
+        }
+    }
+
+    #[derive(Clone)]
+    struct Linear {
+        weight: Tensor,
+        bias: Tensor,
+    }
+
+    impl Linear {
+        fn new(d_input: usize, d_output: usize, device: &Device) -> Self {
+            Self {
+                weight: random(&[d_output, d_input], device),
+                bias: random(&[d_output], device),
+            }
+        }
+
+        fn forward(&self, x: &Tensor) -> candle_core::Result<Tensor> {
+            x.broadcast_matmul(&self.weight.t()?)?
+                .broadcast_add(&self.bias)
+        }
+    }
+
+    #[derive(Clone)]
+    struct LayerNorm {
+        gamma: Tensor,
+        beta: Tensor,
+    }
+
+    impl LayerNorm {
+        fn new(device: &Device) -> Self {
+            Self {
+                gamma: Tensor::ones(ENCODER.d_model, dtype(), device).unwrap(),
+                beta: Tensor::zeros(ENCODER.d_model, dtype(), device).unwrap(),
+            }
+        }
+
+        fn forward(&self, x: &Tensor) -> candle_core::Result<Tensor> {
+            let x = x.broadcast_sub(&x.mean_keepdim(D::Minus1)?)?;
+            let variance = x.sqr()?.mean_keepdim(D::Minus1)?;
+            x.broadcast_div(&(variance + 1e-5)?.sqrt()?)?
+                .broadcast_mul(&self.gamma)?
+                .broadcast_add(&self.beta)
+        }
+    }
+
+    #[derive(Clone)]
+    struct EncoderLayer {
+        norm_attention: LayerNorm,
+        query: Linear,
+        key: Linear,
+        value: Linear,
+        output: Linear,
+        norm_ff: LayerNorm,
+        ff_input: Linear,
+        ff_output: Linear,
+    }
+
+    impl EncoderLayer {
+        fn new(device: &Device) -> Self {
+            let d_model = ENCODER.d_model;
+            Self {
+                norm_attention: LayerNorm::new(device),
+                query: Linear::new(d_model, d_model, device),
+                key: Linear::new(d_model, d_model, device),
+                value: Linear::new(d_model, d_model, device),
+                output: Linear::new(d_model, d_model, device),
+                norm_ff: LayerNorm::new(device),
+                ff_input: Linear::new(d_model, ENCODER.d_ff, device),
+                ff_output: Linear::new(ENCODER.d_ff, d_model, device),
+            }
+        }
+
+        fn attention(&self, x: &Tensor) -> candle_core::Result<Tensor> {
+            let (batch_size, seq_length, d_model) = x.dims3()?;
+            let n_heads = ENCODER.n_heads;
+            let d_k = d_model / n_heads;
+            let heads = |x: Tensor| {
+                x.reshape((batch_size, seq_length, n_heads, d_k))?
+                    .transpose(1, 2)?
+                    .contiguous()
+            };
+            let query = heads(self.query.forward(x)?)?;
+            let key = heads(self.key.forward(x)?)?;
+            let value = heads(self.value.forward(x)?)?;
+
+            let scores = (query.matmul(&key.t()?.contiguous()?)? / (d_k as f64).sqrt())?;
+            let context = softmax(&scores)?.matmul(&value)?;
+            let context = context
+                .transpose(1, 2)?
+                .reshape((batch_size, seq_length, d_model))?;
+            self.output.forward(&context)
+        }
+
+        fn forward(&self, x: Tensor) -> candle_core::Result<Tensor> {
+            let x = (&x + self.attention(&self.norm_attention.forward(&x)?)?)?;
+            let ff = self
+                .ff_input
+                .forward(&self.norm_ff.forward(&x)?)?
+                .gelu_erf()?;
+            &x + self.ff_output.forward(&ff)?
+        }
+    }
+
+    #[derive(Clone)]
+    struct Encoder {
+        token_embedding: Tensor,
+        position_embedding: Tensor,
+        layers: Vec<EncoderLayer>,
+        output: Linear,
+    }
+
+    impl Encoder {
+        fn new(device: &Device) -> Self {
+            Self {
+                token_embedding: random(&[ENCODER.vocab_size, ENCODER.d_model], device),
+                position_embedding: random(&[ENCODER.max_seq_length, ENCODER.d_model], device),
+                layers: (0..ENCODER.n_layers)
+                    .map(|_| EncoderLayer::new(device))
+                    .collect(),
+                output: Linear::new(ENCODER.d_model, ENCODER.n_classes, device),
+            }
+        }
+
+        fn infer(&self, tokens: &Tensor) -> candle_core::Result<Tensor> {
+            let (batch_size, seq_length) = tokens.dims2()?;
+            let positions = Tensor::arange(0u32, seq_length as u32, tokens.device())?;
+            let mut x = self
+                .token_embedding
+                .embedding(&tokens.flatten_all()?)?
+                .reshape((batch_size, seq_length, ENCODER.d_model))?
+                .broadcast_add(&self.position_embedding.embedding(&positions)?)?;
+            for layer in &self.layers {
+                x = layer.forward(x)?;
+            }
+            softmax(&self.output.forward(&x)?.narrow(1, 0, 1)?.squeeze(1)?)
+        }
+    }
+
+    struct EncoderBenchmark {
+        device: Device,
+    }
+
+    impl Benchmark for EncoderBenchmark {
+        type Input = (Encoder, Tensor);
+        type Output = Tensor;
+
+        fn name(&self) -> String {
+            format!("transformer-encoder-inference-{DTYPE}")
+        }
+
+        fn shapes(&self) -> Vec<Vec<usize>> {
+            vec![ENCODER_SHAPE.to_vec()]
+        }
+
+        fn execute(&self, (model, tokens): Self::Input) -> Self::Output {
+            model.infer(&tokens).unwrap()
+        }
+
+        fn prepare(&self) -> Self::Input {
+            let tokens: Vec<u32> = encoder_tokens().into_iter().map(|t| t as u32).collect();
+            let tokens = Tensor::from_vec(tokens, ENCODER_SHAPE, &self.device).unwrap();
+            (Encoder::new(&self.device), tokens)
+        }
+
+        fn sync(&self) {
+            self.device.synchronize().unwrap();
+        }
+    }
+
+    pub fn bench(device: Device) -> Vec<BenchmarkResult> {
+        let mut results: Vec<BenchmarkResult> = MATMULS
+            .into_iter()
+            .map(|problem| {
+                run_benchmark(MatmulBenchmark {
+                    problem,
+                    device: device.clone(),
+                })
+            })
+            .collect();
+        results.extend(CONVS.iter().map(|problem| {
+            run_benchmark(Conv2dBenchmark {
+                problem,
+                device: device.clone(),
+            })
+        }));
+        results.push(run_benchmark(EncoderBenchmark { device }));
+        results
+    }
+}
+
+#[cfg(any(feature = "baseline-ort-cpu", feature = "baseline-ort-cuda"))]
+mod onnxruntime {
+    use super::*;
+    use ort::{
+        execution_providers::CUDAExecutionProvider,
+        session::{Session, SessionInputValue},
+        tensor::TensorElementType,
+        value::{DynValue, Tensor, ValueType},
+    };
+    use std::{fs, path::Path, sync::Mutex};
+
+    /// Directory of the ONNX models to run, exported from the workloads of the Burn benchmarks.
+    const MODELS_DIR_ENV: &str = "BURN_BENCH_ONNX_MODELS";
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum Provider {
+        Cpu,
+        Cuda,
+    }
+
+    /// Inference of an ONNX model on inputs matching its signature, the dynamic dimensions
+    /// being set to 1.
+    struct ModelBenchmark {
+        name: String,
+        session: Mutex<Session>,
+    }
+
+    impl ModelBenchmark {
+        fn new(path: &Path, provider: Provider) -> Self {
+            let mut builder = Session::builder().unwrap();
+            if let Provider::Cuda = provider {
+                builder = builder
+                    .with_execution_providers([CUDAExecutionProvider::default()
+                        .build()
+                        .error_on_failure()])
+                    .unwrap();
+            }
+            let session = builder.commit_from_file(path).unwrap();
+            let stem = path.file_stem().unwrap().to_string_lossy();
+            Self {
+                name: format!("{stem}-{DTYPE}"),
+                session: Mutex::new(session),
+            }
+        }
+
+        fn input_shapes(&self) -> Vec<(String, Vec<i64>, TensorElementType)> {
+            let session = self.session.lock().unwrap();
+            session
+                .inputs
+                .iter()
+                .map(|input| match &input.input_type {
+                    ValueType::Tensor { ty, shape, .. } => {
+                        let shape = shape.iter().map(|&dim| dim.max(1)).collect();
+                        (input.name.clone(), shape, *ty)
+                    }
+                    other => panic!("Unsupported input {} of type {other:?}", input.name),
+                })
+                .collect()
+        }
+    }
+
+    impl Benchmark for ModelBenchmark {
+        type Input = Arc<Vec<(String, DynValue)>>;
+        type Output = usize;
+
+        fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn shapes(&self) -> Vec<Vec<usize>> {
+            self.input_shapes()
+                .into_iter()
+                .map(|(_, shape, _)| shape.into_iter().map(|dim| dim as usize).collect())
+                .collect()
+        }
+
+        /// Returns the number of outputs, the session is executed whether they are used or not.
+        fn execute(&self, input: Self::Input) -> Self::Output {
+            let inputs: Vec<(&str, SessionInputValue)> = input
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.view().into()))
+                .collect();
+            let mut session = self.session.lock().unwrap();
+            session.run(inputs).unwrap().len()
+        }
+
+        fn prepare(&self) -> Self::Input {
+            let inputs = self
+                .input_shapes()
+                .into_iter()
+                .map(|(name, shape, ty)| {
+                    let len = shape.iter().product::<i64>() as usize;
+                    let value = match ty {
+                        TensorElementType::Float32 => {
+                            let data = (0..len).map(|i| (i % 100) as f32 / 100.0).collect();
+                            Tensor::<f32>::from_array((shape, data)).unwrap().into_dyn()
+                        }
+                        TensorElementType::Int64 => {
+                            Tensor::<i64>::from_array((shape, vec![0i64; len]))
+                                .unwrap()
+                                .into_dyn()
+                        }
+                        ty => panic!("Unsupported input {name} of type {ty:?}"),
+                    };
+                    (name, value)
+                })
+                .collect();
+            Arc::new(inputs)
+        }
+
+        fn sync(&self) {
+            // The session runs synchronously.
+        }
+    }
+
+    pub fn bench(provider: Provider) -> Vec<BenchmarkResult> {
+        assert_eq!(DTYPE, "f32", "The ONNX Runtime baseline only supports f32");
+        let Ok(dir) = std::env::var(MODELS_DIR_ENV) else {
+            log::warn!("{MODELS_DIR_ENV} is not set, there is no ONNX model to run");
+            return vec![];
+        };
+        let mut models: Vec<_> = fs::read_dir(&dir)
+            .unwrap_or_else(|e| panic!("Failed to read the ONNX models in {dir} ({e})"))
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "onnx"))
+            .collect();
+        models.sort();
+        models
+            .iter()
+            .map(|path| run_benchmark(ModelBenchmark::new(path, provider)))
+            .collect()
+    }
+}
+
+fn main() {
+    burnbench::bench_on_baseline!();
+}
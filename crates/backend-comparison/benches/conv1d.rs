@@ -0,0 +1,127 @@
+use burn::tensor::{
+    Distribution, Element, Shape, Tensor, backend::Backend, module::conv1d, ops::ConvOptions,
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use std::hint::black_box;
+
+pub struct Conv1dBenchmark<B: Backend> {
+    suffix: &'static str,
+    input_shape: Shape,
+    weight_shape: Shape,
+    bias_shape: Shape,
+    options: ConvOptions<1>,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for Conv1dBenchmark<B> {
+    type Input = (Tensor<B, 3>, Tensor<B, 3>, Tensor<B, 1>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("conv1d-{}-{:?}", self.suffix, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            self.input_shape.to_vec(),
+            self.weight_shape.to_vec(),
+            self.bias_shape.to_vec(),
+        ]
+    }
+
+    fn execute(&self, (x, w, b): Self::Input) -> Self::Output {
+        conv1d(x, w, Some(b), self.options.clone())
+    }
+
+    fn prepare(&self) -> Self::Input {
+        (
+            Tensor::random(
+                self.input_shape.clone(),
+                Distribution::Default,
+                &self.device,
+            ),
+            Tensor::random(
+                self.weight_shape.clone(),
+                Distribution::Default,
+                &self.device,
+            ),
+            Tensor::random(self.bias_shape.clone(), Distribution::Default, &self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+
+    fn num_samples(&self) -> usize {
+        40
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let batch_size = 8;
+
+    // Whisper encoder stem: 80 mel bins over 30 seconds of audio.
+    let whisper1 = Conv1dBenchmark::<B> {
+        suffix: "input_80x3000_weight_512x3_stride_1",
+        input_shape: [batch_size, 80, 3000].into(),
+        weight_shape: [512, 80, 3].into(),
+        bias_shape: [512].into(),
+        options: ConvOptions::new([1], [1], [1], 1),
+        device: device.clone(),
+    };
+
+    let whisper2 = Conv1dBenchmark::<B> {
+        suffix: "input_512x3000_weight_512x3_stride_2",
+        input_shape: [batch_size, 512, 3000].into(),
+        weight_shape: [512, 512, 3].into(),
+        bias_shape: [512].into(),
+        options: ConvOptions::new([2], [1], [1], 1),
+        device: device.clone(),
+    };
+
+    // Wav2vec2 feature extractor: raw waveform of 10 seconds at 16kHz.
+    let wav2vec1 = Conv1dBenchmark::<B> {
+        suffix: "input_1x160000_weight_512x10_stride_5",
+        input_shape: [batch_size, 1, 160_000].into(),
+        weight_shape: [512, 1, 10].into(),
+        bias_shape: [512].into(),
+        options: ConvOptions::new([5], [0], [1], 1),
+        device: device.clone(),
+    };
+
+    let wav2vec2 = Conv1dBenchmark::<B> {
+        suffix: "input_512x31999_weight_512x3_stride_2",
+        input_shape: [batch_size, 512, 31_999].into(),
+        weight_shape: [512, 512, 3].into(),
+        bias_shape: [512].into(),
+        options: ConvOptions::new([2], [0], [1], 1),
+        device: device.clone(),
+    };
+
+    // Conformer convolution module: depthwise convolution with a large kernel.
+    let conformer = Conv1dBenchmark::<B> {
+        suffix: "depthwise_input_256x500_weight_256x31_stride_1",
+        input_shape: [batch_size, 256, 500].into(),
+        weight_shape: [256, 1, 31].into(),
+        bias_shape: [256].into(),
+        options: ConvOptions::new([1], [15], [1], 256),
+        device: device.clone(),
+    };
+
+    let benches = vec![whisper1, whisper2, wav2vec1, wav2vec2, conformer];
+    let mut results = Vec::new();
+
+    for bench in benches {
+        println!("Running {}", bench.name());
+        let result = black_box(run_benchmark(bench));
+        results.push(result);
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -207,9 +207,57 @@ fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
         device: device.clone(),
     };
 
+    // MobileNet-style depthwise convolutions, where `groups == channels`.
+    let depthwise1 = Conv2dBenchmark::<B> {
+        suffix: "depthwise_input_32x112x112_weight_32x3x3_stride_1",
+        input_shape: [batch_size, 32, 112, 112].into(),
+        weight_shape: [32, 1, 3, 3].into(),
+        bias_shape: [32].into(),
+        options: ConvOptions::new([1, 1], [1, 1], [1, 1], 32),
+        device: device.clone(),
+    };
+
+    let depthwise2 = Conv2dBenchmark::<B> {
+        suffix: "depthwise_input_144x56x56_weight_144x3x3_stride_2",
+        input_shape: [batch_size, 144, 56, 56].into(),
+        weight_shape: [144, 1, 3, 3].into(),
+        bias_shape: [144].into(),
+        options: ConvOptions::new([2, 2], [1, 1], [1, 1], 144),
+        device: device.clone(),
+    };
+
+    let depthwise3 = Conv2dBenchmark::<B> {
+        suffix: "depthwise_input_576x14x14_weight_576x3x3_stride_1",
+        input_shape: [batch_size, 576, 14, 14].into(),
+        weight_shape: [576, 1, 3, 3].into(),
+        bias_shape: [576].into(),
+        options: ConvOptions::new([1, 1], [1, 1], [1, 1], 576),
+        device: device.clone(),
+    };
+
+    // Pointwise convolution following a depthwise one.
+    let pointwise = Conv2dBenchmark::<B> {
+        suffix: "pointwise_input_144x28x28_weight_32x1x1_stride_1",
+        input_shape: [batch_size, 144, 28, 28].into(),
+        weight_shape: [32, 144, 1, 1].into(),
+        bias_shape: [32].into(),
+        options: ConvOptions::new([1, 1], [0, 0], [1, 1], 1),
+        device: device.clone(),
+    };
+
+    // ResNeXt-style grouped convolution with 32 groups.
+    let grouped = Conv2dBenchmark::<B> {
+        suffix: "grouped_input_128x56x56_weight_128x3x3_groups_32",
+        input_shape: [batch_size, 128, 56, 56].into(),
+        weight_shape: [128, 128 / 32, 3, 3].into(),
+        bias_shape: [128].into(),
+        options: ConvOptions::new([1, 1], [1, 1], [1, 1], 32),
+        device: device.clone(),
+    };
+
     let benches = vec![
         benchmark, conv1, conv2, conv3, conv4, conv5, conv6, conv7, conv8, conv9, conv10, conv11,
-        conv12, conv13,
+        conv12, conv13, depthwise1, depthwise2, depthwise3, pointwise, grouped,
     ];
     let mut results = Vec::new();
 
@@ -0,0 +1,84 @@
+use burn::tensor::{Distribution, Element, Shape, Tensor, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum ScanOp {
+    Cumsum,
+    Cumprod,
+    Cummax,
+}
+
+#[derive(new)]
+struct CumulativeBenchmark<B: Backend> {
+    op: ScanOp,
+    shape: Shape,
+    dim: usize,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for CumulativeBenchmark<B> {
+    type Input = Tensor<B, 3>;
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("{:?}-dim{}-{:?}", self.op, self.dim, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, tensor: Self::Input) -> Self::Output {
+        match self.op {
+            ScanOp::Cumsum => tensor.cumsum(self.dim),
+            ScanOp::Cumprod => tensor.cumprod(self.dim),
+            ScanOp::Cummax => tensor.cummax(self.dim),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        // Values close to 1 so that the products don't overflow or vanish.
+        Tensor::random(
+            self.shape.clone(),
+            Distribution::Uniform(0.99, 1.01),
+            &self.device,
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+    let shape: Shape = [32, 512, 1024].into();
+
+    for op in [ScanOp::Cumsum, ScanOp::Cumprod, ScanOp::Cummax] {
+        for dim in 0..3 {
+            results.push(run_benchmark(CumulativeBenchmark::<B>::new(
+                op,
+                shape.clone(),
+                dim,
+                device.clone(),
+            )));
+        }
+    }
+
+    // A single long scan, e.g. the prefix sum of a flattened tensor.
+    let long_shape: Shape = [1, 1, 16 * 1024 * 1024].into();
+    results.push(run_benchmark(CumulativeBenchmark::<B>::new(
+        ScanOp::Cumsum,
+        long_shape,
+        2,
+        device.clone(),
+    )));
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,123 @@
+use burn::tensor::{Distribution, Element, Shape, Tensor, activation::gelu, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+/// Patterns of operations that a fusion engine is expected to merge into fewer kernels.
+///
+/// Run the same backend with and without the `-fusion` feature, e.g.
+/// `--backends wgpu wgpu-fusion --report-layout pivot`, to get the speedup of each pattern.
+#[derive(Debug, Clone, Copy)]
+enum FusionPattern {
+    /// A chain of elementwise operations of the given length.
+    Elementwise(usize),
+    /// A chain of elementwise operations followed by a reduction on the last dimension.
+    ElementwiseReduce(usize),
+    /// A matmul followed by a chain of elementwise operations.
+    MatmulEpilogue(usize),
+    /// A reduction broadcasted back to the input, as in a softmax.
+    ReduceBroadcast,
+}
+
+impl FusionPattern {
+    fn name(&self) -> String {
+        match self {
+            FusionPattern::Elementwise(length) => format!("elementwise{length}"),
+            FusionPattern::ElementwiseReduce(length) => format!("elementwise{length}-reduce"),
+            FusionPattern::MatmulEpilogue(length) => format!("matmul-elementwise{length}"),
+            FusionPattern::ReduceBroadcast => "reduce-broadcast".to_string(),
+        }
+    }
+}
+
+/// Apply `length` cheap elementwise operations.
+fn elementwise_chain<B: Backend>(mut tensor: Tensor<B, 3>, length: usize) -> Tensor<B, 3> {
+    for i in 0..length {
+        tensor = match i % 3 {
+            0 => tensor * 0.5,
+            1 => tensor + 0.25,
+            _ => tensor.tanh(),
+        };
+    }
+    tensor
+}
+
+#[derive(new)]
+struct FusionBenchmark<B: Backend> {
+    pattern: FusionPattern,
+    shape: Shape,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for FusionBenchmark<B> {
+    type Input = (Tensor<B, 3>, Tensor<B, 3>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("fusion-{}-{:?}", self.pattern.name(), B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Self::Output {
+        match self.pattern {
+            FusionPattern::Elementwise(length) => elementwise_chain(lhs, length),
+            FusionPattern::ElementwiseReduce(length) => elementwise_chain(lhs, length).sum_dim(2),
+            FusionPattern::MatmulEpilogue(length) => {
+                elementwise_chain(gelu(lhs.matmul(rhs)), length)
+            }
+            FusionPattern::ReduceBroadcast => {
+                let shifted = lhs.clone() - lhs.max_dim(2);
+                let exp = shifted.exp();
+                exp.clone() / exp.sum_dim(2)
+            }
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let [batch, rows, cols] = [self.shape[0], self.shape[1], self.shape[2]];
+        let lhs = Tensor::random(self.shape.clone(), Distribution::Default, &self.device);
+        let rhs = match self.pattern {
+            FusionPattern::MatmulEpilogue(_) => {
+                Tensor::random([batch, cols, rows], Distribution::Default, &self.device)
+            }
+            _ => Tensor::empty([0, 0, 0], &self.device),
+        };
+
+        (lhs, rhs)
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut patterns = Vec::new();
+    for length in [2, 8, 32] {
+        patterns.push(FusionPattern::Elementwise(length));
+        patterns.push(FusionPattern::ElementwiseReduce(length));
+        patterns.push(FusionPattern::MatmulEpilogue(length));
+    }
+    patterns.push(FusionPattern::ReduceBroadcast);
+
+    let shape: Shape = [32, 512, 1024].into();
+    let matmul_shape: Shape = [8, 1024, 1024].into();
+
+    patterns
+        .into_iter()
+        .map(|pattern| {
+            let shape = match pattern {
+                FusionPattern::MatmulEpilogue(_) => matmul_shape.clone(),
+                _ => shape.clone(),
+            };
+            run_benchmark(FusionBenchmark::<B>::new(pattern, shape, device.clone()))
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,276 @@
+use burn::{
+    nn::{Embedding, EmbeddingConfig},
+    tensor::{
+        Distribution, Element, IndexingUpdateOp, Int, Shape, Tensor,
+        backend::{AutodiffBackend, Backend},
+    },
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum IndexOp {
+    /// Gather `indices_len` entries along the dimension 1 of a `[batch, len, dim]` tensor.
+    Gather,
+    /// Scatter-add `indices_len` rows along the dimension 1 of a `[batch, len, dim]` tensor.
+    Scatter,
+    /// Add `indices_len` rows into a `[len, dim]` tensor, as done by embedding gradients.
+    SelectAssign,
+}
+
+#[derive(new)]
+struct IndexBenchmark<B: Backend> {
+    op: IndexOp,
+    shape: Shape,
+    indices_len: usize,
+    device: B::Device,
+}
+
+enum IndexInput<B: Backend> {
+    Rank3(Tensor<B, 3>, Tensor<B, 3, Int>, Tensor<B, 3>),
+    Rank2(Tensor<B, 2>, Tensor<B, 1, Int>, Tensor<B, 2>),
+}
+
+impl<B: Backend> Clone for IndexInput<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Rank3(tensor, indices, values) => {
+                Self::Rank3(tensor.clone(), indices.clone(), values.clone())
+            }
+            Self::Rank2(tensor, indices, values) => {
+                Self::Rank2(tensor.clone(), indices.clone(), values.clone())
+            }
+        }
+    }
+}
+
+enum IndexOutput<B: Backend> {
+    Rank3(Tensor<B, 3>),
+    Rank2(Tensor<B, 2>),
+}
+
+impl<B: Backend> Benchmark for IndexBenchmark<B> {
+    type Input = IndexInput<B>;
+    type Output = IndexOutput<B>;
+
+    fn name(&self) -> String {
+        let op = match self.op {
+            IndexOp::Gather => "gather",
+            IndexOp::Scatter => "scatter",
+            IndexOp::SelectAssign => "select_assign",
+        };
+        format!("{op}-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec(), vec![self.indices_len]]
+    }
+
+    fn execute(&self, input: Self::Input) -> Self::Output {
+        match (self.op, input) {
+            (IndexOp::Gather, IndexInput::Rank3(tensor, indices, _)) => {
+                IndexOutput::Rank3(tensor.gather(1, indices))
+            }
+            (IndexOp::Scatter, IndexInput::Rank3(tensor, indices, values)) => {
+                IndexOutput::Rank3(tensor.scatter(1, indices, values, IndexingUpdateOp::Add))
+            }
+            (IndexOp::SelectAssign, IndexInput::Rank2(tensor, indices, values)) => {
+                IndexOutput::Rank2(tensor.select_assign(0, indices, values, IndexingUpdateOp::Add))
+            }
+            _ => unreachable!("Input doesn't match the indexing operation"),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let dims = self.shape.to_vec();
+        let index_range = Distribution::Uniform(0.0, dims[dims.len() - 2] as f64);
+
+        match self.op {
+            IndexOp::Gather | IndexOp::Scatter => {
+                let indices_shape = [dims[0], self.indices_len, dims[2]];
+                IndexInput::Rank3(
+                    Tensor::random(self.shape.clone(), Distribution::Default, &self.device),
+                    Tensor::random(indices_shape, index_range, &self.device),
+                    Tensor::random(indices_shape, Distribution::Default, &self.device),
+                )
+            }
+            IndexOp::SelectAssign => IndexInput::Rank2(
+                Tensor::random(self.shape.clone(), Distribution::Default, &self.device),
+                Tensor::random([self.indices_len], index_range, &self.device),
+                Tensor::random(
+                    [self.indices_len, dims[1]],
+                    Distribution::Default,
+                    &self.device,
+                ),
+            ),
+        }
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[derive(new)]
+struct EmbeddingBenchmark<B: Backend> {
+    vocab_size: usize,
+    d_model: usize,
+    batch_size: usize,
+    seq_length: usize,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for EmbeddingBenchmark<B> {
+    type Input = (Embedding<B>, Tensor<B, 2, Int>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("embedding-forward-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        embedding_shapes(
+            self.vocab_size,
+            self.d_model,
+            self.batch_size,
+            self.seq_length,
+        )
+    }
+
+    fn execute(&self, (embedding, tokens): Self::Input) -> Self::Output {
+        embedding.forward(tokens)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        prepare_embedding(
+            self.vocab_size,
+            self.d_model,
+            [self.batch_size, self.seq_length],
+            &self.device,
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[derive(new)]
+struct EmbeddingBackwardBenchmark<B: AutodiffBackend> {
+    vocab_size: usize,
+    d_model: usize,
+    batch_size: usize,
+    seq_length: usize,
+    device: B::Device,
+}
+
+impl<B: AutodiffBackend> Benchmark for EmbeddingBackwardBenchmark<B> {
+    type Input = (Embedding<B>, Tensor<B, 2, Int>);
+    type Output = B::Gradients;
+
+    fn name(&self) -> String {
+        format!("embedding-backward-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        embedding_shapes(
+            self.vocab_size,
+            self.d_model,
+            self.batch_size,
+            self.seq_length,
+        )
+    }
+
+    fn execute(&self, (embedding, tokens): Self::Input) -> Self::Output {
+        embedding.forward(tokens).sum().backward()
+    }
+
+    fn prepare(&self) -> Self::Input {
+        prepare_embedding(
+            self.vocab_size,
+            self.d_model,
+            [self.batch_size, self.seq_length],
+            &self.device,
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+fn embedding_shapes(
+    vocab_size: usize,
+    d_model: usize,
+    batch_size: usize,
+    seq_length: usize,
+) -> Vec<Vec<usize>> {
+    vec![vec![vocab_size, d_model], vec![batch_size, seq_length]]
+}
+
+fn prepare_embedding<B: Backend>(
+    vocab_size: usize,
+    d_model: usize,
+    tokens_shape: [usize; 2],
+    device: &B::Device,
+) -> (Embedding<B>, Tensor<B, 2, Int>) {
+    let embedding = EmbeddingConfig::new(vocab_size, d_model).init(device);
+    let tokens = Tensor::random(
+        tokens_shape,
+        Distribution::Uniform(0.0, vocab_size as f64),
+        device,
+    );
+    (embedding, tokens)
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    // (shape, indices_len)
+    let rank3_configs: [([usize; 3], usize); 2] = [([32, 1024, 1024], 256), ([8, 4096, 512], 4096)];
+    for op in [IndexOp::Gather, IndexOp::Scatter] {
+        for (shape, indices_len) in rank3_configs {
+            let benchmark = IndexBenchmark::<B>::new(op, shape.into(), indices_len, device.clone());
+            results.push(run_benchmark(benchmark));
+        }
+    }
+
+    // Token gradients accumulated into the rows of a large vocabulary.
+    for (shape, indices_len) in [([32_000, 1024], 8192), ([128_000, 2048], 16_384)] {
+        let benchmark = IndexBenchmark::<B>::new(
+            IndexOp::SelectAssign,
+            shape.into(),
+            indices_len,
+            device.clone(),
+        );
+        results.push(run_benchmark(benchmark));
+    }
+
+    // (vocab_size, d_model, batch_size, seq_length)
+    let embedding_configs = [(32_000, 1024, 32, 512), (128_000, 2048, 8, 1024)];
+    for (vocab_size, d_model, batch_size, seq_length) in embedding_configs {
+        results.push(run_benchmark(EmbeddingBenchmark::<B>::new(
+            vocab_size,
+            d_model,
+            batch_size,
+            seq_length,
+            device.clone(),
+        )));
+        results.push(run_benchmark(EmbeddingBackwardBenchmark::<
+            burn::backend::Autodiff<B>,
+        >::new(
+            vocab_size,
+            d_model,
+            batch_size,
+            seq_length,
+            device.clone(),
+        )));
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
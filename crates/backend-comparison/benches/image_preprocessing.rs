@@ -0,0 +1,95 @@
+use burn::tensor::{
+    Element, Int, Tensor, TensorData,
+    backend::Backend,
+    module::interpolate,
+    ops::{InterpolateMode, InterpolateOptions},
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+use rand::{RngExt as _, rng};
+
+/// ImageNet normalization statistics.
+const MEAN: [f32; 3] = [0.485, 0.456, 0.406];
+const STD: [f32; 3] = [0.229, 0.224, 0.225];
+
+/// Typical input pipeline of a vision model: the raw `u8` images in NHWC are uploaded, batched,
+/// converted to float, normalized, permuted to NCHW and resized.
+#[derive(new)]
+struct ImagePreprocessingBenchmark<B: Backend> {
+    batch_size: usize,
+    image_size: [usize; 2],
+    output_size: [usize; 2],
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for ImagePreprocessingBenchmark<B> {
+    type Input = Vec<TensorData>;
+    type Output = Tensor<B, 4>;
+
+    fn name(&self) -> String {
+        format!("image-preprocessing-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        let [height, width] = self.image_size;
+        vec![
+            vec![self.batch_size, height, width, 3],
+            self.output_size.to_vec(),
+        ]
+    }
+
+    fn execute(&self, images: Self::Input) -> Self::Output {
+        let images = images
+            .into_iter()
+            .map(|data| Tensor::<B, 3, Int>::from_data(data, &self.device))
+            .collect();
+        let batch = Tensor::stack::<4>(images, 0).float() / 255.0;
+
+        let mean = Tensor::<B, 1>::from_floats(MEAN, &self.device).reshape([1, 1, 1, 3]);
+        let std = Tensor::<B, 1>::from_floats(STD, &self.device).reshape([1, 1, 1, 3]);
+        let batch = ((batch - mean) / std).permute([0, 3, 1, 2]);
+
+        interpolate(
+            batch,
+            self.output_size,
+            InterpolateOptions::new(InterpolateMode::Bilinear),
+        )
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let [height, width] = self.image_size;
+
+        (0..self.batch_size)
+            .map(|_| {
+                let pixels: Vec<u8> = (0..height * width * 3).map(|_| rng().random()).collect();
+                TensorData::new(pixels, [height, width, 3])
+            })
+            .collect()
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    // (batch size, image size): VGA and Full HD frames resized for a classifier.
+    let problems = [(32, [480, 640]), (8, [1080, 1920])];
+
+    problems
+        .into_iter()
+        .map(|(batch_size, image_size)| {
+            run_benchmark(ImagePreprocessingBenchmark::<B>::new(
+                batch_size,
+                image_size,
+                [224, 224],
+                device.clone(),
+            ))
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,77 @@
+use burn::tensor::{Distribution, Element, Int, Shape, Tensor, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum IntOp {
+    Add,
+    Mul,
+    Sum,
+    Matmul,
+}
+
+#[derive(new)]
+struct IntOpBenchmark<B: Backend> {
+    op: IntOp,
+    shape_lhs: Shape,
+    shape_rhs: Shape,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for IntOpBenchmark<B> {
+    type Input = (Tensor<B, 3, Int>, Tensor<B, 3, Int>);
+    type Output = Tensor<B, 3, Int>;
+
+    fn name(&self) -> String {
+        format!("int-{:?}-{:?}", self.op, B::IntElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape_lhs.to_vec(), self.shape_rhs.to_vec()]
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Self::Output {
+        match self.op {
+            IntOp::Add => lhs + rhs,
+            IntOp::Mul => lhs * rhs,
+            IntOp::Sum => lhs.sum_dim(2),
+            IntOp::Matmul => lhs.matmul(rhs),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        // Small values so that the products and sums don't overflow.
+        let distribution = Distribution::Uniform(0.0, 16.0);
+        (
+            Tensor::random(self.shape_lhs.clone(), distribution, &self.device),
+            Tensor::random(self.shape_rhs.clone(), distribution, &self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let shape: [usize; 3] = [32, 512, 1024];
+    let elementwise = [IntOp::Add, IntOp::Mul, IntOp::Sum]
+        .map(|op| IntOpBenchmark::<B>::new(op, shape.into(), shape.into(), device.clone()));
+    let matmul = IntOpBenchmark::<B>::new(
+        IntOp::Matmul,
+        [8, 1024, 1024].into(),
+        [8, 1024, 1024].into(),
+        device.clone(),
+    );
+
+    elementwise
+        .into_iter()
+        .chain([matmul])
+        .map(run_benchmark)
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,95 @@
+use burn::tensor::{
+    Distribution, Element, Shape, Tensor,
+    backend::Backend,
+    module::interpolate,
+    ops::{InterpolateMode, InterpolateOptions},
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(new)]
+struct InterpolateBenchmark<B: Backend> {
+    mode: InterpolateMode,
+    input_shape: Shape,
+    output_size: [usize; 2],
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for InterpolateBenchmark<B> {
+    type Input = Tensor<B, 4>;
+    type Output = Tensor<B, 4>;
+
+    fn name(&self) -> String {
+        let direction = if self.output_size[0] > self.input_shape[2] {
+            "up"
+        } else {
+            "down"
+        };
+        format!(
+            "interpolate-{:?}-{direction}-{:?}",
+            self.mode,
+            B::FloatElem::dtype()
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.input_shape.to_vec(), self.output_size.to_vec()]
+    }
+
+    fn execute(&self, input: Self::Input) -> Self::Output {
+        interpolate(
+            input,
+            self.output_size,
+            InterpolateOptions::new(self.mode.clone()),
+        )
+    }
+
+    fn prepare(&self) -> Self::Input {
+        Tensor::random(
+            self.input_shape.clone(),
+            Distribution::Default,
+            &self.device,
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    // (input shape, output size)
+    let problems = [
+        // Segmentation decoder upsampling a feature map by 4.
+        ([8, 256, 128, 128], [512, 512]),
+        // Diffusion UNet upsampling a latent by 2.
+        ([8, 320, 64, 64], [128, 128]),
+        // Downsampling a batch of images for a classifier.
+        ([16, 3, 1024, 1024], [224, 224]),
+    ];
+
+    for mode in [
+        InterpolateMode::Nearest,
+        InterpolateMode::Bilinear,
+        InterpolateMode::Bicubic,
+    ] {
+        for (input_shape, output_size) in problems {
+            results.push(run_benchmark(InterpolateBenchmark::<B>::new(
+                mode.clone(),
+                input_shape.into(),
+                output_size,
+                device.clone(),
+            )));
+        }
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,86 @@
+use burn::tensor::{
+    Bool, Distribution, Element, Shape, Tensor, activation::softmax, backend::Backend,
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum MaskOp {
+    /// Replace the masked values with a constant.
+    MaskFill,
+    /// Pick the masked values from another tensor.
+    MaskWhere,
+    /// Mask the attention scores before the softmax.
+    AttentionMask,
+}
+
+#[derive(new)]
+struct MaskingBenchmark<B: Backend> {
+    op: MaskOp,
+    shape: Shape,
+    /// Proportion of `true` values in the mask, in percent.
+    density: u32,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for MaskingBenchmark<B> {
+    type Input = (Tensor<B, 4>, Tensor<B, 4, Bool>, Tensor<B, 4>);
+    type Output = Tensor<B, 4>;
+
+    fn name(&self) -> String {
+        let op = match self.op {
+            MaskOp::MaskFill => "mask_fill",
+            MaskOp::MaskWhere => "mask_where",
+            MaskOp::AttentionMask => "attention_mask",
+        };
+        format!("{op}-density{}-{:?}", self.density, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, (tensor, mask, values): Self::Input) -> Self::Output {
+        match self.op {
+            MaskOp::MaskFill => tensor.mask_fill(mask, 0.0),
+            MaskOp::MaskWhere => tensor.mask_where(mask, values),
+            MaskOp::AttentionMask => softmax(tensor.mask_fill(mask, -1.0e4), 3),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let random =
+            || Tensor::<B, 4>::random(self.shape.clone(), Distribution::Default, &self.device);
+        let mask = random().lower_elem(self.density as f64 / 100.0);
+
+        (random(), mask, random())
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+    // Attention scores: [batch_size, n_heads, seq_length, seq_length].
+    let shape: Shape = [8, 16, 1024, 1024].into();
+
+    for op in [MaskOp::MaskFill, MaskOp::MaskWhere, MaskOp::AttentionMask] {
+        for density in [1, 50, 99] {
+            results.push(run_benchmark(MaskingBenchmark::<B>::new(
+                op,
+                shape.clone(),
+                density,
+                device.clone(),
+            )));
+        }
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
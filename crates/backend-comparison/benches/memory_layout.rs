@@ -0,0 +1,121 @@
+use burn::tensor::{Distribution, Element, Shape, Tensor, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum LayoutOp {
+    /// Take the first half of the dimension 1.
+    Slice,
+    /// Overwrite the first half of the dimension 1.
+    SliceAssign,
+    /// Concatenate two halves along the given dimension.
+    Cat(usize),
+    /// Swap the last two dimensions and flatten, which forces a contiguous copy.
+    PermuteContiguous,
+    /// Broadcast along the batch dimension and flatten, which forces a contiguous copy.
+    Expand,
+}
+
+#[derive(new)]
+struct MemoryLayoutBenchmark<B: Backend> {
+    op: LayoutOp,
+    shape: Shape,
+    device: B::Device,
+}
+
+impl<B: Backend> MemoryLayoutBenchmark<B> {
+    fn dims(&self) -> [usize; 3] {
+        [self.shape[0], self.shape[1], self.shape[2]]
+    }
+}
+
+impl<B: Backend> Benchmark for MemoryLayoutBenchmark<B> {
+    type Input = (Tensor<B, 3>, Tensor<B, 3>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        let op = match self.op {
+            LayoutOp::Slice => "slice".to_string(),
+            LayoutOp::SliceAssign => "slice_assign".to_string(),
+            LayoutOp::Cat(dim) => format!("cat-dim{dim}"),
+            LayoutOp::PermuteContiguous => "permute_contiguous".to_string(),
+            LayoutOp::Expand => "expand".to_string(),
+        };
+        format!("{op}-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Self::Output {
+        let [batch, rows, cols] = self.dims();
+
+        match self.op {
+            LayoutOp::Slice => lhs.slice([0..batch, 0..rows / 2, 0..cols]),
+            LayoutOp::SliceAssign => lhs.slice_assign([0..batch, 0..rows / 2, 0..cols], rhs),
+            LayoutOp::Cat(dim) => Tensor::cat(vec![lhs, rhs], dim),
+            LayoutOp::PermuteContiguous => lhs
+                .permute([0, 2, 1])
+                .reshape([batch, cols * rows])
+                .reshape([batch, cols, rows]),
+            LayoutOp::Expand => lhs
+                .expand([batch, rows, cols])
+                .reshape([batch * rows, cols])
+                .reshape([batch, rows, cols]),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let [batch, rows, cols] = self.dims();
+        let random = |shape: [usize; 3]| Tensor::random(shape, Distribution::Default, &self.device);
+
+        match self.op {
+            LayoutOp::Slice | LayoutOp::PermuteContiguous => {
+                (random(self.dims()), Tensor::empty([0, 0, 0], &self.device))
+            }
+            LayoutOp::SliceAssign => (random(self.dims()), random([batch, rows / 2, cols])),
+            LayoutOp::Cat(dim) => {
+                let mut half = self.dims();
+                half[dim] /= 2;
+                (random(half), random(half))
+            }
+            LayoutOp::Expand => (
+                random([1, rows, cols]),
+                Tensor::empty([0, 0, 0], &self.device),
+            ),
+        }
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let ops = [
+        LayoutOp::Slice,
+        LayoutOp::SliceAssign,
+        LayoutOp::Cat(0),
+        LayoutOp::Cat(1),
+        LayoutOp::Cat(2),
+        LayoutOp::PermuteContiguous,
+        LayoutOp::Expand,
+    ];
+    let shape: Shape = [32, 512, 1024].into();
+
+    ops.into_iter()
+        .map(|op| {
+            run_benchmark(MemoryLayoutBenchmark::<B>::new(
+                op,
+                shape.clone(),
+                device.clone(),
+            ))
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
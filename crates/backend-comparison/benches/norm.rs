@@ -0,0 +1,188 @@
+use burn::{
+    nn::{
+        BatchNorm, BatchNormConfig, GroupNorm, GroupNormConfig, LayerNorm, LayerNormConfig,
+        RmsNorm, RmsNormConfig,
+    },
+    tensor::{
+        Distribution, Element, Shape, Tensor,
+        backend::{AutodiffBackend, Backend},
+    },
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum NormKind {
+    /// Normalizes the last dimension.
+    Layer,
+    /// Normalizes the last dimension.
+    Rms,
+    /// Normalizes the channels, the dimension 1.
+    Batch,
+    /// Normalizes groups of channels, the dimension 1.
+    Group(usize),
+}
+
+impl NormKind {
+    fn name(&self) -> &'static str {
+        match self {
+            NormKind::Layer => "layer_norm",
+            NormKind::Rms => "rms_norm",
+            NormKind::Batch => "batch_norm",
+            NormKind::Group(_) => "group_norm",
+        }
+    }
+
+    fn init<B: Backend>(&self, shape: &Shape, device: &B::Device) -> Norm<B> {
+        let dims = shape.to_vec();
+        match self {
+            NormKind::Layer => Norm::Layer(LayerNormConfig::new(dims[dims.len() - 1]).init(device)),
+            NormKind::Rms => Norm::Rms(RmsNormConfig::new(dims[dims.len() - 1]).init(device)),
+            NormKind::Batch => Norm::Batch(BatchNormConfig::new(dims[1]).init(device)),
+            NormKind::Group(num_groups) => {
+                Norm::Group(GroupNormConfig::new(*num_groups, dims[1]).init(device))
+            }
+        }
+    }
+}
+
+enum Norm<B: Backend> {
+    Layer(LayerNorm<B>),
+    Rms(RmsNorm<B>),
+    Batch(BatchNorm<B>),
+    Group(GroupNorm<B>),
+}
+
+impl<B: Backend> Norm<B> {
+    fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        match self {
+            Norm::Layer(norm) => norm.forward(input),
+            Norm::Rms(norm) => norm.forward(input),
+            Norm::Batch(norm) => norm.forward(input),
+            Norm::Group(norm) => norm.forward(input),
+        }
+    }
+}
+
+impl<B: Backend> Clone for Norm<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Norm::Layer(norm) => Norm::Layer(norm.clone()),
+            Norm::Rms(norm) => Norm::Rms(norm.clone()),
+            Norm::Batch(norm) => Norm::Batch(norm.clone()),
+            Norm::Group(norm) => Norm::Group(norm.clone()),
+        }
+    }
+}
+
+#[derive(new)]
+struct NormBenchmark<B: Backend, const D: usize> {
+    kind: NormKind,
+    shape: Shape,
+    device: B::Device,
+}
+
+impl<B: Backend, const D: usize> Benchmark for NormBenchmark<B, D> {
+    type Input = (Norm<B>, Tensor<B, D>);
+    type Output = Tensor<B, D>;
+
+    fn name(&self) -> String {
+        format!("{}-forward-{:?}", self.kind.name(), B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, (norm, input): Self::Input) -> Self::Output {
+        norm.forward(input)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        (
+            self.kind.init(&self.shape, &self.device),
+            Tensor::random(self.shape.clone(), Distribution::Default, &self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[derive(new)]
+struct NormBackwardBenchmark<B: AutodiffBackend, const D: usize> {
+    kind: NormKind,
+    shape: Shape,
+    device: B::Device,
+}
+
+impl<B: AutodiffBackend, const D: usize> Benchmark for NormBackwardBenchmark<B, D> {
+    type Input = (Norm<B>, Tensor<B, D>);
+    type Output = B::Gradients;
+
+    fn name(&self) -> String {
+        format!("{}-backward-{:?}", self.kind.name(), B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, (norm, input): Self::Input) -> Self::Output {
+        norm.forward(input).sum().backward()
+    }
+
+    fn prepare(&self) -> Self::Input {
+        (
+            self.kind.init(&self.shape, &self.device),
+            Tensor::random(self.shape.clone(), Distribution::Default, &self.device).require_grad(),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+fn bench_norm<B: Backend, const D: usize>(
+    kind: NormKind,
+    shape: [usize; D],
+    device: &B::Device,
+    results: &mut Vec<BenchmarkResult>,
+) {
+    results.push(run_benchmark(NormBenchmark::<B, D>::new(
+        kind,
+        shape.into(),
+        device.clone(),
+    )));
+    results.push(run_benchmark(NormBackwardBenchmark::<
+        burn::backend::Autodiff<B>,
+        D,
+    >::new(kind, shape.into(), device.clone())));
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    // Transformer activations: [batch_size, seq_length, d_model].
+    for kind in [NormKind::Layer, NormKind::Rms] {
+        for shape in [[32, 512, 768], [8, 2048, 4096]] {
+            bench_norm::<B, 3>(kind, shape, device, &mut results);
+        }
+    }
+
+    // CNN feature maps: [batch_size, channels, height, width].
+    for kind in [NormKind::Batch, NormKind::Group(32)] {
+        for shape in [[32, 64, 112, 112], [64, 256, 28, 28]] {
+            bench_norm::<B, 4>(kind, shape, device, &mut results);
+        }
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
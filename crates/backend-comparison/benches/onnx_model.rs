@@ -0,0 +1,54 @@
+use burn::tensor::{Element, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+/// Model imported by the build script from the ONNX file given with `--onnx-model`.
+mod onnx {
+    include!(concat!(env!("OUT_DIR"), "/onnx_model.rs"));
+}
+
+#[derive(new)]
+struct OnnxModelBenchmark<B: Backend> {
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for OnnxModelBenchmark<B> {
+    type Input = (onnx::model::Model<B>, onnx::Inputs<B>);
+    type Output = Box<dyn core::any::Any>;
+
+    fn name(&self) -> String {
+        format!("{}-inference-{:?}", onnx::MODEL_NAME, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        onnx::INPUT_SHAPES
+            .iter()
+            .map(|shape| shape.to_vec())
+            .collect()
+    }
+
+    fn execute(&self, (model, inputs): Self::Input) -> Self::Output {
+        onnx::forward(&model, inputs)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        // The weights are initialized randomly, they don't change the duration of the inference.
+        (
+            onnx::model::Model::new(&self.device),
+            onnx::inputs(&self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    vec![run_benchmark(OnnxModelBenchmark::<B>::new(device.clone()))]
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
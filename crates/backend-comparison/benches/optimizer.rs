@@ -0,0 +1,169 @@
+use std::cell::RefCell;
+
+use burn::{
+    nn::{Linear, LinearConfig, Relu},
+    optim::{AdamConfig, AdamWConfig, GradientsParams, Optimizer, SgdConfig},
+    prelude::*,
+    tensor::{Distribution, Element, backend::AutodiffBackend},
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+const LEARNING_RATE: f64 = 1e-4;
+
+#[derive(Module, Debug)]
+pub struct Mlp<B: Backend> {
+    layers: Vec<Linear<B>>,
+    activation: Relu,
+}
+
+impl<B: Backend> Mlp<B> {
+    pub fn new(d_model: usize, num_layers: usize, device: &B::Device) -> Self {
+        Self {
+            layers: (0..num_layers)
+                .map(|_| LinearConfig::new(d_model, d_model).init(device))
+                .collect(),
+            activation: Relu::new(),
+        }
+    }
+
+    pub fn forward(&self, mut x: Tensor<B, 2>) -> Tensor<B, 2> {
+        for layer in self.layers.iter() {
+            x = self.activation.forward(layer.forward(x));
+        }
+        x
+    }
+}
+
+/// Time a single optimizer step over all the parameters of the model.
+///
+/// The gradients are computed while preparing the inputs, and the optimizer already went
+/// through one step so that its state is initialized, only the update itself is measured.
+pub struct OptimizerBenchmark<B: AutodiffBackend, O> {
+    optimizer_name: &'static str,
+    optimizer: O,
+    d_model: usize,
+    num_layers: usize,
+    batch_size: usize,
+    grads: RefCell<Option<GradientsParams>>,
+    device: B::Device,
+}
+
+impl<B: AutodiffBackend, O> OptimizerBenchmark<B, O> {
+    pub fn new(
+        optimizer_name: &'static str,
+        optimizer: O,
+        d_model: usize,
+        num_layers: usize,
+        batch_size: usize,
+        device: B::Device,
+    ) -> Self {
+        Self {
+            optimizer_name,
+            optimizer,
+            d_model,
+            num_layers,
+            batch_size,
+            grads: RefCell::new(None),
+            device,
+        }
+    }
+
+    fn gradients(&self, model: &Mlp<B>) -> GradientsParams {
+        let input = Tensor::random(
+            [self.batch_size, self.d_model],
+            Distribution::Default,
+            &self.device,
+        );
+        let grads = model.forward(input).mean().backward();
+        GradientsParams::from_grads(grads, model)
+    }
+}
+
+impl<B: AutodiffBackend, O: Optimizer<Mlp<B>, B> + Clone> Benchmark for OptimizerBenchmark<B, O> {
+    type Input = (Mlp<B>, O);
+    type Output = Mlp<B>;
+
+    fn name(&self) -> String {
+        format!(
+            "optimizer-{}-step-{:?}",
+            self.optimizer_name,
+            B::FloatElem::dtype()
+        )
+        .to_lowercase()
+    }
+
+    fn options(&self) -> Option<String> {
+        let num_params = self.num_layers * (self.d_model * self.d_model + self.d_model);
+        Some(format!("params={num_params}"))
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.num_layers, self.d_model, self.d_model]]
+    }
+
+    fn execute(&self, (model, mut optimizer): Self::Input) -> Self::Output {
+        let grads = self
+            .grads
+            .borrow_mut()
+            .take()
+            .expect("Gradients should be computed when preparing the inputs");
+        optimizer.step(LEARNING_RATE, model, grads)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let mut optimizer = self.optimizer.clone();
+        let model = Mlp::new(self.d_model, self.num_layers, &self.device);
+        let grads = self.gradients(&model);
+        let model = optimizer.step(LEARNING_RATE, model, grads);
+
+        *self.grads.borrow_mut() = Some(self.gradients(&model));
+        (model, optimizer)
+    }
+
+    fn prepare_cloned(&self) -> bool {
+        // The gradients are consumed by each step.
+        false
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    type AD<Inner> = burn::backend::Autodiff<Inner>;
+    // 8 layers of 1024x1024, a bit more than 8M parameters.
+    let (d_model, num_layers, batch_size) = (1024, 8, 32);
+
+    vec![
+        run_benchmark(OptimizerBenchmark::<AD<B>, _>::new(
+            "sgd",
+            SgdConfig::new().init::<AD<B>, Mlp<AD<B>>>(),
+            d_model,
+            num_layers,
+            batch_size,
+            device.clone(),
+        )),
+        run_benchmark(OptimizerBenchmark::<AD<B>, _>::new(
+            "adam",
+            AdamConfig::new().init::<AD<B>, Mlp<AD<B>>>(),
+            d_model,
+            num_layers,
+            batch_size,
+            device.clone(),
+        )),
+        run_benchmark(OptimizerBenchmark::<AD<B>, _>::new(
+            "adamw",
+            AdamWConfig::new().init::<AD<B>, Mlp<AD<B>>>(),
+            d_model,
+            num_layers,
+            batch_size,
+            device.clone(),
+        )),
+    ]
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,246 @@
+use burn::{
+    module::Quantizer,
+    prelude::*,
+    tensor::{
+        Distribution, Element,
+        quantization::{
+            BlockSize, Calibration, QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore,
+            QuantValue,
+        },
+    },
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+/// Quantization schemes benchmarked, with the tag used in the benchmark names.
+fn schemes() -> Vec<(QuantScheme, &'static str)> {
+    let scheme = |value, store, level| QuantScheme {
+        value,
+        param: QuantParam::F16,
+        store,
+        level,
+        mode: QuantMode::Symmetric,
+    };
+
+    vec![
+        (
+            scheme(QuantValue::Q8S, QuantStore::Native, QuantLevel::Tensor),
+            "q8t",
+        ),
+        (
+            scheme(
+                QuantValue::Q8S,
+                QuantStore::Native,
+                QuantLevel::Block(BlockSize::new([32])),
+            ),
+            "q8b32",
+        ),
+        (
+            scheme(
+                QuantValue::Q8S,
+                QuantStore::PackedU32(0),
+                QuantLevel::Block(BlockSize::new([32])),
+            ),
+            "q8b32-packed",
+        ),
+        (
+            scheme(
+                QuantValue::Q4F,
+                QuantStore::PackedU32(0),
+                QuantLevel::Block(BlockSize::new([32])),
+            ),
+            "q4b32",
+        ),
+    ]
+}
+
+/// Matmul between a float lhs and a quantized rhs, as done with quantized weights.
+struct QuantMatmulBenchmark<B: Backend> {
+    scheme: QuantScheme,
+    scheme_tag: &'static str,
+    shape_lhs: Shape,
+    shape_rhs: Shape,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for QuantMatmulBenchmark<B> {
+    type Input = (Tensor<B, 3>, Tensor<B, 3>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!(
+            "quant-matmul-{}-{:?}",
+            self.scheme_tag,
+            B::FloatElem::dtype()
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape_lhs.to_vec(), self.shape_rhs.to_vec()]
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Self::Output {
+        lhs.matmul(rhs)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        let lhs = Tensor::random(self.shape_lhs.clone(), Distribution::Default, &self.device);
+        let rhs = Tensor::random(self.shape_rhs.clone(), Distribution::Default, &self.device)
+            .quantize_dynamic(&self.scheme);
+
+        (lhs, rhs)
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[derive(Module, Debug)]
+pub struct Mlp<B: Backend> {
+    layers: Vec<nn::Linear<B>>,
+    activation: nn::Gelu,
+}
+
+impl<B: Backend> Mlp<B> {
+    pub fn new(d_model: usize, num_layers: usize, device: &B::Device) -> Self {
+        Self {
+            layers: (0..num_layers)
+                .map(|_| nn::LinearConfig::new(d_model, d_model).init(device))
+                .collect(),
+            activation: nn::Gelu::new(),
+        }
+    }
+
+    pub fn forward(&self, mut x: Tensor<B, 3>) -> Tensor<B, 3> {
+        for layer in self.layers.iter() {
+            x = self.activation.forward(layer.forward(x));
+        }
+        x
+    }
+}
+
+/// End-to-end inference of a model whose weights are all quantized.
+struct QuantModelBenchmark<B: Backend> {
+    scheme_tag: &'static str,
+    model: Mlp<B>,
+    d_model: usize,
+    num_layers: usize,
+    batch_size: usize,
+    seq_length: usize,
+    device: B::Device,
+}
+
+impl<B: Backend> QuantModelBenchmark<B> {
+    fn new(
+        scheme: Option<(QuantScheme, &'static str)>,
+        d_model: usize,
+        num_layers: usize,
+        [batch_size, seq_length]: [usize; 2],
+        device: &B::Device,
+    ) -> Self {
+        let model = Mlp::new(d_model, num_layers, device);
+        let (model, scheme_tag) = match scheme {
+            Some((scheme, tag)) => {
+                let mut quantizer = Quantizer {
+                    calibration: Calibration::MinMax,
+                    scheme,
+                };
+                (model.quantize_weights(&mut quantizer), tag)
+            }
+            None => (model, "float"),
+        };
+
+        Self {
+            scheme_tag,
+            model,
+            d_model,
+            num_layers,
+            batch_size,
+            seq_length,
+            device: device.clone(),
+        }
+    }
+}
+
+impl<B: Backend> Benchmark for QuantModelBenchmark<B> {
+    type Input = Tensor<B, 3>;
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("quant-mlp-{}-{:?}", self.scheme_tag, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            vec![self.batch_size, self.seq_length, self.d_model],
+            vec![self.num_layers, self.d_model, self.d_model],
+        ]
+    }
+
+    fn execute(&self, input: Self::Input) -> Self::Output {
+        self.model.forward(input)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        Tensor::random(
+            [self.batch_size, self.seq_length, self.d_model],
+            Distribution::Default,
+            &self.device,
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    // (lhs, rhs): decoding with a single token, then a prefill of 512 tokens.
+    let problems = [
+        ([1, 1, 4096], [1, 4096, 4096]),
+        ([1, 512, 4096], [1, 4096, 4096]),
+        ([1, 512, 4096], [1, 4096, 11008]),
+    ];
+    for (scheme, scheme_tag) in schemes() {
+        for (shape_lhs, shape_rhs) in problems {
+            results.push(run_benchmark(QuantMatmulBenchmark::<B> {
+                scheme,
+                scheme_tag,
+                shape_lhs: shape_lhs.into(),
+                shape_rhs: shape_rhs.into(),
+                device: device.clone(),
+            }));
+        }
+    }
+
+    // The float model is the reference for the quantized ones.
+    let (d_model, num_layers) = (4096, 8);
+    for batch_sizes in [[1, 1], [1, 512]] {
+        results.push(run_benchmark(QuantModelBenchmark::<B>::new(
+            None,
+            d_model,
+            num_layers,
+            batch_sizes,
+            device,
+        )));
+        for scheme in schemes() {
+            results.push(run_benchmark(QuantModelBenchmark::<B>::new(
+                Some(scheme),
+                d_model,
+                num_layers,
+                batch_sizes,
+                device,
+            )));
+        }
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
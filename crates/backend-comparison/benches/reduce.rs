@@ -1,6 +1,6 @@
 use burn::tensor::Int;
 use burn::tensor::{Distribution, Element, Shape, Tensor, backend::Backend};
-use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use burnbench::{Benchmark, BenchmarkId, BenchmarkResult, run_benchmark};
 
 enum Instruction {
     ArgMin(usize),
@@ -81,6 +81,25 @@ impl<B: Backend> Benchmark for ReduceBenchmark<B> {
         .to_lowercase()
     }
 
+    fn id(&self) -> BenchmarkId {
+        let (variant, axis, fused) = match self.instruction {
+            Instruction::ArgMin(axis) => ("argmin", Some(axis), false),
+            Instruction::ArgMinFused(axis) => ("argmin", Some(axis), true),
+            Instruction::SumDim(axis) => ("sum", Some(axis), false),
+            Instruction::SumDimFused(axis) => ("sum", Some(axis), true),
+            Instruction::Sum => ("sum", None, false),
+        };
+        let dtype = format!("{:?}", B::FloatElem::dtype()).to_lowercase();
+        let id = BenchmarkId::new("reduce")
+            .with_variant(variant)
+            .with_param("dtype", dtype)
+            .with_param("fused", fused);
+        match axis {
+            Some(axis) => id.with_param("axis", axis),
+            None => id.with_param("axis", "full"),
+        }
+    }
+
     fn sync(&self) {
         B::sync(&self.device).unwrap();
     }
@@ -0,0 +1,195 @@
+use burn::{
+    nn::{Gru, GruConfig, Lstm, LstmConfig},
+    tensor::{
+        Distribution, Element, Tensor,
+        backend::{AutodiffBackend, Backend},
+    },
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum RnnKind {
+    Lstm,
+    Gru,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RnnProblem {
+    batch_size: usize,
+    seq_length: usize,
+    d_input: usize,
+    d_hidden: usize,
+}
+
+impl RnnProblem {
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            vec![self.batch_size, self.seq_length, self.d_input],
+            vec![self.d_input, self.d_hidden],
+        ]
+    }
+}
+
+enum Rnn<B: Backend> {
+    Lstm(Lstm<B>),
+    Gru(Gru<B>),
+}
+
+impl<B: Backend> Rnn<B> {
+    fn init(kind: RnnKind, problem: &RnnProblem, device: &B::Device) -> Self {
+        match kind {
+            RnnKind::Lstm => {
+                Rnn::Lstm(LstmConfig::new(problem.d_input, problem.d_hidden, true).init(device))
+            }
+            RnnKind::Gru => {
+                Rnn::Gru(GruConfig::new(problem.d_input, problem.d_hidden, true).init(device))
+            }
+        }
+    }
+
+    fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
+        match self {
+            Rnn::Lstm(lstm) => lstm.forward(input, None).0,
+            Rnn::Gru(gru) => gru.forward(input, None),
+        }
+    }
+}
+
+impl<B: Backend> Clone for Rnn<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Rnn::Lstm(lstm) => Rnn::Lstm(lstm.clone()),
+            Rnn::Gru(gru) => Rnn::Gru(gru.clone()),
+        }
+    }
+}
+
+fn prepare<B: Backend>(
+    kind: RnnKind,
+    problem: &RnnProblem,
+    device: &B::Device,
+) -> (Rnn<B>, Tensor<B, 3>) {
+    (
+        Rnn::init(kind, problem, device),
+        Tensor::random(
+            [problem.batch_size, problem.seq_length, problem.d_input],
+            Distribution::Default,
+            device,
+        ),
+    )
+}
+
+#[derive(new)]
+struct RnnBenchmark<B: Backend> {
+    kind: RnnKind,
+    problem: RnnProblem,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for RnnBenchmark<B> {
+    type Input = (Rnn<B>, Tensor<B, 3>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("{:?}-forward-{:?}", self.kind, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.problem.shapes()
+    }
+
+    fn execute(&self, (rnn, input): Self::Input) -> Self::Output {
+        rnn.forward(input)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        prepare(self.kind, &self.problem, &self.device)
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[derive(new)]
+struct RnnBackwardBenchmark<B: AutodiffBackend> {
+    kind: RnnKind,
+    problem: RnnProblem,
+    device: B::Device,
+}
+
+impl<B: AutodiffBackend> Benchmark for RnnBackwardBenchmark<B> {
+    type Input = (Rnn<B>, Tensor<B, 3>);
+    type Output = B::Gradients;
+
+    fn name(&self) -> String {
+        format!("{:?}-backward-{:?}", self.kind, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.problem.shapes()
+    }
+
+    fn execute(&self, (rnn, input): Self::Input) -> Self::Output {
+        rnn.forward(input).sum().backward()
+    }
+
+    fn prepare(&self) -> Self::Input {
+        prepare(self.kind, &self.problem, &self.device)
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    let problems = [
+        // Small streaming model, e.g. keyword spotting.
+        RnnProblem {
+            batch_size: 1,
+            seq_length: 100,
+            d_input: 40,
+            d_hidden: 128,
+        },
+        // Sequence tagging.
+        RnnProblem {
+            batch_size: 32,
+            seq_length: 128,
+            d_input: 256,
+            d_hidden: 512,
+        },
+        // Speech encoder over long sequences.
+        RnnProblem {
+            batch_size: 8,
+            seq_length: 512,
+            d_input: 512,
+            d_hidden: 1024,
+        },
+    ];
+
+    for kind in [RnnKind::Lstm, RnnKind::Gru] {
+        for problem in problems {
+            results.push(run_benchmark(RnnBenchmark::<B>::new(
+                kind,
+                problem,
+                device.clone(),
+            )));
+            results.push(run_benchmark(RnnBackwardBenchmark::<
+                burn::backend::Autodiff<B>,
+            >::new(
+                kind, problem, device.clone()
+            )));
+        }
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,72 @@
+use burn::tensor::{Distribution, Element, Shape, Tensor, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+/// Execute a long chain of tiny dependent operations, where the duration is dominated by the
+/// dispatch of each operation rather than the computation itself.
+#[derive(new)]
+struct SmallOpsBenchmark<B: Backend> {
+    shape: Shape,
+    num_ops: usize,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for SmallOpsBenchmark<B> {
+    type Input = (Tensor<B, 3>, Tensor<B, 3>);
+    type Output = Tensor<B, 3>;
+
+    fn name(&self) -> String {
+        format!("small-ops-{}-{:?}", self.num_ops, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn options(&self) -> Option<String> {
+        Some(format!("ops={}", self.num_ops))
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn work(&self) -> Option<(f64, String)> {
+        Some((self.num_ops as f64, "ops".to_string()))
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Self::Output {
+        let mut tensor = lhs;
+        for _ in 0..self.num_ops {
+            tensor = tensor.add(rhs.clone());
+        }
+        tensor
+    }
+
+    fn prepare(&self) -> Self::Input {
+        (
+            Tensor::random(self.shape.clone(), Distribution::Default, &self.device),
+            Tensor::random(self.shape.clone(), Distribution::Default, &self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let shape: Shape = [1, 16, 16].into();
+
+    [1_000, 10_000]
+        .into_iter()
+        .map(|num_ops| {
+            run_benchmark(SmallOpsBenchmark::<B>::new(
+                shape.clone(),
+                num_ops,
+                device.clone(),
+            ))
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
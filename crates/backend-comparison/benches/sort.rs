@@ -0,0 +1,89 @@
+use burn::tensor::{Distribution, Element, Int, Shape, Tensor, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum SortOp {
+    Sort,
+    Argsort,
+    Topk(usize),
+}
+
+enum SortOutput<B: Backend> {
+    Values(Tensor<B, 2>),
+    Indices(Tensor<B, 2, Int>),
+}
+
+#[derive(new)]
+struct SortBenchmark<B: Backend> {
+    op: SortOp,
+    shape: Shape,
+    dim: usize,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for SortBenchmark<B> {
+    type Input = Tensor<B, 2>;
+    type Output = SortOutput<B>;
+
+    fn name(&self) -> String {
+        let dtype = B::FloatElem::dtype();
+        match self.op {
+            SortOp::Sort => format!("sort-dim{}-{dtype:?}", self.dim),
+            SortOp::Argsort => format!("argsort-dim{}-{dtype:?}", self.dim),
+            SortOp::Topk(k) => format!("topk{k}-dim{}-{dtype:?}", self.dim),
+        }
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, tensor: Self::Input) -> Self::Output {
+        match self.op {
+            SortOp::Sort => SortOutput::Values(tensor.sort(self.dim)),
+            SortOp::Argsort => SortOutput::Indices(tensor.argsort(self.dim)),
+            SortOp::Topk(k) => SortOutput::Values(tensor.topk(k, self.dim)),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        Tensor::random(self.shape.clone(), Distribution::Default, &self.device)
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    // Logits of a batch of beams/sequences over the vocabulary, sorted along the vocabulary.
+    let logits_shapes: [[usize; 2]; 3] = [[8, 32_000], [64, 32_000], [16, 128_000]];
+    // A long column, sorted along the first dimension.
+    let column_shape: [usize; 2] = [65_536, 64];
+
+    for op in [
+        SortOp::Sort,
+        SortOp::Argsort,
+        SortOp::Topk(4),
+        SortOp::Topk(50),
+    ] {
+        for shape in logits_shapes {
+            let benchmark = SortBenchmark::<B>::new(op, shape.into(), 1, device.clone());
+            results.push(run_benchmark(benchmark));
+        }
+
+        let benchmark = SortBenchmark::<B>::new(op, column_shape.into(), 0, device.clone());
+        results.push(run_benchmark(benchmark));
+    }
+
+    results
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
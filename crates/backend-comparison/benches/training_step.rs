@@ -0,0 +1,266 @@
+use burn::{
+    backend::Autodiff,
+    module::AutodiffModule,
+    nn::{
+        Embedding, EmbeddingConfig, Linear, LinearConfig, PaddingConfig2d, Relu,
+        conv::{Conv2d, Conv2dConfig},
+        loss::CrossEntropyLossConfig,
+        pool::{AdaptiveAvgPool2d, AdaptiveAvgPool2dConfig, MaxPool2d, MaxPool2dConfig},
+        transformer::{TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput},
+    },
+    optim::{AdamWConfig, GradientsParams, Optimizer},
+    prelude::*,
+    tensor::{Distribution, Element, backend::AutodiffBackend},
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+const LEARNING_RATE: f64 = 1e-4;
+
+/// A model that can compute its training loss on a batch.
+pub trait TrainingModel<B: AutodiffBackend>: AutodiffModule<B> {
+    type Batch: Clone;
+
+    fn loss(&self, batch: Self::Batch) -> Tensor<B, 1>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageBatch<B: Backend> {
+    pub images: Tensor<B, 4>,
+    pub targets: Tensor<B, 1, Int>,
+}
+
+/// CIFAR-scale CNN: three convolution blocks followed by a classification head.
+#[derive(Module, Debug)]
+pub struct Cnn<B: Backend> {
+    conv1: Conv2d<B>,
+    conv2: Conv2d<B>,
+    conv3: Conv2d<B>,
+    pool: MaxPool2d,
+    global_pool: AdaptiveAvgPool2d,
+    output: Linear<B>,
+    activation: Relu,
+}
+
+impl<B: Backend> Cnn<B> {
+    pub fn new(num_classes: usize, device: &B::Device) -> Self {
+        let conv = |channels: [usize; 2]| {
+            Conv2dConfig::new(channels, [3, 3])
+                .with_padding(PaddingConfig2d::Same)
+                .init(device)
+        };
+
+        Self {
+            conv1: conv([3, 32]),
+            conv2: conv([32, 64]),
+            conv3: conv([64, 128]),
+            pool: MaxPool2dConfig::new([2, 2]).with_strides([2, 2]).init(),
+            global_pool: AdaptiveAvgPool2dConfig::new([1, 1]).init(),
+            output: LinearConfig::new(128, num_classes).init(device),
+            activation: Relu::new(),
+        }
+    }
+
+    pub fn forward(&self, images: Tensor<B, 4>) -> Tensor<B, 2> {
+        let [batch_size, ..] = images.dims();
+
+        let x = self
+            .pool
+            .forward(self.activation.forward(self.conv1.forward(images)));
+        let x = self
+            .pool
+            .forward(self.activation.forward(self.conv2.forward(x)));
+        let x = self
+            .global_pool
+            .forward(self.activation.forward(self.conv3.forward(x)));
+
+        self.output.forward(x.reshape([batch_size, 128]))
+    }
+}
+
+impl<B: AutodiffBackend> TrainingModel<B> for Cnn<B> {
+    type Batch = ImageBatch<B>;
+
+    fn loss(&self, batch: Self::Batch) -> Tensor<B, 1> {
+        let output = self.forward(batch.images);
+        CrossEntropyLossConfig::new()
+            .init(&output.device())
+            .forward(output, batch.targets)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenBatch<B: Backend> {
+    pub tokens: Tensor<B, 2, Int>,
+    pub labels: Tensor<B, 1, Int>,
+}
+
+/// Small transformer encoder classifying token sequences from their first token.
+#[derive(Module, Debug)]
+pub struct Transformer<B: Backend> {
+    transformer: TransformerEncoder<B>,
+    embedding_token: Embedding<B>,
+    embedding_pos: Embedding<B>,
+    output: Linear<B>,
+}
+
+impl<B: Backend> Transformer<B> {
+    pub fn new(
+        config: &TransformerEncoderConfig,
+        vocab_size: usize,
+        max_seq_length: usize,
+        num_classes: usize,
+        device: &B::Device,
+    ) -> Self {
+        Self {
+            transformer: config.init(device),
+            embedding_token: EmbeddingConfig::new(vocab_size, config.d_model).init(device),
+            embedding_pos: EmbeddingConfig::new(max_seq_length, config.d_model).init(device),
+            output: LinearConfig::new(config.d_model, num_classes).init(device),
+        }
+    }
+
+    pub fn forward(&self, tokens: Tensor<B, 2, Int>) -> Tensor<B, 2> {
+        let [batch_size, seq_length] = tokens.dims();
+        let positions = Tensor::arange(0..seq_length as i64, &tokens.device())
+            .reshape([1, seq_length])
+            .repeat_dim(0, batch_size);
+        let embedding =
+            self.embedding_token.forward(tokens) + self.embedding_pos.forward(positions);
+
+        let encoded = self
+            .transformer
+            .forward(TransformerEncoderInput::new(embedding));
+        let [_, _, d_model] = encoded.dims();
+
+        self.output.forward(
+            encoded
+                .slice([0..batch_size, 0..1])
+                .reshape([batch_size, d_model]),
+        )
+    }
+}
+
+impl<B: AutodiffBackend> TrainingModel<B> for Transformer<B> {
+    type Batch = TokenBatch<B>;
+
+    fn loss(&self, batch: Self::Batch) -> Tensor<B, 1> {
+        let output = self.forward(batch.tokens);
+        CrossEntropyLossConfig::new()
+            .init(&output.device())
+            .forward(output, batch.labels)
+    }
+}
+
+fn train_step<B, M, O>(model: M, optimizer: &mut O, batch: M::Batch) -> M
+where
+    B: AutodiffBackend,
+    M: TrainingModel<B>,
+    O: Optimizer<M, B>,
+{
+    let grads = model.loss(batch).backward();
+    let grads = GradientsParams::from_grads(grads, &model);
+    optimizer.step(LEARNING_RATE, model, grads)
+}
+
+/// Time a full training iteration: forward, loss, backward and optimizer update.
+pub struct TrainingStepBenchmark<B: AutodiffBackend, M: TrainingModel<B>, O> {
+    model_name: &'static str,
+    shapes: Vec<Vec<usize>>,
+    model: M,
+    batch: M::Batch,
+    optimizer: O,
+    device: B::Device,
+}
+
+impl<B, M, O> Benchmark for TrainingStepBenchmark<B, M, O>
+where
+    B: AutodiffBackend,
+    M: TrainingModel<B>,
+    O: Optimizer<M, B> + Clone,
+{
+    type Input = (M, O, M::Batch);
+    type Output = M;
+
+    fn name(&self) -> String {
+        format!(
+            "training-step-{}-{:?}",
+            self.model_name,
+            B::FloatElem::dtype()
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.shapes.clone()
+    }
+
+    fn execute(&self, (model, mut optimizer, batch): Self::Input) -> Self::Output {
+        train_step(model, &mut optimizer, batch)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        // One step beforehand so that the optimizer state is initialized.
+        let mut optimizer = self.optimizer.clone();
+        let model = train_step(self.model.clone(), &mut optimizer, self.batch.clone());
+        (model, optimizer, self.batch.clone())
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    let num_classes = 10;
+
+    // CIFAR-10 sized images.
+    let (batch_size, image_size) = (64, 32);
+    let cnn = TrainingStepBenchmark::<Autodiff<B>, _, _> {
+        model_name: "cnn",
+        shapes: vec![vec![batch_size, 3, image_size, image_size]],
+        model: Cnn::new(num_classes, device),
+        batch: ImageBatch {
+            images: Tensor::random(
+                [batch_size, 3, image_size, image_size],
+                Distribution::Default,
+                device,
+            ),
+            targets: Tensor::random(
+                [batch_size],
+                Distribution::Uniform(0.0, num_classes as f64),
+                device,
+            ),
+        },
+        optimizer: AdamWConfig::new().init(),
+        device: device.clone(),
+    };
+
+    let (batch_size, seq_length, vocab_size) = (32, 128, 10_000);
+    let config = TransformerEncoderConfig::new(256, 1024, 8, 4).with_norm_first(true);
+    let transformer = TrainingStepBenchmark::<Autodiff<B>, _, _> {
+        model_name: "transformer",
+        shapes: vec![vec![batch_size, seq_length]],
+        model: Transformer::new(&config, vocab_size, seq_length, num_classes, device),
+        batch: TokenBatch {
+            tokens: Tensor::random(
+                [batch_size, seq_length],
+                Distribution::Uniform(0.0, vocab_size as f64),
+                device,
+            ),
+            labels: Tensor::random(
+                [batch_size],
+                Distribution::Uniform(0.0, num_classes as f64),
+                device,
+            ),
+        },
+        optimizer: AdamWConfig::new().init(),
+        device: device.clone(),
+    };
+
+    vec![run_benchmark(cnn), run_benchmark(transformer)]
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
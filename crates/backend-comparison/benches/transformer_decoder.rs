@@ -0,0 +1,206 @@
+use burn::{
+    nn::{Embedding, EmbeddingConfig, Linear, LinearConfig, RmsNorm, RmsNormConfig},
+    prelude::*,
+    tensor::{
+        Element,
+        activation::{silu, softmax},
+    },
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+#[derive(Config, Debug)]
+pub struct DecoderConfig {
+    d_model: usize,
+    d_ff: usize,
+    n_heads: usize,
+    n_layers: usize,
+    vocab_size: usize,
+}
+
+/// Llama-style decoder block: pre-norm self-attention followed by a SwiGLU feed-forward.
+#[derive(Module, Debug)]
+pub struct DecoderBlock<B: Backend> {
+    attention_norm: RmsNorm<B>,
+    query: Linear<B>,
+    key: Linear<B>,
+    value: Linear<B>,
+    output: Linear<B>,
+    ffn_norm: RmsNorm<B>,
+    gate: Linear<B>,
+    up: Linear<B>,
+    down: Linear<B>,
+    n_heads: usize,
+}
+
+#[derive(Module, Debug)]
+pub struct Decoder<B: Backend> {
+    embedding: Embedding<B>,
+    blocks: Vec<DecoderBlock<B>>,
+    norm: RmsNorm<B>,
+    lm_head: Linear<B>,
+}
+
+/// Keys and values of the previous tokens for each block, `[batch_size, n_heads, seq_len, d_head]`.
+type KvCache<B> = Vec<Option<(Tensor<B, 4>, Tensor<B, 4>)>>;
+
+impl DecoderConfig {
+    pub fn init<B: Backend>(&self, device: &B::Device) -> Decoder<B> {
+        let linear = |d_input, d_output| {
+            LinearConfig::new(d_input, d_output)
+                .with_bias(false)
+                .init(device)
+        };
+        let blocks = (0..self.n_layers)
+            .map(|_| DecoderBlock {
+                attention_norm: RmsNormConfig::new(self.d_model).init(device),
+                query: linear(self.d_model, self.d_model),
+                key: linear(self.d_model, self.d_model),
+                value: linear(self.d_model, self.d_model),
+                output: linear(self.d_model, self.d_model),
+                ffn_norm: RmsNormConfig::new(self.d_model).init(device),
+                gate: linear(self.d_model, self.d_ff),
+                up: linear(self.d_model, self.d_ff),
+                down: linear(self.d_ff, self.d_model),
+                n_heads: self.n_heads,
+            })
+            .collect();
+
+        Decoder {
+            embedding: EmbeddingConfig::new(self.vocab_size, self.d_model).init(device),
+            blocks,
+            norm: RmsNormConfig::new(self.d_model).init(device),
+            lm_head: linear(self.d_model, self.vocab_size),
+        }
+    }
+}
+
+impl<B: Backend> DecoderBlock<B> {
+    /// Process a single token `[batch_size, 1, d_model]`, appending its key and value to the cache.
+    fn forward_step(
+        &self,
+        x: Tensor<B, 3>,
+        cache: &mut Option<(Tensor<B, 4>, Tensor<B, 4>)>,
+    ) -> Tensor<B, 3> {
+        let [batch_size, _, d_model] = x.dims();
+        let d_head = d_model / self.n_heads;
+        let heads = |tensor: Tensor<B, 3>| {
+            tensor
+                .reshape([batch_size, 1, self.n_heads, d_head])
+                .swap_dims(1, 2)
+        };
+
+        let normed = self.attention_norm.forward(x.clone());
+        let query = heads(self.query.forward(normed.clone()));
+        let mut key = heads(self.key.forward(normed.clone()));
+        let mut value = heads(self.value.forward(normed));
+
+        if let Some((cached_key, cached_value)) = cache.take() {
+            key = Tensor::cat(vec![cached_key, key], 2);
+            value = Tensor::cat(vec![cached_value, value], 2);
+        }
+        *cache = Some((key.clone(), value.clone()));
+
+        // A single query attends to every previous token, no causal mask is required.
+        let scores = query.matmul(key.swap_dims(2, 3)) / (d_head as f64).sqrt();
+        let attention = softmax(scores, 3)
+            .matmul(value)
+            .swap_dims(1, 2)
+            .reshape([batch_size, 1, d_model]);
+        let x = x + self.output.forward(attention);
+
+        let normed = self.ffn_norm.forward(x.clone());
+        let ffn = silu(self.gate.forward(normed.clone())) * self.up.forward(normed);
+        x + self.down.forward(ffn)
+    }
+}
+
+impl<B: Backend> Decoder<B> {
+    /// Greedily generate `num_tokens` tokens from the start tokens `[batch_size, 1]`.
+    pub fn generate(&self, tokens: Tensor<B, 2, Int>, num_tokens: usize) -> Tensor<B, 2, Int> {
+        let [batch_size, _] = tokens.dims();
+        let mut cache: KvCache<B> = vec![None; self.blocks.len()];
+        let mut token = tokens;
+        let mut generated = Vec::with_capacity(num_tokens);
+
+        for _ in 0..num_tokens {
+            let mut x = self.embedding.forward(token);
+            for (block, cache) in self.blocks.iter().zip(cache.iter_mut()) {
+                x = block.forward_step(x, cache);
+            }
+            let logits = self.lm_head.forward(self.norm.forward(x));
+            token = logits.argmax(2).reshape([batch_size, 1]);
+            generated.push(token.clone());
+        }
+
+        Tensor::cat(generated, 1)
+    }
+}
+
+pub struct TransformerDecoderBenchmark<B: Backend> {
+    batch_size: usize,
+    num_tokens: usize,
+    config: DecoderConfig,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for TransformerDecoderBenchmark<B> {
+    type Input = (Decoder<B>, Tensor<B, 2, Int>);
+    type Output = Tensor<B, 2, Int>;
+
+    fn name(&self) -> String {
+        format!("transformer-decoder-kv-cache-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn options(&self) -> Option<String> {
+        Some(format!("tokens={}", self.num_tokens))
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.batch_size, self.num_tokens]]
+    }
+
+    fn work(&self) -> Option<(f64, String)> {
+        Some((
+            (self.batch_size * self.num_tokens) as f64,
+            "tokens".to_string(),
+        ))
+    }
+
+    fn execute(&self, (model, tokens): Self::Input) -> Self::Output {
+        model.generate(tokens, self.num_tokens)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        (
+            self.config.init(&self.device),
+            Tensor::zeros([self.batch_size, 1], &self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    // Something similar to a small Llama model.
+    let config = DecoderConfig::new(512, 1408, 8, 4, 32_000);
+    let num_tokens = 128;
+
+    [1, 8]
+        .into_iter()
+        .map(|batch_size| {
+            run_benchmark(TransformerDecoderBenchmark::<B> {
+                batch_size,
+                num_tokens,
+                config: config.clone(),
+                device: device.clone(),
+            })
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
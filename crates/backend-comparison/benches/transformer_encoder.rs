@@ -132,17 +132,27 @@ impl<B: Backend> Model<B> {
 }
 
 pub struct TransformerEncoderBenchmark<B: Backend, const AD: bool> {
+    model_name: &'static str,
     shape: Shape,
     device: B::Device,
     config: ModelConfig,
 }
 
+impl<B: Backend, const AD: bool> TransformerEncoderBenchmark<B, AD> {
+    fn tokens(&self) -> Tensor<B, 2, Int> {
+        // Wrap around the vocabulary for the large shapes.
+        Tensor::arange(0..self.shape.num_elements() as i64, &self.device)
+            .remainder_scalar(self.config.vocab_size as i64)
+            .reshape(self.shape.clone())
+    }
+}
+
 impl<B: AutodiffBackend> Benchmark for TransformerEncoderBenchmark<B, true> {
     type Input = (Model<B>, TrainingBatch<B>);
     type Output = B::Gradients;
 
     fn name(&self) -> String {
-        format!("transformer-encoder-training-{:?}", B::FloatElem::dtype()).to_lowercase()
+        format!("{}-training-{:?}", self.model_name, B::FloatElem::dtype()).to_lowercase()
     }
 
     fn shapes(&self) -> Vec<Vec<usize>> {
@@ -158,8 +168,7 @@ impl<B: AutodiffBackend> Benchmark for TransformerEncoderBenchmark<B, true> {
         (
             self.config.init(&self.device),
             TrainingBatch {
-                tokens: Tensor::arange(0..self.shape.num_elements() as i64, &self.device)
-                    .reshape(self.shape.clone()),
+                tokens: self.tokens(),
                 labels: Tensor::arange(0..self.shape[0] as i64, &self.device),
                 mask_pad: Tensor::<B, 2>::zeros(self.shape.clone(), &self.device).equal_elem(0.0),
             },
@@ -176,7 +185,7 @@ impl<B: Backend> Benchmark for TransformerEncoderBenchmark<B, false> {
     type Output = Tensor<B, 2>;
 
     fn name(&self) -> String {
-        format!("transformer-encoder-inference-{:?}", B::FloatElem::dtype()).to_lowercase()
+        format!("{}-inference-{:?}", self.model_name, B::FloatElem::dtype()).to_lowercase()
     }
 
     fn shapes(&self) -> Vec<Vec<usize>> {
@@ -191,8 +200,7 @@ impl<B: Backend> Benchmark for TransformerEncoderBenchmark<B, false> {
         (
             self.config.init(&self.device),
             InferenceBatch {
-                tokens: Tensor::arange(0..self.shape.num_elements() as i64, &self.device)
-                    .reshape(self.shape.clone()),
+                tokens: self.tokens(),
                 mask_pad: Tensor::<B, 2>::zeros(self.shape.clone(), &self.device).equal_elem(0.0),
             },
         )
@@ -217,20 +225,44 @@ fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
     let sequence_length = 256;
     let shape = [batch_size, sequence_length];
     let benchmark_inference = TransformerEncoderBenchmark::<B, false> {
+        model_name: "transformer-encoder",
         shape: shape.into(),
         device: device.clone(),
         config: config.clone(),
     };
     let benchmark_training = TransformerEncoderBenchmark::<Autodiff<B>, true> {
+        model_name: "transformer-encoder",
         shape: shape.into(),
         device: device.clone(),
         config,
     };
 
-    vec![
+    let mut results = vec![
         run_benchmark(benchmark_inference),
         run_benchmark(benchmark_training),
-    ]
+    ];
+
+    // Something similar to BERT-base, to track how the inference scales with the sequence
+    // length and the batch size.
+    let max_seq_length = 2048;
+    let config = ModelConfig::new(
+        TransformerEncoderConfig::new(768, 3072, 12, 12),
+        2,
+        30_522,
+        max_seq_length,
+    );
+    for sequence_length in [128, 512, max_seq_length] {
+        for batch_size in [1, 8, 32] {
+            results.push(run_benchmark(TransformerEncoderBenchmark::<B, false> {
+                model_name: "bert-encoder",
+                shape: [batch_size, sequence_length].into(),
+                device: device.clone(),
+                config: config.clone(),
+            }));
+        }
+    }
+
+    results
 }
 
 fn main() {
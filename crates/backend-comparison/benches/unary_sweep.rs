@@ -0,0 +1,96 @@
+use burn::tensor::{
+    Distribution, Element, Shape, Tensor,
+    activation::{gelu, sigmoid},
+    backend::Backend,
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+use derive_new::new;
+
+#[derive(Debug, Clone, Copy)]
+enum UnaryOp {
+    Exp,
+    Log,
+    Tanh,
+    Erf,
+    Sqrt,
+    Sigmoid,
+    Gelu,
+}
+
+impl UnaryOp {
+    const ALL: [UnaryOp; 7] = [
+        UnaryOp::Exp,
+        UnaryOp::Log,
+        UnaryOp::Tanh,
+        UnaryOp::Erf,
+        UnaryOp::Sqrt,
+        UnaryOp::Sigmoid,
+        UnaryOp::Gelu,
+    ];
+}
+
+#[derive(new)]
+struct UnaryOpBenchmark<B: Backend, const D: usize> {
+    op: UnaryOp,
+    shape: Shape,
+    device: B::Device,
+}
+
+impl<B: Backend, const D: usize> Benchmark for UnaryOpBenchmark<B, D> {
+    type Input = Tensor<B, D>;
+    type Output = Tensor<B, D>;
+
+    fn name(&self) -> String {
+        format!("unary-{:?}-{:?}", self.op, B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, args: Self::Input) -> Self::Output {
+        match self.op {
+            UnaryOp::Exp => args.exp(),
+            UnaryOp::Log => args.log(),
+            UnaryOp::Tanh => args.tanh(),
+            UnaryOp::Erf => args.erf(),
+            UnaryOp::Sqrt => args.sqrt(),
+            UnaryOp::Sigmoid => sigmoid(args),
+            UnaryOp::Gelu => gelu(args),
+        }
+    }
+
+    fn prepare(&self) -> Self::Input {
+        // Strictly positive values so that log and sqrt stay in their domain.
+        Tensor::random(
+            self.shape.clone(),
+            Distribution::Uniform(0.01, 1.0),
+            &self.device,
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    const D: usize = 3;
+    let shape: Shape = [32, 512, 1024].into();
+
+    UnaryOp::ALL
+        .into_iter()
+        .map(|op| {
+            run_benchmark(UnaryOpBenchmark::<B, D>::new(
+                op,
+                shape.clone(),
+                device.clone(),
+            ))
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
@@ -0,0 +1,187 @@
+use burn::{
+    nn::{
+        GroupNorm, GroupNormConfig, PaddingConfig2d,
+        attention::{MhaInput, MultiHeadAttention, MultiHeadAttentionConfig},
+        conv::{Conv2d, Conv2dConfig},
+    },
+    prelude::*,
+    tensor::{
+        Distribution, Element,
+        activation::silu,
+        module::interpolate,
+        ops::{InterpolateMode, InterpolateOptions},
+    },
+};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+const NUM_GROUPS: usize = 32;
+
+fn conv3x3<B: Backend>(channels: [usize; 2], stride: usize, device: &B::Device) -> Conv2d<B> {
+    Conv2dConfig::new(channels, [3, 3])
+        .with_stride([stride, stride])
+        .with_padding(PaddingConfig2d::Explicit(1, 1))
+        .init(device)
+}
+
+#[derive(Module, Debug)]
+pub struct ResBlock<B: Backend> {
+    norm1: GroupNorm<B>,
+    conv1: Conv2d<B>,
+    norm2: GroupNorm<B>,
+    conv2: Conv2d<B>,
+    skip: Option<Conv2d<B>>,
+}
+
+impl<B: Backend> ResBlock<B> {
+    pub fn new(channels_in: usize, channels_out: usize, device: &B::Device) -> Self {
+        let skip = (channels_in != channels_out)
+            .then(|| Conv2dConfig::new([channels_in, channels_out], [1, 1]).init(device));
+
+        Self {
+            norm1: GroupNormConfig::new(NUM_GROUPS, channels_in).init(device),
+            conv1: conv3x3([channels_in, channels_out], 1, device),
+            norm2: GroupNormConfig::new(NUM_GROUPS, channels_out).init(device),
+            conv2: conv3x3([channels_out, channels_out], 1, device),
+            skip,
+        }
+    }
+
+    pub fn forward(&self, x: Tensor<B, 4>) -> Tensor<B, 4> {
+        let h = self.conv1.forward(silu(self.norm1.forward(x.clone())));
+        let h = self.conv2.forward(silu(self.norm2.forward(h)));
+        let skip = match &self.skip {
+            Some(skip) => skip.forward(x),
+            None => x,
+        };
+        h + skip
+    }
+}
+
+/// Self-attention over the spatial positions of the feature map.
+#[derive(Module, Debug)]
+pub struct AttentionBlock<B: Backend> {
+    norm: GroupNorm<B>,
+    attention: MultiHeadAttention<B>,
+}
+
+impl<B: Backend> AttentionBlock<B> {
+    pub fn new(channels: usize, n_heads: usize, device: &B::Device) -> Self {
+        Self {
+            norm: GroupNormConfig::new(NUM_GROUPS, channels).init(device),
+            attention: MultiHeadAttentionConfig::new(channels, n_heads).init(device),
+        }
+    }
+
+    pub fn forward(&self, x: Tensor<B, 4>) -> Tensor<B, 4> {
+        let [batch_size, channels, height, width] = x.dims();
+        let h = self
+            .norm
+            .forward(x.clone())
+            .reshape([batch_size, channels, height * width])
+            .swap_dims(1, 2);
+        let h = self.attention.forward(MhaInput::self_attn(h)).context;
+        let h = h
+            .swap_dims(1, 2)
+            .reshape([batch_size, channels, height, width]);
+        x + h
+    }
+}
+
+/// One resolution level of a diffusion UNet: a downsampling path and the matching upsampling
+/// path with its skip connection.
+#[derive(Module, Debug)]
+pub struct UNetBlock<B: Backend> {
+    down_res: ResBlock<B>,
+    down_attn: AttentionBlock<B>,
+    downsample: Conv2d<B>,
+    mid: ResBlock<B>,
+    upsample: Conv2d<B>,
+    up_res: ResBlock<B>,
+    up_attn: AttentionBlock<B>,
+}
+
+impl<B: Backend> UNetBlock<B> {
+    pub fn new(channels: usize, n_heads: usize, device: &B::Device) -> Self {
+        Self {
+            down_res: ResBlock::new(channels, channels, device),
+            down_attn: AttentionBlock::new(channels, n_heads, device),
+            downsample: conv3x3([channels, channels], 2, device),
+            mid: ResBlock::new(channels, channels, device),
+            upsample: conv3x3([channels, channels], 1, device),
+            up_res: ResBlock::new(2 * channels, channels, device),
+            up_attn: AttentionBlock::new(channels, n_heads, device),
+        }
+    }
+
+    pub fn forward(&self, x: Tensor<B, 4>) -> Tensor<B, 4> {
+        let [_, _, height, width] = x.dims();
+
+        let skip = self.down_attn.forward(self.down_res.forward(x));
+        let h = self.mid.forward(self.downsample.forward(skip.clone()));
+
+        let h = interpolate(
+            h,
+            [height, width],
+            InterpolateOptions::new(InterpolateMode::Nearest),
+        );
+        let h = self.upsample.forward(h);
+        let h = self.up_res.forward(Tensor::cat(vec![h, skip], 1));
+        self.up_attn.forward(h)
+    }
+}
+
+pub struct UNetBenchmark<B: Backend> {
+    shape: Shape,
+    n_heads: usize,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for UNetBenchmark<B> {
+    type Input = (UNetBlock<B>, Tensor<B, 4>);
+    type Output = Tensor<B, 4>;
+
+    fn name(&self) -> String {
+        format!("unet-block-{:?}", B::FloatElem::dtype()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn execute(&self, (model, input): Self::Input) -> Self::Output {
+        model.forward(input)
+    }
+
+    fn prepare(&self) -> Self::Input {
+        (
+            UNetBlock::new(self.shape[1], self.n_heads, &self.device),
+            Tensor::random(self.shape.clone(), Distribution::Default, &self.device),
+        )
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    // First levels of a Stable Diffusion UNet on 512x512 images, with latents of 64x64 and a
+    // batch of 2 for classifier-free guidance.
+    let shapes = [[2, 320, 64, 64], [2, 640, 32, 32], [2, 1280, 16, 16]];
+
+    shapes
+        .into_iter()
+        .map(|shape| {
+            run_benchmark(UNetBenchmark::<B> {
+                shape: shape.into(),
+                n_heads: 8,
+                device: device.clone(),
+            })
+        })
+        .collect()
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
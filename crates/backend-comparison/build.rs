@@ -0,0 +1,101 @@
+/// ONNX model benchmarked by the `onnx-model` bench, given by the runner with `--onnx-model`.
+const ONNX_MODEL_ENV: &str = "BURN_BENCH_ONNX_MODEL";
+/// Shapes of the float inputs of the model, such as `1x3x224x224,1x10`.
+const ONNX_INPUT_SHAPES_ENV: &str = "BURN_BENCH_ONNX_INPUT_SHAPES";
+
+fn main() {
+    println!("cargo:rerun-if-env-changed={ONNX_MODEL_ENV}");
+    println!("cargo:rerun-if-env-changed={ONNX_INPUT_SHAPES_ENV}");
+
+    #[cfg(feature = "onnx-model")]
+    onnx::import_model();
+}
+
+#[cfg(feature = "onnx-model")]
+mod onnx {
+    use super::*;
+    use burn_import::onnx::ModelGen;
+    use std::{env, fs, path::Path};
+
+    /// Import the model with burn-import and generate the code calling it on random inputs.
+    pub fn import_model() {
+        let model = env::var(ONNX_MODEL_ENV)
+            .unwrap_or_else(|_| panic!("{ONNX_MODEL_ENV} should be the path of the ONNX model"));
+        let shapes = env::var(ONNX_INPUT_SHAPES_ENV)
+            .unwrap_or_else(|_| panic!("{ONNX_INPUT_SHAPES_ENV} should be set"));
+        let shapes = parse_shapes(&shapes);
+        println!("cargo:rerun-if-changed={model}");
+
+        ModelGen::new()
+            .input(&model)
+            .out_dir("onnx/")
+            .run_from_script();
+
+        let name = Path::new(&model)
+            .file_stem()
+            .expect("The model should be a file")
+            .to_string_lossy()
+            .to_string();
+        let out_dir = env::var("OUT_DIR").unwrap();
+        let generated = Path::new(&out_dir).join("onnx").join(format!("{name}.rs"));
+        fs::write(
+            Path::new(&out_dir).join("onnx_model.rs"),
+            wrapper(&name, &generated.to_string_lossy(), &shapes),
+        )
+        .unwrap();
+    }
+
+    fn parse_shapes(shapes: &str) -> Vec<Vec<usize>> {
+        shapes
+            .split(',')
+            .map(|shape| {
+                shape
+                    .split('x')
+                    .map(|dim| dim.trim().parse().expect("Invalid input shape"))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn wrapper(name: &str, generated: &str, shapes: &[Vec<usize>]) -> String {
+        let types: Vec<String> = shapes
+            .iter()
+            .map(|shape| format!("Tensor<B, {}>", shape.len()))
+            .collect();
+        let values: Vec<String> = shapes
+            .iter()
+            .map(|shape| format!("Tensor::random({shape:?}, Distribution::Default, device)"))
+            .collect();
+        let slices: Vec<String> = shapes.iter().map(|shape| format!("&{shape:?}")).collect();
+        let args: Vec<String> = (0..shapes.len()).map(|i| format!("inputs.{i}")).collect();
+
+        format!(
+            r#"use burn::tensor::{{Distribution, Tensor, backend::Backend}};
+
+pub const MODEL_NAME: &str = {name:?};
+pub const INPUT_SHAPES: &[&[usize]] = &[{slices}];
+
+pub mod model {{
+    include!({generated:?});
+}}
+
+pub type Inputs<B> = ({types},);
+
+pub fn inputs<B: Backend>(device: &B::Device) -> Inputs<B> {{
+    ({values},)
+}}
+
+pub fn forward<B: Backend>(
+    model: &model::Model<B>,
+    inputs: Inputs<B>,
+) -> Box<dyn core::any::Any> {{
+    Box::new(model.forward({args}))
+}}
+"#,
+            slices = slices.join(", "),
+            types = types.join(", "),
+            values = values.join(", "),
+            args = args.join(", "),
+        )
+    }
+}
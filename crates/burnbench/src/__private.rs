@@ -1,3 +1,4 @@
+#[cfg(not(target_family = "wasm"))]
 use tracing_subscriber::{
     Layer,
     filter::{LevelFilter, filter_fn},
@@ -6,6 +7,11 @@ use tracing_subscriber::{
     util::SubscriberInitExt,
 };
 
+#[cfg(target_family = "wasm")]
+pub use crate::browser::finish;
+#[cfg(target_family = "wasm")]
+pub use wasm_bindgen_futures::spawn_local;
+
 /// Simple parse to retrieve additional argument passed to cargo bench command
 /// We cannot use clap here as clap parser does not allow to have unknown arguments.
 pub fn get_argument<'a>(args: &'a [String], arg_name: &'a str) -> Option<&'a str> {
@@ -31,6 +37,33 @@ pub fn get_sharing_url(args: &[String]) -> Option<&str> {
     get_argument(args, "--sharing-url")
 }
 
+/// Variable of the run passed by the runner to the benchmarks, such as `BURN_BENCH_TAG`.
+///
+/// The benchmarks running in a browser read them from the page served by the runner.
+pub fn run_env(name: &str) -> Option<String> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_family = "wasm")] {
+            crate::browser::run_env(name)
+        } else {
+            std::env::var(name).ok()
+        }
+    }
+}
+
+/// Source of the Burn dependency passed by the runner to the benchmarks.
+pub fn burn_source_from_env() -> Option<crate::BurnSource> {
+    let source = run_env("BURN_BENCH_BURN_SOURCE")?;
+    serde_json::from_str(&source).ok()
+}
+
+/// The panics stop the benchmarks running in a browser, the runner is notified with the message.
+#[cfg(target_family = "wasm")]
+pub fn init_log() -> Result<(), String> {
+    std::panic::set_hook(Box::new(|info| finish(Some(info.to_string()))));
+    Ok(())
+}
+
+#[cfg(not(target_family = "wasm"))]
 pub fn init_log() -> Result<(), String> {
     let layer = tracing_subscriber::fmt::layer()
         .with_writer(std::io::stderr)
@@ -53,6 +86,7 @@ pub fn init_log() -> Result<(), String> {
     result.map_err(|err| format!("{err:?}"))
 }
 
+#[cfg(not(target_family = "wasm"))]
 fn update_panic_hook() {
     let hook = std::panic::take_hook();
 
@@ -72,9 +106,20 @@ macro_rules! define_types {
             url: Option<&str>,
             token: Option<&str>,
             feature: &str,
+            dtype: Option<String>,
         ) {
-            let burn_version =
-                std::env::var("BURN_BENCH_BURN_VERSION").unwrap_or_else(|_| "main".to_string());
+            let burn_version = $crate::__private::run_env("BURN_BENCH_BURN_VERSION")
+                .unwrap_or_else(|| "main".to_string());
+            let tag = $crate::__private::run_env("BURN_BENCH_TAG");
+            let note = $crate::__private::run_env("BURN_BENCH_NOTE");
+            let container = $crate::__private::run_env("BURN_BENCH_CONTAINER");
+            let run_id = $crate::__private::run_env("BURN_BENCH_RUN_ID");
+            let burn_bench_git_hash = $crate::__private::run_env("BURN_BENCH_GIT_HASH");
+            let burn_source = $crate::__private::burn_source_from_env();
+            let cargo_profile = $crate::__private::run_env("BURN_BENCH_CARGO_PROFILE");
+            let rustflags = $crate::__private::run_env("BURN_BENCH_RUSTFLAGS");
+            let threads = $crate::__private::run_env("BURN_BENCH_THREADS")
+                .and_then(|threads| threads.parse().ok());
 
             let records: Vec<$crate::BenchmarkRecord> = benches
                 .into_iter()
@@ -82,7 +127,20 @@ macro_rules! define_types {
                     backend: backend_name.clone(),
                     device: device.clone(),
                     feature: feature.to_string(),
+                    dtype: dtype.clone(),
                     burn_version: burn_version.clone(),
+                    tag: tag.clone(),
+                    note: note.clone(),
+                    container: container.clone(),
+                    run_id: run_id.clone(),
+                    burn_bench_git_hash: burn_bench_git_hash.clone(),
+                    burn_source: burn_source.clone(),
+                    cargo_profile: cargo_profile.clone(),
+                    rustflags: rustflags.clone(),
+                    threads,
+                    normalized: None,
+                    kernel_profile: None,
+                    kernel_launches: None,
                     system_info: $crate::BenchmarkSystemInfo::new(),
                     results: $crate::BenchmarkResult {
                         raw: $crate::BenchmarkDurations {
@@ -98,9 +156,13 @@ macro_rules! define_types {
                         },
                         git_hash: bench.git_hash,
                         name: bench.name,
+                        id: bench.id,
                         options: bench.options,
                         shapes: bench.shapes,
                         timestamp: bench.timestamp,
+                        energy: bench.energy,
+                        allocations: bench.allocations,
+                        throughput: bench.throughput,
                     },
                 })
                 .collect();
@@ -172,7 +234,7 @@ macro_rules! bench_on_backend {
             panic!("vulkan benchmarks are not supported on macOS, use the wgpu backend instead.");
         }
 
-        #[cfg(any(feature = "wgpu", feature = "vulkan"))]
+        #[cfg(all(not(target_family = "wasm"), any(feature = "wgpu", feature = "vulkan")))]
         {
             use burn::backend::Wgpu;
 
@@ -180,6 +242,21 @@ macro_rules! bench_on_backend {
             $crate::bench_on_backend!($fn_name, Wgpu<$dtype>, device);
         }
 
+        // WebGPU can only be initialized asynchronously in the browser, the benchmarks run once
+        // `main` has returned.
+        #[cfg(all(target_family = "wasm", feature = "wgpu"))]
+        {
+            use burn::backend::Wgpu;
+            use burn::backend::wgpu::{graphics::WebGpu, init_setup_async};
+
+            $crate::__private::spawn_local(async move {
+                let device = Default::default();
+                init_setup_async::<WebGpu>(&device, Default::default()).await;
+                $crate::bench_on_backend!($fn_name, Wgpu<$dtype>, device);
+                $crate::__private::finish(None);
+            });
+        }
+
         #[cfg(feature = "ndarray")]
         {
             use burn::backend::NdArray;
@@ -307,8 +384,21 @@ macro_rules! bench_on_backend {
         let backend_name = <$backend as Backend>::name(&$device);
         #[cfg(feature = "legacy-v16")]
         let backend_name = <$backend as Backend>::name();
+        let dtype = format!(
+            "{:?}",
+            <<$backend as Backend>::FloatElem as burn::tensor::Element>::dtype()
+        )
+        .to_lowercase();
         let benches = $fn_name::<$backend>(&$device);
-        __save_result(benches, backend_name, device_name, url, token, feature_name);
+        __save_result(
+            benches,
+            backend_name,
+            device_name,
+            url,
+            token,
+            feature_name,
+            Some(dtype),
+        );
     };
 }
 
@@ -498,6 +588,11 @@ macro_rules! bench_on_backend_multi_device {
         let backend_name = <$backend as Backend>::name(&$devices.first().unwrap());
         #[cfg(feature = "legacy-v16")]
         let backend_name = <$backend as Backend>::name();
+        let dtype = format!(
+            "{:?}",
+            <<$backend as Backend>::FloatElem as burn::tensor::Element>::dtype()
+        )
+        .to_lowercase();
         let benches = $fn_name::<$backend>(&$devices);
         __save_result(
             benches,
@@ -506,6 +601,110 @@ macro_rules! bench_on_backend_multi_device {
             url,
             token,
             feature_name,
+            Some(dtype),
+        );
+    };
+}
+
+/// Run the `bench` function of the engine selected by the `baseline-*` feature, which runs the
+/// workloads directly on the engine underlying a Burn backend.
+#[macro_export]
+macro_rules! bench_on_baseline {
+    () => {{
+        $crate::define_types!();
+        $crate::__private::init_log().unwrap();
+
+        #[cfg(feature = "baseline-tch-cpu")]
+        {
+            let device = tch::Device::Cpu;
+            $crate::bench_on_baseline!(
+                libtorch::bench,
+                "libtorch-native",
+                "baseline-tch-cpu",
+                device
+            );
+        }
+
+        #[cfg(feature = "baseline-tch-cuda")]
+        {
+            let device = tch::Device::Cuda(0);
+            $crate::bench_on_baseline!(
+                libtorch::bench,
+                "libtorch-native",
+                "baseline-tch-cuda",
+                device
+            );
+        }
+
+        #[cfg(feature = "baseline-candle-cpu")]
+        {
+            let device = candle_core::Device::Cpu;
+            $crate::bench_on_baseline!(
+                candle::bench,
+                "candle-native",
+                "baseline-candle-cpu",
+                device
+            );
+        }
+
+        #[cfg(feature = "baseline-candle-cuda")]
+        {
+            let device = candle_core::Device::new_cuda(0).unwrap();
+            $crate::bench_on_baseline!(
+                candle::bench,
+                "candle-native",
+                "baseline-candle-cuda",
+                device
+            );
+        }
+
+        #[cfg(feature = "baseline-ort-cpu")]
+        {
+            let device = onnxruntime::Provider::Cpu;
+            $crate::bench_on_baseline!(
+                onnxruntime::bench,
+                "onnxruntime",
+                "baseline-ort-cpu",
+                device
+            );
+        }
+
+        #[cfg(feature = "baseline-ort-cuda")]
+        {
+            let device = onnxruntime::Provider::Cuda;
+            $crate::bench_on_baseline!(
+                onnxruntime::bench,
+                "onnxruntime",
+                "baseline-ort-cuda",
+                device
+            );
+        }
+    }};
+
+    ($fn_name:path, $engine:literal, $feature:literal, $device:ident) => {
+        let args: Vec<String> = std::env::args().collect();
+        let url = $crate::__private::get_sharing_url(&args);
+        let token = $crate::__private::get_sharing_token(&args);
+
+        // The engines are not Burn backends, the dtype is the one selected by the feature.
+        let dtype: Option<&str> = None;
+        #[cfg(feature = "f32")]
+        let dtype = Some("f32");
+        #[cfg(feature = "f16")]
+        let dtype = Some("f16");
+        #[cfg(feature = "bf16")]
+        let dtype = Some("bf16");
+
+        let device_name = format!("{:?}", &$device);
+        let benches = $fn_name($device);
+        __save_result(
+            benches,
+            $engine.to_string(),
+            device_name,
+            url,
+            token,
+            $feature,
+            dtype.map(str::to_string),
         );
     };
 }
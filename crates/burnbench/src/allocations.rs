@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Feature of the benchmarks crate, forwarded to `burnbench/count-allocations`, installing the
+/// counting allocator in the bench executables.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) const COUNT_ALLOCATIONS_FEATURE: &str = "count-allocations";
+
+/// Allocations made on the host during the measured executions of a benchmark, by every thread.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationMeasurement {
+    /// Average number of allocations per execution.
+    pub allocations: f64,
+    /// Average number of bytes allocated per execution.
+    pub bytes: f64,
+    /// Peak of the memory allocated over the memory allocated before the executions, in bytes.
+    pub peak_bytes: u64,
+}
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+/// Measurement of the last executions, taken with the result of the benchmark.
+static LAST_MEASUREMENT: Mutex<Option<AllocationMeasurement>> = Mutex::new(None);
+
+/// Global allocator counting the allocations before forwarding them to the system allocator.
+#[cfg(feature = "count-allocations")]
+struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        record_allocation(layout.size() as u64);
+        // SAFETY: forwarded with the layout of the caller.
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        record_allocation(layout.size() as u64);
+        // SAFETY: forwarded with the layout of the caller.
+        unsafe { std::alloc::System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        // SAFETY: forwarded with the pointer and the layout of the caller.
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        // Counted as a new allocation, as the memory is usually moved.
+        LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        record_allocation(new_size as u64);
+        // SAFETY: forwarded with the pointer, the layout and the size of the caller.
+        unsafe { std::alloc::System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[cfg_attr(not(feature = "count-allocations"), allow(dead_code))]
+fn record_allocation(bytes: u64) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(bytes, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+/// Run the executions, measuring their allocations when the counting allocator is installed.
+pub(crate) fn measure<R>(executions: usize, run: impl FnOnce() -> R) -> R {
+    if !cfg!(feature = "count-allocations") {
+        return run();
+    }
+    let live = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(live, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let output = run();
+    let executions = executions.max(1) as f64;
+    let measurement = AllocationMeasurement {
+        allocations: (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / executions,
+        bytes: (ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes) as f64 / executions,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    *LAST_MEASUREMENT.lock().unwrap() = Some(measurement);
+    output
+}
+
+/// Take the measurement of the last executions, `None` when they were not measured.
+pub(crate) fn take_measurement() -> Option<AllocationMeasurement> {
+    LAST_MEASUREMENT.lock().unwrap().take()
+}
@@ -1,5 +1,8 @@
-use std::{pin::Pin, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, pin::Pin, time::Duration};
 
+use crate::allocations;
+use crate::energy::EnergySampler;
 use crate::{BenchmarkComputations, BenchmarkDurations, BenchmarkResult, TimingMethod};
 
 /// Benchmark trait.
@@ -37,6 +40,13 @@ pub trait Benchmark {
     /// defined in the crate Cargo.toml
     fn name(&self) -> String;
 
+    /// Identity of the benchmark used to sort and group the results.
+    ///
+    /// Derived from [Benchmark::name()] by default, see [BenchmarkId::from_name].
+    fn id(&self) -> BenchmarkId {
+        BenchmarkId::from_name(&self.name())
+    }
+
     /// The options passed to the benchmark.
     fn options(&self) -> Option<String> {
         None
@@ -47,6 +57,12 @@ pub trait Benchmark {
         vec![]
     }
 
+    /// Work done by one execution with its unit, such as the number of tokens generated, to
+    /// report the throughput of the benchmark.
+    fn work(&self) -> Option<(f64, String)> {
+        None
+    }
+
     /// Wait for computation to complete.
     fn sync(&self);
 
@@ -59,7 +75,7 @@ pub trait Benchmark {
     /// device duration is available or not.
     fn profile_full(&self, args: Self::Input) -> ProfileDuration {
         self.sync();
-        let start_time = std::time::Instant::now();
+        let start_time = web_time::Instant::now();
         let out = self.execute(args);
         self.sync();
         core::mem::drop(out);
@@ -88,19 +104,23 @@ pub trait Benchmark {
             }
 
             // Real execution.
-            for _ in 0..self.num_samples() {
-                durations.push(execute(args.clone()));
-            }
+            allocations::measure(self.num_samples(), || {
+                for _ in 0..self.num_samples() {
+                    durations.push(execute(args.clone()));
+                }
+            });
         } else {
             // Warmup
             for _ in 0..5 {
                 let _duration = execute(self.prepare());
             }
 
-            // Real execution.
-            for _ in 0..self.num_samples() {
-                durations.push(execute(self.prepare()));
-            }
+            // Real execution, the allocations of the preparation included.
+            allocations::measure(self.num_samples(), || {
+                for _ in 0..self.num_samples() {
+                    durations.push(execute(self.prepare()));
+                }
+            });
         }
 
         BenchmarkDurations {
@@ -118,6 +138,86 @@ pub trait Benchmark {
     }
 }
 
+/// Structured identity of a benchmark, such as the `general` variant of `matmul` with the `f16`
+/// dtype, rather than its formatted name.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct BenchmarkId {
+    /// Operation or workload benchmarked, such as `matmul`.
+    pub op: String,
+    /// Variant of the operation, such as `general`.
+    pub variant: Option<String>,
+    /// Parameters of the benchmark, such as the axis, the dtype or the layout.
+    pub params: BTreeMap<String, String>,
+}
+
+impl BenchmarkId {
+    /// Dtypes recognized at the end of the benchmark names.
+    const DTYPES: [&str; 5] = ["f64", "f32", "f16", "bf16", "flex32"];
+
+    /// Create the identity of the operation.
+    pub fn new(op: impl Into<String>) -> Self {
+        Self {
+            op: op.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the variant of the operation.
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Add a parameter of the benchmark.
+    pub fn with_param(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.params.insert(key.into(), value.to_string());
+        self
+    }
+
+    /// Derive the identity from a name such as `matmul-general-f16`: the operation is the part
+    /// before the first `-`, a dtype at the end becomes the `dtype` parameter and the rest is the
+    /// variant.
+    pub fn from_name(name: &str) -> Self {
+        let mut parts: Vec<&str> = name.split('-').collect();
+        let mut id = Self::new(parts.remove(0));
+        if let Some(dtype) = parts.last().map(|part| part.to_lowercase())
+            && Self::DTYPES.contains(&dtype.as_str())
+        {
+            parts.pop();
+            id = id.with_param("dtype", dtype);
+        }
+        if !parts.is_empty() {
+            id = id.with_variant(parts.join("-"));
+        }
+        id
+    }
+}
+
+/// Throughput of a benchmark, the work done by one execution over its median duration.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Throughput {
+    /// Work done per second.
+    pub value: f64,
+    /// Unit of the work, such as `tokens`.
+    pub unit: String,
+}
+
+impl Throughput {
+    /// Throughput of the work done by one execution lasting the median duration.
+    pub fn new(work: f64, unit: impl Into<String>, median: Duration) -> Self {
+        Self {
+            value: work / median.as_secs_f64(),
+            unit: unit.into(),
+        }
+    }
+}
+
+impl fmt::Display for Throughput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {}/s", self.value, self.unit)
+    }
+}
+
 /// Result from profiling between two measurements. This can either be a duration or a future that resolves to a duration.
 pub enum ProfileDuration {
     /// Client profile contains a full duration.
@@ -168,24 +268,153 @@ pub fn run_benchmark<BM>(benchmark: BM) -> BenchmarkResult
 where
     BM: Benchmark,
 {
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
+    let timestamp = web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
         .unwrap()
         .as_millis();
-    let output = std::process::Command::new("git")
-        .args(["rev-parse", "HEAD"])
-        .output()
-        .unwrap();
-    let git_hash = String::from_utf8(output.stdout).unwrap().trim().to_string();
+    let git_hash = git_hash();
+    let name = benchmark.name();
+    let id = benchmark.id();
+    ProgressLine::Start { name: name.clone() }.print();
+    let start = web_time::Instant::now();
+    let sampler = EnergySampler::start();
     let durations = benchmark.run(TimingMethod::System);
+    let computed = BenchmarkComputations::new(&durations);
+    let energy = sampler.and_then(|sampler| sampler.stop(computed.median));
+    let allocations = allocations::take_measurement();
+    let throughput = benchmark
+        .work()
+        .map(|(work, unit)| Throughput::new(work, unit, computed.median));
+    ProgressLine::End {
+        name: name.clone(),
+        duration: start.elapsed(),
+    }
+    .print();
 
     BenchmarkResult {
         raw: durations.clone(),
-        computed: BenchmarkComputations::new(&durations),
+        computed,
         git_hash,
-        name: benchmark.name(),
+        name,
+        id: Some(id),
         options: benchmark.options(),
         shapes: benchmark.shapes(),
         timestamp,
+        energy,
+        allocations,
+        throughput,
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn git_hash() -> String {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Processes can't be spawned in the browser, the commit is the one of the workspace of the run.
+#[cfg(target_family = "wasm")]
+fn git_hash() -> String {
+    crate::browser::run_env("BURN_BENCH_GIT_HASH").unwrap_or_default()
+}
+
+const PROGRESS_PREFIX: &str = "##burnbench:";
+
+/// Line printed by the benchmark binaries to report their progress to the runner, such as
+/// `##burnbench:start name=matmul` and `##burnbench:end duration_ms=1520 name=matmul`.
+///
+/// The name comes last since it may contain spaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ProgressLine {
+    /// The benchmark started.
+    Start { name: String },
+    /// The benchmark finished after the given duration, including the warmup.
+    End { name: String, duration: Duration },
+}
+
+impl fmt::Display for ProgressLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgressLine::Start { name } => write!(f, "{PROGRESS_PREFIX}start name={name}"),
+            ProgressLine::End { name, duration } => write!(
+                f,
+                "{PROGRESS_PREFIX}end duration_ms={} name={name}",
+                duration.as_millis()
+            ),
+        }
+    }
+}
+
+impl ProgressLine {
+    /// Print the line in the output read by the runner.
+    fn print(&self) {
+        #[cfg(not(target_family = "wasm"))]
+        println!("{self}");
+        #[cfg(target_family = "wasm")]
+        crate::browser::print_line(&self.to_string());
+    }
+
+    /// Parse a line of the output of a benchmark binary, `None` if it isn't a progress line.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().strip_prefix(PROGRESS_PREFIX)?;
+        if let Some(name) = line.strip_prefix("start name=") {
+            return Some(ProgressLine::Start {
+                name: name.to_string(),
+            });
+        }
+        let (duration, name) = line
+            .strip_prefix("end duration_ms=")?
+            .split_once(" name=")?;
+        Some(ProgressLine::End {
+            name: name.to_string(),
+            duration: Duration::from_millis(duration.parse().ok()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_id_from_name() {
+        assert_eq!(
+            BenchmarkId::from_name("matmul-general-f16"),
+            BenchmarkId::new("matmul")
+                .with_variant("general")
+                .with_param("dtype", "f16")
+        );
+        assert_eq!(
+            BenchmarkId::from_name("reduce-argmin-1-fused-BF16"),
+            BenchmarkId::new("reduce")
+                .with_variant("argmin-1-fused")
+                .with_param("dtype", "bf16")
+        );
+        assert_eq!(BenchmarkId::from_name("data"), BenchmarkId::new("data"));
+    }
+
+    #[test]
+    fn test_progress_line_round_trip() {
+        let lines = [
+            ProgressLine::Start {
+                name: "matmul-general".to_string(),
+            },
+            ProgressLine::End {
+                name: "conv2d f16".to_string(),
+                duration: Duration::from_millis(1520),
+            },
+        ];
+        for line in lines {
+            assert_eq!(ProgressLine::parse(&line.to_string()), Some(line));
+        }
+        assert_eq!(ProgressLine::parse("Running benches/matmul.rs"), None);
+        assert_eq!(
+            ProgressLine::parse("##burnbench:end duration_ms=x name=a"),
+            None
+        );
     }
 }
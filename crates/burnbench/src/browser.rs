@@ -0,0 +1,73 @@
+//! Glue of the benchmarks compiled to WebAssembly and run in a browser by `burnbench run --wasm`.
+//!
+//! The page served by the runner exposes the environment of the run in `window.burnBenchEnv`,
+//! and the benchmarks send their output and their records back to the runner over HTTP.
+
+use crate::{BenchmarkRecord, validate_record};
+use wasm_bindgen::JsValue;
+use web_sys::XmlHttpRequest;
+
+/// Endpoint receiving the lines printed by the benchmarks.
+const OUTPUT_ENDPOINT: &str = "/output";
+/// Endpoint receiving the records of the benchmarks.
+const RESULTS_ENDPOINT: &str = "/results";
+/// Endpoint notified when the benchmarks are done, with the error that stopped them if any.
+const DONE_ENDPOINT: &str = "/done";
+
+/// Variable of the run passed by the runner to the page, such as `BURN_BENCH_RUN_ID`.
+pub(crate) fn run_env(name: &str) -> Option<String> {
+    let window = web_sys::window()?;
+    let env = js_sys::Reflect::get(&window, &JsValue::from_str("burnBenchEnv")).ok()?;
+    js_sys::Reflect::get(&env, &JsValue::from_str(name))
+        .ok()?
+        .as_string()
+}
+
+/// User agent of the browser, which stands for the operating system in the records.
+pub(crate) fn user_agent() -> String {
+    web_sys::window()
+        .and_then(|window| window.navigator().user_agent().ok())
+        .unwrap_or_else(|| "unknown browser".to_string())
+}
+
+/// Print a line of output in the terminal of the runner.
+pub(crate) fn print_line(line: &str) {
+    if let Err(e) = post(OUTPUT_ENDPOINT, line) {
+        web_sys::console::log_1(&e);
+    }
+}
+
+/// Send the benchmarks results to the runner, which saves them like the native benchmarks do.
+///
+/// The sharing URL and token are ignored, the runner shares the results it collected.
+pub fn save_records(
+    records: Vec<BenchmarkRecord>,
+    _url: Option<&str>,
+    _token: Option<&str>,
+) -> Result<(), std::io::Error> {
+    for record in records.iter() {
+        validate_record(record)?;
+    }
+    let body = serde_json::to_string(&records).map_err(std::io::Error::other)?;
+    post(RESULTS_ENDPOINT, &body)
+        .map_err(|e| std::io::Error::other(format!("failed to send the records ({e:?})")))
+}
+
+/// Notify the runner that the benchmarks are done, so that it closes the browser.
+pub fn finish(error: Option<String>) {
+    if let Err(e) = post(DONE_ENDPOINT, error.as_deref().unwrap_or_default()) {
+        web_sys::console::log_1(&e);
+    }
+}
+
+/// Post the body to the runner, waiting for the request to complete so that the output and the
+/// records are received in order and before the runner is notified that the benchmarks are done.
+fn post(endpoint: &str, body: &str) -> Result<(), JsValue> {
+    let request = XmlHttpRequest::new()?;
+    request.open_with_async("POST", endpoint, false)?;
+    request.send_with_opt_str(Some(body))?;
+    match request.status()? {
+        200 => Ok(()),
+        status => Err(JsValue::from_str(&format!("{endpoint} returned {status}"))),
+    }
+}
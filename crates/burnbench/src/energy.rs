@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Environment variable enabling the energy measurement of the benchmarks.
+pub(crate) const MEASURE_ENERGY_ENV: &str = "BURN_BENCH_MEASURE_ENERGY";
+
+/// Energy consumed by a benchmark.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnergyMeasurement {
+    /// Energy of one execution in Joules, estimated from the average power and the median
+    /// duration.
+    pub joules: f64,
+    /// Average power drawn during the benchmark in Watts.
+    pub average_watts: f64,
+}
+
+/// Source of the energy consumed by a device.
+trait PowerSource {
+    /// Start measuring the consumed energy.
+    fn start(&mut self);
+    /// Energy consumed since the start in Joules, if it could be measured.
+    fn stop(&mut self, elapsed: Duration) -> Option<f64>;
+}
+
+/// Integrates the energy consumed by every available device during a benchmark.
+pub(crate) struct EnergySampler {
+    sources: Vec<Box<dyn PowerSource>>,
+    start: Instant,
+}
+
+impl EnergySampler {
+    /// Start sampling when the measurement is enabled and at least one source is available.
+    pub(crate) fn start() -> Option<Self> {
+        std::env::var(MEASURE_ENERGY_ENV).ok()?;
+
+        #[cfg_attr(target_family = "wasm", allow(unused_mut))]
+        let mut sources: Vec<Box<dyn PowerSource>> = vec![];
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(source) = nvml::NvmlSource::new() {
+            sources.push(Box::new(source));
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(source) = rapl::RaplSource::new() {
+            sources.push(Box::new(source));
+        }
+        #[cfg(target_os = "macos")]
+        sources.push(Box::new(powermetrics::PowermetricsSource::default()));
+        if sources.is_empty() {
+            log::warn!("No power source available, the energy is not measured");
+            return None;
+        }
+
+        for source in sources.iter_mut() {
+            source.start();
+        }
+        Some(Self {
+            sources,
+            start: Instant::now(),
+        })
+    }
+
+    /// Stop sampling and compute the energy of one execution lasting `median`.
+    pub(crate) fn stop(mut self, median: Duration) -> Option<EnergyMeasurement> {
+        let elapsed = self.start.elapsed();
+        let joules: Vec<f64> = self
+            .sources
+            .iter_mut()
+            .filter_map(|source| source.stop(elapsed))
+            .collect();
+        if joules.is_empty() || elapsed.is_zero() {
+            return None;
+        }
+
+        let average_watts = joules.iter().sum::<f64>() / elapsed.as_secs_f64();
+        Some(EnergyMeasurement {
+            joules: average_watts * median.as_secs_f64(),
+            average_watts,
+        })
+    }
+}
+
+/// Energy counters of the NVIDIA GPUs.
+#[cfg(not(target_family = "wasm"))]
+mod nvml {
+    use super::PowerSource;
+    use nvml_wrapper::Nvml;
+    use std::time::Duration;
+
+    pub(super) struct NvmlSource {
+        nvml: Nvml,
+        /// Energy counters in millijoules at the start.
+        counters: Vec<u64>,
+    }
+
+    impl NvmlSource {
+        pub(super) fn new() -> Option<Self> {
+            let source = Self {
+                nvml: Nvml::init().ok()?,
+                counters: vec![],
+            };
+            // GPUs older than Volta don't have an energy counter.
+            (!source.read()?.is_empty()).then_some(source)
+        }
+
+        fn read(&self) -> Option<Vec<u64>> {
+            let count = self.nvml.device_count().ok()?;
+            (0..count)
+                .map(|index| {
+                    self.nvml
+                        .device_by_index(index)
+                        .and_then(|device| device.total_energy_consumption())
+                        .ok()
+                })
+                .collect()
+        }
+    }
+
+    impl PowerSource for NvmlSource {
+        fn start(&mut self) {
+            self.counters = self.read().unwrap_or_default();
+        }
+
+        fn stop(&mut self, _elapsed: Duration) -> Option<f64> {
+            let counters = self.read()?;
+            let millijoules: u64 = counters
+                .iter()
+                .zip(&self.counters)
+                .map(|(end, start)| end.saturating_sub(*start))
+                .sum();
+            Some(millijoules as f64 / 1000.0)
+        }
+    }
+}
+
+/// Energy counters of the CPU packages exposed by the powercap framework.
+#[cfg(target_os = "linux")]
+mod rapl {
+    use super::PowerSource;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+
+    struct Package {
+        dir: PathBuf,
+        max_range: u64,
+        start: u64,
+    }
+
+    pub(super) struct RaplSource {
+        packages: Vec<Package>,
+    }
+
+    fn read_counter(dir: &Path, file: &str) -> Option<u64> {
+        fs::read_to_string(dir.join(file)).ok()?.trim().parse().ok()
+    }
+
+    impl RaplSource {
+        pub(super) fn new() -> Option<Self> {
+            let packages: Vec<Package> = fs::read_dir("/sys/class/powercap")
+                .ok()?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                // Only the packages, their subzones are included in their counter.
+                .filter(|dir| {
+                    dir.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| {
+                            name.starts_with("intel-rapl:") && name.matches(':').count() == 1
+                        })
+                })
+                .filter_map(|dir| {
+                    // Reading the counters usually requires elevated permissions.
+                    read_counter(&dir, "energy_uj")?;
+                    Some(Package {
+                        max_range: read_counter(&dir, "max_energy_range_uj").unwrap_or(u64::MAX),
+                        dir,
+                        start: 0,
+                    })
+                })
+                .collect();
+            (!packages.is_empty()).then_some(Self { packages })
+        }
+    }
+
+    impl PowerSource for RaplSource {
+        fn start(&mut self) {
+            for package in self.packages.iter_mut() {
+                package.start = read_counter(&package.dir, "energy_uj").unwrap_or_default();
+            }
+        }
+
+        fn stop(&mut self, _elapsed: Duration) -> Option<f64> {
+            let mut microjoules = 0;
+            for package in &self.packages {
+                let end = read_counter(&package.dir, "energy_uj")?;
+                // The counter wraps around once it reaches its maximum range.
+                microjoules += if end >= package.start {
+                    end - package.start
+                } else {
+                    package.max_range - package.start + end
+                };
+            }
+            Some(microjoules as f64 / 1_000_000.0)
+        }
+    }
+}
+
+/// Power samples of the CPU and GPU reported by `powermetrics`, which requires sudo.
+#[cfg(target_os = "macos")]
+mod powermetrics {
+    use super::PowerSource;
+    use std::{
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+        thread::JoinHandle,
+        time::Duration,
+    };
+
+    #[derive(Default)]
+    pub(super) struct PowermetricsSource {
+        process: Option<Child>,
+        /// Power samples in milliwatts.
+        reader: Option<JoinHandle<Vec<f64>>>,
+    }
+
+    impl PowerSource for PowermetricsSource {
+        fn start(&mut self) {
+            let Ok(mut process) = Command::new("sudo")
+                .args([
+                    "-n",
+                    "powermetrics",
+                    "--samplers",
+                    "cpu_power,gpu_power",
+                    "-i",
+                    "100",
+                ])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+            else {
+                return;
+            };
+            let stdout = BufReader::new(process.stdout.take().unwrap());
+            self.reader = Some(std::thread::spawn(move || {
+                stdout
+                    .lines()
+                    .map_while(Result::ok)
+                    .filter_map(|line| parse_combined_power(&line))
+                    .collect()
+            }));
+            self.process = Some(process);
+        }
+
+        fn stop(&mut self, elapsed: Duration) -> Option<f64> {
+            let mut process = self.process.take()?;
+            // Terminate instead of killing so that sudo forwards the signal to powermetrics.
+            Command::new("kill")
+                .args(["-TERM", &process.id().to_string()])
+                .status()
+                .ok();
+            process.wait().ok();
+            let samples = self.reader.take()?.join().ok()?;
+            if samples.is_empty() {
+                return None;
+            }
+            let average_milliwatts = samples.iter().sum::<f64>() / samples.len() as f64;
+            Some(average_milliwatts / 1000.0 * elapsed.as_secs_f64())
+        }
+    }
+
+    /// Parse lines like `Combined Power (CPU + GPU + ANE): 1234 mW`.
+    fn parse_combined_power(line: &str) -> Option<f64> {
+        let value = line.strip_prefix("Combined Power")?.split(':').nth(1)?;
+        value.trim().strip_suffix("mW")?.trim().parse().ok()
+    }
+}
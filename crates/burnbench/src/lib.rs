@@ -1,30 +1,31 @@
 pub mod __private;
+mod allocations;
 mod benchmark;
+#[cfg(target_family = "wasm")]
+mod browser;
+mod energy;
 mod persistence;
+#[cfg(not(target_family = "wasm"))]
 mod runner;
+#[cfg(not(target_family = "wasm"))]
+mod server;
+#[cfg(not(target_family = "wasm"))]
+mod server_client;
 
 pub(crate) mod system_info;
 
+pub use allocations::AllocationMeasurement;
 pub use benchmark::*;
+#[cfg(target_family = "wasm")]
+pub use browser::save_records;
+pub use energy::*;
 pub use persistence::*;
+#[cfg(not(target_family = "wasm"))]
 pub use runner::*;
 pub use system_info::*;
 
+#[cfg(not(target_family = "wasm"))]
 const BENCHMARKS_TARGET_DIR: &str = "target/benchmarks";
-const TRACEL_CI_SERVER_BASE_URL: &str = if cfg!(debug_assertions) {
-    // development
-    "http://localhost:8000/v1/"
-} else {
-    // production
-    "https://user-benchmark-server-812794505978.northamerica-northeast1.run.app/v1/"
-};
-
-const BENCHMARK_WEBSITE_URL: &str = if cfg!(debug_assertions) || cfg!(test) {
-    "http://localhost:4321/"
-} else {
-    "https://burn.dev/"
-};
-
 #[macro_export]
 macro_rules! ci_errorln {
     ($($arg:tt)*) => {{
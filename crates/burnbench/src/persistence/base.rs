@@ -1,10 +1,18 @@
-use crate::auth::get_auth_header_value;
+#[cfg(not(target_family = "wasm"))]
+use super::validate_record;
+use crate::allocations::AllocationMeasurement;
+use crate::energy::EnergyMeasurement;
+#[cfg(not(target_family = "wasm"))]
+use crate::server_client::ServerClient;
 use crate::system_info::BenchmarkSystemInfo;
+use crate::{BenchmarkId, Throughput};
 
-use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, USER_AGENT};
 use serde::{Deserialize, Serialize, Serializer, de::Visitor, ser::SerializeStruct};
+#[cfg(not(target_family = "wasm"))]
+use std::fs;
+#[cfg(not(target_family = "wasm"))]
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use std::{fs, io::Write};
 
 /// Result of a benchmark run, with metadata
 #[derive(Default, Clone, Serialize, Deserialize)]
@@ -13,16 +21,27 @@ pub struct BenchmarkResult {
     pub raw: BenchmarkDurations,
     /// Computed values for the run
     pub computed: BenchmarkComputations,
-    /// Git commit hash of the commit in which the run occurred
+    /// Git commit hash of the repository in which the benchmark binary ran
+    ///
+    /// Ambiguous when the benchmarks are not run from the burn-bench workspace, see
+    /// [BenchmarkRecord::burn_bench_git_hash] and [BenchmarkRecord::burn_source].
     pub git_hash: String,
     /// Name of the benchmark
     pub name: String,
+    /// Identity of the benchmark, unknown for the older records
+    pub id: Option<BenchmarkId>,
     /// Options passed to the benchmark
     pub options: Option<String>,
     /// Shape dimensions
     pub shapes: Vec<Vec<usize>>,
     /// Time just before the run
     pub timestamp: u128,
+    /// Energy consumed by the benchmark, when measured
+    pub energy: Option<EnergyMeasurement>,
+    /// Allocations on the host per execution, when counted
+    pub allocations: Option<AllocationMeasurement>,
+    /// Throughput of the benchmark, when it reports its work
+    pub throughput: Option<Throughput>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -107,13 +126,167 @@ pub struct BenchmarkRecord {
     pub backend: String,
     pub device: String,
     pub feature: String,
+    /// Float type the benchmark ran with, such as `f16`, unknown for the older records.
+    pub dtype: Option<String>,
     pub burn_version: String,
+    pub tag: Option<String>,
+    pub note: Option<String>,
+    /// Container image, with its digest, in which the benchmark ran.
+    pub container: Option<String>,
+    /// Identifier of the `burnbench run` invocation that produced the record.
+    pub run_id: Option<String>,
+    /// Commit of the burn-bench workspace running the benchmarks.
+    pub burn_bench_git_hash: Option<String>,
+    /// Source of the Burn dependency the benchmarks were built with.
+    pub burn_source: Option<BurnSource>,
+    /// Cargo profile the benchmarks were built with, when not the default bench profile.
+    pub cargo_profile: Option<String>,
+    /// Flags passed to rustc with `RUSTFLAGS`, such as `-C target-cpu=native`.
+    pub rustflags: Option<String>,
+    /// Number of threads the CPU backend was limited to in a thread count sweep.
+    pub threads: Option<usize>,
+    /// Median relative to the reference backend of the same run, set by the runner at the end
+    /// of the run.
+    pub normalized: Option<NormalizedScore>,
+    /// Summary of the kernels of the bench executable profiled with `ncu`, set by the runner
+    /// with `--profile-headless`.
+    pub kernel_profile: Option<KernelProfile>,
+    /// Kernel launches of the bench executable traced with `nsys`, set by the runner with
+    /// `--kernel-stats`.
+    pub kernel_launches: Option<KernelLaunches>,
     pub system_info: BenchmarkSystemInfo,
     pub results: BenchmarkResult,
 }
 
+/// Median of a benchmark relative to the same benchmark run with a reference backend on the same
+/// machine, which compares machines despite their absolute differences.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedScore {
+    /// Feature of the reference backend, such as `tch-cpu`.
+    pub reference: String,
+    /// Median divided by the median of the reference, below 1 when faster than the reference.
+    pub ratio: f64,
+}
+
+/// Summary of the `ncu` profile of the bench executable the benchmark belongs to.
+///
+/// The averages are weighted by the GPU time of the kernels, in percent of the peak of the GPU.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelProfile {
+    /// Kernels taking the most GPU time, by decreasing time.
+    pub top_kernels: Vec<KernelTime>,
+    /// Active warps per SM, in percent of the maximum of the SM.
+    pub achieved_occupancy: f64,
+    /// Throughput of the memory hierarchy, in percent of its peak.
+    pub memory_throughput: f64,
+    /// Throughput of the SMs, in percent of their peak.
+    pub sm_utilization: f64,
+}
+
+/// GPU time of a kernel in a profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelTime {
+    pub name: String,
+    /// Number of launches of the kernel.
+    pub calls: u64,
+    /// Share of the GPU time of the profile, in percent.
+    pub time_percent: f64,
+}
+
+/// Kernels launched on the GPU by the bench executable the benchmark belongs to, over all its
+/// executions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelLaunches {
+    /// Number of kernel launches.
+    pub launches: u64,
+    /// Number of distinct kernels launched.
+    pub distinct_kernels: u64,
+}
+
+/// Source the Burn dependency of the benchmarks was resolved from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum BurnSource {
+    /// Released version from crates.io.
+    CratesIo { version: String },
+    /// Branch or commit of the Burn repository.
+    #[serde(rename_all = "camelCase")]
+    Git {
+        branch: Option<String>,
+        rev: Option<String>,
+    },
+    /// Local checkout, with its commit and whether it had uncommitted changes.
+    #[serde(rename_all = "camelCase")]
+    Path {
+        path: String,
+        git_hash: Option<String>,
+        dirty: bool,
+    },
+}
+
+impl BurnSource {
+    /// Whether the source always resolves to the same Burn sources, unlike a branch without its
+    /// resolved commit or a local checkout with uncommitted changes.
+    pub fn is_pinned(&self) -> bool {
+        match self {
+            Self::CratesIo { .. } => true,
+            Self::Git { rev, .. } => rev.is_some(),
+            Self::Path {
+                git_hash, dirty, ..
+            } => git_hash.is_some() && !dirty,
+        }
+    }
+
+    /// Short commit of a local checkout, suffixed with `+dirty` when it had uncommitted changes.
+    pub fn local_revision(&self) -> Option<String> {
+        let Self::Path {
+            git_hash, dirty, ..
+        } = self
+        else {
+            return None;
+        };
+        let commit = match git_hash {
+            Some(git_hash) => &git_hash[..git_hash.len().min(7)],
+            None => "unknown",
+        };
+        Some(if *dirty {
+            format!("{commit}+dirty")
+        } else {
+            commit.to_string()
+        })
+    }
+}
+
+/// Variable overriding the directory where the benchmark records are cached, for the benches
+/// running with another home directory.
+#[cfg(not(target_family = "wasm"))]
+pub const CACHE_DIR_ENV: &str = "BURN_BENCH_CACHE_DIR";
+
+/// Return the directory where the benchmark records are cached.
+#[cfg(not(target_family = "wasm"))]
+pub fn benchmarks_cache_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(CACHE_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    dirs::home_dir()
+        .expect("Home directory should exist")
+        .join(".cache")
+        .join("burn")
+        .join("burnbench")
+}
+
 /// Save the benchmarks results on disk.
 ///
+/// The records are saved in the cache directory, which keeps the history of all the runs, and in
+/// the directory of the cargo invocation given by the runner with the `BURN_BENCH_RESULTS_DIR`
+/// environment variable, from which it collects the results of the invocation.
+///
+/// The records are first checked with [validate_record], a malformed record is rejected with an
+/// [InvalidData](std::io::ErrorKind::InvalidData) error instead of being saved or shared.
+///
 /// The structure is flat so that it can be easily queried from a database
 /// like MongoDB.
 ///
@@ -123,7 +296,22 @@ pub struct BenchmarkRecord {
 ///      "backend": "backend name",
 ///      "device": "device name",
 ///      "feature": "feature name",
+///      "dtype": "float type of the benchmark, such as f16",
 ///      "gitHash": "hash",
+///      "burnVersion": "burn version",
+///      "tag": "tag of the run",
+///      "note": "note attached to the run",
+///      "container": "image@digest of the container of the run",
+///      "runId": "identifier of the run, see its manifest in the cache directory",
+///      "burnBenchGitHash": "commit of the burn-bench workspace",
+///      "burnSource": {"kind": "cratesIo" | "git" | "path", ...},
+///      "cargoProfile": "cargo profile of the build, if not the bench profile",
+///      "rustflags": "RUSTFLAGS of the build, if any",
+///      "threads": "number of threads of the CPU backend, in a thread count sweep",
+///      "normalized": {"reference": "feature of the reference backend", "ratio": "median ratio"},
+///      "kernelProfile": {"topKernels": [{"name": ..., "calls": ..., "timePercent": ...}, ...], "achievedOccupancy": ..., "memoryThroughput": ..., "smUtilization": ...},
+///      "kernelLaunches": {"launches": "number of kernel launches", "distinctKernels": "number of distinct kernels"},
+///      "benchmarkId": {"op": "operation", "variant": "variant of the operation", "params": {"key": "value", ...}},
 ///      "max": "duration in microseconds",
 ///      "mean": "duration in microseconds",
 ///      "median": "duration in microseconds",
@@ -136,78 +324,80 @@ pub struct BenchmarkRecord {
 ///      "systemInfo": { "cpus": ["cpu1", "cpu2", ...], "gpus": ["gpu1", "gpu2", ...]}
 ///      "timestamp": "timestamp",
 ///      "variance": "duration in microseconds",
+///      "energyJoules": "energy of one execution in Joules, if measured",
+///      "averageWatts": "average power during the benchmark in Watts, if measured",
+///      "hostAllocations": {"allocations": "per execution", "bytes": "per execution", "peakBytes": "peak growth"},
+///      "throughput": {"value": "work done per second", "unit": "unit of the work"},
 ///    },
 ///    { ... }
 /// ]
 /// ```
+#[cfg(not(target_family = "wasm"))]
 pub fn save_records(
     records: Vec<BenchmarkRecord>,
     url: Option<&str>,
     token: Option<&str>,
 ) -> Result<(), std::io::Error> {
-    let cache_dir = dirs::home_dir()
-        .expect("Home directory should exist")
-        .join(".cache")
-        .join("burn")
-        .join("burnbench");
+    let mut dirs = vec![benchmarks_cache_dir()];
+    if let Ok(results_dir) = std::env::var("BURN_BENCH_RESULTS_DIR") {
+        dirs.push(PathBuf::from(results_dir));
+    }
+    save_validated_records(&records, &dirs)?;
+
+    if let Some(upload_url) = url
+        && !records.is_empty()
+    {
+        upload_records(
+            &records,
+            token.expect("An auth token should be provided."),
+            upload_url,
+        );
+    }
+
+    Ok(())
+}
 
-    if !cache_dir.exists() {
-        fs::create_dir_all(&cache_dir)?;
+/// Validate the records and write them in every directory.
+///
+/// The records are all validated before any is saved, so that a run is never half saved.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn save_validated_records(
+    records: &[BenchmarkRecord],
+    dirs: &[PathBuf],
+) -> Result<(), std::io::Error> {
+    for record in records.iter() {
+        validate_record(record)?;
     }
+    for dir in dirs {
+        fs::create_dir_all(dir)?;
+    }
+    write_records(records, dirs);
+    Ok(())
+}
 
-    for record in records {
+/// Write each record in its own file in every directory.
+#[cfg(not(target_family = "wasm"))]
+fn write_records(records: &[BenchmarkRecord], dirs: &[impl AsRef<Path>]) {
+    for record in records.iter() {
         let file_name = format!(
             "bench_{}_{}.json",
             record.results.name, record.results.timestamp
         );
-        let file_path = cache_dir.join(file_name);
-        let file =
-            fs::File::create(file_path.clone()).expect("Benchmark file should exist or be created");
-        serde_json::to_writer_pretty(file, &record)
-            .expect("Benchmark file should be updated with benchmark results");
-
-        // Append the benchmark result filepath in the benchmark_results.tx file of
-        // cache folder to be later picked by benchrun
-        let benchmark_results_path = cache_dir.join("benchmark_results.txt");
-        let mut benchmark_results_file = fs::OpenOptions::new()
-            .append(true)
-            .create(true)
-            .open(benchmark_results_path)
-            .unwrap();
-        benchmark_results_file
-            .write_all(format!("{}\n", file_path.to_string_lossy()).as_bytes())
-            .unwrap();
-
-        if let Some(upload_url) = url {
-            upload_record(
-                &record,
-                token.expect("An auth token should be provided."),
-                upload_url,
-            );
+        for dir in dirs {
+            let file = fs::File::create(dir.as_ref().join(&file_name))
+                .expect("Benchmark file should exist or be created");
+            serde_json::to_writer_pretty(file, &record)
+                .expect("Benchmark file should be updated with benchmark results");
         }
     }
-
-    Ok(())
 }
 
-fn upload_record(record: &BenchmarkRecord, token: &str, url: &str) {
-    println!("Sharing results...");
-    let client = reqwest::blocking::Client::new();
-    let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, "burnbench".parse().unwrap());
-    headers.insert(ACCEPT, "application/json".parse().unwrap());
-    headers.insert(AUTHORIZATION, get_auth_header_value(token).parse().unwrap());
-    // post the benchmark record
-    let response = client
-        .post(url)
-        .headers(headers)
-        .json(record)
-        .send()
-        .expect("Request should be sent successfully.");
-    if response.status().is_success() {
-        println!("Results shared successfully.");
-    } else {
-        println!("Failed to share results. Status: {}", response.status());
+#[cfg(not(target_family = "wasm"))]
+fn upload_records(records: &[BenchmarkRecord], token: &str, url: &str) {
+    println!("Sharing {} results...", records.len());
+    match ServerClient::new(url).upload_records(token, records) {
+        Ok(()) => println!("Results shared successfully."),
+        Err(e) => println!("Failed to share results ({e})"),
     }
 }
 
@@ -237,8 +427,22 @@ impl Serialize for BenchmarkRecord {
             ("backend", &self.backend),
             ("device", &self.device),
             ("feature", &self.feature),
+            ("dtype", &self.dtype),
             ("gitHash", &self.results.git_hash),
             ("burnVersion", &self.burn_version),
+            ("tag", &self.tag),
+            ("note", &self.note),
+            ("container", &self.container),
+            ("runId", &self.run_id),
+            ("burnBenchGitHash", &self.burn_bench_git_hash),
+            ("burnSource", &self.burn_source),
+            ("cargoProfile", &self.cargo_profile),
+            ("rustflags", &self.rustflags),
+            ("threads", &self.threads),
+            ("normalized", &self.normalized),
+            ("kernelProfile", &self.kernel_profile),
+            ("kernelLaunches", &self.kernel_launches),
+            ("benchmarkId", &self.results.id),
             ("max", &self.results.computed.max.as_micros()),
             ("mean", &self.results.computed.mean.as_micros()),
             ("median", &self.results.computed.median.as_micros()),
@@ -250,7 +454,14 @@ impl Serialize for BenchmarkRecord {
             ("systemInfo", &self.system_info),
             ("shapes", &self.results.shapes),
             ("timestamp", &self.results.timestamp),
-            ("variance", &self.results.computed.variance.as_micros())
+            ("variance", &self.results.computed.variance.as_micros()),
+            ("energyJoules", &self.results.energy.map(|e| e.joules)),
+            (
+                "averageWatts",
+                &self.results.energy.map(|e| e.average_watts)
+            ),
+            ("hostAllocations", &self.results.allocations),
+            ("throughput", &self.results.throughput)
         )
     }
 }
@@ -267,14 +478,34 @@ impl<'de> Visitor<'de> for BenchmarkRecordVisitor {
         A: serde::de::MapAccess<'de>,
     {
         let mut br = BenchmarkRecord::default();
+        let mut joules = None;
+        let mut average_watts = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "backend" => br.backend = map.next_value::<String>()?,
                 "device" => br.device = map.next_value::<String>()?,
                 "feature" => br.feature = map.next_value::<String>()?,
+                "dtype" => br.dtype = map.next_value::<Option<String>>()?,
                 "burnVersion" => br.burn_version = map.next_value::<String>()?,
+                "tag" => br.tag = map.next_value::<Option<String>>()?,
+                "note" => br.note = map.next_value::<Option<String>>()?,
+                "container" => br.container = map.next_value::<Option<String>>()?,
+                "runId" => br.run_id = map.next_value::<Option<String>>()?,
+                "burnBenchGitHash" => {
+                    br.burn_bench_git_hash = map.next_value::<Option<String>>()?
+                }
+                "burnSource" => br.burn_source = map.next_value::<Option<BurnSource>>()?,
+                "cargoProfile" => br.cargo_profile = map.next_value::<Option<String>>()?,
+                "rustflags" => br.rustflags = map.next_value::<Option<String>>()?,
+                "threads" => br.threads = map.next_value::<Option<usize>>()?,
+                "normalized" => br.normalized = map.next_value::<Option<NormalizedScore>>()?,
+                "kernelProfile" => br.kernel_profile = map.next_value::<Option<KernelProfile>>()?,
+                "kernelLaunches" => {
+                    br.kernel_launches = map.next_value::<Option<KernelLaunches>>()?
+                }
                 "gitHash" => br.results.git_hash = map.next_value::<String>()?,
                 "name" => br.results.name = map.next_value::<String>()?,
+                "benchmarkId" => br.results.id = map.next_value::<Option<BenchmarkId>>()?,
                 "max" => {
                     let value = map.next_value::<u64>()?;
                     br.results.computed.max = Duration::from_micros(value);
@@ -301,9 +532,21 @@ impl<'de> Visitor<'de> for BenchmarkRecordVisitor {
                     let value = map.next_value::<u64>()?;
                     br.results.computed.variance = Duration::from_micros(value)
                 }
+                "energyJoules" => joules = map.next_value::<Option<f64>>()?,
+                "averageWatts" => average_watts = map.next_value::<Option<f64>>()?,
+                "hostAllocations" => {
+                    br.results.allocations = map.next_value::<Option<AllocationMeasurement>>()?
+                }
+                "throughput" => br.results.throughput = map.next_value::<Option<Throughput>>()?,
                 _ => panic!("Unexpected Key: {}", key),
             }
         }
+        if let (Some(joules), Some(average_watts)) = (joules, average_watts) {
+            br.results.energy = Some(EnergyMeasurement {
+                joules,
+                average_watts,
+            });
+        }
 
         Ok(br)
     }
@@ -416,6 +659,155 @@ mod tests {
         assert!(record.results.raw.durations[9] == Duration::from_nanos(8511474));
     }
 
+    #[test]
+    fn test_fully_populated_record_roundtrip() {
+        let mut record = BenchmarkRecord {
+            backend: "cuda".to_string(),
+            device: "Cuda(0)".to_string(),
+            feature: "cuda-fusion".to_string(),
+            dtype: Some("f16".to_string()),
+            burn_version: "main".to_string(),
+            tag: Some("after-fusion-rework".to_string()),
+            note: Some("fusion enabled".to_string()),
+            container: Some("nvidia/cuda@sha256:0123abcd".to_string()),
+            run_id: Some("0b5b0d4e-7f1c-4d43-9a43-1fb1bde2b6c7".to_string()),
+            burn_bench_git_hash: Some("4fada32".to_string()),
+            burn_source: Some(BurnSource::Path {
+                path: "/home/me/burn".to_string(),
+                git_hash: Some("0123abcd".to_string()),
+                dirty: true,
+            }),
+            cargo_profile: Some("release-lto".to_string()),
+            rustflags: Some("-C target-cpu=native".to_string()),
+            threads: Some(4),
+            normalized: Some(NormalizedScore {
+                reference: "tch-cpu".to_string(),
+                ratio: 0.5,
+            }),
+            kernel_profile: Some(KernelProfile {
+                top_kernels: vec![KernelTime {
+                    name: "matmul_kernel".to_string(),
+                    calls: 10,
+                    time_percent: 75.0,
+                }],
+                achieved_occupancy: 48.5,
+                memory_throughput: 62.0,
+                sm_utilization: 80.25,
+            }),
+            kernel_launches: Some(KernelLaunches {
+                launches: 1200,
+                distinct_kernels: 4,
+            }),
+            ..Default::default()
+        };
+        record.results = BenchmarkResult {
+            raw: BenchmarkDurations {
+                timing_method: TimingMethod::System,
+                durations: vec![Duration::from_micros(10), Duration::from_micros(30)],
+            },
+            computed: BenchmarkComputations {
+                mean: Duration::from_micros(20),
+                median: Duration::from_micros(20),
+                variance: Duration::from_micros(100),
+                min: Duration::from_micros(10),
+                max: Duration::from_micros(30),
+            },
+            git_hash: "02d37011ab4dc773286e5983c09cde61f95ba4b5".to_string(),
+            name: "reduce".to_string(),
+            id: Some(
+                BenchmarkId::new("reduce")
+                    .with_variant("argmin")
+                    .with_param("axis", 1),
+            ),
+            options: Some("keepdim".to_string()),
+            shapes: vec![vec![32, 512, 1024]],
+            timestamp: 1710208069697,
+            energy: Some(EnergyMeasurement {
+                joules: 1.5,
+                average_watts: 120.0,
+            }),
+            allocations: Some(AllocationMeasurement {
+                allocations: 2.5,
+                bytes: 4096.0,
+                peak_bytes: 8192,
+            }),
+            throughput: Some(Throughput::new(256.0, "tokens", Duration::from_millis(500))),
+        };
+
+        let json = serde_json::to_value(&record).unwrap();
+        for (key, expected) in [
+            ("dtype", r#""f16""#),
+            ("threads", "4"),
+            (
+                "benchmarkId",
+                r#"{"op":"reduce","variant":"argmin","params":{"axis":"1"}}"#,
+            ),
+            (
+                "burnSource",
+                r#"{"kind":"path","path":"/home/me/burn","gitHash":"0123abcd","dirty":true}"#,
+            ),
+            ("normalized", r#"{"reference":"tch-cpu","ratio":0.5}"#),
+            ("kernelLaunches", r#"{"launches":1200,"distinctKernels":4}"#),
+            ("energyJoules", "1.5"),
+            ("averageWatts", "120.0"),
+            (
+                "hostAllocations",
+                r#"{"allocations":2.5,"bytes":4096.0,"peakBytes":8192}"#,
+            ),
+            ("throughput", r#"{"value":512.0,"unit":"tokens"}"#),
+        ] {
+            let expected = serde_json::from_str::<serde_json::Value>(expected).unwrap();
+            assert_eq!(json[key], expected, "{key}");
+        }
+
+        // Every field is serialized, so a lossless roundtrip serializes back to the same json.
+        let roundtrip = serde_json::from_value::<BenchmarkRecord>(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&roundtrip).unwrap(), json);
+    }
+
+    #[test]
+    fn test_record_without_optional_fields() {
+        let record = serde_json::from_str::<BenchmarkRecord>(
+            r#"{
+                "backend": "wgpu",
+                "device": "DefaultDevice",
+                "feature": "wgpu",
+                "gitHash": "02d37011ab4dc773286e5983c09cde61f95ba4b5",
+                "burnVersion": "0.16.0",
+                "name": "unary",
+                "max": 30,
+                "mean": 20,
+                "median": 20,
+                "min": 10,
+                "numSamples": 2,
+                "options": null,
+                "rawDurations": [{"secs": 0, "nanos": 10000}, {"secs": 0, "nanos": 30000}],
+                "shapes": [[32, 512, 1024]],
+                "timestamp": 1710208069697,
+                "variance": 100
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(record.burn_version, "0.16.0");
+        assert!(record.dtype.is_none());
+        assert!(record.tag.is_none());
+        assert!(record.note.is_none());
+        assert!(record.container.is_none());
+        assert!(record.run_id.is_none());
+        assert!(record.burn_bench_git_hash.is_none());
+        assert!(record.burn_source.is_none());
+        assert!(record.cargo_profile.is_none());
+        assert!(record.rustflags.is_none());
+        assert!(record.threads.is_none());
+        assert!(record.normalized.is_none());
+        assert!(record.kernel_profile.is_none());
+        assert!(record.kernel_launches.is_none());
+        assert!(record.results.id.is_none());
+        assert!(record.results.energy.is_none());
+        assert!(record.results.allocations.is_none());
+        assert!(record.results.throughput.is_none());
+    }
+
     #[test]
     fn test_min_max_median_durations_even_number_of_samples() {
         let durations = BenchmarkDurations {
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+
+use super::BenchmarkRecord;
+
+/// Metrics backend receiving the completed records, to feed existing performance dashboards.
+#[derive(Debug, Clone)]
+pub(crate) enum MetricsExporter {
+    /// Prometheus pushgateway, the metrics are grouped under the job.
+    Prometheus { url: String, job: String },
+    /// InfluxDB write endpoint accepting the line protocol, including its bucket and
+    /// organization parameters.
+    Influx { url: String, token: Option<String> },
+}
+
+impl MetricsExporter {
+    /// Push the records to the metrics backend.
+    pub(crate) fn export(&self, records: &[BenchmarkRecord]) -> Result<(), String> {
+        let client = reqwest::blocking::Client::new();
+        let request = match self {
+            Self::Prometheus { url, job } => client
+                .put(format!("{}/metrics/job/{job}", url.trim_end_matches('/')))
+                .header(CONTENT_TYPE, "text/plain; version=0.0.4")
+                .body(prometheus_metrics(records)),
+            Self::Influx { url, token } => {
+                let request = client
+                    .post(url)
+                    .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+                    .body(influx_lines(records));
+                match token {
+                    Some(token) => request.header(AUTHORIZATION, format!("Token {token}")),
+                    None => request,
+                }
+            }
+        };
+
+        let response = request.send().map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{}: {}",
+                response.status(),
+                response.text().unwrap_or_default()
+            ))
+        }
+    }
+}
+
+/// Labels identifying the series of a record.
+fn labels(record: &BenchmarkRecord) -> Vec<(&'static str, String)> {
+    vec![
+        ("benchmark", record.results.name.clone()),
+        ("shapes", format!("{:?}", record.results.shapes)),
+        ("backend", record.backend.clone()),
+        ("feature", record.feature.clone()),
+        ("device", record.device.clone()),
+        ("burn_version", record.burn_version.clone()),
+        ("tag", record.tag.clone().unwrap_or_default()),
+        ("host", record.system_info.host()),
+    ]
+}
+
+/// Durations of a record in seconds, by metric name.
+fn durations(record: &BenchmarkRecord) -> [(&'static str, f64); 4] {
+    let computed = &record.results.computed;
+    [
+        ("median", computed.median.as_secs_f64()),
+        ("mean", computed.mean.as_secs_f64()),
+        ("min", computed.min.as_secs_f64()),
+        ("max", computed.max.as_secs_f64()),
+    ]
+}
+
+/// Records in the Prometheus text exposition format.
+///
+/// The pushgateway rejects duplicate series, so only the latest record of each series is kept.
+fn prometheus_metrics(records: &[BenchmarkRecord]) -> String {
+    let mut latest: BTreeMap<String, &BenchmarkRecord> = BTreeMap::new();
+    for record in records {
+        let labels = labels(record)
+            .into_iter()
+            .map(|(key, value)| format!("{key}=\"{}\"", escape_prometheus(&value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        match latest.get(&labels) {
+            Some(other) if other.results.timestamp > record.results.timestamp => {}
+            _ => {
+                latest.insert(labels, record);
+            }
+        }
+    }
+
+    let mut metrics = String::new();
+    for (index, metric) in ["median", "mean", "min", "max"].iter().enumerate() {
+        metrics.push_str(&format!("# TYPE burnbench_{metric}_seconds gauge\n"));
+        for (labels, record) in &latest {
+            let value = durations(record)[index].1;
+            metrics.push_str(&format!("burnbench_{metric}_seconds{{{labels}}} {value}\n"));
+        }
+    }
+    metrics
+}
+
+fn escape_prometheus(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+/// Records in the InfluxDB line protocol, timestamped in nanoseconds.
+fn influx_lines(records: &[BenchmarkRecord]) -> String {
+    let mut lines = String::new();
+    for record in records {
+        let tags: String = labels(record)
+            .into_iter()
+            // Empty tag values are not allowed.
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| format!(",{key}={}", escape_influx(&value)))
+            .collect();
+        let mut fields: Vec<String> = durations(record)
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        fields.push(format!("samples={}i", record.results.raw.durations.len()));
+        if let Some(energy) = record.results.energy {
+            fields.push(format!("energy_joules={}", energy.joules));
+            fields.push(format!("average_watts={}", energy.average_watts));
+        }
+        lines.push_str(&format!(
+            "burnbench{tags} {} {}\n",
+            fields.join(","),
+            record.results.timestamp * 1_000_000
+        ));
+    }
+    lines
+}
+
+fn escape_influx(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace(',', r"\,")
+        .replace('=', r"\=")
+        .replace(' ', r"\ ")
+        .replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn record(timestamp: u128, median_ms: u64) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord {
+            backend: "cuda".to_string(),
+            feature: "cuda-fusion".to_string(),
+            burn_version: "main".to_string(),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        record.results.shapes = vec![vec![2, 3]];
+        record.results.timestamp = timestamp;
+        record.results.computed.median = Duration::from_millis(median_ms);
+        record
+    }
+
+    #[test]
+    fn test_prometheus_metrics_keep_latest_series() {
+        let metrics = prometheus_metrics(&[record(2, 20), record(1, 10)]);
+        let medians: Vec<&str> = metrics
+            .lines()
+            .filter(|line| line.starts_with("burnbench_median_seconds{"))
+            .collect();
+        assert_eq!(medians.len(), 1);
+        assert!(
+            medians[0].contains("benchmark=\"matmul\""),
+            "{}",
+            medians[0]
+        );
+        assert!(medians[0].ends_with(" 0.02"), "{}", medians[0]);
+    }
+
+    #[test]
+    fn test_influx_lines() {
+        let lines = influx_lines(&[record(1_700_000_000_000, 20)]);
+        assert!(
+            lines.starts_with(
+                r"burnbench,benchmark=matmul,shapes=[[2\,\ 3]],backend=cuda,feature=cuda-fusion,burn_version=main"
+            ),
+            "{lines}"
+        );
+        assert!(lines.contains(" median=0.02,"), "{lines}");
+        assert!(
+            lines.trim_end().ends_with(" 1700000000000000000"),
+            "{lines}"
+        );
+    }
+}
@@ -1,2 +1,8 @@
 mod base;
+#[cfg(not(target_family = "wasm"))]
+mod export;
+mod validation;
 pub use base::*;
+#[cfg(not(target_family = "wasm"))]
+pub(crate) use export::*;
+pub use validation::*;
@@ -0,0 +1,245 @@
+use super::BenchmarkRecord;
+use serde_json::{Map, Value};
+use std::fmt::Display;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/// Features of the benchmarks crate selecting the backend, the label of the records.
+pub(crate) const KNOWN_FEATURES: [&str; 30] = [
+    "baseline-candle-cpu",
+    "baseline-candle-cuda",
+    "baseline-ort-cpu",
+    "baseline-ort-cuda",
+    "baseline-tch-cpu",
+    "baseline-tch-cuda",
+    "candle-accelerate",
+    "candle-cpu",
+    "candle-cuda",
+    "candle-metal",
+    "cpu",
+    "cpu-fusion",
+    "cuda",
+    "cuda-fusion",
+    "metal",
+    "metal-fusion",
+    "ndarray",
+    "ndarray-blas-accelerate",
+    "ndarray-blas-netlib",
+    "ndarray-blas-openblas",
+    "ndarray-simd",
+    "rocm",
+    "rocm-fusion",
+    "tch-cpu",
+    "tch-cuda",
+    "tch-metal",
+    "vulkan",
+    "vulkan-fusion",
+    "wgpu",
+    "wgpu-fusion",
+];
+
+/// Fields of the serialized record that must be present and not empty.
+const REQUIRED_FIELDS: [&str; 5] = ["backend", "device", "feature", "burnVersion", "name"];
+
+/// Records older than this are from a clock that was not set, in milliseconds since the epoch
+/// (2020-01-01).
+const MIN_TIMESTAMP: u128 = 1_577_836_800_000;
+/// Tolerated clock skew of the machine for the records timestamped in the future, in
+/// milliseconds.
+const MAX_CLOCK_SKEW: u128 = 24 * 60 * 60 * 1000;
+
+/// Problems found in a record, which is rejected before being saved or shared.
+#[derive(Debug)]
+pub struct InvalidRecord {
+    pub name: String,
+    pub problems: Vec<String>,
+}
+
+impl Display for InvalidRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid benchmark record '{}': ", self.name)?;
+        write!(f, "{}", self.problems.join(", "))
+    }
+}
+
+impl std::error::Error for InvalidRecord {}
+
+impl From<InvalidRecord> for std::io::Error {
+    fn from(error: InvalidRecord) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// Validate the record against the schema of the shared records: the required fields, the
+/// ranges of the measures and the labels of the backend.
+///
+/// The record is checked in its serialized form, the one saved and uploaded.
+pub fn validate_record(record: &BenchmarkRecord) -> Result<(), InvalidRecord> {
+    let mut problems = vec![];
+    match serde_json::to_value(record) {
+        Ok(Value::Object(fields)) => validate_fields(&fields, &mut problems),
+        Ok(_) => problems.push("not serialized as an object".to_string()),
+        Err(e) => problems.push(format!("not serializable ({e})")),
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidRecord {
+            name: record.results.name.clone(),
+            problems,
+        })
+    }
+}
+
+fn validate_fields(fields: &Map<String, Value>, problems: &mut Vec<String>) {
+    for field in REQUIRED_FIELDS {
+        match fields.get(field).and_then(Value::as_str) {
+            Some(value) if !value.trim().is_empty() => {}
+            _ => problems.push(format!("missing {field}")),
+        }
+    }
+    let str_field = |field: &str| {
+        fields
+            .get(field)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    };
+    let u64_field = |field: &str| fields.get(field).and_then(Value::as_u64);
+
+    // Labels
+    let feature = str_field("feature");
+    let backend = str_field("backend");
+    if !feature.is_empty() && !KNOWN_FEATURES.contains(&feature) {
+        problems.push(format!("unknown feature '{feature}'"));
+    } else if feature.ends_with("-fusion") && !backend.contains("fusion") {
+        // The name of a fusion backend wraps the one of the backend it fuses.
+        problems.push(format!(
+            "backend '{backend}' of feature '{feature}' is not fused"
+        ));
+    }
+
+    // Measures
+    let num_samples = fields
+        .get("rawDurations")
+        .and_then(Value::as_array)
+        .map(Vec::len)
+        .unwrap_or_default();
+    if num_samples == 0 {
+        problems.push("no samples".to_string());
+    }
+    match (
+        u64_field("min"),
+        u64_field("median"),
+        u64_field("mean"),
+        u64_field("max"),
+    ) {
+        (Some(min), Some(median), Some(mean), Some(max)) => {
+            if !(min <= median && median <= max) || !(min <= mean && mean <= max) {
+                problems.push(format!(
+                    "inconsistent durations (min {min}µs, median {median}µs, mean {mean}µs, max {max}µs)"
+                ));
+            }
+        }
+        _ => problems.push("missing durations".to_string()),
+    }
+
+    match fields.get("timestamp").and_then(Value::as_u64) {
+        Some(timestamp) => {
+            let timestamp = timestamp as u128;
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|now| now.as_millis())
+                .unwrap_or_default();
+            if timestamp < MIN_TIMESTAMP || timestamp > now + MAX_CLOCK_SKEW {
+                problems.push(format!("timestamp {timestamp} out of range"));
+            }
+        }
+        None => problems.push("missing timestamp".to_string()),
+    }
+
+    for field in ["energyJoules", "averageWatts"] {
+        if let Some(value) = fields.get(field).and_then(Value::as_f64)
+            && !(value.is_finite() && value >= 0.0)
+        {
+            problems.push(format!("{field} {value} out of range"));
+        }
+    }
+    if let Some(ratio) = fields
+        .get("normalized")
+        .and_then(|normalized| normalized.get("ratio"))
+        .and_then(Value::as_f64)
+        && !(ratio.is_finite() && ratio > 0.0)
+    {
+        problems.push(format!("normalized ratio {ratio} out of range"));
+    }
+    if let Some(profile) = fields.get("kernelProfile").filter(|p| !p.is_null()) {
+        for field in ["achievedOccupancy", "memoryThroughput", "smUtilization"] {
+            if let Some(value) = profile.get(field).and_then(Value::as_f64)
+                && !(0.0..=100.0).contains(&value)
+            {
+                problems.push(format!("kernelProfile {field} {value} out of range"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BenchmarkDurations;
+    use std::time::Duration;
+
+    fn record(feature: &str, backend: &str) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord {
+            backend: backend.to_string(),
+            device: "Cuda(0)".to_string(),
+            feature: feature.to_string(),
+            burn_version: "0.19.0".to_string(),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        record.results.timestamp = 1_760_000_000_000;
+        record.results.raw = BenchmarkDurations {
+            timing_method: Default::default(),
+            durations: vec![Duration::from_millis(2), Duration::from_millis(4)],
+        };
+        record.results.computed.min = Duration::from_millis(2);
+        record.results.computed.median = Duration::from_millis(4);
+        record.results.computed.mean = Duration::from_millis(3);
+        record.results.computed.max = Duration::from_millis(4);
+        record
+    }
+
+    #[test]
+    fn test_validate_record_accepts_consistent_record() {
+        assert!(validate_record(&record("cuda-fusion", "fusion<cubecl<cuda>>")).is_ok());
+        assert!(validate_record(&record("baseline-tch-cuda", "libtorch-native")).is_ok());
+    }
+
+    #[test]
+    fn test_validate_record_rejects_labeling_bugs() {
+        let error = validate_record(&record("cuda-fusion", "cubecl<cuda>")).unwrap_err();
+        assert_eq!(
+            error.problems,
+            vec!["backend 'cubecl<cuda>' of feature 'cuda-fusion' is not fused"]
+        );
+
+        let error = validate_record(&record("cuda-fuson", "fusion<cubecl<cuda>>")).unwrap_err();
+        assert_eq!(error.problems, vec!["unknown feature 'cuda-fuson'"]);
+    }
+
+    #[test]
+    fn test_validate_record_rejects_malformed_record() {
+        let mut record = record("cuda", "cubecl<cuda>");
+        record.device = String::new();
+        record.results.computed.min = Duration::from_millis(5);
+        record.results.raw.durations.pop();
+        record.results.timestamp = 0;
+
+        let error = validate_record(&record).unwrap_err();
+        assert_eq!(error.problems.len(), 3, "{error}");
+        assert_eq!(error.problems[0], "missing device");
+        assert!(error.problems[1].starts_with("inconsistent durations"));
+        assert_eq!(error.problems[2], "timestamp 0 out of range");
+    }
+}
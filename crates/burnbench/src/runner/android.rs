@@ -0,0 +1,270 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    sync::Arc,
+};
+
+use glob::glob;
+
+use super::doctor::check_rust_target;
+use super::interrupt;
+use super::processor::{OutputProcessor, StepProcessor, process_output};
+use crate::persistence::benchmarks_cache_dir;
+
+/// Targets of the Android devices the benches can be cross-compiled for.
+pub(crate) const ANDROID_TARGETS: [&str; 4] = [
+    "aarch64-linux-android",
+    "armv7-linux-androideabi",
+    "x86_64-linux-android",
+    "i686-linux-android",
+];
+/// API level of the NDK compilers linking the benches.
+const ANDROID_API_LEVEL: u32 = 28;
+/// Directory of the device where the benches are pushed and run.
+const DEVICE_DIR: &str = "/data/local/tmp/burnbench";
+
+/// Android device connected with adb running the benches cross-compiled for its target.
+///
+/// The device is the one selected by `ANDROID_SERIAL` when several are connected, as for any adb
+/// command.
+#[derive(Debug, Clone)]
+pub(crate) struct AndroidDevice {
+    target: String,
+}
+
+impl AndroidDevice {
+    /// Check the tools required to build and run the benches, and that a device is connected.
+    pub(crate) fn new(target: &str) -> Result<Self, String> {
+        check_rust_target(target)?;
+        let state = adb_output(&["get-state"])
+            .map_err(|_| "adb not found, install the Android platform tools".to_string())?;
+        if state != "device" {
+            return Err(
+                "no Android device connected, or several without ANDROID_SERIAL".to_string(),
+            );
+        }
+        Ok(Self {
+            target: target.to_string(),
+        })
+    }
+
+    /// Model and Android version of the device, such as `Pixel 8 (Android 14)`.
+    pub(crate) fn description(&self) -> String {
+        let model = adb_output(&["shell", "getprop", "ro.product.model"]).unwrap_or_default();
+        let release =
+            adb_output(&["shell", "getprop", "ro.build.version.release"]).unwrap_or_default();
+        format!("{model} (Android {release})")
+    }
+
+    /// Build the benches, run them on the device and pull their records in `results_dir`.
+    pub(crate) fn run(
+        &self,
+        benches: &[String],
+        params: &[&str],
+        envs: &[(String, String)],
+        processor: Arc<dyn OutputProcessor>,
+        results_dir: &Path,
+    ) -> io::Result<ExitStatus> {
+        // The stale artifacts are removed to find the ones of this build.
+        let patterns: Vec<String> = if benches[0] == "all" {
+            vec!["*".to_string()]
+        } else {
+            benches
+                .iter()
+                .map(|bench| bench.replace('-', "_"))
+                .collect()
+        };
+        for artifact in self.artifacts(&patterns) {
+            fs::remove_file(artifact)?;
+        }
+
+        let step: Arc<dyn OutputProcessor> = Arc::new(StepProcessor(processor.clone()));
+        let cargo = Command::new("cargo")
+            .env("CARGO_TERM_COLOR", "always")
+            .envs(ndk_envs(&self.target))
+            .envs(envs.iter().map(|(k, v)| (k, v)))
+            .args(["bench", "--no-run", "--target", &self.target])
+            .args(params)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut status = process_output(cargo, &step)?;
+
+        if status.success() {
+            for artifact in self.artifacts(&patterns) {
+                status = run_artifact(&artifact, envs, &step, results_dir)?;
+                if !status.success() || interrupt::is_interrupted() {
+                    break;
+                }
+            }
+        }
+        processor.finish();
+        Ok(status)
+    }
+
+    /// Executables of the benches matching the patterns, such as `unary_sweep`.
+    fn artifacts(&self, patterns: &[String]) -> Vec<PathBuf> {
+        let deps = Path::new(crate::BENCHMARKS_TARGET_DIR)
+            .join(&self.target)
+            .join("release")
+            .join("deps");
+        patterns
+            .iter()
+            .flat_map(|pattern| {
+                let pattern = deps.join(format!("{pattern}-*"));
+                glob(&pattern.to_string_lossy())
+                    .into_iter()
+                    .flat_map(|paths| paths.filter_map(Result::ok))
+            })
+            // The dependency files of the benches are next to them.
+            .filter(|path| path.extension().is_none())
+            .collect()
+    }
+}
+
+/// Push the bench to the device, run it and pull the records it saved.
+fn run_artifact(
+    artifact: &Path,
+    envs: &[(String, String)],
+    processor: &Arc<dyn OutputProcessor>,
+    results_dir: &Path,
+) -> io::Result<ExitStatus> {
+    let name = artifact
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let binary = format!("{DEVICE_DIR}/{name}");
+    let device_results = format!("{DEVICE_DIR}/results");
+    let status = adb(&["shell", "mkdir", "-p", DEVICE_DIR])?;
+    if !status.success() {
+        return Ok(status);
+    }
+    let status = adb(&["push", &artifact.to_string_lossy(), &binary])?;
+    if !status.success() {
+        return Ok(status);
+    }
+
+    // The records are saved in the cache of the home directory and in the results directory,
+    // both on the device.
+    let mut script = format!(
+        "rm -rf {device_results} && mkdir -p {device_results} {DEVICE_DIR}/home && cd {DEVICE_DIR} && env HOME={DEVICE_DIR}/home BURN_BENCH_RESULTS_DIR={device_results}"
+    );
+    for (key, value) in envs
+        .iter()
+        .filter(|(key, _)| key != "BURN_BENCH_RESULTS_DIR")
+    {
+        script.push_str(&format!(" {key}={}", shell_quote(value)));
+    }
+    script.push_str(&format!(" {binary} --bench"));
+
+    processor.process_line(&format!("Running {name} on the device"));
+    let shell = adb_command(&["shell", &script])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let status = process_output(shell, processor)?;
+
+    // The records of the benchmarks that completed are kept even if a later one failed.
+    pull_records(&device_results, results_dir)?;
+    adb(&["shell", "rm", "-rf", &binary, &device_results])?;
+    Ok(status)
+}
+
+/// Pull the records saved on the device into the results directory and the local cache.
+fn pull_records(device_results: &str, results_dir: &Path) -> io::Result<()> {
+    let cache_dir = benchmarks_cache_dir();
+    fs::create_dir_all(&cache_dir)?;
+    fs::create_dir_all(results_dir)?;
+    let files = adb_output(&["shell", "ls", device_results]).unwrap_or_default();
+    for file in files.split_whitespace() {
+        let local = results_dir.join(file);
+        let status = adb(&[
+            "pull",
+            &format!("{device_results}/{file}"),
+            &local.to_string_lossy(),
+        ])?;
+        if status.success() {
+            fs::copy(&local, cache_dir.join(file))?;
+        } else {
+            tracing::warn!("Failed to pull the record {file} from the device");
+        }
+    }
+    Ok(())
+}
+
+/// Compilers of the NDK linking the benches and building the C dependencies, unless already
+/// configured.
+fn ndk_envs(target: &str) -> Vec<(String, PathBuf)> {
+    let Ok(ndk) = std::env::var("ANDROID_NDK_HOME") else {
+        return vec![];
+    };
+    // The NDK only ships x86_64 toolchains, run through Rosetta on Apple silicon.
+    let host = if cfg!(target_os = "macos") {
+        "darwin-x86_64"
+    } else {
+        "linux-x86_64"
+    };
+    let bin = Path::new(&ndk)
+        .join("toolchains")
+        .join("llvm")
+        .join("prebuilt")
+        .join(host)
+        .join("bin");
+    let compiler_target = match target {
+        "armv7-linux-androideabi" => "armv7a-linux-androideabi",
+        target => target,
+    };
+    let clang = bin.join(format!("{compiler_target}{ANDROID_API_LEVEL}-clang"));
+    let target = target.replace('-', "_");
+    [
+        (
+            format!("CARGO_TARGET_{}_LINKER", target.to_uppercase()),
+            clang.clone(),
+        ),
+        (format!("CC_{target}"), clang),
+        (format!("AR_{target}"), bin.join("llvm-ar")),
+    ]
+    .into_iter()
+    .filter(|(key, _)| std::env::var(key).is_err())
+    .collect()
+}
+
+/// Quote the value for the shell of the device.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn adb_command(args: &[&str]) -> Command {
+    let mut command = Command::new("adb");
+    command.args(args);
+    command
+}
+
+fn adb(args: &[&str]) -> io::Result<ExitStatus> {
+    adb_command(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+}
+
+/// Trimmed standard output of the adb command.
+fn adb_output(args: &[&str]) -> io::Result<String> {
+    let output = adb_command(args).stderr(Stdio::null()).output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("main"), "'main'");
+        assert_eq!(
+            shell_quote(r#"{"kind":"git","branch":"it's"}"#),
+            r#"'{"kind":"git","branch":"it'\''s"}'"#
+        );
+    }
+}
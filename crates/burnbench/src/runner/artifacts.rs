@@ -0,0 +1,134 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+/// Object storage receiving the artifacts of a run, given as `s3://bucket/prefix` or
+/// `gs://bucket/prefix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ArtifactStore {
+    provider: Provider,
+    /// URL of the prefix, without trailing slash.
+    url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Provider {
+    S3,
+    Gcs,
+}
+
+impl FromStr for ArtifactStore {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (provider, path) = if let Some(path) = s.strip_prefix("s3://") {
+            (Provider::S3, path)
+        } else if let Some(path) = s.strip_prefix("gs://") {
+            (Provider::Gcs, path)
+        } else {
+            return Err(format!(
+                "invalid artifact store '{s}', expected s3://bucket/prefix or gs://bucket/prefix"
+            ));
+        };
+        if path.split('/').next().is_none_or(str::is_empty) {
+            return Err(format!("missing bucket in artifact store '{s}'"));
+        }
+
+        Ok(Self {
+            provider,
+            url: s.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+/// Kind of artifact, naming the directory receiving it in the store.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ArtifactKind {
+    Manifest,
+    Record,
+    Log,
+    Profile,
+}
+
+impl ArtifactKind {
+    fn dir(&self) -> &'static str {
+        match self {
+            Self::Manifest => "",
+            Self::Record => "records/",
+            Self::Log => "logs/",
+            Self::Profile => "profiles/",
+        }
+    }
+}
+
+impl ArtifactStore {
+    /// URL of the artifact of the run in the store.
+    fn destination(&self, run_id: &str, kind: ArtifactKind, file: &Path) -> Option<String> {
+        let name = match kind {
+            ArtifactKind::Manifest => "manifest.json".into(),
+            _ => file.file_name()?.to_string_lossy(),
+        };
+        Some(format!("{}/{run_id}/{}{name}", self.url, kind.dir()))
+    }
+
+    /// Upload the existing artifacts under the directory of the run, with the CLI of the
+    /// provider, and return the URL of that directory.
+    pub(crate) fn upload(
+        &self,
+        run_id: &str,
+        artifacts: &[(ArtifactKind, PathBuf)],
+    ) -> io::Result<String> {
+        for (kind, file) in artifacts.iter().filter(|(_, file)| file.is_file()) {
+            let Some(destination) = self.destination(run_id, *kind, file) else {
+                continue;
+            };
+            let mut command = match self.provider {
+                Provider::S3 => {
+                    let mut command = Command::new("aws");
+                    command.args(["s3", "cp", "--only-show-errors"]);
+                    command
+                }
+                Provider::Gcs => {
+                    let mut command = Command::new("gcloud");
+                    command.args(["storage", "cp", "--no-user-output-enabled"]);
+                    command
+                }
+            };
+            let status = command.arg(file).arg(&destination).status()?;
+            if !status.success() {
+                return Err(io::Error::other(format!(
+                    "failed to upload {} ({status})",
+                    file.display()
+                )));
+            }
+        }
+
+        Ok(format!("{}/{run_id}/", self.url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_artifact_store() {
+        let store = "s3://bench-results/burn/".parse::<ArtifactStore>().unwrap();
+        assert_eq!(store.provider, Provider::S3);
+        assert_eq!(
+            store.destination("42", ArtifactKind::Log, Path::new("target/logs/a.log")),
+            Some("s3://bench-results/burn/42/logs/a.log".to_string())
+        );
+        assert_eq!(
+            store.destination("42", ArtifactKind::Manifest, Path::new("runs/42.json")),
+            Some("s3://bench-results/burn/42/manifest.json".to_string())
+        );
+        let store = "gs://bench-results".parse::<ArtifactStore>().unwrap();
+        assert_eq!(store.provider, Provider::Gcs);
+        assert!("gs://".parse::<ArtifactStore>().is_err());
+        assert!("https://bench-results".parse::<ArtifactStore>().is_err());
+    }
+}
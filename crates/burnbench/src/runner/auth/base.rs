@@ -1,10 +1,12 @@
+#[cfg(not(target_os = "android"))]
 use arboard::Clipboard;
 use serde::{Deserialize, Serialize};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::{
     error::Error,
-    fs::{self, File},
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
     path::{Path, PathBuf},
     thread, time,
 };
@@ -14,6 +16,10 @@ const FIVE_SECONDS: time::Duration = time::Duration::new(5, 0);
 const GITHUB_API_VERSION_HEADER: &str = "X-GitHub-Api-Version";
 const GITHUB_API_VERSION: &str = "2022-11-28";
 const GITHUB_BOT_TOKEN_ENV_VAR: &str = "GITHUB_BOT_TOKEN";
+/// Lifetime of the access tokens issued by the Burnbench GitHub application.
+const USER_ACCESS_TOKEN_LIFETIME: time::Duration = time::Duration::from_secs(8 * 60 * 60);
+/// Scopes a classic OAuth token needs for the benchmark server to identify the user.
+const REQUIRED_SCOPES: [&str; 1] = ["read:user"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Tokens {
@@ -30,11 +36,6 @@ pub(crate) struct Tokens {
     pub refresh_token: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub(crate) struct UserInfo {
-    pub nickname: String,
-}
-
 pub(crate) fn get_bot_token() -> Option<String> {
     std::env::var(GITHUB_BOT_TOKEN_ENV_VAR).ok()
 }
@@ -86,31 +87,147 @@ pub(crate) fn get_tokens() -> Option<Tokens> {
 
 /// Returns the authenticated user name from access token
 pub(crate) fn get_username(access_token: &str) -> Result<UserInfo, Box<dyn Error>> {
+    Ok(ServerClient::default().get_user(access_token)?)
+}
+
+/// Check that the token is accepted by GitHub and by the benchmark server before starting a
+/// run, so that a long run is not rejected when sharing its results at the end.
+pub(crate) fn validate_token(access_token: &str) -> Result<(), String> {
+    if !is_supported_token(access_token) {
+        return Err(
+            "unsupported token format, expected a Burnbench user token (ghu_), a fine-grained personal access token (github_pat_) or a classic token (ghp_, gho_)"
+                .to_string(),
+        );
+    }
+
     let client = reqwest::blocking::Client::new();
     let response = client
-        .get(format!("{TRACEL_CI_SERVER_BASE_URL}users/me"))
+        .get("https://api.github.com/user")
         .header(reqwest::header::USER_AGENT, "burnbench")
-        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
         .header(
             reqwest::header::AUTHORIZATION,
             get_auth_header_value(access_token),
         )
-        .send()?;
-    let status = response.status();
-    if status != reqwest::StatusCode::OK {
-        return Err(format!("error {status}").into());
+        .header(GITHUB_API_VERSION_HEADER, GITHUB_API_VERSION)
+        .send()
+        .map_err(|e| format!("GitHub is unreachable ({e})"))?;
+    match response.status() {
+        status if status.is_success() => {}
+        reqwest::StatusCode::UNAUTHORIZED => {
+            return Err("the token is invalid, expired or revoked".to_string());
+        }
+        status => return Err(format!("GitHub rejected the token ({status})")),
     }
-    let user_info = response.json::<UserInfo>()?;
-    Ok(user_info)
+    // Only classic tokens report their scopes, the other tokens are checked by the benchmark
+    // server below.
+    if let Some(scopes) = response
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|scopes| scopes.to_str().ok())
+    {
+        let missing = missing_scopes(scopes);
+        if !missing.is_empty() {
+            return Err(format!(
+                "the token is missing the scopes {}",
+                missing.join(", ")
+            ));
+        }
+    }
+
+    match get_username(access_token) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!(
+            "the benchmark server rejected the token ({e}), fine-grained tokens need read access to the account profile"
+        )),
+    }
+}
+
+/// Required scopes absent from the comma-separated `X-OAuth-Scopes` header.
+///
+/// An empty header means the scopes are not reported, none is considered missing.
+fn missing_scopes(scopes: &str) -> Vec<&'static str> {
+    if scopes.trim().is_empty() {
+        return vec![];
+    }
+    let granted: Vec<&str> = scopes.split(',').map(str::trim).collect();
+    REQUIRED_SCOPES
+        .into_iter()
+        // The `user` scope includes `read:user`.
+        .filter(|scope| !granted.contains(scope) && !granted.contains(&"user"))
+        .collect()
+}
+
+/// Whether the token is a kind of GitHub token accepted by the benchmark server.
+pub(crate) fn is_supported_token(access_token: &str) -> bool {
+    ["ghu_", "github_pat_", "ghp_", "gho_"]
+        .iter()
+        .any(|prefix| access_token.starts_with(prefix))
+}
+
+/// Human readable kind of the access token.
+pub(crate) fn token_kind(access_token: &str) -> &'static str {
+    if access_token.starts_with("ghu_") {
+        "Burnbench user token"
+    } else if access_token.starts_with("github_pat_") {
+        "fine-grained personal access token"
+    } else if access_token.starts_with("ghp_") {
+        "classic personal access token"
+    } else if access_token.starts_with("gho_") {
+        "OAuth access token"
+    } else {
+        "unsupported token"
+    }
+}
+
+/// Time elapsed since the tokens were saved in the cache.
+pub(crate) fn get_tokens_age() -> Option<time::Duration> {
+    let modified = fs::metadata(get_auth_cache_file_path())
+        .and_then(|metadata| metadata.modified())
+        .ok()?;
+    modified.elapsed().ok()
+}
+
+/// Remaining lifetime of a user access token saved `age` ago, `None` once it has expired.
+pub(crate) fn remaining_lifetime(age: time::Duration) -> Option<time::Duration> {
+    USER_ACCESS_TOKEN_LIFETIME.checked_sub(age)
+}
+
+/// Delete the cached tokens from the OS keyring and from the cache file. The file is
+/// overwritten before being removed so that the tokens do not linger on disk.
+///
+/// Returns false if there were no cached tokens.
+pub(crate) fn delete_tokens() -> io::Result<bool> {
+    let mut deleted = false;
+    if let Some(entry) = keyring_entry() {
+        match entry.delete_credential() {
+            Ok(()) => deleted = true,
+            Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(io::Error::other(e)),
+        }
+    }
+    let path = get_auth_cache_file_path();
+    if !path.exists() {
+        return Ok(deleted);
+    }
+    let len = fs::metadata(&path)?.len();
+    let mut file = OpenOptions::new().write(true).open(&path)?;
+    file.write_all(&vec![0; len as usize])?;
+    file.sync_all()?;
+    drop(file);
+    fs::remove_file(&path)?;
+    Ok(true)
 }
 
 pub(crate) fn get_auth_header_value(access_token: &str) -> String {
     if access_token.starts_with("ghu_") {
         format!("Bearer {}", access_token)
-    } else if access_token.starts_with("github_pat_") {
+    } else if is_supported_token(access_token) {
         format!("token {}", access_token)
     } else {
-        panic!("Unsupported token format. Only 'ghu_' and 'github_pat' format are supported.");
+        panic!(
+            "Unsupported token format. Only 'ghu_', 'github_pat_', 'ghp_' and 'gho_' formats are supported."
+        );
     }
 }
 
@@ -128,6 +245,7 @@ fn auth() -> Option<Tokens> {
     println!("\n    {}\n", flow.verification_uri.clone().unwrap());
     let user_code = flow.user_code.clone().unwrap();
     println!("👉 And enter code: {}", &user_code);
+    #[cfg(not(target_os = "android"))]
     if let Ok(mut clipboard) = Clipboard::new()
         && clipboard.set_text(user_code).is_ok()
     {
@@ -152,9 +270,21 @@ fn auth() -> Option<Tokens> {
     }
 }
 
+/// Return the tokens saved in the OS keyring, or in the cache file when the keyring is
+/// unavailable.
+pub(crate) fn get_tokens_from_cache() -> Option<Tokens> {
+    get_tokens_from_keyring().or_else(get_tokens_from_file)
+}
+
+/// Return the tokens saved in the OS keyring
+pub(crate) fn get_tokens_from_keyring() -> Option<Tokens> {
+    let secret = keyring_entry()?.get_password().ok()?;
+    serde_json::from_str(&secret).ok()
+}
+
 /// Return the token saved in the cache file
 #[inline]
-fn get_tokens_from_cache() -> Option<Tokens> {
+fn get_tokens_from_file() -> Option<Tokens> {
     let path = get_auth_cache_file_path();
     let file = File::open(path).ok()?;
     let tokens: Tokens = serde_json::from_reader(file).ok()?;
@@ -181,22 +311,16 @@ fn refresh_tokens(tokens: &Tokens) -> Option<Tokens> {
     if let Some(ref refresh_token) = tokens.refresh_token {
         println!("Access token must be refreshed.");
         println!("Refreshing token...");
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .post(format!("{TRACEL_CI_SERVER_BASE_URL}auth/refresh-token"))
-            .header(reqwest::header::USER_AGENT, "burnbench")
-            .header(reqwest::header::CONTENT_TYPE, "application/json")
-            .header(
-                reqwest::header::AUTHORIZATION,
-                format!("Bearer-Refresh {}", refresh_token),
-            )
-            // it is important to explicitly add an empty body otherwise
-            // reqwest won't send the request in release build
-            .body(reqwest::blocking::Body::from(""))
-            .send();
-        response.ok()?.json::<Tokens>().ok().inspect(|_new_tokens| {
-            println!("✅ Token refreshed!");
-        })
+        match ServerClient::default().refresh_tokens(refresh_token) {
+            Ok(new_tokens) => {
+                println!("✅ Token refreshed!");
+                Some(new_tokens)
+            }
+            Err(e) => {
+                println!("Failed to refresh the token ({e})");
+                None
+            }
+        }
     } else {
         // PAT tokens does not need to be refreshed, we just return back the initial tokens
         println!("✅️ PAT tokens don't need to be refreshed. Nothing done.");
@@ -205,17 +329,62 @@ fn refresh_tokens(tokens: &Tokens) -> Option<Tokens> {
 }
 
 /// Return the file path for the auth cache on disk
-fn get_auth_cache_file_path() -> PathBuf {
+pub(crate) fn get_auth_cache_file_path() -> PathBuf {
     let home_dir = dirs::home_dir().expect("an home directory should exist");
     let path_dir = home_dir.join(".cache").join("burn").join("burnbench");
     #[cfg(test)]
     let path_dir = path_dir.join("test");
     let path = Path::new(&path_dir);
-    path.join("token.txt")
+    // The tokens of self-hosted benchmark servers are stored separately
+    match server_key() {
+        Some(key) => path.join(format!("token-{key}.txt")),
+        None => path.join("token.txt"),
+    }
 }
 
-/// Save token in Burn cache directory and adjust file permissions
+/// Return the entry of the tokens in the OS keyring.
+///
+/// The keyring is never used by the tests so that they don't touch the credentials of the user.
+#[cfg(not(test))]
+fn keyring_entry() -> Option<keyring::Entry> {
+    let user = match server_key() {
+        Some(key) => format!("github-tokens@{key}"),
+        None => "github-tokens".to_string(),
+    };
+    keyring::Entry::new("burnbench", &user).ok()
+}
+
+#[cfg(test)]
+fn keyring_entry() -> Option<keyring::Entry> {
+    None
+}
+
+/// Save the tokens in the OS keyring, falling back to the Burn cache directory when no
+/// keyring is available, for instance on headless CI machines.
 fn save_tokens(tokens: &Tokens) {
+    let secret = serde_json::to_string(tokens).expect("Tokens should be serializable.");
+    match keyring_entry().map(|entry| entry.set_password(&secret)) {
+        Some(Ok(())) => {
+            // Don't leave an older plaintext copy behind
+            let path = get_auth_cache_file_path();
+            if path.exists() && fs::remove_file(&path).is_err() {
+                println!(
+                    "⚠️ Cannot remove the previous token file: {}",
+                    path.display()
+                );
+            }
+            println!("✅ Token saved in the OS keyring.");
+        }
+        Some(Err(e)) => {
+            println!("⚠️ Cannot use the OS keyring ({e}), saving the token to a file instead.");
+            save_tokens_to_file(tokens);
+        }
+        None => save_tokens_to_file(tokens),
+    }
+}
+
+/// Save token in Burn cache directory and adjust file permissions
+fn save_tokens_to_file(tokens: &Tokens) {
     let path = get_auth_cache_file_path();
     fs::create_dir_all(path.parent().expect("path should have a parent directory"))
         .expect("directory should be created");
@@ -232,7 +401,11 @@ fn save_tokens(tokens: &Tokens) {
 #[cfg(test)]
 use serial_test::serial;
 
-use crate::{TRACEL_CI_SERVER_BASE_URL, runner::auth::github_device_flow::DeviceFlow};
+use crate::{
+    runner::auth::github_device_flow::DeviceFlow,
+    server::server_key,
+    server_client::{ServerClient, UserInfo},
+};
 
 #[cfg(test)]
 mod tests {
@@ -350,6 +523,51 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_delete_saved_tokens() {
+        cleanup_test_environment();
+        save_tokens(&make_tokens("ghu_access", "refresh"));
+        assert!(delete_tokens().unwrap());
+        assert!(get_tokens_from_cache().is_none());
+        assert!(!delete_tokens().unwrap());
+        cleanup_test_environment();
+    }
+
+    #[test]
+    fn test_remaining_lifetime() {
+        let hour = time::Duration::from_secs(60 * 60);
+        assert_eq!(remaining_lifetime(hour), Some(7 * hour));
+        assert_eq!(remaining_lifetime(9 * hour), None);
+    }
+
+    #[test]
+    fn test_token_kind() {
+        assert_eq!(token_kind("ghu_abc123"), "Burnbench user token");
+        assert_eq!(
+            token_kind("github_pat_abc123"),
+            "fine-grained personal access token"
+        );
+        assert_eq!(token_kind("ghp_abc123"), "classic personal access token");
+        assert_eq!(token_kind("gho_abc123"), "OAuth access token");
+        assert_eq!(token_kind("glpat-abc123"), "unsupported token");
+    }
+
+    #[test]
+    fn test_missing_scopes() {
+        assert_eq!(missing_scopes("repo, read:user"), Vec::<&str>::new());
+        assert_eq!(missing_scopes("user, repo"), Vec::<&str>::new());
+        assert_eq!(missing_scopes("repo"), vec!["read:user"]);
+        assert_eq!(missing_scopes(""), Vec::<&str>::new());
+        assert_eq!(missing_scopes(" "), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_validate_token_rejects_unsupported_format() {
+        let error = validate_token("glpat-abc123").unwrap_err();
+        assert!(error.contains("unsupported token format"), "{error}");
+    }
+
     #[test]
     fn test_get_auth_header_value_with_ghu_token() {
         let token = "ghu_abc123";
@@ -0,0 +1,106 @@
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Environment variable set when running the benchmarks of a backend, such as `CUBECL_DEBUG` or
+/// `OMP_NUM_THREADS`, given as `[backend=<backend>:]KEY=VALUE`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct BackendEnv {
+    /// Backend the variable is set for, every backend when not given.
+    pub(crate) backend: Option<String>,
+    pub(crate) key: String,
+    pub(crate) value: String,
+}
+
+impl FromStr for BackendEnv {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid variable '{s}', expected [backend=<backend>:]KEY=VALUE");
+        let (backend, variable) = match s.strip_prefix("backend=") {
+            Some(rest) => {
+                let (backend, variable) = rest.split_once(':').ok_or_else(invalid)?;
+                (Some(backend.to_string()), variable)
+            }
+            None => (None, s),
+        };
+        let (key, value) = variable.split_once('=').ok_or_else(invalid)?;
+        let valid_key = !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key || backend.as_deref() == Some("") {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            backend,
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Variables to set for the backend, the ones given for the backend taking precedence over the
+/// ones given for every backend.
+pub(crate) fn backend_envs(envs: &[BackendEnv], backend: &str) -> Vec<(String, String)> {
+    let global = envs.iter().filter(|env| env.backend.is_none());
+    let specific = envs
+        .iter()
+        .filter(|env| env.backend.as_deref() == Some(backend));
+    // The last value of a variable is the one set on the command.
+    global
+        .chain(specific)
+        .map(|env| (env.key.clone(), env.value.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend_env() {
+        assert_eq!(
+            "backend=cuda:CUBECL_DEBUG=1".parse::<BackendEnv>(),
+            Ok(BackendEnv {
+                backend: Some("cuda".to_string()),
+                key: "CUBECL_DEBUG".to_string(),
+                value: "1".to_string(),
+            })
+        );
+        assert_eq!(
+            "RUSTFLAGS=-C target-cpu=native".parse::<BackendEnv>(),
+            Ok(BackendEnv {
+                backend: None,
+                key: "RUSTFLAGS".to_string(),
+                value: "-C target-cpu=native".to_string(),
+            })
+        );
+        assert!("backend=cuda".parse::<BackendEnv>().is_err());
+        assert!("backend=:KEY=1".parse::<BackendEnv>().is_err());
+        assert!("1KEY=1".parse::<BackendEnv>().is_err());
+    }
+
+    #[test]
+    fn test_backend_envs_override_global_ones() {
+        let envs: Vec<BackendEnv> = [
+            "backend=ndarray:RAYON_NUM_THREADS=4",
+            "RAYON_NUM_THREADS=8",
+            "backend=cuda:CUBECL_DEBUG=1",
+        ]
+        .iter()
+        .map(|env| env.parse().unwrap())
+        .collect();
+
+        assert_eq!(
+            backend_envs(&envs, "ndarray"),
+            vec![
+                ("RAYON_NUM_THREADS".to_string(), "8".to_string()),
+                ("RAYON_NUM_THREADS".to_string(), "4".to_string()),
+            ]
+        );
+        assert_eq!(
+            backend_envs(&envs, "wgpu"),
+            vec![("RAYON_NUM_THREADS".to_string(), "8".to_string())]
+        );
+    }
+}
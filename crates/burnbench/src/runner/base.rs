@@ -1,41 +1,135 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::builder::PossibleValuesParser;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use regex::Regex;
+use semver::Version;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io;
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
-use std::process::ExitStatus;
+use std::process::Command;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use strum::{Display, EnumIter, IntoEnumIterator};
+use uuid::Uuid;
 
 use super::auth::Tokens;
+use crate::allocations::COUNT_ALLOCATIONS_FEATURE;
 use crate::endgroup;
+use crate::energy::MEASURE_ENERGY_ENV;
 use crate::group;
+use crate::persistence::{BenchmarkRecord, BurnSource, MetricsExporter, benchmarks_cache_dir};
 use crate::runner::workflow::send_output_results;
-use crate::system_info::BenchmarkSystemInfo;
-use crate::{BENCHMARK_WEBSITE_URL, TRACEL_CI_SERVER_BASE_URL};
+use crate::server::{init_server_urls, server_base_url, website_url};
+use crate::server_client::ServerClient;
+use crate::system_info::{BenchmarkSystemInfo, LOCKED_CLOCKS_ENV};
 
-use super::auth::get_tokens;
+use super::android::{ANDROID_TARGETS, AndroidDevice};
+use super::artifacts::{ArtifactKind, ArtifactStore};
 use super::auth::get_username;
-use super::dependency::Dependency;
-use super::processor::{CargoRunner, NiceProcessor, OutputProcessor, Profiling, VerboseProcessor};
+use super::auth::{
+    delete_tokens, get_auth_cache_file_path, get_bot_token, get_tokens, get_tokens_age,
+    get_tokens_from_cache, get_tokens_from_keyring, is_supported_token, remaining_lifetime,
+    token_kind, validate_token,
+};
+use super::backend_env::{BackendEnv, backend_envs};
+use super::binary_size::{self, format_size, with_executables};
+use super::cache::{RecordCache, current_git_hash};
+use super::calibration;
+use super::clean::{self, CleanTarget, TARGET_LOCK, disk_usage};
+use super::clocks::ClockLock;
+use super::container::Container;
+use super::dependency::{Dependency, resolve_branch};
+use super::diff::ResultSource;
+use super::disk_space::check_disk_space;
+use super::doctor::{Platforms, run_diagnostics};
+use super::events::{CombinationOutcome, Event, EventStream, EventTarget, with_events};
+use super::feature_mapping::FeatureMapping;
+use super::flamegraph;
+use super::html::write_html_report;
+use super::interrupt;
+use super::junit::write_junit_report;
+use super::lock::FileLock;
+use super::logging::{self, init_tracing, with_log_file};
+use super::manifest::{RunManifest, manifest_path};
+use super::ncu;
+use super::normalization::normalize_record_files;
+use super::notify::Notifier;
+use super::nsys::KernelTraces;
+use super::outliers::{OUTLIER_FACTOR, find_outliers};
+use super::pinning::{Pinning, parse_cores};
+use super::plan::{GHA_MAX_JOBS, MatrixEntry, Suite, gha_matrix};
+use super::preflight::check_compatibility;
+use super::processor::{
+    CargoRunner, NiceProcessor, OutputProcessor, Profiling, SinkProcessor, VerboseProcessor,
+};
 use super::progressbar::RunnerProgressBar;
-use super::reports::{BenchmarkCollection, FailedBenchmark};
+use super::remote::{Remote, forwarded_args};
+use super::reports::{
+    BenchmarkCollection, ChangeThresholds, FailedBenchmark, GroupBy, ReportFilter, RowOrder,
+    SkippedBenchmark, SortBy, latest_run_id, parse_duration, run_dir,
+};
+use super::shard::Shard;
+use super::state::{Combination, RunState};
+use super::threads::{ThreadCount, resolve_thread_counts, thread_envs};
+use super::trend::{
+    DEFAULT_SHIFT_THRESHOLD, TrendGrouping, get_run_shift_alert, get_shift_alert, get_trend_table,
+    trends,
+};
+use super::tui::{self, CombinationState, CombinationStatus};
+use super::wasm::WasmTarget;
+
+/// Value of `--resume` without a run id, resuming the last run.
+const LATEST_RUN: &str = "latest";
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
     #[clap(subcommand)]
     command: Commands,
+
+    /// Base URL of a self-hosted benchmark server to authenticate with and share the results to,
+    /// defaults to the BURN_BENCH_SERVER_URL environment variable then to the Tracel server
+    #[clap(long = "server-url", global = true)]
+    server_url: Option<String>,
+
+    /// Base URL of the website showing the results of the benchmark server, defaults to the
+    /// BURN_BENCH_WEBSITE_URL environment variable then to burn.dev
+    #[clap(long = "website-url", global = true)]
+    website_url: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Authenticate using GitHub
-    Auth,
+    Auth(AuthArgs),
     /// List all available backends
     List,
     /// Runs benchmarks
     Run(RunArgs),
+    /// Generate a report from the local benchmark records
+    Report(ReportArgs),
+    /// Check the environment required by the backends
+    Doctor,
+    /// Generate the shell completions of burnbench
+    Completions(CompletionsArgs),
+    /// Select and monitor the benchmarks in an interactive terminal UI
+    Tui(TuiArgs),
+    /// Combine the records exported from several machines into a comparison report
+    Merge(MergeArgs),
+    /// Compare two sets of results without running the benchmarks again
+    Diff(DiffArgs),
+    /// Expand a benchmark suite into the jobs of a CI matrix
+    Plan(PlanArgs),
+    /// Remove the build artifacts, logs, profiles or results of the runs
+    Clean(CleanArgs),
+    /// Show the trend of the medians of the local benchmark records over time
+    Trend(TrendArgs),
+    /// Push the local benchmark records to a Prometheus pushgateway or an InfluxDB endpoint
+    Export(ExportArgs),
+    /// Manage the results shared to the benchmark server
+    Share(ShareArgs),
 }
 
 /// Information about the crate to benchmark.
@@ -45,6 +139,43 @@ struct CrateInfo {
     name: String,
     /// The path from which the command burnbench will be run.
     path: PathBuf,
+    /// Root of the workspace containing the crate.
+    workspace: PathBuf,
+}
+
+impl CrateInfo {
+    /// Crate of benchmarks outside of the burn-bench workspace, read from its manifest.
+    fn from_path(path: &Path) -> Result<Self, String> {
+        let path = fs::canonicalize(path).map_err(|e| format!("{} ({e})", path.display()))?;
+        let manifest = read_manifest(&path.join("Cargo.toml"))?;
+        let name = manifest
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| format!("{} is not the path of a crate", path.display()))?
+            .to_string();
+        // The closest parent declaring a workspace, which holds the lockfile of the crate.
+        let workspace = path
+            .ancestors()
+            .find(|dir| {
+                read_manifest(&dir.join("Cargo.toml"))
+                    .is_ok_and(|manifest| manifest.contains_key("workspace"))
+            })
+            .unwrap_or(&path)
+            .to_path_buf();
+        Ok(Self {
+            name,
+            path,
+            workspace,
+        })
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<toml::Table, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{} ({e})", path.display()))?;
+    content
+        .parse()
+        .map_err(|e| format!("invalid manifest {} ({e})", path.display()))
 }
 
 #[derive(Parser, Debug)]
@@ -53,6 +184,11 @@ struct RunArgs {
     #[clap(short = 's', long = "share")]
     share: bool,
 
+    /// Share the results without asking for confirmation when they deviate from the local
+    /// history of the same hardware
+    #[clap(long = "force-share", requires = "share")]
+    force_share: bool,
+
     /// Enable verbose mode
     #[clap(short = 'v', long = "verbose")]
     verbose: bool,
@@ -61,6 +197,11 @@ struct RunArgs {
     #[clap(short = 'B', long = "backends", num_args(1..), required = true)]
     backends: Vec<BackendValues>,
 
+    /// Crate of benches to run instead of backend-comparison, using `burnbench::Benchmark` and
+    /// `bench_on_backend!` with the same backend and dtype features
+    #[clap(long = "bench-crate")]
+    pub bench_crate: Option<PathBuf>,
+
     /// Space separated list of benches to run
     #[clap(short = 'b', long = "benches", num_args(0..))]
     benches: Vec<String>,
@@ -74,6 +215,14 @@ struct RunArgs {
     #[clap(short = 'd', long = "dtypes", num_args(0..))]
     pub dtypes: Vec<BenchDType>,
 
+    /// Tag attached to the results to tell the runs apart
+    #[clap(short = 't', long = "tag")]
+    pub tag: Option<String>,
+
+    /// Free-form note attached to the results
+    #[clap(long = "note")]
+    pub note: Option<String>,
+
     #[clap(short = 'p', long = "profile", default_value = "false")]
     pub profile: bool,
 
@@ -81,6 +230,501 @@ struct RunArgs {
     pub ncu_path: String,
     #[arg(long, default_value = "ncu-ui")]
     pub ncu_ui_path: String,
+    /// With --profile, also sample the CPU stacks of the benches with perf and write a
+    /// differential flamegraph between the first version and each of the others
+    #[clap(long = "flamegraph", requires = "profile")]
+    pub flamegraph: bool,
+    /// Profile the benches without opening ncu-ui, then run them and attach the top kernels,
+    /// achieved occupancy, memory throughput and SM utilization of the profile to their records
+    #[clap(long = "profile-headless")]
+    pub profile_headless: bool,
+    /// Trace the benches of the CUDA backends with nsys and attach their number of kernel
+    /// launches and distinct kernels to their records
+    #[clap(
+        long = "kernel-stats",
+        conflicts_with_all = ["pin_cores", "numa_node", "wasm", "adb", "container", "profile", "profile_headless"]
+    )]
+    pub kernel_stats: bool,
+
+    /// Layout of the results table
+    #[clap(long = "report-layout", value_enum, default_value_t = ReportLayout::Rows)]
+    pub report_layout: ReportLayout,
+
+    /// Order of the rows of the results table within their group
+    #[clap(long = "report-sort-by", value_enum, default_value_t = SortBy::Median)]
+    pub report_sort_by: SortBy,
+
+    /// Rows of the results table kept together
+    #[clap(long = "report-group-by", value_enum, default_value_t = GroupBy::Bench)]
+    pub report_group_by: GroupBy,
+
+    /// Filters applied to the results before rendering the report
+    ///
+    /// One or more of: min-median=<duration>, name=<regex>, only-regressions[=<percent>],
+    /// top=<count>.
+    #[clap(long = "report-filter", num_args(1..))]
+    pub report_filters: Vec<ReportFilter>,
+
+    /// Relative slowdown in percent against the first version above which results are
+    /// highlighted in red
+    #[clap(long = "regression-threshold", default_value_t = 5.0)]
+    pub regression_threshold: f64,
+
+    /// Relative speedup in percent against the first version above which results are
+    /// highlighted in green
+    #[clap(long = "improvement-threshold", default_value_t = 5.0)]
+    pub improvement_threshold: f64,
+
+    /// Backend used as the reference for the summary scores
+    ///
+    /// Defaults to the first backend of the list.
+    #[clap(long = "reference-backend")]
+    pub reference_backend: Option<BackendValues>,
+
+    /// Write a JUnit XML report of the benchmark outcomes to the given file
+    #[clap(long = "junit")]
+    pub junit: Option<PathBuf>,
+
+    /// Maximum duration of the whole run, such as `30m` or `2h`
+    ///
+    /// Once exceeded, the remaining combinations are skipped and reported as such.
+    #[clap(long = "time-budget", value_parser = parse_duration)]
+    pub time_budget: Option<Duration>,
+
+    /// Run only a shard of the version × backend × bench × dtype matrix, given as
+    /// `<index>/<count>` such as `2/5`, to split the run between several CI runners
+    ///
+    /// The runners given the same arguments run disjoint shards, whose records can be combined
+    /// with `burnbench merge`.
+    #[clap(long = "shard")]
+    pub shard: Option<Shard>,
+
+    /// Resume an interrupted run, skipping the combinations it already completed
+    ///
+    /// Resumes the last run started on the machine, or the run with the given id when other
+    /// runs were started since.
+    #[clap(
+        long = "resume",
+        value_name = "RUN_ID",
+        num_args = 0..=1,
+        default_missing_value = LATEST_RUN
+    )]
+    pub resume: Option<String>,
+
+    /// Skip the compatibility check of the benchmarks against each Burn version
+    #[clap(long = "skip-preflight")]
+    pub skip_preflight: bool,
+
+    /// Skip the check that the disk of the target directory has room for the builds of each
+    /// version and backend
+    #[clap(long = "skip-disk-check")]
+    pub skip_disk_check: bool,
+
+    /// Also run the fusion or non-fusion variant of each backend and report the fusion speedup
+    #[clap(long = "with-fusion-variants")]
+    pub with_fusion_variants: bool,
+
+    /// Reuse the records of the local history for the combinations already run on the same
+    /// commit and hardware instead of running them again
+    #[clap(long = "skip-cached")]
+    pub skip_cached: bool,
+
+    /// Run the benchmarks on another machine over SSH, given as `user@host[:dir]`
+    ///
+    /// The workspace is copied to `dir`, by default `~/burnbench-remote/<workspace>`, and the
+    /// records are pulled back into the local cache.
+    #[clap(long = "remote")]
+    pub remote: Option<Remote>,
+
+    /// Run each cargo invocation inside the given container image, such as a CUDA or ROCm image
+    /// with a Rust toolchain
+    ///
+    /// The digest of the image is stored in the records.
+    #[clap(long = "container")]
+    pub container: Option<String>,
+
+    /// ONNX model benchmarked by the onnx-model bench, imported with burn-import when the bench
+    /// is built
+    ///
+    /// The onnx-model bench is run by default when the model is given.
+    #[clap(long = "onnx-model", requires = "onnx_input_shapes")]
+    pub onnx_model: Option<PathBuf>,
+
+    /// Space separated shapes of the float inputs of the ONNX model, such as `1x3x224x224`
+    #[clap(long = "onnx-input-shapes", num_args(1..), value_parser = parse_input_shape)]
+    pub onnx_input_shapes: Vec<String>,
+
+    /// Build the benches for WebAssembly and run them on WebGPU in a headless Chrome or Chromium
+    /// browser
+    ///
+    /// Requires the `wasm32-unknown-unknown` target and `wasm-bindgen-cli`, and the wgpu backend.
+    #[clap(long = "wasm")]
+    pub wasm: bool,
+
+    /// Browser running the benches built with `--wasm`, found in the `PATH` by default
+    #[clap(long = "browser", requires = "wasm")]
+    pub browser: Option<PathBuf>,
+
+    /// Cross-compile the benches for an Android target, run with `--adb`
+    #[clap(
+        long = "target",
+        requires = "adb",
+        value_parser = PossibleValuesParser::new(ANDROID_TARGETS)
+    )]
+    pub target: Option<String>,
+
+    /// Run the benches cross-compiled with `--target` on the Android device connected with adb,
+    /// selected with `ANDROID_SERIAL` when several are connected
+    ///
+    /// Requires the target installed with rustup and the NDK in `ANDROID_NDK_HOME`.
+    #[clap(long = "adb", requires = "target", conflicts_with = "wasm")]
+    pub adb: bool,
+
+    /// Cargo profile building the benches instead of the default bench profile, such as a
+    /// profile with LTO defined in the manifest of the benchmarks crate
+    ///
+    /// Recorded in the results, which are not reused from the cache with `--skip-cached`.
+    #[clap(
+        long = "cargo-profile",
+        conflicts_with_all = ["wasm", "adb", "skip_cached", "profile", "profile_headless"]
+    )]
+    pub cargo_profile: Option<String>,
+
+    /// Flags passed to rustc with `RUSTFLAGS` when building the benches, such as
+    /// `-C target-cpu=native`
+    ///
+    /// Replaces the `RUSTFLAGS` of the environment and is recorded in the results.
+    #[clap(
+        long = "rustflags",
+        allow_hyphen_values = true,
+        conflicts_with_all = ["wasm", "adb", "skip_cached"]
+    )]
+    pub rustflags: Option<String>,
+
+    /// Comma separated cargo features appended to the ones enabled for the backend, such as
+    /// unstable features of Burn enabled with `burn/<feature>`
+    #[clap(
+        long = "extra-features",
+        value_delimiter = ',',
+        num_args(1..),
+        conflicts_with = "skip_cached"
+    )]
+    pub extra_features: Vec<String>,
+
+    /// Environment variable set when running the benches, given as `KEY=VALUE` for every backend
+    /// or as `backend=<backend>:KEY=VALUE` for one of them, such as `backend=cuda:CUBECL_DEBUG=1`
+    ///
+    /// Can be repeated, the variables are recorded in the run manifest.
+    #[clap(long = "env")]
+    pub envs: Vec<BackendEnv>,
+
+    /// Comma separated numbers of threads, or `max` for all the cores, the CPU backends are run
+    /// with in turn to report how they scale
+    ///
+    /// Applies to the ndarray, tch-cpu and candle-cpu backends and to their baselines.
+    #[clap(long = "threads", value_delimiter = ',', num_args(1..))]
+    pub threads: Vec<ThreadCount>,
+
+    /// Measure the energy consumed by the benchmarks with NVML, RAPL or powermetrics
+    #[clap(long = "measure-energy")]
+    pub measure_energy: bool,
+
+    /// Count the allocations made on the host by each execution of the benchmarks, with a
+    /// counting allocator enabled by the `count-allocations` feature of the benchmarks crate
+    #[clap(long = "count-allocations")]
+    pub count_allocations: bool,
+
+    /// Lock the GPU clocks during the run to reduce the variance caused by boost clocks
+    ///
+    /// Requires root privileges, the clocks are restored at the end of the run.
+    #[clap(long = "lock-clocks")]
+    pub lock_clocks: bool,
+
+    /// Pin the benches to the given CPU cores, such as `0-7` or `0,2,4-7`, with taskset
+    ///
+    /// Reduces the run-to-run variance on multi-socket machines, the pinning is recorded in the
+    /// system info of the results.
+    #[clap(
+        long = "pin-cores",
+        value_parser = parse_cores,
+        conflicts_with_all = ["numa_node", "wasm", "adb", "container", "profile", "profile_headless"]
+    )]
+    pub pin_cores: Option<String>,
+
+    /// Pin the benches to the cores and memory of the given NUMA node with numactl
+    #[clap(
+        long = "numa-node",
+        conflicts_with_all = ["wasm", "adb", "container", "profile", "profile_headless"]
+    )]
+    pub numa_node: Option<u32>,
+
+    /// Estimate the noise of the machine with a calibration kernel before the run
+    ///
+    /// The changes smaller than the noise score are not highlighted in the report.
+    #[clap(long = "calibrate")]
+    pub calibrate: bool,
+
+    /// Upload the records, logs and profiles of the run to `s3://bucket/prefix` or
+    /// `gs://bucket/prefix`, under a directory named after the run
+    ///
+    /// Uses the `aws` or `gcloud` CLI and their configured credentials.
+    #[clap(long = "artifact-store")]
+    pub artifact_store: Option<ArtifactStore>,
+
+    /// Send a notification with the results when the run ends, to the desktop by default or to
+    /// the URL of a Slack or Discord incoming webhook
+    #[clap(long = "notify", num_args(0..=1), default_missing_value = "desktop")]
+    pub notify: Option<Notifier>,
+
+    /// Stream the events of the run as NDJSON to a file, or to a file descriptor given as a
+    /// number, so that external tools can follow its progress
+    #[clap(long = "emit-events")]
+    pub emit_events: Option<EventTarget>,
+
+    /// URL receiving the results webhook instead of the benchmark server, defaults to the
+    /// WEBHOOK_URL environment variable. Unlike the benchmark server, it receives every run, with
+    /// the inputs of the GitHub workflow when WEBHOOK_INPUTS_FILE is set
+    #[clap(long = "webhook-url")]
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct TuiArgs {
+    /// One or more Burn versions, git branches, or commit hashes
+    ///
+    /// Default using @main.
+    #[clap(short = 'V', long = "versions", num_args(0..))]
+    versions: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+struct MergeArgs {
+    /// Record files or directories containing them, such as copies of the local cache of other
+    /// machines
+    #[clap(required = true, num_args(1..))]
+    inputs: Vec<PathBuf>,
+
+    /// Output file of the markdown report, printed to stdout by default
+    #[clap(short = 'o', long = "output")]
+    output: Option<PathBuf>,
+
+    /// Relative slowdown in percent against the first column above which results are
+    /// highlighted in red
+    #[clap(long = "regression-threshold", default_value_t = 5.0)]
+    regression_threshold: f64,
+
+    /// Relative speedup in percent against the first column above which results are
+    /// highlighted in green
+    #[clap(long = "improvement-threshold", default_value_t = 5.0)]
+    improvement_threshold: f64,
+}
+
+#[derive(Parser, Debug)]
+struct DiffArgs {
+    /// Results compared against: a record file or directory, the id of a local run, or the link
+    /// of a shared run
+    base: ResultSource,
+
+    /// Results compared to the base, given the same way
+    new: ResultSource,
+
+    /// Output file of the markdown report, printed to stdout by default
+    #[clap(short = 'o', long = "output")]
+    output: Option<PathBuf>,
+
+    /// Relative slowdown in percent against the base above which results are highlighted in red
+    #[clap(long = "regression-threshold", default_value_t = 5.0)]
+    regression_threshold: f64,
+
+    /// Relative speedup in percent against the base above which results are highlighted in
+    /// green
+    #[clap(long = "improvement-threshold", default_value_t = 5.0)]
+    improvement_threshold: f64,
+}
+
+#[derive(Parser, Debug)]
+struct CleanArgs {
+    /// Comma separated kinds of files to remove, the build artifacts, logs and profiles by
+    /// default
+    #[clap(value_enum, value_delimiter = ',')]
+    targets: Vec<CleanTarget>,
+
+    /// Remove every kind of file, including the results used by the reports and trends
+    #[clap(long = "all", conflicts_with = "targets")]
+    all: bool,
+
+    /// List the files that would be removed with their size, without removing them
+    #[clap(long = "dry-run")]
+    dry_run: bool,
+}
+
+#[derive(Parser, Debug)]
+struct PlanArgs {
+    /// TOML file of the suite, listing its benches, backends, dtypes and the runner label of
+    /// each backend
+    suite: PathBuf,
+
+    /// Print the jobs as the JSON matrix of a GitHub Actions `strategy.matrix`
+    #[clap(long = "gha")]
+    gha: bool,
+}
+
+#[derive(Parser, Debug)]
+struct TrendArgs {
+    /// Regex the names of the benchmarks must match
+    #[clap(long = "name")]
+    name: Option<Regex>,
+
+    /// Comma separated features of the backends to show, every feature by default
+    #[clap(long = "features", value_delimiter = ',', num_args(1..))]
+    features: Vec<String>,
+
+    /// Points of the trends
+    #[clap(long = "group-by", value_enum, default_value_t = TrendGrouping::Date)]
+    group_by: TrendGrouping,
+
+    /// Number of the most recent points shown per trend
+    #[clap(long = "last", default_value_t = 20)]
+    last: usize,
+
+    /// Relative change in percent of the level of a trend below which its shifts are not
+    /// flagged as change-points
+    #[clap(long = "threshold", default_value_t = DEFAULT_SHIFT_THRESHOLD)]
+    threshold: f64,
+
+    /// Send an alert when new shifts are detected at the last point of the trends, to the
+    /// desktop by default or to the URL of a Slack or Discord incoming webhook
+    #[clap(long = "notify", num_args(0..=1), default_missing_value = "desktop")]
+    notify: Option<Notifier>,
+}
+
+#[derive(Parser, Debug)]
+struct ExportArgs {
+    /// Metrics backend receiving the records
+    #[clap(value_enum)]
+    target: ExportTarget,
+
+    /// URL of the Prometheus pushgateway or of the InfluxDB write endpoint, including its
+    /// bucket and organization parameters
+    ///
+    /// Defaults to the BURN_BENCH_EXPORT_URL environment variable.
+    #[clap(long = "url")]
+    url: Option<String>,
+
+    /// Job grouping the metrics in the Prometheus pushgateway
+    #[clap(long = "job", default_value = "burnbench")]
+    job: String,
+
+    /// API token of InfluxDB
+    ///
+    /// Defaults to the BURN_BENCH_EXPORT_TOKEN environment variable.
+    #[clap(long = "token")]
+    token: Option<String>,
+
+    /// Export every record of the local cache instead of only the ones of the last run
+    #[clap(short = 'a', long = "all")]
+    all: bool,
+}
+
+#[derive(Parser, Debug)]
+struct AuthArgs {
+    #[clap(subcommand)]
+    command: Option<AuthCommands>,
+}
+
+#[derive(Subcommand, Debug)]
+enum AuthCommands {
+    /// Delete the cached access token, for instance to switch GitHub accounts
+    Logout,
+    /// Show the current access token and whether the benchmark server accepts it
+    Status,
+}
+
+#[derive(Parser, Debug)]
+struct ShareArgs {
+    /// Retract shared results from the benchmark server, given the ID of a record or the ID of a
+    /// run printed when it started to retract all its records
+    #[clap(long = "delete", required = true, num_args(1..))]
+    delete: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+struct CompletionsArgs {
+    /// Shell to generate the completions for
+    #[clap(value_enum)]
+    shell: Shell,
+}
+
+#[derive(Parser, Debug)]
+struct ReportArgs {
+    /// Format of the generated report
+    #[clap(short = 'f', long = "format", value_enum, default_value_t = ReportFormat::Html)]
+    format: ReportFormat,
+
+    /// Output file of the report
+    ///
+    /// Defaults to `burnbench-report.html` for HTML, markdown reports are printed to stdout.
+    #[clap(short = 'o', long = "output")]
+    output: Option<PathBuf>,
+
+    /// Layout of the markdown table
+    #[clap(long = "layout", value_enum, default_value_t = ReportLayout::Rows)]
+    layout: ReportLayout,
+
+    /// Order of the rows of the markdown table within their group, with the rows layout
+    #[clap(long = "sort-by", value_enum, default_value_t = SortBy::Median)]
+    sort_by: SortBy,
+
+    /// Rows of the markdown table kept together, with the rows layout
+    #[clap(long = "group-by", value_enum, default_value_t = GroupBy::Bench)]
+    group_by: GroupBy,
+
+    /// Filters applied to the records before rendering the report
+    ///
+    /// One or more of: min-median=<duration>, name=<regex>, only-regressions[=<percent>],
+    /// top=<count>.
+    #[clap(long = "filter", num_args(1..))]
+    filters: Vec<ReportFilter>,
+
+    /// Relative slowdown in percent against the first version above which results are
+    /// highlighted in red
+    #[clap(long = "regression-threshold", default_value_t = 5.0)]
+    regression_threshold: f64,
+
+    /// Relative speedup in percent against the first version above which results are
+    /// highlighted in green
+    #[clap(long = "improvement-threshold", default_value_t = 5.0)]
+    improvement_threshold: f64,
+
+    /// Include every record of the local cache instead of only the ones of the last run
+    #[clap(short = 'a', long = "all")]
+    all: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum, Display)]
+enum ReportLayout {
+    /// One row per benchmark record
+    #[strum(to_string = "rows")]
+    Rows,
+    /// One row per benchmark with backends and versions as columns
+    #[strum(to_string = "pivot")]
+    Pivot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum, Display)]
+enum ReportFormat {
+    #[strum(to_string = "html")]
+    Html,
+    #[strum(to_string = "markdown")]
+    Markdown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum, Display)]
+enum ExportTarget {
+    #[strum(to_string = "prometheus")]
+    Prometheus,
+    #[strum(to_string = "influx")]
+    Influx,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum, Display, EnumIter)]
@@ -99,6 +743,18 @@ enum BenchDType {
 enum BackendValues {
     #[strum(to_string = "all")]
     All,
+    #[strum(to_string = "baseline-candle-cpu")]
+    BaselineCandleCpu,
+    #[strum(to_string = "baseline-candle-cuda")]
+    BaselineCandleCuda,
+    #[strum(to_string = "baseline-ort-cpu")]
+    BaselineOrtCpu,
+    #[strum(to_string = "baseline-ort-cuda")]
+    BaselineOrtCuda,
+    #[strum(to_string = "baseline-tch-cpu")]
+    BaselineTchCpu,
+    #[strum(to_string = "baseline-tch-cuda")]
+    BaselineTchCuda,
     #[strum(to_string = "candle-cpu")]
     CandleCpu,
     #[strum(to_string = "candle-cuda")]
@@ -149,18 +805,198 @@ enum BackendValues {
     MetalFusion,
 }
 
+impl BackendValues {
+    /// The same backend with the fusion engine enabled, if any.
+    fn fusion_variant(&self) -> Option<Self> {
+        match self {
+            Self::Cuda => Some(Self::CudaFusion),
+            Self::Cpu => Some(Self::CpuFusion),
+            #[cfg(target_os = "linux")]
+            Self::Rocm => Some(Self::RocmFusion),
+            Self::Wgpu => Some(Self::WgpuFusion),
+            Self::Vulkan => Some(Self::VulkanFusion),
+            Self::Metal => Some(Self::MetalFusion),
+            _ => None,
+        }
+    }
+
+    /// Whether the backend runs the workloads of the `baseline` bench directly on an engine,
+    /// without Burn.
+    fn is_baseline(&self) -> bool {
+        matches!(
+            self,
+            Self::BaselineCandleCpu
+                | Self::BaselineCandleCuda
+                | Self::BaselineOrtCpu
+                | Self::BaselineOrtCuda
+                | Self::BaselineTchCpu
+                | Self::BaselineTchCuda
+        )
+    }
+
+    /// Whether the backend launches CUDA kernels, which nsys can trace.
+    fn runs_on_cuda(&self) -> bool {
+        matches!(
+            self,
+            Self::Cuda
+                | Self::CudaFusion
+                | Self::CandleCuda
+                | Self::TchCuda
+                | Self::BaselineCandleCuda
+                | Self::BaselineOrtCuda
+                | Self::BaselineTchCuda
+        )
+    }
+
+    /// Whether the backend runs on the CPU thread pools whose size is set by the thread count
+    /// sweep.
+    fn sweeps_threads(&self) -> bool {
+        matches!(
+            self,
+            Self::Ndarray
+                | Self::NdarraySimd
+                | Self::NdarrayBlasAccelerate
+                | Self::NdarrayBlasNetlib
+                | Self::NdarrayBlasOpenblas
+                | Self::TchCpu
+                | Self::CandleCpu
+                | Self::BaselineTchCpu
+                | Self::BaselineCandleCpu
+        )
+    }
+
+    /// Check that the platform required by the backend is available on this machine.
+    fn availability(&self, platforms: &Platforms) -> Result<(), String> {
+        let macos_only = || {
+            if cfg!(target_os = "macos") {
+                Ok(())
+            } else {
+                Err("only available on macOS".to_string())
+            }
+        };
+
+        match self {
+            Self::BaselineCandleCuda
+            | Self::BaselineOrtCuda
+            | Self::CandleCuda
+            | Self::Cuda
+            | Self::CudaFusion => platforms.cuda(),
+            Self::BaselineTchCuda | Self::TchCuda => {
+                platforms.libtorch().and_then(|_| platforms.cuda())
+            }
+            Self::BaselineTchCpu | Self::TchCpu => platforms.libtorch(),
+            Self::TchMetal => platforms.libtorch().and_then(|_| macos_only()),
+            Self::CandleMetal | Self::Metal | Self::MetalFusion => {
+                macos_only().and_then(|_| platforms.metal())
+            }
+            Self::NdarrayBlasAccelerate => macos_only(),
+            #[cfg(target_os = "linux")]
+            Self::Rocm | Self::RocmFusion => platforms.rocm(),
+            Self::Vulkan | Self::VulkanFusion => {
+                if cfg!(target_os = "macos") {
+                    Err("not supported on macOS, use wgpu instead".to_string())
+                } else {
+                    platforms.vulkan()
+                }
+            }
+            Self::Wgpu | Self::WgpuFusion => platforms.wgpu(),
+            Self::All
+            | Self::BaselineCandleCpu
+            | Self::BaselineOrtCpu
+            | Self::CandleCpu
+            | Self::Cpu
+            | Self::CpuFusion
+            | Self::Ndarray
+            | Self::NdarraySimd
+            | Self::NdarrayBlasNetlib
+            | Self::NdarrayBlasOpenblas => Ok(()),
+        }
+    }
+
+    /// The same backend without the fusion engine, if the backend uses fusion.
+    fn without_fusion(&self) -> Option<Self> {
+        Self::iter().find(|b| b.fusion_variant().as_ref() == Some(self))
+    }
+}
+
+/// Check that the baseline backends only run the `baseline` bench, which only runs on them.
+fn check_baselines(benches: &[String], backends: &[BackendValues]) -> Result<(), String> {
+    let baselines = backends.iter().filter(|b| b.is_baseline()).count();
+    let baseline_bench = benches.iter().any(|b| b == "baseline");
+    if baseline_bench && baselines < backends.len() {
+        return Err("the baseline bench only runs on the baseline-* backends".to_string());
+    }
+    if baselines > 0 && benches.iter().any(|b| b != "baseline") {
+        return Err("the baseline-* backends only run the baseline bench".to_string());
+    }
+    Ok(())
+}
+
+/// Check that the shape is made of positive dimensions separated by `x`.
+fn parse_input_shape(shape: &str) -> Result<String, String> {
+    let valid = shape
+        .split('x')
+        .all(|dim| dim.parse::<usize>().is_ok_and(|dim| dim > 0));
+    if valid {
+        Ok(shape.to_string())
+    } else {
+        Err(format!(
+            "invalid input shape '{shape}', expected dimensions separated by x such as 1x3x224x224"
+        ))
+    }
+}
+
+/// Add the missing fusion or non-fusion sibling of each backend, right after it.
+fn with_fusion_variants(backends: &[BackendValues]) -> Vec<BackendValues> {
+    let mut expanded = vec![];
+    for backend in backends {
+        let sibling = backend
+            .fusion_variant()
+            .or_else(|| backend.without_fusion());
+        for backend in std::iter::once(backend.clone()).chain(sibling) {
+            if !expanded.contains(&backend) {
+                expanded.push(backend);
+            }
+        }
+    }
+    expanded
+}
+
 /// Execute burnbench on the provided crate located at the provided path.
 pub fn execute<P: AsRef<Path>>(name: &str, path: P) {
     let path: &Path = path.as_ref();
     let info = CrateInfo {
         name: name.to_string(),
         path: path.join("crates").join(name),
+        workspace: path.to_path_buf(),
     };
     let args = Args::parse();
+    init_server_urls(args.server_url, args.website_url);
     match args.command {
-        Commands::Auth => command_auth(),
+        Commands::Auth(auth_args) => match auth_args.command {
+            None => command_auth(),
+            Some(AuthCommands::Logout) => command_auth_logout(),
+            Some(AuthCommands::Status) => command_auth_status(),
+        },
         Commands::List => command_list(),
-        Commands::Run(run_args) => command_run(&info, run_args),
+        Commands::Run(run_args) => match &run_args.bench_crate {
+            Some(path) => match CrateInfo::from_path(path) {
+                Ok(info) => command_run(&info, run_args),
+                Err(e) => eprintln!("❌ Cannot read the bench crate {e}."),
+            },
+            None => command_run(&info, run_args),
+        },
+        Commands::Report(report_args) => command_report(report_args),
+        Commands::Doctor => run_diagnostics(),
+        Commands::Completions(completions_args) => command_completions(&info, completions_args),
+        Commands::Tui(tui_args) => command_tui(&info, tui_args),
+        Commands::Merge(merge_args) => command_merge(merge_args),
+        Commands::Diff(diff_args) => command_diff(diff_args),
+        Commands::Plan(plan_args) => command_plan(&info, plan_args),
+        Commands::Clean(clean_args) => command_clean(clean_args),
+        Commands::Trend(trend_args) => command_trend(trend_args),
+        Commands::Export(export_args) => command_export(export_args),
+        Commands::Share(share_args) => command_share(share_args),
     }
 }
 
@@ -180,115 +1016,1587 @@ fn command_auth() {
     }
 }
 
+/// Delete the cached access token so that the next authentication asks for a GitHub account.
+fn command_auth_logout() {
+    match delete_tokens() {
+        Ok(true) => println!("👋 Logged out, the cached access token has been deleted."),
+        Ok(false) => println!("ℹ️ Not logged in, there is no cached access token."),
+        Err(e) => eprintln!(
+            "❌ Failed to delete the cached access token at {} ({e})",
+            get_auth_cache_file_path().display()
+        ),
+    }
+    if get_bot_token().is_some() {
+        println!("⚠️ The GITHUB_BOT_TOKEN environment variable is still set and takes precedence.");
+    }
+}
+
+/// Display the current access token without starting the authentication flow.
+fn command_auth_status() {
+    let (access_token, source) = match get_bot_token() {
+        Some(token) => (token, "GITHUB_BOT_TOKEN environment variable".to_string()),
+        None => match get_tokens_from_keyring() {
+            Some(tokens) => (tokens.access_token, "OS keyring".to_string()),
+            None => match get_tokens_from_cache() {
+                Some(tokens) => (
+                    tokens.access_token,
+                    get_auth_cache_file_path().display().to_string(),
+                ),
+                None => {
+                    println!("🔒 Not logged in, run `burnbench auth` to authenticate.");
+                    return;
+                }
+            },
+        },
+    };
+    println!("🔑 Token: {} (from {source})", token_kind(&access_token));
+    if access_token.starts_with("ghu_") && get_bot_token().is_none() {
+        match get_tokens_age().map(remaining_lifetime) {
+            Some(Some(remaining)) => println!(
+                "⏳ Expires in about {}h{:02}m",
+                remaining.as_secs() / 3600,
+                remaining.as_secs() / 60 % 60
+            ),
+            Some(None) => println!("⏳ Expired, it will be refreshed on the next authentication"),
+            None => println!("⏳ Expiry unknown"),
+        }
+    } else {
+        println!("⏳ Expiry managed on GitHub");
+    }
+    if !ServerClient::default().is_reachable() {
+        println!("🌐 Benchmark server: unreachable");
+        return;
+    }
+    println!("🌐 Benchmark server: reachable");
+    if !is_supported_token(&access_token) {
+        return;
+    }
+    match get_username(&access_token) {
+        Ok(user_info) => println!("👤 Username: {}", user_info.nickname),
+        Err(e) => println!("👤 Username: unknown, the token was rejected ({e})"),
+    }
+}
+
 fn command_list() {
     println!("Available Backends:");
     for backend in BackendValues::iter() {
         println!("- {}", backend);
     }
-}
-
-fn command_run(info: &CrateInfo, mut run_args: RunArgs) {
+}
+
+/// Print the completions of the CLI for the requested shell.
+///
+/// The bench names are read from the manifest of the benchmarks crate so that they are completed
+/// as well.
+fn command_completions(info: &CrateInfo, completions_args: CompletionsArgs) {
+    let mut benches = vec!["all".to_string()];
+    benches.extend(get_bench_names(info));
+    let mut command = Args::command().mut_subcommand("run", |run| {
+        run.mut_arg("benches", |arg| {
+            arg.value_parser(PossibleValuesParser::new(benches))
+        })
+    });
+    let name = command.get_name().to_string();
+    clap_complete::generate(
+        completions_args.shell,
+        &mut command,
+        name,
+        &mut io::stdout(),
+    );
+}
+
+/// Select the combinations to run in the terminal UI, then run them while displaying their
+/// progress and finally the results table.
+fn command_tui(info: &CrateInfo, tui_args: TuiArgs) {
+    init_tracing(false);
+    let backends = BackendValues::iter()
+        .filter(|b| b != &BackendValues::All && !b.is_baseline())
+        .map(|b| b.to_string())
+        .collect();
+    let dtypes = BenchDType::iter().map(|d| d.to_string()).collect();
+    let selection = match tui::select(get_bench_names(info), backends, dtypes) {
+        Ok(Some(selection)) => selection,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("❌ Failed to start the terminal UI ({e})");
+            return;
+        }
+    };
+    let versions = if tui_args.versions.is_empty() {
+        vec!["main".to_string()]
+    } else {
+        tui_args.versions
+    };
+
+    let mut combinations = vec![];
+    for version in &versions {
+        for backend in &selection.backends {
+            for dtype in &selection.dtypes {
+                combinations.push(CombinationStatus {
+                    version: version.clone(),
+                    backend: backend.clone(),
+                    dtype: dtype.clone(),
+                    state: CombinationState::Queued,
+                });
+            }
+        }
+    }
+    let statuses = Mutex::new(combinations);
+    let report = Mutex::new(None);
+    let mut report_collection = BenchmarkCollection::default();
+    let url = server_base_url();
+
+    let result = std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let count = statuses.lock().unwrap().len();
+            for index in 0..count {
+                if interrupt::is_interrupted() {
+                    break;
+                }
+                let start = Instant::now();
+                let (version, backend, dtype) = {
+                    let mut statuses = statuses.lock().unwrap();
+                    let status = &mut statuses[index];
+                    status.state = CombinationState::Running(start);
+                    (
+                        status.version.clone(),
+                        status.backend.clone(),
+                        status.dtype.clone(),
+                    )
+                };
+                let bench_dtype = BenchDType::from_str(&dtype, true).unwrap();
+                let log_path = logging::log_path(&Combination {
+                    bench: selection.benches.join(", "),
+                    backend: backend.clone(),
+                    version: version.clone(),
+                    dtype: dtype.clone(),
+                    threads: None,
+                });
+                let (processor, log_path) = with_log_file(Arc::new(SinkProcessor), log_path);
+                let results_dir = report_collection
+                    .invocation_dir()
+                    .expect("Results directory should be created");
+                let invocation = CargoInvocation {
+                    benches: &selection.benches,
+                    backend: &backend,
+                    dtype: &bench_dtype,
+                    url,
+                    token: None,
+                    version: &version,
+                    revision: None,
+                    profile: &Profiling::Deactivated,
+                    cargo_profile: None,
+                    extra_features: &[],
+                    run_envs: &[],
+                    container: None,
+                    wasm: None,
+                    android: None,
+                    results_dir: &results_dir,
+                };
+                let status = run_cargo(info, invocation, processor);
+                if interrupt::is_interrupted() {
+                    statuses.lock().unwrap()[index].state = CombinationState::Queued;
+                    break;
+                }
+                let success = status.unwrap_or(false);
+                statuses.lock().unwrap()[index].state = if success {
+                    CombinationState::Succeeded(start.elapsed())
+                } else {
+                    report_collection.push_failed_benchmark(FailedBenchmark {
+                        bench: selection.benches.join(", "),
+                        backend,
+                        version,
+                        dtype,
+                        log_path,
+                    });
+                    CombinationState::Failed(start.elapsed())
+                };
+            }
+
+            let table = report_collection.load_records().get_ascii_table(
+                &ChangeThresholds {
+                    regression: 5.0,
+                    improvement: 5.0,
+                },
+                RowOrder::default(),
+            );
+            *report.lock().unwrap() = Some(table);
+        });
+
+        tui::monitor(&statuses, &report)
+    });
+
+    if let Err(e) = result {
+        eprintln!("❌ The terminal UI failed ({e})");
+    }
+    if let Some(table) = report.into_inner().unwrap() {
+        println!("{table}");
+    }
+}
+
+/// Render the local benchmark records in the requested format.
+fn command_report(report_args: ReportArgs) {
+    let mut collection = BenchmarkCollection::from_previous_run();
+    if report_args.all {
+        collection.load_all_records();
+    } else {
+        collection.load_records();
+    }
+    collection.apply_filters(&report_args.filters);
+    if collection.records().is_empty() {
+        eprintln!("❌ No benchmark records found, run some benchmarks first.");
+        return;
+    }
+
+    match report_args.format {
+        ReportFormat::Html => {
+            let path = report_args
+                .output
+                .unwrap_or_else(|| PathBuf::from("burnbench-report.html"));
+            match write_html_report(&collection, &path) {
+                Ok(()) => println!("📊 HTML report written to {}", path.display()),
+                Err(e) => eprintln!("❌ Failed to write HTML report ({e})"),
+            }
+        }
+        ReportFormat::Markdown => {
+            let thresholds = ChangeThresholds {
+                regression: report_args.regression_threshold,
+                improvement: report_args.improvement_threshold,
+            };
+            let table = match report_args.layout {
+                ReportLayout::Rows => collection.get_ascii_table(
+                    &thresholds,
+                    RowOrder {
+                        sort_by: report_args.sort_by,
+                        group_by: report_args.group_by,
+                    },
+                ),
+                ReportLayout::Pivot => collection.get_pivot_table(&thresholds),
+            };
+            match report_args.output {
+                Some(path) => match fs::write(&path, table) {
+                    Ok(()) => println!("📊 Markdown report written to {}", path.display()),
+                    Err(e) => eprintln!("❌ Failed to write markdown report ({e})"),
+                },
+                None => println!("{table}"),
+            }
+        }
+    }
+}
+
+/// Render the records exported from several machines with one column per host.
+fn command_merge(merge_args: MergeArgs) {
+    let mut collection = BenchmarkCollection::from_previous_run();
+    collection.load_exported_records(&merge_args.inputs);
+    if collection.records().is_empty() {
+        eprintln!("❌ No benchmark records found in the given files and directories.");
+        return;
+    }
+
+    let mut table = collection.get_host_pivot_table(&ChangeThresholds {
+        regression: merge_args.regression_threshold,
+        improvement: merge_args.improvement_threshold,
+    });
+    if let Some(summary) = collection.get_normalized_summary() {
+        table.push_str(&format!("\n\n{summary}"));
+    }
+    match merge_args.output {
+        Some(path) => match fs::write(&path, table) {
+            Ok(()) => println!("📊 Merged report written to {}", path.display()),
+            Err(e) => eprintln!("❌ Failed to write merged report ({e})"),
+        },
+        None => println!("{table}"),
+    }
+}
+
+/// Render the comparison of two sets of results.
+fn command_diff(diff_args: DiffArgs) {
+    let (base, new) = match (diff_args.base.load(), diff_args.new.load()) {
+        (Ok(base), Ok(new)) => (base, new),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("❌ Failed to load the results ({e}).");
+            return;
+        }
+    };
+
+    let table = BenchmarkCollection::from_records(new).get_diff_table(
+        &BenchmarkCollection::from_records(base),
+        &ChangeThresholds {
+            regression: diff_args.regression_threshold,
+            improvement: diff_args.improvement_threshold,
+        },
+    );
+    match diff_args.output {
+        Some(path) => match fs::write(&path, table) {
+            Ok(()) => println!("📊 Diff report written to {}", path.display()),
+            Err(e) => eprintln!("❌ Failed to write diff report ({e})"),
+        },
+        None => println!("{table}"),
+    }
+}
+
+/// Remove the files of the runs, listing them with their size.
+fn command_clean(clean_args: CleanArgs) {
+    let targets = if clean_args.all {
+        CleanTarget::iter().collect()
+    } else if clean_args.targets.is_empty() {
+        CleanTarget::DEFAULT.to_vec()
+    } else {
+        clean_args.targets
+    };
+    // Wait for the runs building the benches, which would fail if their artifacts disappeared.
+    let _lock = if !clean_args.dry_run && targets.contains(&CleanTarget::Build) {
+        match FileLock::acquire(
+            &Path::new(crate::BENCHMARKS_TARGET_DIR).join(TARGET_LOCK),
+            "the benchmarks target directory",
+        ) {
+            Ok(lock) => Some(lock),
+            Err(e) => {
+                eprintln!("❌ Failed to lock the benchmarks target directory ({e}).");
+                return;
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut freed = 0;
+    let mut found = false;
+    for target in targets {
+        for path in target.paths() {
+            found = true;
+            let bytes = disk_usage(&path);
+            if !clean_args.dry_run
+                && let Err(e) = clean::remove(&path)
+            {
+                eprintln!("❌ Failed to remove {} ({e})", path.display());
+                continue;
+            }
+            freed += bytes;
+            println!(
+                "{:>10}  {:<8}  {}",
+                format_size(bytes),
+                target.to_string(),
+                path.display()
+            );
+        }
+    }
+    if !found {
+        println!("Nothing to clean.");
+    } else if clean_args.dry_run {
+        println!(
+            "\n🧹 {} would be freed, rerun without --dry-run to remove the files.",
+            format_size(freed)
+        );
+    } else {
+        println!("\n🧹 {} freed.", format_size(freed));
+    }
+}
+
+/// Expand the suite into the valid combinations of bench, backend and dtype, with the runner and
+/// the cargo features of each.
+fn command_plan(info: &CrateInfo, plan_args: PlanArgs) {
+    let suite = match Suite::load(&plan_args.suite) {
+        Ok(suite) => suite,
+        Err(e) => {
+            eprintln!("❌ Failed to load the suite ({e}).");
+            return;
+        }
+    };
+    let bench_names = get_bench_names(info);
+    let benches = if suite.benches.is_empty() {
+        bench_names.clone()
+    } else {
+        suite.benches.clone()
+    };
+    if let Some(bench) = benches.iter().find(|bench| !bench_names.contains(bench)) {
+        eprintln!("❌ Unknown bench '{bench}' in the suite.");
+        return;
+    }
+    let backends: Result<Vec<BackendValues>, String> = suite
+        .backends
+        .iter()
+        .map(|backend| BackendValues::from_str(backend, false))
+        .collect();
+    let dtypes: Result<Vec<BenchDType>, String> = if suite.dtypes.is_empty() {
+        Ok(vec![BenchDType::F32])
+    } else {
+        suite
+            .dtypes
+            .iter()
+            .map(|dtype| BenchDType::from_str(dtype, false))
+            .collect()
+    };
+    let (backends, dtypes) = match (backends, dtypes) {
+        (Ok(backends), Ok(dtypes)) => (backends, dtypes),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("❌ Invalid suite ({e}).");
+            return;
+        }
+    };
+
+    let name = &info.name;
+    let mut entries = vec![];
+    for backend in &backends {
+        for bench in &benches {
+            // The baseline bench and the Burn benches don't run on the same backends.
+            if check_baselines(std::slice::from_ref(bench), std::slice::from_ref(backend)).is_err()
+            {
+                continue;
+            }
+            for dtype in &dtypes {
+                let features: Vec<String> = [backend.to_string(), dtype.to_string()]
+                    .into_iter()
+                    .chain(get_required_features(info, bench))
+                    .map(|feature| format!("{name}/{feature}"))
+                    .collect();
+                entries.push(MatrixEntry {
+                    backend: backend.to_string(),
+                    bench: bench.clone(),
+                    dtype: dtype.to_string(),
+                    runner: suite.runner(&backend.to_string()).to_string(),
+                    features: features.join(","),
+                });
+            }
+        }
+    }
+
+    if plan_args.gha {
+        if entries.len() > GHA_MAX_JOBS {
+            eprintln!(
+                "⚠️ The matrix has {} jobs, more than the {GHA_MAX_JOBS} allowed by GitHub Actions.",
+                entries.len()
+            );
+        }
+        println!("{}", gha_matrix(&entries));
+    } else {
+        for entry in &entries {
+            println!(
+                "{} @ {} {} on {} ({})",
+                entry.bench, entry.backend, entry.dtype, entry.runner, entry.features
+            );
+        }
+        println!("{} jobs", entries.len());
+    }
+}
+
+/// Render the trends of the benchmarks in the local cache.
+fn command_trend(trend_args: TrendArgs) {
+    let mut collection = BenchmarkCollection::from_previous_run();
+    collection.load_all_records();
+    let records: Vec<BenchmarkRecord> = collection
+        .records()
+        .iter()
+        .filter(|record| {
+            trend_args
+                .name
+                .as_ref()
+                .is_none_or(|name| name.is_match(&record.results.name))
+                && (trend_args.features.is_empty() || trend_args.features.contains(&record.feature))
+        })
+        .cloned()
+        .collect();
+    if records.is_empty() {
+        eprintln!("❌ No benchmark records found, run some benchmarks first.");
+        return;
+    }
+
+    let trends = trends(&records, trend_args.group_by, trend_args.last);
+    println!("{}", get_trend_table(&trends, trend_args.threshold));
+    if let Some(notifier) = trend_args.notify
+        && let Some(alert) = get_shift_alert(&trends, trend_args.threshold)
+        && let Err(e) = notifier.notify("⚠️ Burnbench detected performance shifts", &alert)
+    {
+        eprintln!("❌ Failed to send the notification ({e})");
+    }
+}
+
+fn command_export(export_args: ExportArgs) {
+    let Some(url) = export_args
+        .url
+        .or_else(|| std::env::var("BURN_BENCH_EXPORT_URL").ok())
+    else {
+        eprintln!("❌ No export URL, pass --url or set BURN_BENCH_EXPORT_URL.");
+        return;
+    };
+    let exporter = match export_args.target {
+        ExportTarget::Prometheus => MetricsExporter::Prometheus {
+            url,
+            job: export_args.job,
+        },
+        ExportTarget::Influx => MetricsExporter::Influx {
+            url,
+            token: export_args
+                .token
+                .or_else(|| std::env::var("BURN_BENCH_EXPORT_TOKEN").ok()),
+        },
+    };
+
+    let mut collection = BenchmarkCollection::from_previous_run();
+    if export_args.all {
+        collection.load_all_records();
+    } else {
+        collection.load_records();
+    }
+    if collection.records().is_empty() {
+        eprintln!("❌ No benchmark records found, run some benchmarks first.");
+        return;
+    }
+
+    match exporter.export(collection.records()) {
+        Ok(()) => println!(
+            "📤 Exported {} records to {}",
+            collection.records().len(),
+            export_args.target
+        ),
+        Err(e) => eprintln!("❌ Failed to export the records ({e})"),
+    }
+}
+
+/// Retract the shared results of records or runs from the benchmark server.
+fn command_share(share_args: ShareArgs) {
+    let Some(tokens) = get_tokens() else {
+        eprintln!("❌ Failed to authenticate, cannot retract the results.");
+        return;
+    };
+    let client = ServerClient::default();
+    for id in share_args.delete {
+        // Run ids are UUIDs, anything else is the id of a record
+        let (kind, result) = if Uuid::parse_str(&id).is_ok() {
+            ("run", client.delete_run(&tokens.access_token, &id))
+        } else {
+            ("record", client.delete_record(&tokens.access_token, &id))
+        };
+        match result {
+            Ok(()) => println!("🗑️ Retracted the results of {kind} {id}"),
+            Err(e) => eprintln!("❌ Failed to retract the results of {kind} {id} ({e})"),
+        }
+    }
+}
+
+fn command_run(info: &CrateInfo, mut run_args: RunArgs) {
+    init_tracing(run_args.verbose);
+    // The headless profiling goes through the same checks and build as --profile.
+    run_args.profile |= run_args.profile_headless;
+    if run_args.bench_crate.is_some() && (run_args.remote.is_some() || run_args.container.is_some())
+    {
+        eprintln!(
+            "❌ Bench crates outside of the workspace can't be run with --remote or --container."
+        );
+        return;
+    }
+    if run_args.wasm
+        && (run_args.remote.is_some()
+            || run_args.container.is_some()
+            || run_args.share
+            || run_args.profile)
+    {
+        eprintln!(
+            "❌ The benches built with --wasm can't be run with --remote, --container, --share or --profile."
+        );
+        return;
+    }
+    if run_args.adb
+        && (run_args.remote.is_some()
+            || run_args.container.is_some()
+            || run_args.share
+            || run_args.profile)
+    {
+        eprintln!(
+            "❌ The benches run with --adb can't be run with --remote, --container, --share or --profile."
+        );
+        return;
+    }
+    if run_args.flamegraph && !cfg!(target_os = "linux") {
+        eprintln!("❌ The stacks are sampled with perf, --flamegraph is only supported on Linux.");
+        return;
+    }
+    if run_args.flamegraph && run_args.versions.len() < 2 {
+        eprintln!(
+            "⚠️ The flamegraphs compare the first version to the others, only the stacks are sampled with a single version."
+        );
+    }
+    if let Some(remote) = &run_args.remote {
+        command_run_remote(info, remote, &run_args.versions);
+        return;
+    }
     let mut tokens: Option<Tokens> = None;
     if run_args.share {
         tokens = get_tokens();
+        if let Some(t) = &tokens
+            && let Err(e) = validate_token(&t.access_token)
+        {
+            eprintln!("❌ Cannot share the results, {e}.");
+            return;
+        }
     }
     // collect benchmarks and benches to execute
     let mut backends = run_args.backends.clone();
+    let mut unavailable_backends = vec![];
     if backends.contains(&BackendValues::All) {
-        backends = BackendValues::iter()
-            .filter(|b| b != &BackendValues::All)
-            .collect();
+        // Only keep the backends that can run on this machine
+        let platforms = Platforms::default();
+        backends = vec![];
+        // The baselines don't run the Burn benchmarks
+        for backend in
+            BackendValues::iter().filter(|b| b != &BackendValues::All && !b.is_baseline())
+        {
+            match backend.availability(&platforms) {
+                Ok(()) => backends.push(backend),
+                Err(reason) => {
+                    println!("⏭️ Skipping backend {backend}: {reason}");
+                    unavailable_backends.push((backend, reason));
+                }
+            }
+        }
+    }
+    if run_args.with_fusion_variants {
+        backends = with_fusion_variants(&backends);
+    }
+    if run_args.wasm
+        && let Some(backend) = backends
+            .iter()
+            .find(|b| !matches!(b, BackendValues::Wgpu | BackendValues::WgpuFusion))
+    {
+        eprintln!("❌ The {backend} backend can't run in the browser, use wgpu with --wasm.");
+        return;
+    }
+    if run_args.adb
+        && let Some(backend) = backends.iter().find(|b| {
+            !matches!(
+                b,
+                BackendValues::Ndarray
+                    | BackendValues::NdarraySimd
+                    | BackendValues::Wgpu
+                    | BackendValues::WgpuFusion
+            )
+        })
+    {
+        eprintln!("❌ The {backend} backend can't run on Android, use ndarray or wgpu with --adb.");
+        return;
+    }
+    let thread_counts = resolve_thread_counts(&run_args.threads);
+    if !thread_counts.is_empty() && !backends.iter().any(BackendValues::sweeps_threads) {
+        println!(
+            "⚠️ None of the backends runs on the CPU thread pools, --threads is ignored. Use ndarray, tch-cpu or candle-cpu."
+        );
     }
     let access_token = tokens.map(|t| t.access_token);
+    let reference_backend = run_args
+        .reference_backend
+        .clone()
+        .unwrap_or_else(|| backends[0].clone());
 
     // Set the defaults
     if run_args.dtypes.is_empty() {
         run_args.dtypes.push(BenchDType::F32);
     }
+    if run_args.benches.is_empty() && run_args.onnx_model.is_some() {
+        run_args.benches.push("onnx-model".to_string());
+    }
     if run_args.benches.is_empty() {
         run_args.benches.push("all".to_string());
     }
     if run_args.versions.is_empty() {
         run_args.versions.push("main".to_string());
     }
+    // The benches are dealt to the shards one by one, so all of them are listed.
+    if let Some(shard) = &run_args.shard {
+        if run_args.benches == ["all"] {
+            let baselines = backends.iter().all(BackendValues::is_baseline);
+            run_args.benches = get_bench_names(info)
+                .into_iter()
+                .filter(|bench| (bench == "baseline") == baselines)
+                .collect();
+        }
+        println!("🧩 Running shard {shard} of the benchmarks");
+    }
+    if let Err(e) = check_baselines(&run_args.benches, &backends) {
+        eprintln!("❌ {e}, run them separately.");
+        return;
+    }
+    if let Some(env) = run_args.envs.iter().find(|env| {
+        env.backend
+            .as_ref()
+            .is_some_and(|backend| BackendValues::from_str(backend, true).is_err())
+    }) {
+        eprintln!(
+            "❌ Unknown backend {} of the variable {}.",
+            env.backend.as_deref().unwrap_or_default(),
+            env.key
+        );
+        return;
+    }
+    if run_args.benches.iter().any(|b| b == "onnx-model") && run_args.onnx_model.is_none() {
+        eprintln!("❌ The onnx-model bench requires the model given with --onnx-model.");
+        return;
+    }
+
+    // Metadata passed to the benchmarks and stored in their records, a resumed run keeps its id
+    let run_id = match run_args.resume.as_deref() {
+        Some(LATEST_RUN) => match latest_run_id() {
+            Some(run_id) => run_id,
+            None => {
+                eprintln!("❌ There is no previous run to resume.");
+                return;
+            }
+        },
+        Some(run_id) if !run_dir(run_id).exists() => {
+            eprintln!("❌ There is no run {run_id} to resume.");
+            return;
+        }
+        Some(run_id) => run_id.to_string(),
+        None => Uuid::new_v4().to_string(),
+    };
+    let mut run_envs = vec![("BURN_BENCH_RUN_ID".to_string(), run_id.clone())];
+    let burn_bench_git_hash = current_git_hash(&info.workspace);
+    if let Some(git_hash) = &burn_bench_git_hash {
+        run_envs.push(("BURN_BENCH_GIT_HASH".to_string(), git_hash.clone()));
+    }
+    if let Some(tag) = run_args.tag {
+        run_envs.push(("BURN_BENCH_TAG".to_string(), tag));
+    }
+    if let Some(note) = run_args.note {
+        run_envs.push(("BURN_BENCH_NOTE".to_string(), note));
+    }
+    if let Some(profile) = &run_args.cargo_profile {
+        run_envs.push(("BURN_BENCH_CARGO_PROFILE".to_string(), profile.clone()));
+    }
+    if let Some(rustflags) = &run_args.rustflags {
+        run_envs.push(("RUSTFLAGS".to_string(), rustflags.clone()));
+        run_envs.push(("BURN_BENCH_RUSTFLAGS".to_string(), rustflags.clone()));
+    }
+    // Read by the build script of the benchmarks, from the directory of the crate.
+    if let Some(model) = &run_args.onnx_model {
+        match fs::canonicalize(model) {
+            Ok(path) => {
+                run_envs.push((
+                    "BURN_BENCH_ONNX_MODEL".to_string(),
+                    path.to_string_lossy().to_string(),
+                ));
+                run_envs.push((
+                    "BURN_BENCH_ONNX_INPUT_SHAPES".to_string(),
+                    run_args.onnx_input_shapes.join(","),
+                ));
+            }
+            Err(e) => {
+                eprintln!("❌ Cannot read the ONNX model {} ({e})", model.display());
+                return;
+            }
+        }
+    }
+    if run_args.count_allocations {
+        run_args
+            .extra_features
+            .push(format!("{}/{COUNT_ALLOCATIONS_FEATURE}", info.name));
+    }
+    if run_args.measure_energy {
+        run_envs.push((MEASURE_ENERGY_ENV.to_string(), "1".to_string()));
+    }
+    let container = match &run_args.container {
+        Some(_) if run_args.profile => {
+            eprintln!("❌ Profiling is not supported with --container.");
+            return;
+        }
+        Some(_) if run_args.versions.iter().any(|v| v.starts_with("local")) => {
+            eprintln!("❌ Local Burn checkouts can't be benchmarked with --container.");
+            return;
+        }
+        Some(image) => match Container::new(image) {
+            Ok(container) => {
+                println!("🐳 Running the benchmarks in {}", container.digest());
+                run_envs.push((
+                    "BURN_BENCH_CONTAINER".to_string(),
+                    container.digest().to_string(),
+                ));
+                Some(container)
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to prepare the container {image} ({e})");
+                return;
+            }
+        },
+        None => None,
+    };
+    let wasm = if run_args.wasm {
+        match WasmTarget::new(run_args.browser.as_deref()) {
+            Ok(wasm) => Some(wasm),
+            Err(e) => {
+                eprintln!("❌ Cannot run the benches in the browser, {e}.");
+                return;
+            }
+        }
+    } else {
+        None
+    };
+    let android = match run_args.target.as_deref() {
+        Some(target) if run_args.adb => match AndroidDevice::new(target) {
+            Ok(device) => {
+                println!("📱 Running the benchmarks on {}", device.description());
+                Some(device)
+            }
+            Err(e) => {
+                eprintln!("❌ Cannot run the benches on the Android device, {e}.");
+                return;
+            }
+        },
+        _ => None,
+    };
 
+    interrupt::install_handler();
+    if !run_args.skip_disk_check
+        && let Err(e) = check_disk_space(run_args.versions.len(), backends.len())
+    {
+        eprintln!("❌ Not enough disk space, {e}");
+        return;
+    }
+    if !run_args.skip_preflight && !preflight(info, &backends, &run_args.versions) {
+        return;
+    }
+    // The clocks are restored when the lock is dropped at the end of the run.
+    let _clock_lock = if run_args.lock_clocks {
+        match ClockLock::acquire() {
+            Ok(lock) => {
+                println!("🔒 {}", lock.description());
+                run_envs.push((
+                    LOCKED_CLOCKS_ENV.to_string(),
+                    lock.description().to_string(),
+                ));
+                Some(lock)
+            }
+            Err(e) => {
+                eprintln!(
+                    "❌ Failed to lock the GPU clocks ({e}), running with the default clocks"
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+    // Only the bench executables are pinned, cargo runs them through the pinning tool.
+    match Pinning::new(run_args.pin_cores.as_deref(), run_args.numa_node)
+        .and_then(|pinning| pinning.map(|p| p.envs().map(|envs| (p, envs))).transpose())
+    {
+        Ok(Some((pinning, envs))) => {
+            println!("📌 Pinning the benches to {}", pinning.description());
+            run_envs.extend(envs);
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("❌ Failed to pin the benches ({e}).");
+            return;
+        }
+    }
+    if run_args.kernel_stats {
+        if Command::new("nsys").arg("--version").output().is_err() {
+            eprintln!("❌ nsys not found, install Nsight Systems to count the kernel launches.");
+            return;
+        }
+        if !backends.iter().any(BackendValues::runs_on_cuda) {
+            eprintln!("⚠️ --kernel-stats only traces the CUDA backends.");
+        }
+    }
+    // Pin the branches to their current commit for the whole run, and warn about the local
+    // checkouts with uncommitted changes.
+    let mut burn_revisions = BTreeMap::new();
+    for version in &run_args.versions {
+        match Dependency::new(&get_version(version)).source() {
+            BurnSource::Git {
+                branch: Some(branch),
+                ..
+            } => match resolve_branch(&branch) {
+                Some(rev) => {
+                    println!("📌 Burn @ {version} resolved to {rev}");
+                    burn_revisions.insert(version.clone(), rev);
+                }
+                None => tracing::warn!("Failed to resolve the commit of Burn @ {version}"),
+            },
+            source @ BurnSource::Path { dirty: true, .. } => {
+                println!(
+                    "⚠️ The Burn checkout of {version} has uncommitted changes, its results are labeled {}",
+                    source.local_revision().unwrap_or_default()
+                );
+            }
+            _ => {}
+        }
+    }
+    let manifest = RunManifest {
+        run_id: run_id.clone(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+        args: std::env::args().skip(1).collect(),
+        benches: run_args.benches.clone(),
+        backends: backends.iter().map(ToString::to_string).collect(),
+        versions: run_args.versions.clone(),
+        burn_revisions: burn_revisions.clone(),
+        dtypes: run_args.dtypes.iter().map(ToString::to_string).collect(),
+        envs: run_args.envs.clone(),
+        burn_bench_git_hash,
+        system_info: BenchmarkSystemInfo::new(),
+    };
+    match manifest.save() {
+        Ok(path) => println!("🆔 Run {run_id}, manifest written to {}", path.display()),
+        Err(e) => eprintln!("❌ Failed to write the run manifest ({e})"),
+    }
+    let events = match run_args
+        .emit_events
+        .as_ref()
+        .map(EventStream::open)
+        .transpose()
+    {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("❌ Failed to open the event stream ({e})");
+            return;
+        }
+    };
+    let webhook_url = run_args
+        .webhook_url
+        .clone()
+        .or_else(|| std::env::var("WEBHOOK_URL").ok());
     let profiling = if run_args.profile {
         Profiling::Activated {
             ncu_path: run_args.ncu_path,
             ncu_ui_path: run_args.ncu_ui_path,
+            flamegraph: run_args.flamegraph,
+            headless: run_args.profile_headless,
         }
     } else {
         Profiling::Deactivated
     };
-    run_backend_comparison_benchmarks(
-        info,
-        &run_args.benches,
-        &backends,
-        &run_args.versions,
-        &run_args.dtypes,
-        access_token.as_deref(),
-        run_args.verbose,
-        &profiling,
-    );
+    let options = RunOptions {
+        benches: &run_args.benches,
+        backends: &backends,
+        versions: &run_args.versions,
+        dtypes: &run_args.dtypes,
+        token: access_token.as_deref(),
+        force_share: run_args.force_share,
+        verbose: run_args.verbose,
+        profiling: &profiling,
+        cargo_profile: run_args.cargo_profile.as_deref(),
+        extra_features: &run_args.extra_features,
+        run_envs: &run_envs,
+        envs: &run_args.envs,
+        thread_counts: &thread_counts,
+        shard: run_args.shard.as_ref(),
+        report_layout: &run_args.report_layout,
+        report_order: RowOrder {
+            sort_by: run_args.report_sort_by,
+            group_by: run_args.report_group_by,
+        },
+        report_filters: &run_args.report_filters,
+        thresholds: ChangeThresholds {
+            regression: run_args.regression_threshold,
+            improvement: run_args.improvement_threshold,
+        },
+        reference_backend: &reference_backend,
+        junit: run_args.junit.as_deref(),
+        time_budget: run_args.time_budget,
+        resume: run_args.resume.is_some(),
+        skip_cached: run_args.skip_cached,
+        calibrate: run_args.calibrate,
+        kernel_stats: run_args.kernel_stats,
+        container: container.as_ref(),
+        wasm: wasm.as_ref(),
+        android: android.as_ref(),
+        artifact_store: run_args.artifact_store.as_ref(),
+        notifier: run_args.notify.as_ref(),
+        events: events.as_ref(),
+        webhook_url: webhook_url.as_deref(),
+        run_id: &run_id,
+        burn_revisions: &burn_revisions,
+        unavailable_backends: &unavailable_backends,
+    };
+    run_backend_comparison_benchmarks(info, options);
 }
 
-#[allow(clippy::too_many_arguments)]
-fn run_backend_comparison_benchmarks(
-    info: &CrateInfo,
-    benches: &[String],
-    backends: &[BackendValues],
-    versions: &[String],
-    dtypes: &[BenchDType],
-    token: Option<&str>,
+/// Run the benchmarks on the remote machine with the current arguments, then pull the records.
+fn command_run_remote(info: &CrateInfo, remote: &Remote, versions: &[String]) {
+    if versions.iter().any(|v| v.starts_with("local")) {
+        eprintln!("❌ Local Burn checkouts can't be benchmarked with --remote.");
+        return;
+    }
+
+    println!("🔄 Synchronizing the workspace with {}...", remote.host());
+    if let Err(e) = remote.sync(&info.workspace) {
+        eprintln!("❌ Failed to synchronize the workspace ({e})");
+        return;
+    }
+
+    interrupt::install_handler();
+    let bin = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "burnbench".to_string());
+    let args = forwarded_args(std::env::args().skip(1));
+    match remote.run(&info.workspace, &bin, &args, Arc::new(VerboseProcessor)) {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("❌ The remote run failed ({status})"),
+        Err(e) => {
+            eprintln!("❌ Failed to run the benchmarks on {} ({e})", remote.host());
+            return;
+        }
+    }
+    if interrupt::is_interrupted() {
+        return;
+    }
+
+    let result = remote
+        .pull_records(&benchmarks_cache_dir())
+        .and_then(|records| {
+            BenchmarkCollection::default().push_record_files(&records)?;
+            Ok(records.len())
+        });
+    match result {
+        Ok(count) => println!(
+            "📥 Pulled {count} records from {}, run `burnbench report` to render them.",
+            remote.host()
+        ),
+        Err(e) => eprintln!("❌ Failed to pull the records from {} ({e})", remote.host()),
+    }
+}
+
+/// Check that the benchmarks compile against every version with the selected backends before
+/// running the matrix, and print the mismatches found.
+///
+/// Return whether the run can proceed.
+fn preflight(info: &CrateInfo, backends: &[BackendValues], versions: &[String]) -> bool {
+    let name = &info.name;
+    let mut compatible = true;
+
+    for version in versions {
+        if interrupt::is_interrupted() {
+            return false;
+        }
+        println!("🔎 Checking the compatibility with Burn @ {version}");
+        let mut features: Vec<String> = backends.iter().map(|b| format!("{name}/{b}")).collect();
+        features.push(format!("{name}/{}", BenchDType::F32));
+        if let Some(legacy) = legacy_feature(version) {
+            features.push(legacy);
+        }
+
+        let dependency = Dependency::new(&get_version(version));
+        match check_compatibility(
+            name,
+            &info.path,
+            &info.workspace,
+            &dependency,
+            &features.join(","),
+        ) {
+            Ok(mismatches) if mismatches.is_empty() => {}
+            Ok(mismatches) => {
+                compatible = false;
+                eprintln!("❌ The benchmarks are not compatible with Burn @ {version}:");
+                for mismatch in mismatches {
+                    eprintln!("- {mismatch}");
+                }
+            }
+            Err(e) => {
+                compatible = false;
+                eprintln!("❌ Failed to check the compatibility with Burn @ {version} ({e})");
+            }
+        }
+    }
+    if !compatible {
+        eprintln!("Fix the mismatches above or rerun with --skip-preflight to ignore them.");
+    }
+
+    compatible
+}
+
+/// Feature of the benchmarks crate adapting the harness to older Burn versions.
+fn legacy_feature(version: &str) -> Option<String> {
+    let version = Version::parse(version).ok()?;
+    FeatureMapping::embedded()
+        .legacy_feature(&version)
+        .map(str::to_string)
+}
+
+/// Options of a run, shared by all the combinations of its matrix.
+struct RunOptions<'a> {
+    benches: &'a [String],
+    backends: &'a [BackendValues],
+    versions: &'a [String],
+    dtypes: &'a [BenchDType],
+    token: Option<&'a str>,
+    force_share: bool,
     verbose: bool,
-    profiling: &Profiling,
-) {
-    let mut report_collection = BenchmarkCollection::default();
+    profiling: &'a Profiling,
+    cargo_profile: Option<&'a str>,
+    extra_features: &'a [String],
+    run_envs: &'a [(String, String)],
+    envs: &'a [BackendEnv],
+    thread_counts: &'a [usize],
+    shard: Option<&'a Shard>,
+    report_layout: &'a ReportLayout,
+    report_order: RowOrder,
+    report_filters: &'a [ReportFilter],
+    thresholds: ChangeThresholds,
+    reference_backend: &'a BackendValues,
+    junit: Option<&'a Path>,
+    time_budget: Option<Duration>,
+    resume: bool,
+    skip_cached: bool,
+    calibrate: bool,
+    kernel_stats: bool,
+    container: Option<&'a Container>,
+    wasm: Option<&'a WasmTarget>,
+    android: Option<&'a AndroidDevice>,
+    artifact_store: Option<&'a ArtifactStore>,
+    notifier: Option<&'a Notifier>,
+    events: Option<&'a EventStream>,
+    webhook_url: Option<&'a str>,
+    run_id: &'a str,
+    burn_revisions: &'a BTreeMap<String, String>,
+    unavailable_backends: &'a [(BackendValues, String)],
+}
+
+/// A cargo invocation running the benches of a combination.
+struct CargoInvocation<'a> {
+    benches: &'a [String],
+    backend: &'a str,
+    dtype: &'a BenchDType,
+    url: &'a str,
+    token: Option<&'a str>,
+    version: &'a str,
+    revision: Option<&'a str>,
+    profile: &'a Profiling,
+    cargo_profile: Option<&'a str>,
+    extra_features: &'a [String],
+    run_envs: &'a [(String, String)],
+    container: Option<&'a Container>,
+    wasm: Option<&'a WasmTarget>,
+    android: Option<&'a AndroidDevice>,
+    results_dir: &'a Path,
+}
+
+fn run_backend_comparison_benchmarks(info: &CrateInfo, options: RunOptions) {
+    let RunOptions {
+        benches,
+        backends,
+        versions,
+        dtypes,
+        token,
+        force_share,
+        verbose,
+        profiling,
+        cargo_profile,
+        extra_features,
+        run_envs,
+        envs,
+        thread_counts,
+        shard,
+        report_layout,
+        report_order,
+        report_filters,
+        thresholds,
+        reference_backend,
+        junit,
+        time_budget,
+        resume,
+        skip_cached,
+        calibrate,
+        kernel_stats,
+        container,
+        wasm,
+        android,
+        artifact_store,
+        notifier,
+        events,
+        webhook_url,
+        run_id,
+        burn_revisions,
+        unavailable_backends,
+    } = options;
+    // When resuming, keep the results of the previous run so that the report covers the
+    // whole matrix.
+    let mut report_collection = BenchmarkCollection::new(run_id);
+    let mut run_state = if resume {
+        RunState::resume(run_id)
+    } else {
+        RunState::new(run_id)
+    };
+    if resume {
+        println!(
+            "Resuming the run {run_id}, {} combinations already completed",
+            run_state.num_completed()
+        );
+    }
+    if calibrate {
+        println!("📏 Calibrating the noise of the machine...");
+        let noise = calibration::calibrate();
+        println!(
+            "📏 Noise score: ±{:.1}% (timer resolution {:?}, calibration kernel {:.3?})",
+            noise.score, noise.timer_resolution, noise.median
+        );
+        if let Err(e) = report_collection.set_noise_score(noise.score) {
+            eprintln!("❌ Failed to save the noise score ({e})");
+        }
+    }
     let inputs_file = std::env::var("WEBHOOK_INPUTS_FILE");
-    let total_count: u64 = (backends.len() * versions.len() * dtypes.len())
-        .try_into()
-        .unwrap();
+    // The backends running on the CPU thread pools are run once per thread count of the sweep.
+    let threads_of = |backend: &BackendValues| -> Vec<Option<usize>> {
+        if backend.sweeps_threads() && !thread_counts.is_empty() {
+            thread_counts.iter().copied().map(Some).collect()
+        } else {
+            vec![None]
+        }
+    };
+    // Benches of each combination, only the ones of the shard when the matrix is sharded.
+    let mut combination_benches: HashMap<(&String, String, String, Option<usize>), Vec<String>> =
+        HashMap::new();
+    // The backends unavailable with `--backends all` keep their positions in the matrix, so that
+    // runners on different hardware number it the same way and their shards don't overlap.
+    let mut matrix_backends: Vec<&BackendValues> = backends.iter().collect();
+    if !unavailable_backends.is_empty() {
+        matrix_backends.extend(unavailable_backends.iter().map(|(backend, _)| backend));
+        matrix_backends.sort_by_key(|backend| BackendValues::iter().position(|b| &b == *backend));
+    }
+    let mut position = 0;
+    for version in versions {
+        for backend in matrix_backends.iter().copied() {
+            for dtype in dtypes {
+                for threads in threads_of(backend) {
+                    for bench in benches {
+                        if backends.contains(backend)
+                            && shard.is_none_or(|shard| shard.contains(position))
+                        {
+                            combination_benches
+                                .entry((version, backend.to_string(), dtype.to_string(), threads))
+                                .or_default()
+                                .push(bench.clone());
+                        }
+                        position += 1;
+                    }
+                }
+            }
+        }
+    }
+    let combinations_count = combination_benches.len();
+    let total_count: u64 = combinations_count.try_into().unwrap();
     let runner_pb: Option<Arc<Mutex<RunnerProgressBar>>> = if verbose {
         None
     } else {
         Some(Arc::new(Mutex::new(RunnerProgressBar::new(total_count))))
     };
+    for version in versions {
+        for (backend, reason) in unavailable_backends {
+            for dtype in dtypes {
+                report_collection.push_skipped_benchmark(SkippedBenchmark {
+                    bench: benches.join(", "),
+                    backend: backend.to_string(),
+                    version: version.clone(),
+                    dtype: dtype.to_string(),
+                    reason: reason.clone(),
+                });
+            }
+        }
+    }
+
+    let mut record_cache = RecordCache::load();
+    // The records of a custom build configuration are not reused by the default one.
+    let default_build = cargo_profile.is_none()
+        && extra_features.is_empty()
+        && envs.is_empty()
+        && !run_envs.iter().any(|(key, _)| key == "RUSTFLAGS");
+    // Commit and hardware the cached records must match to be reused.
+    let cache_key = if skip_cached {
+        match current_git_hash(&info.path) {
+            Some(git_hash) => Some((git_hash, BenchmarkSystemInfo::new().hardware())),
+            None => {
+                tracing::warn!(
+                    "Failed to get the git hash of the benchmarks, --skip-cached is ignored"
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let start = Instant::now();
+    let mut budget_exhausted = false;
+    let mut log_paths = vec![];
+    // Invocations whose records are shared once the run is complete.
+    let mut shared_invocations = vec![];
+    if let Some(events) = events {
+        events.emit(&Event::RunStarted {
+            run_id,
+            combinations: combinations_count,
+        });
+    }
     // Iterate through every combination of benchmark and backend
     println!("\nBenchmarking Burn @ {versions:?}");
-    for version in versions.iter() {
+    'run: for version in versions.iter() {
         for backend in backends.iter() {
-            for dtype in dtypes.iter() {
-                let bench_str = benches.join(", ");
+            let combinations = dtypes.iter().flat_map(|dtype| {
+                threads_of(backend)
+                    .into_iter()
+                    .map(move |threads| (dtype, threads))
+            });
+            for (dtype, threads) in combinations {
+                if interrupt::is_interrupted() {
+                    break 'run;
+                }
                 let backend_str = backend.to_string();
-                let url = format!("{TRACEL_CI_SERVER_BASE_URL}benchmarks");
+                let Some(benches) = combination_benches.get(&(
+                    version,
+                    backend_str.clone(),
+                    dtype.to_string(),
+                    threads,
+                )) else {
+                    // None of the benches of the combination is in the shard.
+                    continue;
+                };
+                let bench_str = benches.join(", ");
+                // The backend as displayed, with the number of threads in a sweep.
+                let backend_label = match threads {
+                    Some(threads) => format!("{backend_str} ({threads} threads)"),
+                    None => backend_str.clone(),
+                };
+                let url = server_base_url();
+                let combination = Combination {
+                    bench: bench_str.clone(),
+                    backend: backend_str.clone(),
+                    version: version.clone(),
+                    dtype: dtype.to_string(),
+                    threads,
+                };
 
+                if run_state.is_completed(&combination) {
+                    if let Some(ref pb) = runner_pb {
+                        let mut pb = pb.lock().unwrap();
+                        pb.inc_by_one();
+                        pb.succeeded_inc();
+                    }
+                    continue;
+                }
+                let revision = burn_revisions.get(version).map(String::as_str);
+                if let Some((git_hash, hardware)) = &cache_key
+                    && let Some(records) = record_cache.lookup(
+                        &combination,
+                        &burn_dependency(version, revision).1,
+                        git_hash,
+                        hardware,
+                    )
+                {
+                    match report_collection.push_record_files(records) {
+                        Ok(()) => {
+                            tracing::info!(
+                                "Reusing the cached records of {bench_str}@{backend_label}-{dtype} on {version}"
+                            );
+                            if let Some(ref pb) = runner_pb {
+                                let mut pb = pb.lock().unwrap();
+                                pb.inc_by_one();
+                                pb.succeeded_inc();
+                            }
+                            if let Some(events) = events {
+                                events.emit(&Event::CombinationFinished {
+                                    combination: &combination,
+                                    status: CombinationOutcome::Cached,
+                                });
+                            }
+                            if let Err(e) = run_state.mark_completed(combination) {
+                                tracing::error!("Failed to save the run state ({e})");
+                            }
+                            continue;
+                        }
+                        Err(e) => tracing::warn!("Failed to reuse the cached records ({e})"),
+                    }
+                }
+                if !budget_exhausted
+                    && let Some(budget) = time_budget
+                    && start.elapsed() >= budget
+                {
+                    budget_exhausted = true;
+                    let message = format!(
+                        "⏱️ Time budget of {budget:?} exhausted, skipping the remaining benchmarks"
+                    );
+                    match runner_pb {
+                        Some(ref pb) => pb.lock().unwrap().message(message),
+                        None => println!("{message}"),
+                    }
+                }
+                if budget_exhausted {
+                    if let Some(ref pb) = runner_pb {
+                        pb.lock().unwrap().inc_by_one();
+                    }
+                    if let Some(events) = events {
+                        events.emit(&Event::CombinationFinished {
+                            combination: &combination,
+                            status: CombinationOutcome::Skipped,
+                        });
+                    }
+                    report_collection.push_skipped_benchmark(SkippedBenchmark {
+                        bench: combination.bench,
+                        backend: backend_label,
+                        version: combination.version,
+                        dtype: combination.dtype,
+                        reason: "time budget exhausted".to_string(),
+                    });
+                    continue;
+                }
+
+                let span = tracing::info_span!(
+                    "combination",
+                    %version,
+                    backend = %backend_label,
+                    bench = %bench_str,
+                    %dtype
+                );
+                let _entered = span.enter();
                 if verbose {
-                    group!("Running benchmarks: {bench_str}@{backend_str}-{dtype}");
+                    group!("Running benchmarks: {bench_str}@{backend_label}-{dtype}");
+                }
+                let processor: Arc<dyn OutputProcessor> = match runner_pb {
+                    Some(ref pb) => Arc::new(NiceProcessor::new(
+                        bench_str.clone(),
+                        backend_label.clone(),
+                        version.clone(),
+                        pb.clone(),
+                    )),
+                    None => Arc::new(VerboseProcessor),
+                };
+                let processor = with_events(processor, events, &combination);
+                let (processor, log_path) =
+                    with_log_file(processor, logging::log_path(&combination));
+                let (processor, executables) = with_executables(processor);
+                log_paths.extend(log_path.clone());
+                let results_dir = report_collection
+                    .invocation_dir()
+                    .expect("Results directory should be created");
+                if let Some(events) = events {
+                    events.emit(&Event::CombinationStarted {
+                        combination: &combination,
+                    });
                 }
-                let status = run_cargo(
-                    info,
+                let mut combination_envs =
+                    [run_envs, backend_envs(envs, &backend_str).as_slice()].concat();
+                if let Some(threads) = threads {
+                    combination_envs.extend(thread_envs(threads));
+                }
+                // Cargo runs the benches under nsys, which counts the kernels they launch.
+                let traces = if kernel_stats && backend.runs_on_cuda() {
+                    match KernelTraces::new()
+                        .map_err(|e| e.to_string())
+                        .and_then(|traces| traces.env().map(|env| (traces, env)))
+                    {
+                        Ok((traces, env)) => {
+                            combination_envs.push(env);
+                            Some(traces)
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to trace the kernel launches ({e})");
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                let invocation = CargoInvocation {
                     benches,
-                    &backend_str,
+                    backend: &backend_str,
                     dtype,
-                    &url,
-                    token,
-                    &runner_pb,
+                    url,
+                    // The runner shares the records itself, after checking them.
+                    token: None,
                     version,
-                    profiling,
-                );
-                let success = status.unwrap().success();
+                    revision: burn_revisions.get(version).map(String::as_str),
+                    profile: profiling,
+                    cargo_profile,
+                    extra_features,
+                    run_envs: &combination_envs,
+                    container,
+                    wasm,
+                    android,
+                    results_dir: &results_dir,
+                };
+                let status = run_cargo(info, invocation, processor);
+                if interrupt::is_interrupted() {
+                    // The combination was cut short, it is neither completed nor failed.
+                    if verbose {
+                        endgroup!();
+                    }
+                    break 'run;
+                }
+                let success = status.unwrap();
 
                 if success {
                     if let Some(ref pb) = runner_pb {
                         pb.lock().unwrap().succeeded_inc();
                     }
+                    if token.is_some() {
+                        shared_invocations.push(results_dir.clone());
+                    }
+                    if let Profiling::Activated {
+                        ncu_path,
+                        headless: true,
+                        ..
+                    } = profiling
+                    {
+                        let report = PathBuf::from(format!("target/{}.ncu-rep", benches[0]));
+                        let files = BenchmarkCollection::invocation_records(&results_dir);
+                        match ncu::kernel_profile(ncu_path, &report) {
+                            Ok(profile) => {
+                                if let Err(e) = ncu::attach_kernel_profile(&files, &profile) {
+                                    tracing::warn!("Failed to attach the kernel profile ({e})");
+                                }
+                            }
+                            Err(e) => tracing::warn!("Failed to summarize the profile ({e})"),
+                        }
+                    }
+                    if let Some(traces) = &traces
+                        && let Err(e) =
+                            traces.attach(&BenchmarkCollection::invocation_records(&results_dir))
+                    {
+                        tracing::warn!("Failed to count the kernel launches ({e})");
+                    }
+                    let records = BenchmarkCollection::invocation_records(&results_dir);
+                    if let Some(events) = events {
+                        events.emit_records(&combination, &records);
+                        events.emit(&Event::CombinationFinished {
+                            combination: &combination,
+                            status: CombinationOutcome::Succeeded,
+                        });
+                    }
+                    // The executables built in a container are not reachable from the host.
+                    if container.is_none() {
+                        let sizes =
+                            binary_size::measure(&executables.lock().unwrap(), &combination);
+                        if let Err(e) = report_collection.push_binary_sizes(sizes) {
+                            tracing::warn!("Failed to save the binary sizes ({e})");
+                        }
+                    }
+                    if default_build
+                        && let Err(e) = record_cache.insert(combination.clone(), records)
+                    {
+                        tracing::warn!("Failed to cache the records ({e})");
+                    }
+                    if let Err(e) = run_state.mark_completed(combination) {
+                        tracing::error!("Failed to save the run state ({e})");
+                    }
                 } else {
                     if let Some(ref pb) = runner_pb {
                         pb.lock().unwrap().failed_inc();
                     }
+                    if let Some(events) = events {
+                        events.emit(&Event::CombinationFinished {
+                            combination: &combination,
+                            status: CombinationOutcome::Failed,
+                        });
+                    }
+                    if let Some(ref path) = log_path {
+                        tracing::warn!(
+                            "Benchmarks failed, see the full output in {}",
+                            path.display()
+                        );
+                    }
                     report_collection.push_failed_benchmark(FailedBenchmark {
                         bench: bench_str.clone(),
-                        backend: backend_str.clone(),
+                        backend: backend_label.clone(),
+                        version: version.clone(),
+                        dtype: dtype.to_string(),
+                        log_path,
                     })
                 }
                 if verbose {
@@ -302,18 +2610,243 @@ fn run_backend_comparison_benchmarks(
         pb.lock().unwrap().finish();
     }
 
-    let collection = report_collection.load_records();
-    let table = collection.get_ascii_table();
+    if interrupt::is_interrupted() {
+        println!(
+            "\n🛑 Run interrupted, showing the partial results. Rerun with --resume {run_id} to complete it."
+        );
+    }
+    let flamegraphs = match profiling {
+        Profiling::Activated {
+            flamegraph: true, ..
+        } => flamegraph::write_version_diffs(
+            benches,
+            &backends.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            &dtypes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            versions,
+        ),
+        _ => vec![],
+    };
+    for file in &flamegraphs {
+        println!("🔥 Differential flamegraph written to {}", file.display());
+    }
+    // Before the records are loaded, so that the reports and the shared results include the
+    // scores relative to the reference backend.
+    if let Err(e) = normalize_record_files(
+        &report_collection.record_files(),
+        &reference_backend.to_string(),
+    ) {
+        tracing::warn!("Failed to normalize the records ({e})");
+    }
+    let collection = report_collection
+        .load_records()
+        .apply_filters(report_filters);
+    let mut table = match report_layout {
+        ReportLayout::Rows => collection.get_ascii_table(&thresholds, report_order),
+        ReportLayout::Pivot => collection.get_pivot_table(&thresholds),
+    };
+    if let Some(summary) = collection.get_summary(&reference_backend.to_string()) {
+        table.push_str(&format!("\n\n{summary}"));
+    }
+    if let Some(sizes) = collection.get_binary_size_table() {
+        table.push_str(&format!("\n\n{sizes}"));
+    }
+    if let Some(scaling) = collection.get_thread_scaling_table() {
+        table.push_str(&format!("\n\n{scaling}"));
+    }
+    if let Some(kernels) = collection.get_kernel_profile_table() {
+        table.push_str(&format!("\n\n{kernels}"));
+    }
+    if let Some(launches) = collection.get_kernel_launches_table() {
+        table.push_str(&format!("\n\n{launches}"));
+    }
+    if let Some(allocations) = collection.get_allocation_table() {
+        table.push_str(&format!("\n\n{allocations}"));
+    }
+    if let Some(throughput) = collection.get_throughput_table() {
+        table.push_str(&format!("\n\n{throughput}"));
+    }
     let mut output_results = table.clone();
-    let share_link = web_results_url(token, versions);
+    let share_link = web_results_url(token, versions, burn_revisions);
     if let Some(ref url) = share_link {
         output_results.push_str(&format!("\n\n📊 Browse results at {}", url));
     }
     println!("{output_results}");
+    for failed in collection.failed_benchmarks() {
+        if let Some(path) = &failed.log_path {
+            println!(
+                "❌ {} @ {} {} {}: {}",
+                failed.bench,
+                failed.version,
+                failed.backend,
+                failed.dtype,
+                path.display()
+            );
+        }
+    }
+    if let Some(token) = token {
+        share_invocations(&shared_invocations, token, force_share);
+    }
+    if let Some(path) = junit {
+        match write_junit_report(collection, path) {
+            Ok(()) => println!("📝 JUnit report written to {}", path.display()),
+            Err(e) => eprintln!("❌ Failed to write JUnit report ({e})"),
+        }
+    }
+    if let Some(store) = artifact_store {
+        let mut artifacts = vec![(ArtifactKind::Manifest, manifest_path(run_id))];
+        artifacts.extend(
+            collection
+                .record_files()
+                .into_iter()
+                .map(|file| (ArtifactKind::Record, file)),
+        );
+        artifacts.extend(log_paths.into_iter().map(|file| (ArtifactKind::Log, file)));
+        if let Profiling::Activated { .. } = profiling {
+            artifacts.extend(benches.iter().map(|bench| {
+                (
+                    ArtifactKind::Profile,
+                    PathBuf::from(format!("target/{bench}.ncu-rep")),
+                )
+            }));
+            artifacts.extend(
+                flamegraphs
+                    .iter()
+                    .map(|file| (ArtifactKind::Profile, file.clone())),
+            );
+        }
+        match store.upload(run_id, &artifacts) {
+            Ok(url) => println!("📦 Artifacts uploaded to {url}"),
+            Err(e) => eprintln!("❌ Failed to upload the artifacts ({e})"),
+        }
+    }
+    if let Some(events) = events {
+        events.emit(&Event::RunFinished {
+            interrupted: interrupt::is_interrupted(),
+            failed: collection.failed_benchmarks().len(),
+        });
+    }
+    if let Some(notifier) = notifier {
+        let failed = collection.failed_benchmarks().len();
+        let title = if interrupt::is_interrupted() {
+            "🛑 Burnbench run interrupted".to_string()
+        } else if failed > 0 {
+            format!("❌ Burnbench run finished with {failed} failed benchmarks")
+        } else {
+            "✅ Burnbench run finished".to_string()
+        };
+        if let Err(e) = notifier.notify(&title, &output_results) {
+            eprintln!("❌ Failed to send the notification ({e})");
+        }
+        // Alert on the shifts of the benchmarks of the run against their history, so that the
+        // scheduled runs don't need their trends to be checked manually.
+        let mut history = BenchmarkCollection::from_previous_run();
+        history.load_all_records();
+        if let Some(alert) = get_run_shift_alert(collection.records(), history.records()) {
+            println!("\n⚠️ {alert}");
+            if let Err(e) = notifier.notify("⚠️ Burnbench detected performance shifts", &alert)
+            {
+                eprintln!("❌ Failed to send the notification ({e})");
+            }
+        }
+    }
     // 'complete' webhook
-    if let Ok(inputs_file) = inputs_file {
-        send_output_results(&inputs_file, &table, share_link.as_deref());
+    send_output_results(
+        inputs_file.as_deref().ok(),
+        &table,
+        share_link.as_deref(),
+        webhook_url,
+    );
+}
+
+/// Share the records of the invocations of the run, each invocation in its own request.
+///
+/// The results deviating from the local history of the same hardware are likely a
+/// misconfiguration, they are only shared once confirmed unless forced.
+fn share_invocations(invocations: &[PathBuf], token: &str, force: bool) {
+    let invocations: Vec<Vec<BenchmarkRecord>> = invocations
+        .iter()
+        .map(|dir| {
+            BenchmarkCollection::invocation_records(dir)
+                .iter()
+                .filter_map(|file| {
+                    let file = fs::File::open(file).ok()?;
+                    serde_json::from_reader(file).ok()
+                })
+                .collect()
+        })
+        .filter(|records: &Vec<BenchmarkRecord>| !records.is_empty())
+        .collect();
+    if invocations.is_empty() {
+        return;
+    }
+
+    let records = invocations.concat();
+    let history = BenchmarkCollection::from_previous_run()
+        .load_all_records()
+        .records()
+        .to_vec();
+    let outliers = find_outliers(&records, &history);
+    if !outliers.is_empty() {
+        println!(
+            "\n⚠️ Some results deviate by more than {OUTLIER_FACTOR}× from the recent results of this machine, check that the benchmarks were built in release and that the machine is not throttled:"
+        );
+        for outlier in &outliers {
+            println!(
+                "  {} ({}): {:.2?} instead of {:.2?} ({:.1}×)",
+                outlier.name,
+                outlier.feature,
+                outlier.median,
+                outlier.reference,
+                outlier.factor()
+            );
+        }
+        if !force && !confirm("Share them anyway?") {
+            println!("Results not shared, rerun with --force-share to share them anyway.");
+            return;
+        }
+    }
+
+    println!("Sharing {} results...", records.len());
+    let client = ServerClient::default();
+    for records in invocations {
+        if let Err(e) = client.upload_records(token, &records) {
+            eprintln!("❌ Failed to share results ({e})");
+            return;
+        }
+    }
+    println!("Results shared successfully.");
+}
+
+/// Ask a yes/no question on the terminal, answered no when there is no terminal.
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
     }
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Names of the benches declared in the manifest of the benchmarks crate.
+fn get_bench_names(info: &CrateInfo) -> Vec<String> {
+    let cargo_file_path = Path::new(&info.path).join("Cargo.toml");
+    let Ok(content) = fs::read_to_string(&cargo_file_path) else {
+        return vec![];
+    };
+    let Ok(parsed) = content.parse::<toml::Table>() else {
+        return vec![];
+    };
+
+    parsed
+        .get("bench")
+        .and_then(|b| b.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|bench| bench.get("name").and_then(|v| v.as_str()))
+        .map(String::from)
+        .collect()
 }
 
 fn get_required_features(info: &CrateInfo, target_bench: &str) -> Vec<String> {
@@ -345,34 +2878,40 @@ fn get_required_features(info: &CrateInfo, target_bench: &str) -> Vec<String> {
     vec![]
 }
 
-#[allow(clippy::too_many_arguments)]
+/// Build and run the benches of the invocation, returning whether they succeeded.
 fn run_cargo(
     info: &CrateInfo,
-    benches: &[String],
-    backend: &str,
-    dtype: &BenchDType,
-    url: &str,
-    token: Option<&str>,
-    progress_bar: &Option<Arc<Mutex<RunnerProgressBar>>>,
-    version: &str,
-    profile: &Profiling,
-) -> io::Result<ExitStatus> {
-    let bench_str = benches.join(", ");
-    let processor: Arc<dyn OutputProcessor> = if let Some(pb) = progress_bar {
-        Arc::new(NiceProcessor::new(
-            bench_str,
-            backend.to_string(),
-            version.to_string(),
-            pb.clone(),
-        ))
-    } else {
-        Arc::new(VerboseProcessor)
-    };
-    let dependency_version = get_version(version);
-    let dependency = Dependency::new(&dependency_version);
+    invocation: CargoInvocation,
+    processor: Arc<dyn OutputProcessor>,
+) -> io::Result<bool> {
+    let CargoInvocation {
+        benches,
+        backend,
+        dtype,
+        url,
+        token,
+        version,
+        revision,
+        profile,
+        cargo_profile,
+        extra_features,
+        run_envs,
+        container,
+        wasm,
+        android,
+        results_dir,
+    } = invocation;
+    let (dependency, source) = burn_dependency(version, revision);
     let mut features = String::new();
 
-    let guard = dependency.patch(info.path.as_path()).unwrap();
+    // Concurrent runs would patch the same manifest and build in the same target directory.
+    let lock = FileLock::acquire(
+        &Path::new(crate::BENCHMARKS_TARGET_DIR).join(TARGET_LOCK),
+        "the benchmarks target directory",
+    )?;
+    let guard = dependency
+        .patch(info.path.as_path(), info.workspace.as_path())
+        .unwrap();
     let name = &info.name;
     features += &format!("{name}/{backend},{name}/{dtype}");
 
@@ -382,10 +2921,12 @@ fn run_cargo(
         }
     }
 
-    if version.starts_with("0.16") {
-        features += ",legacy-v16";
-    } else if version.starts_with("0.17") {
-        features += ",legacy-v17";
+    if let Some(legacy) = legacy_feature(version) {
+        features += &format!(",{legacy}");
+    }
+
+    for feature in extra_features {
+        features += &format!(",{feature}");
     }
 
     for bench in benches.iter() {
@@ -414,6 +2955,15 @@ fn run_cargo(
         args.push(crate::BENCHMARKS_TARGET_DIR);
     }
 
+    // The benchmarks crate may be outside of the current workspace.
+    let manifest_path = info.path.join("Cargo.toml").to_string_lossy().to_string();
+    args.push("--manifest-path");
+    args.push(&manifest_path);
+    if let Some(profile) = cargo_profile {
+        args.push("--profile");
+        args.push(profile);
+    }
+
     if let Some(t) = token {
         args.push("--");
         args.push("--sharing-url");
@@ -421,17 +2971,65 @@ fn run_cargo(
         args.push("--sharing-token");
         args.push(t);
     }
-    let runner = CargoRunner::new(
-        &args,
-        vec![("BURN_BENCH_BURN_VERSION".to_string(), version.to_string())],
-        processor,
-        profile.clone(),
-    );
-    let status = runner.run();
+    let mut envs = vec![
+        ("BURN_BENCH_BURN_VERSION".to_string(), version.to_string()),
+        (
+            "BURN_BENCH_BURN_SOURCE".to_string(),
+            serde_json::to_string(&source).unwrap(),
+        ),
+        (
+            "BURN_BENCH_RESULTS_DIR".to_string(),
+            results_dir.to_string_lossy().to_string(),
+        ),
+    ];
+    envs.extend(run_envs.iter().cloned());
+    let success = match (wasm, android) {
+        (Some(wasm), _) => wasm
+            .run(benches, &args, &envs, processor, results_dir)
+            .map(|outcome| outcome.success()),
+        (_, Some(android)) => android
+            .run(benches, &args, &envs, processor, results_dir)
+            .map(|status| status.success()),
+        (None, None) => {
+            let mut runner =
+                CargoRunner::new(&args, envs, processor, profile.clone(), container.cloned());
+            if let Profiling::Activated {
+                flamegraph: true, ..
+            } = profile
+            {
+                let stacks =
+                    flamegraph::folded_path(&benches[0], backend, &dtype.to_string(), version);
+                runner = runner.with_stacks(stacks);
+            }
+            runner.run().map(|status| status.success())
+        }
+    };
 
     core::mem::drop(guard);
+    core::mem::drop(lock);
+
+    success
+}
 
-    status
+/// Burn dependency of the version and the source stored in its records.
+///
+/// A branch resolved at the start of the run is benchmarked at that commit.
+fn burn_dependency(version: &str, revision: Option<&str>) -> (Dependency, BurnSource) {
+    let dependency_version = get_version(version);
+    match revision {
+        Some(rev) => (
+            Dependency::new(rev),
+            BurnSource::Git {
+                branch: Some(dependency_version),
+                rev: Some(rev.to_string()),
+            },
+        ),
+        None => {
+            let dependency = Dependency::new(&dependency_version);
+            let source = dependency.source();
+            (dependency, source)
+        }
+    }
 }
 
 /// Take cake of special version names of the form PR#number_sha1 and return sha1.
@@ -445,18 +3043,119 @@ fn get_version(version: &str) -> String {
     version.to_string()
 }
 
-fn web_results_url(token: Option<&str>, versions: &[String]) -> Option<String> {
+fn web_results_url(
+    token: Option<&str>,
+    versions: &[String],
+    burn_revisions: &BTreeMap<String, String>,
+) -> Option<String> {
     if let Some(t) = token
         && let Ok(user) = get_username(t)
     {
         let sysinfo = BenchmarkSystemInfo::new();
         let encoded_os = utf8_percent_encode(&sysinfo.os.name, NON_ALPHANUMERIC).to_string();
         let versions = utf8_percent_encode(&versions.join(","), NON_ALPHANUMERIC).to_string();
-
-        return Some(format!(
+        let mut url = format!(
             "{}benchmarks/community-benchmarks?user={}&sysHardware=Any&os={}&burnVersions={}",
-            BENCHMARK_WEBSITE_URL, user.nickname, encoded_os, versions
-        ));
+            website_url(),
+            user.nickname,
+            encoded_os,
+            versions
+        );
+        if !burn_revisions.is_empty() {
+            let revisions = burn_revisions
+                .iter()
+                .map(|(version, rev)| format!("{version}:{rev}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            url.push_str(&format!(
+                "&burnRevisions={}",
+                utf8_percent_encode(&revisions, NON_ALPHANUMERIC)
+            ));
+        }
+
+        return Some(url);
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crate_info_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"benches\"]\n",
+        )
+        .unwrap();
+        let crate_dir = dir.path().join("benches");
+        fs::create_dir(&crate_dir).unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"my-benches\"\n\n[[bench]]\nname = \"gemm\"\nharness = false\n",
+        )
+        .unwrap();
+
+        let info = CrateInfo::from_path(&crate_dir).unwrap();
+        assert_eq!(info.name, "my-benches");
+        assert_eq!(info.workspace, fs::canonicalize(dir.path()).unwrap());
+        assert_eq!(get_bench_names(&info), vec!["gemm".to_string()]);
+        assert!(CrateInfo::from_path(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_parse_input_shape() {
+        assert_eq!(
+            parse_input_shape("1x3x224x224"),
+            Ok("1x3x224x224".to_string())
+        );
+        assert_eq!(parse_input_shape("16"), Ok("16".to_string()));
+        assert!(parse_input_shape("1x0").is_err());
+        assert!(parse_input_shape("1,3").is_err());
+        assert!(parse_input_shape("").is_err());
+    }
+
+    #[test]
+    fn test_check_baselines() {
+        let benches = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(
+            check_baselines(
+                &benches(&["baseline"]),
+                &[BackendValues::BaselineTchCpu, BackendValues::BaselineOrtCpu]
+            )
+            .is_ok()
+        );
+        assert!(check_baselines(&benches(&["matmul"]), &[BackendValues::TchCpu]).is_ok());
+        assert!(check_baselines(&benches(&["baseline"]), &[BackendValues::TchCpu]).is_err());
+        assert!(
+            check_baselines(
+                &benches(&["baseline"]),
+                &[BackendValues::BaselineTchCpu, BackendValues::TchCpu]
+            )
+            .is_err()
+        );
+        assert!(check_baselines(&benches(&["all"]), &[BackendValues::BaselineCandleCpu]).is_err());
+    }
+
+    #[test]
+    fn test_with_fusion_variants_pairs_backends() {
+        let backends = with_fusion_variants(&[
+            BackendValues::Cuda,
+            BackendValues::WgpuFusion,
+            BackendValues::Ndarray,
+            BackendValues::Wgpu,
+        ]);
+        assert_eq!(
+            backends,
+            vec![
+                BackendValues::Cuda,
+                BackendValues::CudaFusion,
+                BackendValues::WgpuFusion,
+                BackendValues::Wgpu,
+                BackendValues::Ndarray,
+            ]
+        );
+    }
+}
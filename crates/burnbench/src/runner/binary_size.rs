@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+use super::logging::ANSI_ESCAPE;
+use super::processor::OutputProcessor;
+use super::state::Combination;
+
+/// Size of the executable of a bench built for a combination, stripped of its symbols.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BinarySize {
+    pub(crate) bench: String,
+    pub(crate) backend: String,
+    pub(crate) version: String,
+    pub(crate) dtype: String,
+    pub(crate) bytes: u64,
+}
+
+/// Executables of the benches run by cargo, listed in its output.
+pub(crate) type Executables = Arc<Mutex<Vec<PathBuf>>>;
+
+/// Wrap the processor to collect the executables run by `cargo bench` from its output, such as
+/// `Running benches/matmul.rs (target/benchmarks/release/deps/matmul-0123456789abcdef)`.
+pub(crate) fn with_executables(
+    processor: Arc<dyn OutputProcessor>,
+) -> (Arc<dyn OutputProcessor>, Executables) {
+    let executables = Executables::default();
+    let collector = ExecutableCollector {
+        inner: processor,
+        executables: executables.clone(),
+    };
+    (Arc::new(collector), executables)
+}
+
+struct ExecutableCollector {
+    inner: Arc<dyn OutputProcessor>,
+    executables: Executables,
+}
+
+impl OutputProcessor for ExecutableCollector {
+    fn process_line(&self, line: &str) {
+        if let Some(executable) = parse_executable(line) {
+            self.executables.lock().unwrap().push(executable);
+        }
+        self.inner.process_line(line);
+    }
+
+    fn progress(&self) {
+        self.inner.progress();
+    }
+
+    fn finish(&self) {
+        self.inner.finish();
+    }
+}
+
+fn parse_executable(line: &str) -> Option<PathBuf> {
+    let line = ANSI_ESCAPE.replace_all(line, "");
+    let (_, running) = line.trim().split_once("Running ")?;
+    let (_, executable) = running.rsplit_once(" (")?;
+    executable.strip_suffix(')').map(PathBuf::from)
+}
+
+/// Measure the executables of the benches run for the combination.
+pub(crate) fn measure(executables: &[PathBuf], combination: &Combination) -> Vec<BinarySize> {
+    executables
+        .iter()
+        .filter_map(|executable| {
+            let bytes = stripped_size(executable)
+                .inspect_err(|e| {
+                    tracing::warn!("Failed to strip {} ({e})", executable.display());
+                })
+                .ok()?;
+            Some(BinarySize {
+                bench: bench_name(executable),
+                backend: combination.backend.clone(),
+                version: combination.version.clone(),
+                dtype: combination.dtype.clone(),
+                bytes,
+            })
+        })
+        .collect()
+}
+
+/// Name of the bench of an executable such as `unary_sweep-0123456789abcdef`.
+fn bench_name(executable: &Path) -> String {
+    let stem = executable
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    match stem.rsplit_once('-') {
+        Some((name, _hash)) => name.replace('_', "-"),
+        None => stem,
+    }
+}
+
+/// Size of a stripped copy of the executable, the one that would be deployed.
+fn stripped_size(executable: &Path) -> io::Result<u64> {
+    let dir = tempfile::tempdir()?;
+    let copy = dir.path().join(executable.file_name().unwrap_or_default());
+    fs::copy(executable, &copy)?;
+    let status = Command::new("strip")
+        .arg(&copy)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("strip exited with {status}")));
+    }
+    Ok(fs::metadata(&copy)?.len())
+}
+
+/// Human readable size, such as `12.3 MB`.
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(12_345_678), "12.3 MB");
+        assert_eq!(format_size(2_500_000_000), "2.5 GB");
+    }
+
+    #[test]
+    fn test_parse_executable_of_running_line() {
+        let line = "\u{1b}[1m\u{1b}[32m     Running\u{1b}[0m benches/unary_sweep.rs (target/benchmarks/release/deps/unary_sweep-0123456789abcdef)";
+        let executable = parse_executable(line).unwrap();
+
+        assert_eq!(
+            executable,
+            Path::new("target/benchmarks/release/deps/unary_sweep-0123456789abcdef")
+        );
+        assert_eq!(bench_name(&executable), "unary-sweep");
+        assert_eq!(parse_executable("   Compiling burn v0.19.0"), None);
+    }
+}
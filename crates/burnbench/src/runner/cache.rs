@@ -0,0 +1,295 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use super::lock::FileLock;
+use super::state::Combination;
+use crate::persistence::{BenchmarkRecord, BurnSource, benchmarks_cache_dir};
+
+/// Records produced by a combination with a Burn source, on a commit of the benchmarks and on a
+/// machine.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    combination: Combination,
+    /// Missing from the entries of the older caches, which are never reused.
+    #[serde(default)]
+    burn_source: Option<BurnSource>,
+    git_hash: String,
+    hardware: String,
+    records: Vec<PathBuf>,
+}
+
+/// Index of the records stored in the local history, used to reuse them instead of running a
+/// combination again.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct RecordCache {
+    entries: Vec<CacheEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl RecordCache {
+    pub(crate) fn load() -> Self {
+        let path = benchmarks_cache_dir().join("record_cache.json");
+        let mut cache = Self::read(&path);
+        cache.path = path;
+        cache
+    }
+
+    fn read(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Record files of the combination run with the same Burn sources, on the same commit and
+    /// hardware, if they all still exist.
+    ///
+    /// The sources moving over time, such as a branch without its resolved commit, are never
+    /// reused.
+    pub(crate) fn lookup(
+        &self,
+        combination: &Combination,
+        burn_source: &BurnSource,
+        git_hash: &str,
+        hardware: &str,
+    ) -> Option<&[PathBuf]> {
+        if !burn_source.is_pinned() {
+            return None;
+        }
+        self.entries
+            .iter()
+            .find(|entry| {
+                &entry.combination == combination
+                    && entry.burn_source.as_ref() == Some(burn_source)
+                    && entry.git_hash == git_hash
+                    && entry.hardware == hardware
+            })
+            .map(|entry| entry.records.as_slice())
+            .filter(|records| records.iter().all(|record| record.is_file()))
+    }
+
+    /// Index the records produced by the combination and persist the cache on disk.
+    ///
+    /// The Burn source, the commit and the hardware are the ones stored in the records, the
+    /// records of the sources moving over time are not indexed.
+    pub(crate) fn insert(
+        &mut self,
+        combination: Combination,
+        records: Vec<PathBuf>,
+    ) -> io::Result<()> {
+        let Some(first) = records.first() else {
+            return Ok(());
+        };
+        let record: BenchmarkRecord = serde_json::from_reader(fs::File::open(first)?)?;
+        let Some(burn_source) = record.burn_source.filter(BurnSource::is_pinned) else {
+            return Ok(());
+        };
+        let git_hash = record.results.git_hash;
+        let hardware = record.system_info.hardware();
+
+        // Merge with the entries saved by the concurrent runs since the cache was loaded.
+        let _lock = FileLock::acquire(&self.path.with_extension("lock"), "the record cache")?;
+        self.entries = Self::read(&self.path).entries;
+        self.entries.retain(|entry| {
+            entry.combination != combination
+                || entry.burn_source.as_ref() != Some(&burn_source)
+                || entry.git_hash != git_hash
+                || entry.hardware != hardware
+        });
+        self.entries.push(CacheEntry {
+            combination,
+            burn_source: Some(burn_source),
+            git_hash,
+            hardware,
+            records,
+        });
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Commit of the repository containing the benchmarks, as stored in their records.
+pub(crate) fn current_git_hash(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(path)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pinned() -> BurnSource {
+        BurnSource::Git {
+            branch: Some("main".to_string()),
+            rev: Some("0123abcd".to_string()),
+        }
+    }
+
+    fn combination(dtype: &str) -> Combination {
+        Combination {
+            bench: "matmul".to_string(),
+            backend: "wgpu".to_string(),
+            version: "main".to_string(),
+            dtype: dtype.to_string(),
+            threads: None,
+        }
+    }
+
+    #[test]
+    fn test_lookup_requires_same_commit_and_hardware() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let cache = RecordCache {
+            entries: vec![CacheEntry {
+                combination: combination("f32"),
+                burn_source: Some(pinned()),
+                git_hash: "abc".to_string(),
+                hardware: "cpu | gpu".to_string(),
+                records: vec![file.path().to_path_buf()],
+            }],
+            path: PathBuf::new(),
+        };
+
+        assert!(
+            cache
+                .lookup(&combination("f32"), &pinned(), "abc", "cpu | gpu")
+                .is_some()
+        );
+        assert!(
+            cache
+                .lookup(&combination("f16"), &pinned(), "abc", "cpu | gpu")
+                .is_none()
+        );
+        assert!(
+            cache
+                .lookup(&combination("f32"), &pinned(), "def", "cpu | gpu")
+                .is_none()
+        );
+        assert!(
+            cache
+                .lookup(&combination("f32"), &pinned(), "abc", "cpu | other")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_insert_keeps_entries_of_concurrent_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("record_cache.json");
+        let entry = |dtype: &str| CacheEntry {
+            combination: combination(dtype),
+            burn_source: Some(pinned()),
+            git_hash: "abc".to_string(),
+            hardware: "cpu | gpu".to_string(),
+            records: vec![],
+        };
+        let mut cache = RecordCache {
+            entries: vec![],
+            path: path.clone(),
+        };
+        let other = RecordCache {
+            entries: vec![entry("f16")],
+            path: path.clone(),
+        };
+        other.save().unwrap();
+
+        let record = dir.path().join("bench_matmul_0.json");
+        fs::write(
+            &record,
+            serde_json::to_string(&BenchmarkRecord {
+                burn_source: Some(pinned()),
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        cache.insert(combination("f32"), vec![record]).unwrap();
+
+        let saved = RecordCache::read(&path);
+        assert_eq!(saved.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_moving_burn_sources_are_not_cached() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let branch = BurnSource::Git {
+            branch: Some("main".to_string()),
+            rev: None,
+        };
+        let cache = RecordCache {
+            entries: vec![CacheEntry {
+                combination: combination("f32"),
+                burn_source: Some(branch.clone()),
+                git_hash: "abc".to_string(),
+                hardware: "cpu | gpu".to_string(),
+                records: vec![file.path().to_path_buf()],
+            }],
+            path: PathBuf::new(),
+        };
+        assert!(
+            cache
+                .lookup(&combination("f32"), &branch, "abc", "cpu | gpu")
+                .is_none()
+        );
+        assert!(
+            cache
+                .lookup(&combination("f32"), &pinned(), "abc", "cpu | gpu")
+                .is_none()
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = RecordCache {
+            entries: vec![],
+            path: dir.path().join("record_cache.json"),
+        };
+        let record = dir.path().join("bench_matmul_0.json");
+        fs::write(
+            &record,
+            serde_json::to_string(&BenchmarkRecord {
+                burn_source: Some(branch),
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        cache.insert(combination("f32"), vec![record]).unwrap();
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn test_lookup_ignores_deleted_records() {
+        let cache = RecordCache {
+            entries: vec![CacheEntry {
+                combination: combination("f32"),
+                burn_source: Some(pinned()),
+                git_hash: "abc".to_string(),
+                hardware: "cpu | gpu".to_string(),
+                records: vec![PathBuf::from("/nonexistent/bench_matmul_0.json")],
+            }],
+            path: PathBuf::new(),
+        };
+
+        assert!(
+            cache
+                .lookup(&combination("f32"), &pinned(), "abc", "cpu | gpu")
+                .is_none()
+        );
+    }
+}
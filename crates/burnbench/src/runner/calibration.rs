@@ -0,0 +1,112 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Number of timed executions of the calibration kernel.
+const NUM_SAMPLES: usize = 30;
+
+/// Noise of the machine estimated before running the benchmarks.
+pub(crate) struct NoiseCalibration {
+    /// Smallest measurable duration.
+    pub(crate) timer_resolution: Duration,
+    /// Median duration of the calibration kernel.
+    pub(crate) median: Duration,
+    /// Relative changes in percent below which a difference can't be told apart from noise.
+    pub(crate) score: f64,
+}
+
+/// Time a small fixed kernel several times to estimate the timer resolution and the jitter of
+/// the scheduler.
+pub(crate) fn calibrate() -> NoiseCalibration {
+    let timer_resolution = timer_resolution();
+
+    for _ in 0..5 {
+        kernel();
+    }
+    let mut samples: Vec<Duration> = (0..NUM_SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            kernel();
+            start.elapsed()
+        })
+        .collect();
+    samples.sort();
+    let median = samples[samples.len() / 2];
+    let resolution_score = if median.is_zero() {
+        0.0
+    } else {
+        timer_resolution.as_secs_f64() / median.as_secs_f64() * 100.0
+    };
+
+    NoiseCalibration {
+        timer_resolution,
+        median,
+        score: noise_score(&samples).max(resolution_score),
+    }
+}
+
+/// Spread between the first and the last decile of the sorted samples, relative to their
+/// median, in percent.
+fn noise_score(sorted_samples: &[Duration]) -> f64 {
+    let len = sorted_samples.len();
+    let median = sorted_samples[len / 2];
+    if median.is_zero() {
+        return 0.0;
+    }
+    let first_decile = sorted_samples[len / 10];
+    let last_decile = sorted_samples[len - 1 - len / 10];
+    (last_decile - first_decile).as_secs_f64() / median.as_secs_f64() * 100.0
+}
+
+/// Smallest non-zero difference between two consecutive readings of the clock.
+fn timer_resolution() -> Duration {
+    let mut resolution = Duration::MAX;
+    let mut previous = Instant::now();
+    for _ in 0..10_000 {
+        let now = Instant::now();
+        let elapsed = now - previous;
+        if !elapsed.is_zero() {
+            resolution = resolution.min(elapsed);
+        }
+        previous = now;
+    }
+    resolution
+}
+
+/// Fixed amount of integer work lasting about a millisecond.
+fn kernel() -> u64 {
+    let mut state = black_box(0x9e37_79b9_7f4a_7c15_u64);
+    for i in 0..1_000_000u64 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state = state.wrapping_add(i);
+    }
+    black_box(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noise_score_of_stable_samples_is_zero() {
+        let samples = vec![Duration::from_millis(10); 30];
+        assert_eq!(noise_score(&samples), 0.0);
+    }
+
+    #[test]
+    fn test_noise_score_ignores_outer_deciles() {
+        let mut samples: Vec<Duration> = (0..20).map(|_| Duration::from_millis(100)).collect();
+        samples.push(Duration::from_millis(50));
+        samples.push(Duration::from_millis(200));
+        samples.sort();
+        // The deciles are within the stable samples.
+        assert_eq!(noise_score(&samples), 0.0);
+
+        let samples: Vec<Duration> = (90..=110).map(Duration::from_millis).collect();
+        let score = noise_score(&samples);
+        assert!((score - 16.0).abs() < 1e-9, "{score}");
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use strum::{Display, EnumIter};
+
+use crate::persistence::benchmarks_cache_dir;
+
+/// Lock of the target directory held by the runs building the benches.
+pub(crate) const TARGET_LOCK: &str = ".burnbench.lock";
+
+/// Files produced by the runs that can be removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Display, EnumIter)]
+pub(crate) enum CleanTarget {
+    /// Build artifacts of the benches, lockfile snapshots and cargo registry of the containers
+    #[strum(to_string = "build")]
+    Build,
+    /// Logs of the benches
+    #[strum(to_string = "logs")]
+    Logs,
+    /// ncu reports, folded stacks and flamegraphs
+    #[strum(to_string = "profiles")]
+    Profiles,
+    /// Records, runs and reused results in the cache directory, used by the reports and trends
+    #[strum(to_string = "results")]
+    Results,
+}
+
+impl CleanTarget {
+    /// Targets cleaned by default, whose files are produced again by the next runs.
+    pub(crate) const DEFAULT: [Self; 3] = [Self::Build, Self::Logs, Self::Profiles];
+
+    /// Files and directories of the target that exist.
+    pub(crate) fn paths(&self) -> Vec<PathBuf> {
+        let target_dir = Path::new(crate::BENCHMARKS_TARGET_DIR);
+        let paths = match self {
+            Self::Build => entries(target_dir)
+                .into_iter()
+                .filter(|path| {
+                    path.file_name()
+                        .is_some_and(|name| name != "logs" && name != TARGET_LOCK)
+                })
+                .collect(),
+            Self::Logs => vec![target_dir.join("logs")],
+            Self::Profiles => {
+                let mut paths: Vec<PathBuf> = entries(Path::new("target"))
+                    .into_iter()
+                    .filter(|path| {
+                        path.extension()
+                            .is_some_and(|extension| extension == "ncu-rep")
+                    })
+                    .collect();
+                paths.push(PathBuf::from("target/flamegraphs"));
+                paths
+            }
+            Self::Results => vec![benchmarks_cache_dir()],
+        };
+        paths.into_iter().filter(|path| path.exists()).collect()
+    }
+}
+
+/// Size in bytes of the file or of the files in the directory, without following the links.
+pub(crate) fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.is_dir() {
+        entries(path).iter().map(|entry| disk_usage(entry)).sum()
+    } else {
+        metadata.len()
+    }
+}
+
+/// Remove the file or the directory with its files.
+pub(crate) fn remove(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn entries(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_usage_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("release").join("deps");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("matmul"), [0u8; 1000]).unwrap();
+        fs::write(dir.path().join("build.log"), [0u8; 24]).unwrap();
+
+        assert_eq!(disk_usage(dir.path()), 1024);
+        assert_eq!(disk_usage(&dir.path().join("missing")), 0);
+
+        remove(&dir.path().join("release")).unwrap();
+        remove(&dir.path().join("build.log")).unwrap();
+        assert_eq!(disk_usage(dir.path()), 0);
+    }
+}
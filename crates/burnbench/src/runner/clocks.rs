@@ -0,0 +1,92 @@
+use std::process::Command;
+
+enum Vendor {
+    Nvidia,
+    Amd,
+}
+
+/// GPU clocks locked for the duration of the run, restored on drop.
+pub(crate) struct ClockLock {
+    vendor: Vendor,
+    description: String,
+}
+
+impl ClockLock {
+    /// Lock the clocks of the GPUs of the machine, which requires root privileges.
+    pub(crate) fn acquire() -> Result<Self, String> {
+        if let Some(clock) = nvidia_default_clock() {
+            run(
+                "nvidia-smi",
+                &["--lock-gpu-clocks", &format!("{clock},{clock}")],
+            )?;
+            return Ok(Self {
+                vendor: Vendor::Nvidia,
+                description: format!("NVIDIA graphics clock locked at {clock} MHz"),
+            });
+        }
+        if Command::new("rocm-smi")
+            .arg("--showperflevel")
+            .output()
+            .is_ok_and(|output| output.status.success())
+        {
+            run("rocm-smi", &["--setperflevel", "high"])?;
+            return Ok(Self {
+                vendor: Vendor::Amd,
+                description: "AMD performance level locked at high".to_string(),
+            });
+        }
+
+        Err("no NVIDIA or AMD GPU found".to_string())
+    }
+
+    /// Locked clocks, as stored in the system info of the records.
+    pub(crate) fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl Drop for ClockLock {
+    fn drop(&mut self) {
+        let result = match self.vendor {
+            Vendor::Nvidia => run("nvidia-smi", &["--reset-gpu-clocks"]),
+            Vendor::Amd => run("rocm-smi", &["--resetclocks"]),
+        };
+        match result {
+            Ok(()) => println!("🔓 GPU clocks restored"),
+            Err(e) => eprintln!("❌ Failed to restore the GPU clocks ({e})"),
+        }
+    }
+}
+
+/// Lowest default application clock of the NVIDIA GPUs, which they can sustain without boosting.
+fn nvidia_default_clock() -> Option<u32> {
+    let output = Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=clocks.default_applications.graphics",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .min()
+}
+
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("{program} failed: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{program} failed, root privileges are required: {}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        ))
+    }
+}
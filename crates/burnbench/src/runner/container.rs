@@ -0,0 +1,129 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::persistence::benchmarks_cache_dir;
+
+/// Container image in which the cargo invocations run.
+#[derive(Debug, Clone)]
+pub(crate) struct Container {
+    image: String,
+    /// Image reference including its digest, stored in the records.
+    digest: String,
+    /// Options exposing the GPUs of the host to the container.
+    gpu_args: Vec<String>,
+}
+
+impl Container {
+    /// Resolve the digest of the image, pulling it when it is not available locally.
+    pub(crate) fn new(image: &str) -> io::Result<Self> {
+        let digest = match image_digest(image) {
+            Some(digest) => digest,
+            None => {
+                let status = Command::new("docker").args(["pull", image]).status()?;
+                if !status.success() {
+                    return Err(io::Error::other(format!("failed to pull {image}")));
+                }
+                image_digest(image)
+                    .ok_or_else(|| io::Error::other(format!("failed to inspect {image}")))?
+            }
+        };
+
+        let mut gpu_args = vec![];
+        if Command::new("nvidia-smi")
+            .output()
+            .is_ok_and(|output| output.status.success())
+        {
+            gpu_args.extend(["--gpus", "all"].map(String::from));
+        }
+        if Path::new("/dev/kfd").exists() {
+            gpu_args.extend(
+                [
+                    "--device",
+                    "/dev/kfd",
+                    "--device",
+                    "/dev/dri",
+                    "--group-add",
+                    "video",
+                ]
+                .map(String::from),
+            );
+        }
+
+        Ok(Self {
+            image: image.to_string(),
+            digest,
+            gpu_args,
+        })
+    }
+
+    pub(crate) fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    /// Command running cargo in a new container with the environment variables.
+    ///
+    /// The workspace and the records cache are mounted at the same paths as on the host, and the
+    /// files are created with the user of the host, so that the build artifacts and the records
+    /// can be used as if cargo ran on the host.
+    pub(crate) fn cargo_command(&self, envs: &[(String, String)]) -> io::Result<Command> {
+        let workspace = std::env::current_dir()?;
+        let home = dirs::home_dir().ok_or_else(|| io::Error::other("no home directory"))?;
+        let cache_dir = benchmarks_cache_dir();
+        fs::create_dir_all(&cache_dir)?;
+        // Keep the registry of the container between the invocations.
+        let cargo_home: PathBuf = workspace
+            .join(crate::BENCHMARKS_TARGET_DIR)
+            .join("container-cargo");
+        fs::create_dir_all(&cargo_home)?;
+
+        let mut command = Command::new("docker");
+        command.args(["run", "--rm"]);
+        command.args(&self.gpu_args);
+        if let Some(user) = host_user() {
+            command.args(["--user", &user]);
+        }
+        for dir in [&workspace, &cache_dir] {
+            command.args(["--volume", &format!("{}:{}", dir.display(), dir.display())]);
+        }
+        command.args(["--workdir", &workspace.to_string_lossy()]);
+        command.args(["--env", &format!("HOME={}", home.display())]);
+        command.args(["--env", &format!("CARGO_HOME={}", cargo_home.display())]);
+        for (key, value) in envs {
+            command.args(["--env", &format!("{key}={value}")]);
+        }
+        command.args([&self.image, "cargo"]);
+
+        Ok(command)
+    }
+}
+
+/// Reference of the image with its digest, or its ID for images built locally.
+fn image_digest(image: &str) -> Option<String> {
+    let output = Command::new("docker")
+        .args([
+            "image",
+            "inspect",
+            "--format",
+            "{{if .RepoDigests}}{{index .RepoDigests 0}}{{else}}{{.Id}}{{end}}",
+            image,
+        ])
+        .output()
+        .ok()?;
+    let digest = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !digest.is_empty()).then_some(digest)
+}
+
+/// `uid:gid` of the current user.
+fn host_user() -> Option<String> {
+    let id = |flag: &str| {
+        let output = Command::new("id").arg(flag).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    Some(format!("{}:{}", id("-u")?, id("-g")?))
+}
@@ -1,6 +1,10 @@
+use super::cache::current_git_hash;
+use super::feature_mapping::FeatureMapping;
+use crate::persistence::BurnSource;
 use regex::Regex;
 use semver::Version;
 use std::io::Write;
+use std::process::Command;
 use std::time::Duration;
 use std::{
     fs::OpenOptions,
@@ -8,7 +12,8 @@ use std::{
 };
 
 pub(crate) enum Dependency {
-    Local,
+    /// Directory of a local checkout of Burn.
+    Local(String),
     Crate(Version),
     Git(String),
 }
@@ -16,7 +21,10 @@ pub(crate) enum Dependency {
 impl Dependency {
     pub fn new(version: &str) -> Self {
         if version == "local" {
-            Self::Local
+            let burn_dir = std::env::var("BURN_BENCH_BURN_DIR").unwrap_or("../../burn/".into());
+            Self::Local(with_trailing_slash(burn_dir))
+        } else if let Some(burn_dir) = version.strip_prefix("local:") {
+            Self::Local(with_trailing_slash(burn_dir.to_string()))
         } else if let Ok(version) = Version::parse(version) {
             Self::Crate(version)
         } else {
@@ -34,6 +42,18 @@ impl Dependency {
 pub struct CargoDependencyGuard {
     benches: Option<TomlDependencyGuard>,
     workspace: Option<TomlDependencyGuard>,
+    lockfile: Option<LockfileGuard>,
+}
+
+/// Restore the `Cargo.lock` of the workspace once the benchmarks of a version have run.
+///
+/// The lockfile resolved for a pinned version is saved under
+/// `target/benchmarks/lockfiles/` and reused by the next runs of the same version, so that the
+/// comparisons are reproducible.
+struct LockfileGuard {
+    lockfile_path: PathBuf,
+    original_content: Option<Vec<u8>>,
+    snapshot_path: Option<PathBuf>,
 }
 
 struct TomlDependencyGuard {
@@ -49,6 +69,9 @@ struct DependencyContent {
 }
 
 static BURN_BASE: [&str; 3] = ["burn", "burn-common", "burn-import"];
+/// Crates that can be patched at the workspace root alongside Burn, with the
+/// `BURN_BENCH_PATCH_CRATES` environment variable.
+static PATCHABLE_CRATES: [&str; 3] = ["cubecl", "cubecl-common", "burn-import"];
 // Match any char except \} including new lines.
 static REGEX_BASE: &str = r" = \{([^\}]|\n)*\}";
 
@@ -84,7 +107,11 @@ impl DependencyContentUpdate {
             original_content: content.workspace.clone().unwrap(),
         });
 
-        CargoDependencyGuard { benches, workspace }
+        CargoDependencyGuard {
+            benches,
+            workspace,
+            lockfile: None,
+        }
     }
 
     fn perform_update(&self, content: &DependencyContent) -> std::io::Result<()> {
@@ -101,7 +128,7 @@ impl DependencyContentUpdate {
 }
 
 impl DependencyContent {
-    pub fn from_path(base_path: &Path) -> std::io::Result<Self> {
+    pub fn from_path(base_path: &Path, workspace: &Path) -> std::io::Result<Self> {
         let benches_path = Path::new(base_path).join("Cargo.toml");
         let benches = std::fs::read_to_string(&benches_path)?;
         let mut workspace = None;
@@ -117,7 +144,7 @@ impl DependencyContent {
         }
 
         if burn_in_workspace {
-            let cargo_file_path = Path::new(".").join("Cargo.toml");
+            let cargo_file_path = workspace.join("Cargo.toml");
             let content = std::fs::read_to_string(&cargo_file_path)?;
             workspace = Some(content);
             workspace_path = Some(cargo_file_path);
@@ -140,96 +167,208 @@ impl Drop for TomlDependencyGuard {
             .unwrap();
         cargo_file.set_len(0).unwrap();
         write!(cargo_file, "{}", self.original_content).unwrap();
-        log::info!("Reset original cargo file");
+        tracing::info!("Reset original cargo file");
         std::thread::sleep(Duration::from_millis(200));
     }
 }
 
+impl LockfileGuard {
+    fn new(workspace: &Path, snapshot_name: Option<String>) -> std::io::Result<Self> {
+        let lockfile_path = workspace.join("Cargo.lock");
+        let original_content = std::fs::read(&lockfile_path).ok();
+        let snapshot_path = snapshot_name.map(|name| {
+            Path::new(crate::BENCHMARKS_TARGET_DIR)
+                .join("lockfiles")
+                .join(name)
+        });
+
+        if let Some(snapshot_path) = &snapshot_path
+            && snapshot_path.exists()
+        {
+            tracing::info!("Reusing lockfile {}", snapshot_path.display());
+            std::fs::copy(snapshot_path, &lockfile_path)?;
+        }
+
+        Ok(Self {
+            lockfile_path,
+            original_content,
+            snapshot_path,
+        })
+    }
+}
+
+impl Drop for LockfileGuard {
+    fn drop(&mut self) {
+        if let Some(snapshot_path) = &self.snapshot_path
+            && self.lockfile_path.exists()
+        {
+            let saved = snapshot_path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::copy(&self.lockfile_path, snapshot_path));
+            if let Err(e) = saved {
+                tracing::warn!("Failed to save lockfile {}: {e}", snapshot_path.display());
+            }
+        }
+
+        let restored = match &self.original_content {
+            Some(content) => std::fs::write(&self.lockfile_path, content),
+            None => std::fs::remove_file(&self.lockfile_path),
+        };
+        if let Err(e) = restored {
+            tracing::warn!("Failed to restore the original lockfile: {e}");
+        }
+        tracing::info!("Reset original lockfile");
+    }
+}
+
 impl Dependency {
-    pub fn patch(&self, base_path: &Path) -> std::io::Result<CargoDependencyGuard> {
-        let burn_dir = std::env::var("BURN_BENCH_BURN_DIR").unwrap_or("../../burn/".into());
-        let content_original = DependencyContent::from_path(base_path)?;
+    /// Source of the dependency, stored in the records.
+    pub(crate) fn source(&self) -> BurnSource {
+        match self {
+            Dependency::Local(burn_dir) => {
+                let path = std::fs::canonicalize(burn_dir)
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| burn_dir.clone());
+                let dirty = Command::new("git")
+                    .args(["status", "--porcelain"])
+                    .current_dir(burn_dir)
+                    .output()
+                    .is_ok_and(|output| output.status.success() && !output.stdout.is_empty());
+                BurnSource::Path {
+                    path,
+                    git_hash: current_git_hash(Path::new(burn_dir)),
+                    dirty,
+                }
+            }
+            Dependency::Crate(version) => BurnSource::CratesIo {
+                version: version.to_string(),
+            },
+            Dependency::Git(reference) => {
+                let value = |key: &str| {
+                    reference
+                        .strip_prefix(&format!("{key} = \""))
+                        .and_then(|value| value.strip_suffix('"'))
+                        .map(str::to_string)
+                };
+                BurnSource::Git {
+                    branch: value("branch"),
+                    rev: value("rev"),
+                }
+            }
+        }
+    }
+
+    /// Name of the lockfile saved for the dependency, when it always resolves to the same
+    /// sources.
+    ///
+    /// Branches and local checkouts move over time, their lockfile is not reused.
+    fn lockfile_name(&self) -> Option<String> {
+        match self {
+            Dependency::Local(_) => None,
+            Dependency::Crate(version) => Some(format!("burn-{version}.lock")),
+            Dependency::Git(reference) => reference
+                .strip_prefix("rev = \"")
+                .and_then(|rev| rev.strip_suffix('"'))
+                .map(|rev| format!("burn-{rev}.lock")),
+        }
+    }
+
+    /// Point the Burn dependencies of the crate at `base_path` to the version, in the manifest of
+    /// its workspace when they are inherited from it.
+    pub fn patch(
+        &self,
+        base_path: &Path,
+        workspace: &Path,
+    ) -> std::io::Result<CargoDependencyGuard> {
+        let content_original = DependencyContent::from_path(base_path, workspace)?;
 
         let content = match self {
-            Dependency::Local => self.update_burn_local(&content_original, &burn_dir),
+            Dependency::Local(burn_dir) => self.update_burn_local(&content_original, burn_dir),
             Dependency::Crate(version) => self.update_burn_version(&content_original, version),
             Dependency::Git(version) => self.update_burn_git(&content_original, version),
         }?;
 
-        let guard = content.create_guard(&content_original);
+        let mut guard = content.create_guard(&content_original);
+        guard.lockfile = Some(LockfileGuard::new(workspace, self.lockfile_name())?);
         content.perform_update(&content_original)?;
 
+        let patched_crates = patched_crates_from_env();
+        if !patched_crates.is_empty()
+            && let Some(section) = self.patch_section(&patched_crates)
+        {
+            let workspace_path = workspace.join("Cargo.toml");
+            let workspace = std::fs::read_to_string(&workspace_path)?;
+            if guard.workspace.is_none() {
+                guard.workspace = Some(TomlDependencyGuard {
+                    cargo_file_path: workspace_path.clone(),
+                    original_content: workspace.clone(),
+                });
+            }
+            std::fs::write(&workspace_path, insert_patch_section(&workspace, &section))?;
+        }
+
         Ok(guard)
     }
 
-    fn update_feature_flags(version: &Version, content: String) -> String {
-        if version < &Version::new(0, 17, 0) {
-            let content = content
-                .replace("cuda = [\"burn/cuda\"]", "cuda = [\"burn/cuda-jit\"]")
-                .replace("rocm = [\"burn/rocm\"]", "rocm = [\"burn/hip-jit\"]")
-                .replace(
-                    "ndarray-simd = [\"ndarray\", \"burn/simd\"]",
-                    "ndarray-simd = [\"ndarray\"]",
-                )
-                .replace(
-                    "vulkan = [\"burn/vulkan\", \"burn/autotune\"]",
-                    "vulkan = [\"burn/wgpu-spirv\", \"burn/autotune\"]",
-                )
-                .replace(
-                    "metal = [\"burn/vulkan\", \"burn/autotune\"]",
-                    "metal = [\"burn/wgpu\", \"burn/autotune\"]",
-                )
-                .replace(
-                    "ndarray-simd = [\"burn/ndarray\", \"burn/simd\"]",
-                    "ndarray-simd = [\"burn/ndarray\"]",
-                )
-                .replace(
-                    "candle-metal = [\"burn/candle\", \"burn/candle-metal\"]",
-                    "candle-metal = [\"burn/candle\", \"burn/metal\"]",
-                )
-                // Use matching `rand` version (binary and data benchmarks)
-                .replace(
-                    "rand = { version = \"0.9.0\" }",
-                    "rand = { version = \"0.8.5\" }",
-                );
-
-            if (version < &Version::new(0, 16, 1)) & !content.contains("bincode = \"=2.0.0-rc.3\"")
-            {
-                content.replace(
-                    "[dependencies]",
-                    "[dependencies]\nbincode = \"=2.0.0-rc.3\"\nbincode_derive = \"=2.0.0-rc.3\"",
-                )
-            } else {
-                content
-            }
+    /// Entries of the `[patch.crates-io]` section pointing the given crates to the same source
+    /// as Burn.
+    ///
+    /// Released versions are resolved from crates.io and don't need to be patched.
+    fn patch_section(&self, crates: &[String]) -> Option<String> {
+        let cubecl_dir = std::env::var("BURN_BENCH_CUBECL_DIR").unwrap_or("../cubecl/".into());
+        let cubecl_ref = std::env::var("BURN_BENCH_CUBECL_REF").unwrap_or("main".into());
+        let cubecl_ref = if is_commit_hash(&cubecl_ref) {
+            format!("rev = \"{cubecl_ref}\"")
         } else {
-            content
+            format!("branch = \"{cubecl_ref}\"")
+        };
+
+        let mut entries = Vec::with_capacity(crates.len());
+        for name in crates {
+            let is_cubecl = name.starts_with("cubecl");
+            let entry = match self {
+                Dependency::Crate(_) => return None,
+                Dependency::Local(burn_dir) => {
+                    let dir = if is_cubecl { &cubecl_dir } else { burn_dir };
+                    format!("{name} = {{ path = \"{dir}crates/{name}\" }}")
+                }
+                Dependency::Git(reference) => {
+                    let (repo, reference) = if is_cubecl {
+                        ("cubecl", &cubecl_ref)
+                    } else {
+                        ("burn", reference)
+                    };
+                    format!(
+                        "{name} = {{ git = \"https://github.com/tracel-ai/{repo}\", {reference} }}"
+                    )
+                }
+            };
+            entries.push(entry);
         }
+
+        Some(entries.join("\n"))
     }
+
+    fn update_feature_flags(version: &Version, content: String) -> String {
+        FeatureMapping::embedded().apply(version, &content)
+    }
+
     fn update_burn_version(
         &self,
         content: &DependencyContent,
         version: &Version,
     ) -> Result<DependencyContentUpdate, std::io::Error> {
         let version_str = version.to_string();
-        log::info!("Applying Burn version: {version_str}");
+        tracing::info!("Applying Burn version: {version_str}");
 
         // Update burn versions
 
         let update_version = |content: &str| {
             let mut content = content.to_string();
             for base in BURN_BASE {
-                let regex = base.to_string() + REGEX_BASE;
-                let burn_re = Regex::new(&regex).unwrap();
-                content = burn_re
-                    .replace_all(
-                        content.as_str(),
-                        format!(
-                            "{base} = {{ version = \"={}\", default-features = false }}",
-                            version_str
-                        ),
-                    )
-                    .to_string();
+                content =
+                    replace_dependency(&content, base, &format!("version = \"={version_str}\""));
             }
 
             content
@@ -265,18 +404,17 @@ impl Dependency {
         content: &DependencyContent,
         reference: &str,
     ) -> Result<DependencyContentUpdate, std::io::Error> {
-        log::info!("Applying Burn git: {reference}");
+        tracing::info!("Applying Burn git: {reference}");
 
         // Update burn git reference
         let update = |content: &str| {
             let mut content = content.to_string();
             for base in BURN_BASE {
-                let regex = base.to_string() + REGEX_BASE;
-                let burn_re = Regex::new(&regex).unwrap();
-                content = burn_re.replace_all(
-                    content.as_str(),
-                    format!("{base} = {{ git = \"https://github.com/tracel-ai/burn\", {}, default-features = false }}", reference)
-                ).to_string();
+                content = replace_dependency(
+                    &content,
+                    base,
+                    &format!("git = \"https://github.com/tracel-ai/burn\", {reference}"),
+                );
             }
 
             content
@@ -290,7 +428,7 @@ impl Dependency {
         content: &DependencyContent,
         repo_path: &str,
     ) -> Result<DependencyContentUpdate, std::io::Error> {
-        log::info!("Applying Burn local: {repo_path}");
+        tracing::info!("Applying Burn local: {repo_path}");
 
         // Update burn path
         let repo_path = match content.workspace_path {
@@ -302,17 +440,8 @@ impl Dependency {
             let repo_path = repo_path.as_path();
 
             for base in BURN_BASE {
-                let regex = base.to_string() + REGEX_BASE;
-                let burn_re = Regex::new(&regex).unwrap();
-                content = burn_re
-                    .replace_all(
-                        &content,
-                        format!(
-                            "{base} = {{ path = \"{}crates/{base}\", default-features = false }}",
-                            repo_path.to_str().unwrap()
-                        ),
-                    )
-                    .to_string();
+                let path = format!("path = \"{}crates/{base}\"", repo_path.to_str().unwrap());
+                content = replace_dependency(&content, base, &path);
             }
 
             content
@@ -322,8 +451,193 @@ impl Dependency {
     }
 }
 
+/// Point the dependency entries of the crate to the source, keeping their features and whether
+/// they are optional.
+fn replace_dependency(content: &str, base: &str, source: &str) -> String {
+    let regex = base.to_string() + REGEX_BASE;
+    let burn_re = Regex::new(&regex).unwrap();
+    let features_re = Regex::new(r"features = \[[^\]]*\]").unwrap();
+    burn_re
+        .replace_all(content, |captures: &regex::Captures| {
+            let mut entry = format!("{base} = {{ {source}, default-features = false");
+            if let Some(features) = features_re.find(&captures[0]) {
+                entry += &format!(", {}", features.as_str());
+            }
+            if captures[0].contains("optional = true") {
+                entry += ", optional = true";
+            }
+            entry + " }"
+        })
+        .to_string()
+}
+
+fn with_trailing_slash(mut dir: String) -> String {
+    if !dir.ends_with('/') {
+        dir.push('/');
+    }
+    dir
+}
+
+/// Crates listed in `BURN_BENCH_PATCH_CRATES`, separated by commas.
+fn patched_crates_from_env() -> Vec<String> {
+    std::env::var("BURN_BENCH_PATCH_CRATES")
+        .map(|crates| parse_patched_crates(&crates))
+        .unwrap_or_default()
+}
+
+fn parse_patched_crates(crates: &str) -> Vec<String> {
+    crates
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter(|name| {
+            let supported = PATCHABLE_CRATES.contains(name);
+            if !supported {
+                tracing::warn!("Ignoring unsupported crate to patch: {name}");
+            }
+            supported
+        })
+        .map(String::from)
+        .collect()
+}
+
+/// Add the entries to the `[patch.crates-io]` section of the manifest, creating it if needed.
+fn insert_patch_section(manifest: &str, entries: &str) -> String {
+    const HEADER: &str = "[patch.crates-io]";
+
+    match manifest.find(HEADER) {
+        Some(index) => {
+            let (before, after) = manifest.split_at(index + HEADER.len());
+            format!("{before}\n{entries}{after}")
+        }
+        None => format!("{}\n\n{HEADER}\n{entries}\n", manifest.trim_end()),
+    }
+}
+
+/// Commit the branch of the Burn repository currently points to, so that the records of a
+/// branch still identify the benchmarked code once the branch has moved.
+pub(crate) fn resolve_branch(branch: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args([
+            "ls-remote",
+            "https://github.com/tracel-ai/burn",
+            &format!("refs/heads/{branch}"),
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .filter(|sha| is_commit_hash(sha))
+        .map(str::to_string)
+}
+
 fn is_commit_hash(reference: &str) -> bool {
     // Check if the reference is a valid commit hash (7 to 40 hexadecimal characters)
     let re = Regex::new(r"^[0-9a-f]{7,40}$").unwrap();
     re.is_match(reference)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockfile_name_only_for_pinned_versions() {
+        assert_eq!(
+            Dependency::new("0.18.0").lockfile_name().as_deref(),
+            Some("burn-0.18.0.lock")
+        );
+        assert_eq!(
+            Dependency::new("a1b2c3d").lockfile_name().as_deref(),
+            Some("burn-a1b2c3d.lock")
+        );
+        assert_eq!(Dependency::new("main").lockfile_name(), None);
+        assert_eq!(Dependency::new("local").lockfile_name(), None);
+    }
+
+    #[test]
+    fn test_source_of_remote_versions() {
+        assert_eq!(
+            Dependency::new("0.18.0").source(),
+            BurnSource::CratesIo {
+                version: "0.18.0".to_string()
+            }
+        );
+        assert_eq!(
+            Dependency::new("main").source(),
+            BurnSource::Git {
+                branch: Some("main".to_string()),
+                rev: None
+            }
+        );
+        assert_eq!(
+            Dependency::new("a1b2c3d").source(),
+            BurnSource::Git {
+                branch: None,
+                rev: Some("a1b2c3d".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_local_version_with_path() {
+        assert!(matches!(
+            Dependency::new("local:/home/me/burn-experiment"),
+            Dependency::Local(dir) if dir == "/home/me/burn-experiment/"
+        ));
+        assert!(matches!(
+            Dependency::new("local:../burn/"),
+            Dependency::Local(dir) if dir == "../burn/"
+        ));
+    }
+
+    #[test]
+    fn test_replace_dependency_keeps_features_and_optional() {
+        let manifest = "burn = { git = \"https://github.com/tracel-ai/burn\", branch = \"main\", default-features = false }\nburn-import = { git = \"https://github.com/tracel-ai/burn\", branch = \"main\", features = [\"onnx\"], optional = true }\n";
+        let manifest = replace_dependency(manifest, "burn", "version = \"=0.18.0\"");
+        let manifest = replace_dependency(&manifest, "burn-import", "version = \"=0.18.0\"");
+        assert_eq!(
+            manifest,
+            "burn = { version = \"=0.18.0\", default-features = false }\nburn-import = { version = \"=0.18.0\", default-features = false, features = [\"onnx\"], optional = true }\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_patched_crates_ignores_unsupported() {
+        assert_eq!(
+            parse_patched_crates("cubecl, cubecl-common,,serde"),
+            vec!["cubecl".to_string(), "cubecl-common".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_patch_section_of_git_version() {
+        let crates = vec!["cubecl".to_string(), "burn-import".to_string()];
+        let section = Dependency::new("a1b2c3d").patch_section(&crates).unwrap();
+
+        assert!(section.contains(
+            "burn-import = { git = \"https://github.com/tracel-ai/burn\", rev = \"a1b2c3d\" }"
+        ));
+        assert!(section.contains("cubecl = { git = \"https://github.com/tracel-ai/cubecl\""));
+        assert_eq!(Dependency::new("0.18.0").patch_section(&crates), None);
+    }
+
+    #[test]
+    fn test_insert_patch_section() {
+        let manifest = "[workspace]\nmembers = []\n";
+        assert_eq!(
+            insert_patch_section(manifest, "cubecl = { path = \"cubecl\" }"),
+            "[workspace]\nmembers = []\n\n[patch.crates-io]\ncubecl = { path = \"cubecl\" }\n"
+        );
+
+        let manifest = "[patch.crates-io]\nfoo = { path = \"foo\" }\n";
+        assert_eq!(
+            insert_patch_section(manifest, "cubecl = { path = \"cubecl\" }"),
+            "[patch.crates-io]\ncubecl = { path = \"cubecl\" }\nfoo = { path = \"foo\" }\n"
+        );
+    }
+}
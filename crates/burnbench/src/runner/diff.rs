@@ -0,0 +1,112 @@
+use std::{path::PathBuf, str::FromStr};
+
+use uuid::Uuid;
+
+use super::reports::BenchmarkCollection;
+use crate::persistence::BenchmarkRecord;
+use crate::server_client::ServerClient;
+
+/// Set of results compared by the diff command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ResultSource {
+    /// Record file, file holding an array of records, or directory of record files.
+    Path(PathBuf),
+    /// Run in the local cache, given by its id.
+    Run(String),
+    /// Run shared to the benchmark server, given by a link containing its id.
+    Server(String),
+}
+
+impl FromStr for ResultSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("http://") || s.starts_with("https://") {
+            // The id is the last UUID of the link, in its path or in its query.
+            return s
+                .split(['/', '?', '&', '=', '#'])
+                .rev()
+                .find(|segment| Uuid::parse_str(segment).is_ok())
+                .map(|run_id| Self::Server(run_id.to_string()))
+                .ok_or_else(|| format!("no run id found in the link '{s}'"));
+        }
+        let path = PathBuf::from(s);
+        if !path.exists() && Uuid::parse_str(s).is_ok() {
+            return Ok(Self::Run(s.to_string()));
+        }
+        Ok(Self::Path(path))
+    }
+}
+
+impl ResultSource {
+    /// Load the records of the source.
+    pub(crate) fn load(&self) -> Result<Vec<BenchmarkRecord>, String> {
+        let records = match self {
+            Self::Path(path) => {
+                if !path.exists() {
+                    return Err(format!("{} not found", path.display()));
+                }
+                BenchmarkCollection::from_records(vec![])
+                    .load_exported_records(std::slice::from_ref(path))
+                    .records()
+                    .to_vec()
+            }
+            Self::Run(run_id) => BenchmarkCollection::from_records(vec![])
+                .load_all_records()
+                .records()
+                .iter()
+                .filter(|record| record.run_id.as_deref() == Some(run_id.as_str()))
+                .cloned()
+                .collect(),
+            Self::Server(run_id) => ServerClient::default()
+                .get_run_records(run_id)
+                .map_err(|e| format!("failed to download the run {run_id} ({e})"))?,
+        };
+        if records.is_empty() {
+            return Err(format!("no benchmark records found in {self}"));
+        }
+        Ok(records)
+    }
+}
+
+impl std::fmt::Display for ResultSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Run(run_id) => write!(f, "the local run {run_id}"),
+            Self::Server(run_id) => write!(f, "the shared run {run_id}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUN_ID: &str = "0b5c8a7e-3f1d-4c2a-9e6b-7d8f9a0b1c2d";
+
+    #[test]
+    fn test_parse_result_source() {
+        assert_eq!(
+            RUN_ID.parse::<ResultSource>(),
+            Ok(ResultSource::Run(RUN_ID.to_string()))
+        );
+        assert_eq!(
+            format!("https://burn.dev/benchmarks/runs/{RUN_ID}").parse::<ResultSource>(),
+            Ok(ResultSource::Server(RUN_ID.to_string()))
+        );
+        assert_eq!(
+            format!("https://burn.dev/benchmarks?runId={RUN_ID}&os=linux").parse::<ResultSource>(),
+            Ok(ResultSource::Server(RUN_ID.to_string()))
+        );
+        assert!(
+            "https://burn.dev/benchmarks"
+                .parse::<ResultSource>()
+                .is_err()
+        );
+        assert_eq!(
+            "results/before.json".parse::<ResultSource>(),
+            Ok(ResultSource::Path(PathBuf::from("results/before.json")))
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+/// Rough size of the artifacts of the benches built for a backend with a Burn version, each
+/// build adding its own copy of Burn and of the backend dependencies to the target directory.
+const BUILD_SIZE_GB: u64 = 3;
+/// Free space kept for the logs, the records and the linker.
+const HEADROOM_GB: u64 = 5;
+const GB: u64 = 1_000_000_000;
+
+/// Space available on the disk of the directory, or of its closest existing ancestor.
+pub(crate) fn available_space(dir: &Path) -> Option<u64> {
+    let path = dir
+        .ancestors()
+        .find_map(|dir| dir.canonicalize().ok())
+        .or_else(|| env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("/"));
+
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Estimated space needed to build the benches with each version for each backend.
+pub(crate) fn required_space(versions: usize, backends: usize) -> u64 {
+    (versions * backends) as u64 * BUILD_SIZE_GB * GB + HEADROOM_GB * GB
+}
+
+/// Check that the disk of the target directory has room for the builds, returning how to make
+/// room otherwise.
+pub(crate) fn check_disk_space(versions: usize, backends: usize) -> Result<(), String> {
+    let target_dir = Path::new(crate::BENCHMARKS_TARGET_DIR);
+    let Some(available) = available_space(target_dir) else {
+        tracing::warn!(
+            "Failed to find the disk of {}, skipping the disk space check",
+            target_dir.display()
+        );
+        return Ok(());
+    };
+    let required = required_space(versions, backends);
+    if available >= required {
+        return Ok(());
+    }
+    Err(format!(
+        "only {} GB available for {}, about {} GB are needed to build {versions} version(s) \
+        for {backends} backend(s). Free some space with `burnbench clean`, run fewer versions \
+        or backends, or rerun with --skip-disk-check.",
+        available / GB,
+        target_dir.display(),
+        required.div_ceil(GB),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_space_per_build() {
+        assert_eq!(required_space(0, 3), HEADROOM_GB * GB);
+        assert_eq!(
+            required_space(2, 3),
+            6 * BUILD_SIZE_GB * GB + HEADROOM_GB * GB
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use std::{cell::OnceCell, env, path::Path, process::Command};
+
+use super::auth::{get_bot_token, get_tokens_from_cache, get_username};
+use super::disk_space::available_space;
+
+/// Free disk space below which the target directory is reported, the benchmarks of each
+/// backend and version being compiled separately.
+const MIN_FREE_SPACE_GB: u64 = 20;
+
+/// Outcome of a diagnostic: details when the check passes, otherwise how to fix it.
+type Diagnostic = Result<String, String>;
+
+/// Check the environment of the backends and of burnbench, and print the checklist.
+pub(crate) fn run_diagnostics() {
+    let mut checks: Vec<(&str, Diagnostic)> = vec![
+        ("CUDA toolkit", check_cuda()),
+        ("libtorch", check_libtorch()),
+    ];
+    #[cfg(not(target_os = "macos"))]
+    checks.push((
+        "Vulkan loader",
+        check_wgpu_adapters(wgpu::Backend::Vulkan.into()),
+    ));
+    #[cfg(target_os = "macos")]
+    checks.push(("Metal", check_wgpu_adapters(wgpu::Backend::Metal.into())));
+    #[cfg(target_os = "linux")]
+    checks.push(("ROCm", check_rocm()));
+    checks.extend([
+        ("Nsight Compute (ncu)", check_ncu()),
+        ("Disk space", check_disk_space()),
+        ("Authentication", check_auth()),
+    ]);
+
+    println!("🩺 Burnbench environment diagnostics\n");
+    for (name, diagnostic) in &checks {
+        match diagnostic {
+            Ok(details) => println!("✅ {name}: {details}"),
+            Err(hint) => println!("❌ {name}: {hint}"),
+        }
+    }
+
+    let failures = checks.iter().filter(|(_, d)| d.is_err()).count();
+    if failures == 0 {
+        println!("\nEverything looks good!");
+    } else {
+        println!(
+            "\n{failures} check(s) failed, the backends relying on them won't run until they are fixed."
+        );
+    }
+}
+
+/// Availability of the platforms required by the backends, each one being probed at most once.
+#[derive(Default)]
+pub(crate) struct Platforms {
+    cuda: OnceCell<Diagnostic>,
+    libtorch: OnceCell<Diagnostic>,
+    #[cfg(target_os = "linux")]
+    rocm: OnceCell<Diagnostic>,
+    vulkan: OnceCell<Diagnostic>,
+    metal: OnceCell<Diagnostic>,
+    wgpu: OnceCell<Diagnostic>,
+}
+
+impl Platforms {
+    pub(crate) fn cuda(&self) -> Result<(), String> {
+        available(self.cuda.get_or_init(check_cuda_driver))
+    }
+
+    pub(crate) fn libtorch(&self) -> Result<(), String> {
+        available(self.libtorch.get_or_init(check_libtorch))
+    }
+
+    #[cfg(target_os = "linux")]
+    pub(crate) fn rocm(&self) -> Result<(), String> {
+        available(self.rocm.get_or_init(check_rocm))
+    }
+
+    pub(crate) fn vulkan(&self) -> Result<(), String> {
+        available(
+            self.vulkan
+                .get_or_init(|| check_wgpu_adapters(wgpu::Backend::Vulkan.into())),
+        )
+    }
+
+    pub(crate) fn metal(&self) -> Result<(), String> {
+        available(
+            self.metal
+                .get_or_init(|| check_wgpu_adapters(wgpu::Backend::Metal.into())),
+        )
+    }
+
+    /// Any adapter usable by wgpu.
+    pub(crate) fn wgpu(&self) -> Result<(), String> {
+        available(
+            self.wgpu
+                .get_or_init(|| check_wgpu_adapters(wgpu::Backends::all())),
+        )
+    }
+}
+
+fn available(diagnostic: &Diagnostic) -> Result<(), String> {
+    diagnostic.as_ref().map(|_| ()).map_err(Clone::clone)
+}
+
+/// First line of the standard output of the command, if it ran successfully.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(stdout.lines().next().unwrap_or_default().trim().to_string())
+}
+
+/// Check that the standard library of the target is installed to cross-compile the benches.
+pub(crate) fn check_rust_target(target: &str) -> Result<(), String> {
+    let installed = Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default();
+    if installed
+        .lines()
+        .any(|installed| installed.trim() == target)
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "the {target} target is not installed, run `rustup target add {target}`"
+        ))
+    }
+}
+
+fn check_cuda() -> Diagnostic {
+    let nvcc = Command::new("nvcc")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .ok_or("nvcc not found, install the CUDA toolkit and add its `bin` directory to PATH")?;
+    let version = String::from_utf8_lossy(&nvcc.stdout)
+        .lines()
+        .find(|line| line.contains("release"))
+        .map(|line| line.trim().to_string())
+        .unwrap_or_else(|| "nvcc found".to_string());
+
+    match command_output(
+        "nvidia-smi",
+        &["--query-gpu=driver_version", "--format=csv,noheader"],
+    ) {
+        Some(driver) => Ok(format!("{version} (driver {driver})")),
+        None => Err(format!(
+            "{version} but nvidia-smi failed, check that the NVIDIA driver is installed"
+        )),
+    }
+}
+
+fn check_cuda_driver() -> Diagnostic {
+    command_output("nvidia-smi", &["--query-gpu=name", "--format=csv,noheader"])
+        .ok_or_else(|| "no NVIDIA GPU found, nvidia-smi failed".to_string())
+}
+
+fn check_libtorch() -> Diagnostic {
+    let libtorch = env::var("LIBTORCH")
+        .map_err(|_| "LIBTORCH is not set, it is required by the tch backends".to_string())?;
+    let lib_dir = Path::new(&libtorch).join("lib");
+    if !lib_dir.is_dir() {
+        return Err(format!(
+            "LIBTORCH points to {libtorch} which has no `lib` directory"
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let ld_library_path = env::var("LD_LIBRARY_PATH").unwrap_or_default();
+        if !env::split_paths(&ld_library_path).any(|path| path == lib_dir) {
+            return Err(format!(
+                "LD_LIBRARY_PATH does not contain {}",
+                lib_dir.display()
+            ));
+        }
+    }
+
+    Ok(libtorch)
+}
+
+fn check_wgpu_adapters(backends: wgpu::Backends) -> Diagnostic {
+    let instance = wgpu::Instance::default();
+    let adapters = futures_lite::future::block_on(instance.enumerate_adapters(backends));
+    let names: Vec<String> = adapters
+        .iter()
+        .map(|adapter| adapter.get_info().name)
+        .collect();
+
+    if names.is_empty() {
+        Err(format!(
+            "no {backends:?} adapter found, check the installation of the graphics drivers"
+        ))
+    } else {
+        Ok(names.join(", "))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn check_rocm() -> Diagnostic {
+    let rocm_path = env::var("ROCM_PATH").unwrap_or("/opt/rocm".into());
+    if !Path::new(&rocm_path).is_dir() {
+        return Err(format!(
+            "{rocm_path} not found, install ROCm or set ROCM_PATH"
+        ));
+    }
+    command_output("rocminfo", &[])
+        .map(|_| rocm_path)
+        .ok_or_else(|| {
+            "rocminfo failed, check that the ROCm driver is loaded and that the user belongs to the `render` and `video` groups".to_string()
+        })
+}
+
+fn check_ncu() -> Diagnostic {
+    command_output("ncu", &["--version"])
+        .ok_or_else(|| "ncu not found, it is only required to profile with --profile".to_string())
+}
+
+fn check_disk_space() -> Diagnostic {
+    let target_dir = Path::new(crate::BENCHMARKS_TARGET_DIR);
+    let available = available_space(target_dir)
+        .ok_or_else(|| format!("no disk found for {}", target_dir.display()))?;
+
+    let available_gb = available / 1_000_000_000;
+    if available_gb < MIN_FREE_SPACE_GB {
+        Err(format!(
+            "only {available_gb} GB available for {}, at least {MIN_FREE_SPACE_GB} GB is recommended",
+            target_dir.display()
+        ))
+    } else {
+        Ok(format!(
+            "{available_gb} GB available for {}",
+            target_dir.display()
+        ))
+    }
+}
+
+fn check_auth() -> Diagnostic {
+    // Don't start the authentication flow, only check the existing token.
+    let access_token = get_bot_token()
+        .or_else(|| get_tokens_from_cache().map(|tokens| tokens.access_token))
+        .ok_or_else(|| {
+            "not authenticated, run `burnbench auth` to share the results".to_string()
+        })?;
+    get_username(&access_token)
+        .map(|user| format!("authenticated as {}", user.nickname))
+        .map_err(|e| format!("invalid access token ({e}), run `burnbench auth` again"))
+}
@@ -0,0 +1,262 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use crate::BenchmarkRecord;
+
+use super::processor::OutputProcessor;
+use super::state::Combination;
+
+/// Destination of the event stream, a file path or the number of an open file descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum EventTarget {
+    File(PathBuf),
+    Fd(i32),
+}
+
+impl FromStr for EventTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("missing event stream destination".to_string());
+        }
+        Ok(match s.parse::<i32>() {
+            Ok(fd) if fd >= 0 => Self::Fd(fd),
+            _ => Self::File(PathBuf::from(s)),
+        })
+    }
+}
+
+/// Event of a run, written as one JSON object per line.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum Event<'a> {
+    RunStarted {
+        run_id: &'a str,
+        combinations: usize,
+    },
+    CombinationStarted {
+        #[serde(flatten)]
+        combination: &'a Combination,
+    },
+    CombinationFinished {
+        #[serde(flatten)]
+        combination: &'a Combination,
+        status: CombinationOutcome,
+    },
+    SampleRecorded {
+        #[serde(flatten)]
+        combination: &'a Combination,
+        name: &'a str,
+        num_samples: usize,
+        median_us: u128,
+    },
+    UploadComplete {
+        #[serde(flatten)]
+        combination: &'a Combination,
+        success: bool,
+    },
+    RunFinished {
+        interrupted: bool,
+        failed: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CombinationOutcome {
+    Succeeded,
+    Failed,
+    Cached,
+    Skipped,
+}
+
+#[derive(Serialize)]
+struct TimestampedEvent<'a> {
+    timestamp: String,
+    #[serde(flatten)]
+    event: &'a Event<'a>,
+}
+
+/// NDJSON stream of the events of a run, for external tools to follow its progress.
+#[derive(Clone)]
+pub(crate) struct EventStream {
+    writer: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl EventStream {
+    pub(crate) fn open(target: &EventTarget) -> io::Result<Self> {
+        let file = match target {
+            EventTarget::File(path) => File::create(path)?,
+            EventTarget::Fd(fd) => from_fd(*fd)?,
+        };
+        Ok(Self {
+            writer: Arc::new(Mutex::new(BufWriter::new(file))),
+        })
+    }
+
+    /// Write the event and flush it so that readers get it right away.
+    pub(crate) fn emit(&self, event: &Event) {
+        let event = TimestampedEvent {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            event,
+        };
+        let mut writer = self.writer.lock().unwrap();
+        let result = serde_json::to_writer(&mut *writer, &event)
+            .map_err(io::Error::from)
+            .and_then(|_| writeln!(writer))
+            .and_then(|_| writer.flush());
+        if let Err(e) = result {
+            tracing::warn!("Failed to write the event ({e})");
+        }
+    }
+
+    /// Emit a `sample_recorded` event for each of the record files of the combination.
+    pub(crate) fn emit_records(&self, combination: &Combination, files: &[PathBuf]) {
+        for record in files.iter().filter_map(|file| read_record(file)) {
+            self.emit(&Event::SampleRecorded {
+                combination,
+                name: &record.results.name,
+                num_samples: record.results.raw.durations.len(),
+                median_us: record.results.computed.median.as_micros(),
+            });
+        }
+    }
+}
+
+fn read_record(path: &Path) -> Option<BenchmarkRecord> {
+    let file = File::open(path).ok()?;
+    serde_json::from_reader(file).ok()
+}
+
+#[cfg(unix)]
+fn from_fd(fd: i32) -> io::Result<File> {
+    use std::os::fd::FromRawFd;
+
+    // SAFETY: the descriptor is opened by the parent process for burnbench to write the events,
+    // and is owned by this file from now on.
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+#[cfg(not(unix))]
+fn from_fd(_fd: i32) -> io::Result<File> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "file descriptors are only supported on unix",
+    ))
+}
+
+/// Wrap the processor to emit an `upload_complete` event when the benchmarks share their results.
+pub(crate) fn with_events(
+    processor: Arc<dyn OutputProcessor>,
+    events: Option<&EventStream>,
+    combination: &Combination,
+) -> Arc<dyn OutputProcessor> {
+    match events {
+        Some(events) => Arc::new(EventProcessor {
+            inner: processor,
+            events: events.clone(),
+            combination: combination.clone(),
+        }),
+        None => processor,
+    }
+}
+
+struct EventProcessor {
+    inner: Arc<dyn OutputProcessor>,
+    events: EventStream,
+    combination: Combination,
+}
+
+impl OutputProcessor for EventProcessor {
+    fn process_line(&self, line: &str) {
+        let success = if line.contains("Results shared successfully") {
+            Some(true)
+        } else if line.contains("Failed to share results") {
+            Some(false)
+        } else {
+            None
+        };
+        if let Some(success) = success {
+            self.events.emit(&Event::UploadComplete {
+                combination: &self.combination,
+                success,
+            });
+        }
+        self.inner.process_line(line);
+    }
+
+    fn progress(&self) {
+        self.inner.progress();
+    }
+
+    fn finish(&self) {
+        self.inner.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event_target() {
+        assert_eq!("3".parse::<EventTarget>(), Ok(EventTarget::Fd(3)));
+        assert_eq!(
+            "events.ndjson".parse::<EventTarget>(),
+            Ok(EventTarget::File(PathBuf::from("events.ndjson")))
+        );
+        assert!("".parse::<EventTarget>().is_err());
+    }
+
+    #[test]
+    fn test_serialize_event() {
+        let combination = Combination {
+            bench: "matmul".to_string(),
+            backend: "cuda".to_string(),
+            version: "main".to_string(),
+            dtype: "f32".to_string(),
+            threads: None,
+        };
+        let event = Event::CombinationFinished {
+            combination: &combination,
+            status: CombinationOutcome::Succeeded,
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"combination_finished","bench":"matmul","backend":"cuda","version":"main","dtype":"f32","status":"succeeded"}"#
+        );
+    }
+
+    #[test]
+    fn test_emit_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.ndjson");
+        let events = EventStream::open(&EventTarget::File(path.clone())).unwrap();
+        events.emit(&Event::RunStarted {
+            run_id: "42",
+            combinations: 2,
+        });
+        events.emit(&Event::RunFinished {
+            interrupted: false,
+            failed: 0,
+        });
+        let content = std::fs::read_to_string(path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "run_started");
+        assert_eq!(lines[0]["combinations"], 2);
+        assert!(lines[0]["timestamp"].is_string());
+        assert_eq!(lines[1]["event"], "run_finished");
+    }
+}
@@ -0,0 +1,162 @@
+use regex::{NoExpand, Regex};
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Mapping of the features and dependencies of the benchmarks crate per Burn version range.
+static FEATURE_MAPPING: &str = include_str!("feature_mapping.toml");
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FeatureMapping {
+    versions: Vec<VersionMapping>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct VersionMapping {
+    req: String,
+    #[serde(default)]
+    legacy_feature: Option<String>,
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+}
+
+impl FeatureMapping {
+    /// The mapping embedded in burnbench.
+    pub(crate) fn embedded() -> Self {
+        toml::from_str(FEATURE_MAPPING).expect("The feature mapping should be valid")
+    }
+
+    fn matching(&self, version: &Version) -> impl Iterator<Item = &VersionMapping> {
+        self.versions.iter().filter(move |mapping| {
+            VersionReq::parse(&mapping.req)
+                .expect("The version requirement should be valid")
+                .matches(version)
+        })
+    }
+
+    /// Legacy feature of the benchmarks crate to enable for the version.
+    pub(crate) fn legacy_feature(&self, version: &Version) -> Option<&str> {
+        self.matching(version)
+            .filter_map(|mapping| mapping.legacy_feature.as_deref())
+            .last()
+    }
+
+    /// Adjust the manifest of the benchmarks crate to the version.
+    pub(crate) fn apply(&self, version: &Version, manifest: &str) -> String {
+        let mut features = BTreeMap::new();
+        let mut dependencies = BTreeMap::new();
+        for mapping in self.matching(version) {
+            features.extend(mapping.features.iter());
+            dependencies.extend(mapping.dependencies.iter());
+        }
+
+        let mut manifest = manifest.to_string();
+        for (feature, burn_features) in features {
+            let list = burn_features
+                .iter()
+                .map(|f| format!("\"{f}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            manifest = replace_entry(&manifest, feature, &format!("[{list}]")).unwrap_or(manifest);
+        }
+        for (dependency, value) in dependencies {
+            manifest = match replace_entry(&manifest, dependency, value) {
+                Some(manifest) => manifest,
+                None => manifest.replacen(
+                    "[dependencies]",
+                    &format!("[dependencies]\n{dependency} = {value}"),
+                    1,
+                ),
+            };
+        }
+
+        manifest
+    }
+}
+
+/// Replace the value of the single line `key = value` entry of the manifest, if any.
+fn replace_entry(manifest: &str, key: &str, value: &str) -> Option<String> {
+    let re = Regex::new(&format!(r"(?m)^{} *= *[^\n]*$", regex::escape(key))).unwrap();
+    re.is_match(manifest).then(|| {
+        re.replace(manifest, NoExpand(&format!("{key} = {value}")))
+            .to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[features]
+cuda = ["burn/cuda", "burn/autotune"]
+cuda-fusion = ["cuda", "burn/fusion"]
+ndarray-simd = ["ndarray", "burn/simd"]
+rocm = ["burn/rocm", "burn/autotune"]
+
+[dependencies]
+burn = { version = "=0.18.0", default-features = false }
+rand = { version = "0.10.0" }                                # pull the same version as burn
+"#;
+
+    fn version(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn test_embedded_mapping_is_valid() {
+        let mapping = FeatureMapping::embedded();
+        for entry in &mapping.versions {
+            assert!(VersionReq::parse(&entry.req).is_ok(), "{}", entry.req);
+        }
+    }
+
+    #[test]
+    fn test_legacy_feature_per_version() {
+        let mapping = FeatureMapping::embedded();
+        assert_eq!(
+            mapping.legacy_feature(&version("0.16.0")),
+            Some("legacy-v16")
+        );
+        assert_eq!(
+            mapping.legacy_feature(&version("0.16.1")),
+            Some("legacy-v16")
+        );
+        assert_eq!(
+            mapping.legacy_feature(&version("0.17.1")),
+            Some("legacy-v17")
+        );
+        assert_eq!(mapping.legacy_feature(&version("0.18.0")), None);
+    }
+
+    #[test]
+    fn test_manifest_of_current_version_is_untouched() {
+        let mapping = FeatureMapping::embedded();
+        assert_eq!(mapping.apply(&version("0.18.0"), MANIFEST), MANIFEST);
+        assert_eq!(mapping.apply(&version("0.17.1"), MANIFEST), MANIFEST);
+    }
+
+    #[test]
+    fn test_manifest_of_0_16_1() {
+        let manifest = FeatureMapping::embedded().apply(&version("0.16.1"), MANIFEST);
+
+        assert!(manifest.contains("cuda = [\"burn/cuda-jit\", \"burn/autotune\"]\n"));
+        assert!(manifest.contains("cuda-fusion = [\"cuda\", \"burn/fusion\"]\n"));
+        assert!(manifest.contains("ndarray-simd = [\"ndarray\"]\n"));
+        assert!(manifest.contains("rocm = [\"burn/hip-jit\", \"burn/autotune\"]\n"));
+        assert!(manifest.contains("rand = { version = \"0.8.5\" }\n"));
+        assert!(!manifest.contains("bincode"));
+    }
+
+    #[test]
+    fn test_manifest_of_0_16_0() {
+        let manifest = FeatureMapping::embedded().apply(&version("0.16.0"), MANIFEST);
+
+        assert!(manifest.contains("cuda = [\"burn/cuda-jit\", \"burn/autotune\"]\n"));
+        assert!(manifest.contains("rand = { version = \"0.8.5\" }\n"));
+        assert!(manifest.contains("\nbincode = \"=2.0.0-rc.3\"\n"));
+        assert!(manifest.contains("bincode_derive = \"=2.0.0-rc.3\"\n"));
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Directory of the folded stacks and of the differential flamegraphs of the profiled benches.
+const FLAMEGRAPHS_DIR: &str = "target/flamegraphs";
+/// Sampling frequency of `perf record`, off the round values to avoid sampling in lockstep with
+/// periodic work.
+const SAMPLING_FREQUENCY: &str = "999";
+/// Renderers of the differential flamegraphs, tried in order.
+const RENDERERS: [&str; 2] = ["inferno-flamegraph", "flamegraph.pl"];
+
+/// Folded stacks of the bench run on the backend with the Burn version.
+pub(crate) fn folded_path(bench: &str, backend: &str, dtype: &str, version: &str) -> PathBuf {
+    Path::new(FLAMEGRAPHS_DIR).join(format!(
+        "{bench}-{backend}-{dtype}-{}.folded",
+        sanitize(version)
+    ))
+}
+
+/// Sample the CPU stacks of the bench executable with `perf` and write them folded, one stack
+/// per line from the root to the leaf followed by its number of samples.
+pub(crate) fn capture_stacks(
+    bin: &str,
+    envs: &[(String, String)],
+    output: &Path,
+) -> io::Result<()> {
+    if let Some(dir) = output.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let data = output.with_extension("perf.data");
+    let status = Command::new("perf")
+        .args(["record", "-F", SAMPLING_FREQUENCY, "-g", "-o"])
+        .arg(&data)
+        .arg("--")
+        .arg(bin)
+        .envs(envs.iter().map(|(k, v)| (k, v)))
+        .stdout(Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("perf record failed ({status})")));
+    }
+    let script = Command::new("perf")
+        .arg("script")
+        .arg("-i")
+        .arg(&data)
+        .output()?;
+    if !script.status.success() {
+        return Err(io::Error::other(format!(
+            "perf script failed ({})",
+            script.status
+        )));
+    }
+    let stacks = fold_perf_script(&String::from_utf8_lossy(&script.stdout));
+    fs::write(output, format_folded(&stacks))?;
+    fs::remove_file(data)
+}
+
+/// Write the differential flamegraphs of each bench between the first version and each of the
+/// others, for the benches whose stacks were captured with both versions.
+///
+/// The flamegraphs are rendered to SVG with `inferno-flamegraph` or `flamegraph.pl` when one of
+/// them is installed, otherwise the differential folded stacks are left for them.
+pub(crate) fn write_version_diffs(
+    benches: &[String],
+    backends: &[String],
+    dtypes: &[String],
+    versions: &[String],
+) -> Vec<PathBuf> {
+    let Some((base_version, new_versions)) = versions.split_first() else {
+        return vec![];
+    };
+    let mut written = vec![];
+    for bench in benches {
+        for backend in backends {
+            for dtype in dtypes {
+                let base_path = folded_path(bench, backend, dtype, base_version);
+                let Ok(base) = fs::read_to_string(&base_path) else {
+                    continue;
+                };
+                for version in new_versions {
+                    let Ok(new) = fs::read_to_string(folded_path(bench, backend, dtype, version))
+                    else {
+                        continue;
+                    };
+                    let name = format!(
+                        "{bench}-{backend}-{dtype}-{}-vs-{}",
+                        sanitize(base_version),
+                        sanitize(version)
+                    );
+                    let diff_path = Path::new(FLAMEGRAPHS_DIR).join(format!("{name}.diff.folded"));
+                    let diff = diff_folded(&parse_folded(&base), &parse_folded(&new));
+                    if let Err(e) = fs::write(&diff_path, diff) {
+                        tracing::warn!("Failed to write {} ({e})", diff_path.display());
+                        continue;
+                    }
+                    let svg_path = Path::new(FLAMEGRAPHS_DIR).join(format!("{name}.svg"));
+                    match render(&diff_path, &svg_path) {
+                        Ok(true) => written.push(svg_path),
+                        Ok(false) => {
+                            tracing::warn!(
+                                "Install inferno or FlameGraph to render the differential flamegraphs"
+                            );
+                            written.push(diff_path);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to render {} ({e})", svg_path.display());
+                            written.push(diff_path);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    written
+}
+
+/// Fold the samples printed by `perf script`, whose stacks are listed from the leaf to the root
+/// under the header of each sample.
+fn fold_perf_script(script: &str) -> BTreeMap<String, u64> {
+    let mut stacks = BTreeMap::new();
+    for sample in script.split("\n\n") {
+        let mut lines = sample.lines().filter(|line| !line.trim().is_empty());
+        let Some(header) = lines.next() else {
+            continue;
+        };
+        let command = header.split_whitespace().next().unwrap_or("[unknown]");
+        let frames: Vec<&str> = lines.map(frame_symbol).collect();
+        let stack = std::iter::once(command)
+            .chain(frames.into_iter().rev())
+            .collect::<Vec<_>>()
+            .join(";");
+        *stacks.entry(stack).or_insert(0) += 1;
+    }
+    stacks
+}
+
+/// Symbol of a frame such as `7f3a2b1c main+0x1a (/usr/bin/bench)`, without its offset.
+fn frame_symbol(line: &str) -> &str {
+    let frame = line.trim();
+    let symbol = frame.split_once(' ').map_or(frame, |(_, symbol)| symbol);
+    let symbol = symbol
+        .rsplit_once(" (")
+        .map_or(symbol, |(symbol, _)| symbol);
+    symbol
+        .rsplit_once("+0x")
+        .map_or(symbol, |(symbol, _)| symbol)
+}
+
+fn format_folded(stacks: &BTreeMap<String, u64>) -> String {
+    stacks
+        .iter()
+        .map(|(stack, count)| format!("{stack} {count}\n"))
+        .collect()
+}
+
+fn parse_folded(content: &str) -> BTreeMap<String, u64> {
+    content
+        .lines()
+        .filter_map(|line| line.rsplit_once(' '))
+        .filter_map(|(stack, count)| Some((stack.to_string(), count.parse().ok()?)))
+        .collect()
+}
+
+/// Differential folded stacks, each stack followed by its samples with both versions.
+///
+/// The samples of the base are scaled to the total of the new version so that the flamegraph
+/// shows where the time went rather than how long each version was sampled.
+fn diff_folded(base: &BTreeMap<String, u64>, new: &BTreeMap<String, u64>) -> String {
+    let base_total: u64 = base.values().sum();
+    let new_total: u64 = new.values().sum();
+    let scale = if base_total == 0 {
+        1.0
+    } else {
+        new_total as f64 / base_total as f64
+    };
+    let mut stacks: Vec<&String> = base.keys().chain(new.keys()).collect();
+    stacks.sort();
+    stacks.dedup();
+    stacks
+        .into_iter()
+        .map(|stack| {
+            let base_count = base.get(stack).map_or(0.0, |&c| c as f64 * scale).round();
+            let new_count = new.get(stack).copied().unwrap_or(0);
+            format!("{stack} {base_count} {new_count}\n")
+        })
+        .collect()
+}
+
+/// Render the flamegraph with the first renderer installed, returning whether one was found.
+fn render(folded: &Path, svg: &Path) -> io::Result<bool> {
+    for renderer in RENDERERS {
+        match Command::new(renderer)
+            .arg(folded)
+            .stderr(Stdio::null())
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                fs::write(svg, output.stdout)?;
+                return Ok(true);
+            }
+            Ok(output) => {
+                return Err(io::Error::other(format!(
+                    "{renderer} failed ({})",
+                    output.status
+                )));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(false)
+}
+
+fn sanitize(version: &str) -> String {
+    version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_perf_script() {
+        let script = "\
+matmul  4242 1234.000001:     1001001 cpu-clock:
+\t    55d1a2 burn::matmul+0x1a (/target/matmul)
+\t    55d0f0 main+0x10 (/target/matmul)
+
+matmul  4242 1234.001002:     1001001 cpu-clock:
+\t    55d1a2 burn::matmul+0x2b (/target/matmul)
+\t    55d0f0 main+0x10 (/target/matmul)
+
+matmul  4242 1234.002003:     1001001 cpu-clock:
+\t    55d0f0 main+0x12 (/target/matmul)
+";
+
+        let stacks = fold_perf_script(script);
+
+        assert_eq!(
+            format_folded(&stacks),
+            "matmul;main 1\nmatmul;main;burn::matmul 2\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_folded_scales_the_base() {
+        let base = parse_folded("bench;main 10\nbench;main;matmul 10\n");
+        let new = parse_folded("bench;main 10\nbench;main;matmul 20\nbench;main;fusion 10\n");
+
+        assert_eq!(
+            diff_folded(&base, &new),
+            "bench;main 20 10\nbench;main;fusion 0 10\nbench;main;matmul 20 20\n"
+        );
+    }
+}
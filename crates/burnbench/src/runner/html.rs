@@ -0,0 +1,362 @@
+use std::{collections::BTreeMap, fmt::Write as _, fs, io, path::Path};
+
+use crate::persistence::BenchmarkRecord;
+
+use super::reports::{BenchmarkCollection, ShapeFmt, escape_markup as escape, run_label as label};
+
+const PALETTE: [&str; 8] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#9c755f",
+];
+const CHART_WIDTH: f64 = 860.0;
+const LABEL_WIDTH: f64 = 260.0;
+const ROW_HEIGHT: f64 = 30.0;
+const KDE_STEPS: usize = 64;
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.6em; }
+h2 { font-size: 1.2em; margin-top: 2em; }
+table { border-collapse: collapse; font-size: 0.9em; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; }
+th { background: #f0f0f0; cursor: pointer; user-select: none; }
+th[data-order="asc"]::after { content: " ▲"; }
+th[data-order="desc"]::after { content: " ▼"; }
+td.num { text-align: right; font-family: monospace; }
+svg text { font-size: 12px; font-family: sans-serif; }
+"#;
+
+const SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable th").forEach((th, index) => {
+  th.addEventListener("click", () => {
+    const table = th.closest("table");
+    const body = table.tBodies[0];
+    const ascending = th.dataset.order !== "asc";
+    table.querySelectorAll("th").forEach((other) => delete other.dataset.order);
+    th.dataset.order = ascending ? "asc" : "desc";
+    const key = (row) => {
+      const cell = row.cells[index];
+      return cell.dataset.value !== undefined ? parseFloat(cell.dataset.value) : cell.textContent;
+    };
+    Array.from(body.rows)
+      .sort((a, b) => {
+        const x = key(a);
+        const y = key(b);
+        const order = typeof x === "number" ? x - y : x.localeCompare(y);
+        return ascending ? order : -order;
+      })
+      .forEach((row) => body.appendChild(row));
+  });
+});
+"#;
+
+/// Write a standalone HTML report of the collection records.
+///
+/// The report contains a sortable table with every record, and for each benchmark a bar
+/// chart of the medians and a violin plot of the raw samples per backend.
+pub(crate) fn write_html_report(collection: &BenchmarkCollection, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, html_report(collection.records()))
+}
+
+fn html_report(records: &[BenchmarkRecord]) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Burn Benchmarks Report</title>\n");
+    writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>").unwrap();
+    writeln!(
+        html,
+        "<h1>Burn Benchmarks Report</h1>\n<p>{} records, generated on {}.</p>",
+        records.len(),
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+    )
+    .unwrap();
+
+    html.push_str(&records_table(records));
+
+    // Group the records by benchmark name and shapes
+    let mut groups: BTreeMap<(String, String), Vec<&BenchmarkRecord>> = BTreeMap::new();
+    for record in records {
+        let shapes = ShapeFmt::new(&record.results.shapes).to_string();
+        groups
+            .entry((record.results.name.clone(), shapes))
+            .or_default()
+            .push(record);
+    }
+
+    for ((name, shapes), mut group) in groups {
+        group.sort_by(|a, b| label(a).cmp(&label(b)));
+        writeln!(
+            html,
+            "<section>\n<h2>{} <small>{}</small></h2>",
+            escape(&name),
+            escape(&shapes)
+        )
+        .unwrap();
+        html.push_str("<h3>Median</h3>\n");
+        html.push_str(&median_chart(&group));
+        html.push_str("<h3>Raw samples</h3>\n");
+        html.push_str(&violin_chart(&group));
+        html.push_str("</section>\n");
+    }
+
+    writeln!(html, "<script>{SCRIPT}</script>\n</body>\n</html>").unwrap();
+    html
+}
+
+fn records_table(records: &[BenchmarkRecord]) -> String {
+    let mut table = String::from("<table class=\"sortable\">\n<thead>\n<tr>");
+    for header in [
+        "Benchmark",
+        "Burn Version",
+        "Tag",
+        "Note",
+        "Shapes",
+        "DType",
+        "Feature",
+        "Backend",
+        "Device",
+        "Median",
+        "Mean",
+        "Min",
+        "Max",
+        "Samples",
+    ] {
+        write!(table, "<th>{header}</th>").unwrap();
+    }
+    table.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for record in records {
+        let computed = &record.results.computed;
+        table.push_str("<tr>");
+        let shapes = ShapeFmt::new(&record.results.shapes).to_string();
+        let texts: [&str; 9] = [
+            &record.results.name,
+            &record.burn_version,
+            record.tag.as_deref().unwrap_or("-"),
+            record.note.as_deref().unwrap_or("-"),
+            &shapes,
+            record.dtype.as_deref().unwrap_or("-"),
+            &record.feature,
+            &record.backend,
+            &record.device,
+        ];
+        for text in texts {
+            write!(table, "<td>{}</td>", escape(text)).unwrap();
+        }
+        for duration in [computed.median, computed.mean, computed.min, computed.max] {
+            write!(
+                table,
+                "<td class=\"num\" data-value=\"{}\">{:.3?}</td>",
+                duration.as_secs_f64() * 1e6,
+                duration
+            )
+            .unwrap();
+        }
+        let samples = record.results.raw.durations.len();
+        write!(
+            table,
+            "<td class=\"num\" data-value=\"{samples}\">{samples}</td>"
+        )
+        .unwrap();
+        table.push_str("</tr>\n");
+    }
+
+    table.push_str("</tbody>\n</table>\n");
+    table
+}
+
+/// Horizontal bar chart of the median duration of each record.
+fn median_chart(records: &[&BenchmarkRecord]) -> String {
+    let max = records
+        .iter()
+        .map(|r| r.results.computed.median.as_secs_f64())
+        .fold(0.0, f64::max);
+    let bar_span = CHART_WIDTH - LABEL_WIDTH - 100.0;
+    let height = ROW_HEIGHT * records.len() as f64 + 10.0;
+
+    let mut svg = format!(
+        "<svg viewBox=\"0 0 {CHART_WIDTH} {height}\" width=\"{CHART_WIDTH}\" height=\"{height}\">\n"
+    );
+    for (i, record) in records.iter().enumerate() {
+        let median = record.results.computed.median;
+        let top = i as f64 * ROW_HEIGHT + 5.0;
+        let baseline = top + ROW_HEIGHT / 2.0 + 4.0;
+        let width = if max > 0.0 {
+            median.as_secs_f64() / max * bar_span
+        } else {
+            0.0
+        };
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{baseline:.1}\" text-anchor=\"end\">{}</text>",
+            LABEL_WIDTH - 8.0,
+            escape(&label(record)),
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<rect x=\"{LABEL_WIDTH}\" y=\"{:.1}\" width=\"{width:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{median:.3?}</title></rect>",
+            top + 4.0,
+            ROW_HEIGHT - 8.0,
+            PALETTE[i % PALETTE.len()],
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{baseline:.1}\">{median:.3?}</text>",
+            LABEL_WIDTH + width + 6.0,
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Violin plot of the raw samples of each record, sharing the same horizontal axis.
+fn violin_chart(records: &[&BenchmarkRecord]) -> String {
+    let samples: Vec<Vec<f64>> = records
+        .iter()
+        .map(|r| {
+            r.results
+                .raw
+                .durations
+                .iter()
+                .map(|d| d.as_secs_f64() * 1e6)
+                .collect()
+        })
+        .collect();
+    let (lo, hi) = samples
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+    if !lo.is_finite() || !hi.is_finite() {
+        return String::from("<p>No raw samples available.</p>\n");
+    }
+
+    let span = (hi - lo).max(f64::EPSILON);
+    let plot_width = CHART_WIDTH - LABEL_WIDTH - 20.0;
+    let x = |v: f64| LABEL_WIDTH + (v - lo) / span * plot_width;
+    let height = ROW_HEIGHT * records.len() as f64 + 30.0;
+
+    let mut svg = format!(
+        "<svg viewBox=\"0 0 {CHART_WIDTH} {height}\" width=\"{CHART_WIDTH}\" height=\"{height}\">\n"
+    );
+    for (i, (record, values)) in records.iter().zip(samples.iter()).enumerate() {
+        let center = i as f64 * ROW_HEIGHT + ROW_HEIGHT / 2.0 + 5.0;
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+            LABEL_WIDTH - 8.0,
+            center + 4.0,
+            escape(&label(record)),
+        )
+        .unwrap();
+        if values.is_empty() {
+            continue;
+        }
+
+        let density = kernel_density(values, KDE_STEPS);
+        let peak = density.iter().map(|(_, d)| *d).fold(0.0, f64::max);
+        let scale = if peak > 0.0 {
+            (ROW_HEIGHT / 2.0 - 3.0) / peak
+        } else {
+            0.0
+        };
+        let points: Vec<String> = density
+            .iter()
+            .map(|(v, d)| format!("{:.1},{:.1}", x(*v), center - d * scale))
+            .chain(
+                density
+                    .iter()
+                    .rev()
+                    .map(|(v, d)| format!("{:.1},{:.1}", x(*v), center + d * scale)),
+            )
+            .collect();
+        let color = PALETTE[i % PALETTE.len()];
+        writeln!(
+            svg,
+            "<polygon points=\"{}\" fill=\"{color}\" fill-opacity=\"0.6\" stroke=\"{color}\"/>",
+            points.join(" "),
+        )
+        .unwrap();
+
+        let median = x(record.results.computed.median.as_secs_f64() * 1e6);
+        writeln!(
+            svg,
+            "<line x1=\"{median:.1}\" x2=\"{median:.1}\" y1=\"{:.1}\" y2=\"{:.1}\" stroke=\"#222\" stroke-width=\"2\"/>",
+            center - ROW_HEIGHT / 2.0 + 4.0,
+            center + ROW_HEIGHT / 2.0 - 4.0,
+        )
+        .unwrap();
+    }
+
+    let axis = height - 12.0;
+    writeln!(
+        svg,
+        "<text x=\"{LABEL_WIDTH}\" y=\"{axis:.1}\">{lo:.1}µs</text>\n<text x=\"{:.1}\" y=\"{axis:.1}\" text-anchor=\"end\">{hi:.1}µs</text>",
+        LABEL_WIDTH + plot_width,
+    )
+    .unwrap();
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Gaussian kernel density estimate of the values using Silverman's rule of thumb for the
+/// bandwidth. Returns the `(value, density)` pairs evaluated over the range of the values.
+fn kernel_density(values: &[f64], steps: usize) -> Vec<(f64, f64)> {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let bandwidth = 1.06 * std * n.powf(-0.2);
+    let bandwidth = if bandwidth > 0.0 { bandwidth } else { 1.0 };
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+
+    (0..=steps)
+        .map(|i| {
+            let v = min + (max - min) * i as f64 / steps as f64;
+            let density = values
+                .iter()
+                .map(|s| {
+                    let z = (v - s) / bandwidth;
+                    (-0.5 * z * z).exp()
+                })
+                .sum::<f64>()
+                / (n * bandwidth);
+            (v, density)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel_density_spans_the_values_range() {
+        let density = kernel_density(&[1.0, 2.0, 3.0], 4);
+        assert_eq!(density.len(), 5);
+        assert_eq!(density.first().unwrap().0, 1.0);
+        assert_eq!(density.last().unwrap().0, 3.0);
+    }
+
+    #[test]
+    fn test_kernel_density_peaks_at_the_center_of_symmetric_values() {
+        let density = kernel_density(&[1.0, 2.0, 2.0, 3.0], 8);
+        let (peak, _) =
+            density.iter().cloned().fold(
+                (0.0, 0.0),
+                |acc, (v, d)| if d > acc.1 { (v, d) } else { acc },
+            );
+        assert_eq!(peak, 2.0);
+    }
+}
@@ -0,0 +1,47 @@
+use std::process::Child;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+/// The cargo process currently running the benchmarks.
+static RUNNING_CHILD: Mutex<Option<Child>> = Mutex::new(None);
+
+/// Install a Ctrl-C handler that kills the running cargo process instead of terminating
+/// burnbench, so that the patched manifests are restored and the partial results reported.
+pub(crate) fn install_handler() {
+    let result = ctrlc::set_handler(|| {
+        if !is_interrupted() {
+            eprintln!("\n🛑 Interrupted, stopping the benchmarks...");
+        }
+        interrupt();
+    });
+    if let Err(e) = result {
+        eprintln!("❌ Failed to install the Ctrl-C handler ({e})");
+    }
+}
+
+/// Stop the run: the running cargo process is killed and no other one is started.
+pub(crate) fn interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+    if let Some(child) = RUNNING_CHILD.lock().unwrap().as_mut() {
+        child.kill().ok();
+    }
+}
+
+/// Whether the run has been interrupted by the user.
+pub(crate) fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Register the running cargo process so that it is killed on interruption.
+pub(crate) fn register_child(mut child: Child) {
+    if is_interrupted() {
+        child.kill().ok();
+    }
+    *RUNNING_CHILD.lock().unwrap() = Some(child);
+}
+
+/// Take back the registered cargo process once its output has been consumed.
+pub(crate) fn take_child() -> Option<Child> {
+    RUNNING_CHILD.lock().unwrap().take()
+}
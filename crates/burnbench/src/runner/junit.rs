@@ -0,0 +1,104 @@
+use std::{fmt::Write as _, fs, io, path::Path};
+
+use super::reports::{BenchmarkCollection, escape_markup as escape};
+
+/// Write the benchmark outcomes of the collection as a JUnit XML report.
+///
+/// Each successful record becomes a passing test case and each failed
+/// bench/backend/dtype combination becomes a test case with a `<failure>` element.
+/// Skipped combinations, e.g. once the time budget is exhausted, get a `<skipped>` element,
+/// so that generic CI systems can display the results in their test UI.
+pub(crate) fn write_junit_report(collection: &BenchmarkCollection, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, junit_xml(collection))
+}
+
+fn junit_xml(collection: &BenchmarkCollection) -> String {
+    let records = collection.records();
+    let failed = collection.failed_benchmarks();
+    let skipped = collection.skipped_benchmarks();
+    let tests = records.len() + failed.len() + skipped.len();
+    let total_time: f64 = records
+        .iter()
+        .map(|r| r.results.computed.median.as_secs_f64())
+        .sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        xml,
+        "<testsuites name=\"burnbench\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+        tests,
+        failed.len(),
+        skipped.len(),
+        total_time,
+    )
+    .unwrap();
+    writeln!(
+        xml,
+        "  <testsuite name=\"burnbench\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+        tests,
+        failed.len(),
+        skipped.len(),
+        total_time,
+    )
+    .unwrap();
+
+    for record in records {
+        writeln!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"/>",
+            escape(&format!("{}.{}", record.burn_version, record.feature)),
+            escape(&record.results.name),
+            record.results.computed.median.as_secs_f64(),
+        )
+        .unwrap();
+    }
+
+    for benchmark in failed {
+        writeln!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"0\">",
+            escape(&format!(
+                "{}.{}.{}",
+                benchmark.version, benchmark.backend, benchmark.dtype
+            )),
+            escape(&benchmark.bench),
+        )
+        .unwrap();
+        writeln!(
+            xml,
+            "      <failure message=\"benchmark failed\">{}</failure>",
+            escape(&benchmark.to_string()),
+        )
+        .unwrap();
+        xml.push_str("    </testcase>\n");
+    }
+
+    for benchmark in skipped {
+        writeln!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"0\">",
+            escape(&format!(
+                "{}.{}.{}",
+                benchmark.version, benchmark.backend, benchmark.dtype
+            )),
+            escape(&benchmark.bench),
+        )
+        .unwrap();
+        writeln!(
+            xml,
+            "      <skipped message=\"{}\"/>",
+            escape(&benchmark.reason),
+        )
+        .unwrap();
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n");
+    xml.push_str("</testsuites>\n");
+    xml
+}
@@ -0,0 +1,58 @@
+use std::{
+    fs::{self, File, TryLockError},
+    io,
+    path::Path,
+};
+
+/// Advisory lock on a file, released when dropped.
+///
+/// Serializes the `burnbench run` invocations sharing a resource, such as the target directory
+/// of the benchmarks, without preventing other processes from reading it.
+pub(crate) struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Lock the file, waiting for the other invocations holding it to release `what`.
+    pub(crate) fn acquire(path: &Path, what: &str) -> io::Result<Self> {
+        let file = open(path)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                println!("⏳ Waiting for another burnbench run to release {what}...");
+                file.lock()?;
+            }
+            Err(TryLockError::Error(e)) => return Err(e),
+        }
+        Ok(Self { _file: file })
+    }
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(".lock");
+
+        let lock = FileLock::acquire(&path, "the test").unwrap();
+        let other = open(&path).unwrap();
+        assert!(matches!(other.try_lock(), Err(TryLockError::WouldBlock)));
+
+        drop(lock);
+        assert!(other.try_lock().is_ok());
+    }
+}
@@ -0,0 +1,137 @@
+use regex::Regex;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex},
+};
+use tracing_subscriber::{
+    Layer, filter::LevelFilter, layer::SubscriberExt, registry, util::SubscriberInitExt,
+};
+
+use super::processor::OutputProcessor;
+use super::state::Combination;
+
+pub(crate) static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap());
+
+/// Install the subscriber displaying the events of the runner on stderr.
+///
+/// Only the warnings and errors are displayed unless the run is verbose, the output of the
+/// benchmarks being written to the log file of their combination anyway.
+pub(crate) fn init_tracing(verbose: bool) {
+    let level = if verbose {
+        LevelFilter::INFO
+    } else {
+        LevelFilter::WARN
+    };
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_target(false)
+        .with_filter(level);
+    // Fails only when a subscriber is already installed, which is fine.
+    registry().with(layer).try_init().ok();
+}
+
+/// Path of the file receiving the full cargo output of the combination.
+pub(crate) fn log_path(combination: &Combination) -> PathBuf {
+    let mut name = format!(
+        "{}-{}-{}-{}",
+        combination.version,
+        combination.backend,
+        combination.bench.replace(", ", "+"),
+        combination.dtype
+    );
+    if let Some(threads) = combination.threads {
+        name.push_str(&format!("-{threads}t"));
+    }
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    Path::new(crate::BENCHMARKS_TARGET_DIR)
+        .join("logs")
+        .join(format!("{name}.log"))
+}
+
+/// Wrap the processor so that every output line is also written to the log file, returning the
+/// path of the log file when it could be created.
+pub(crate) fn with_log_file(
+    processor: Arc<dyn OutputProcessor>,
+    path: PathBuf,
+) -> (Arc<dyn OutputProcessor>, Option<PathBuf>) {
+    match LogFileProcessor::new(processor.clone(), &path) {
+        Ok(log_processor) => (Arc::new(log_processor), Some(path)),
+        Err(e) => {
+            tracing::warn!("Failed to create the log file {} ({e})", path.display());
+            (processor, None)
+        }
+    }
+}
+
+/// A processor writing the output lines without colors to a log file before forwarding them to
+/// the wrapped processor.
+struct LogFileProcessor {
+    inner: Arc<dyn OutputProcessor>,
+    file: Mutex<BufWriter<File>>,
+}
+
+impl LogFileProcessor {
+    fn new(inner: Arc<dyn OutputProcessor>, path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+
+        Ok(Self {
+            inner,
+            file: Mutex::new(BufWriter::new(file)),
+        })
+    }
+}
+
+impl OutputProcessor for LogFileProcessor {
+    fn process_line(&self, line: &str) {
+        let mut file = self.file.lock().unwrap();
+        writeln!(file, "{}", ANSI_ESCAPE.replace_all(line, "")).ok();
+        self.inner.process_line(line);
+    }
+
+    fn progress(&self) {
+        self.inner.progress();
+    }
+
+    fn finish(&self) {
+        self.file.lock().unwrap().flush().ok();
+        self.inner.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_path_is_a_valid_file_name() {
+        let combination = Combination {
+            bench: "matmul, conv2d".to_string(),
+            backend: "cuda-fusion".to_string(),
+            version: "local:/home/me/burn".to_string(),
+            dtype: "f16".to_string(),
+            threads: None,
+        };
+        assert_eq!(
+            log_path(&combination),
+            Path::new(
+                "target/benchmarks/logs/local__home_me_burn-cuda-fusion-matmul+conv2d-f16.log"
+            )
+        );
+    }
+}
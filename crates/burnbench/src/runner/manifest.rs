@@ -0,0 +1,45 @@
+use serde::Serialize;
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use super::backend_env::BackendEnv;
+use crate::persistence::benchmarks_cache_dir;
+use crate::system_info::BenchmarkSystemInfo;
+
+/// Description of a `burnbench run` invocation, to reconstruct how its records were produced.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RunManifest {
+    pub(crate) run_id: String,
+    /// Start of the run, in RFC 3339 format.
+    pub(crate) started_at: String,
+    /// Command line arguments of the run.
+    pub(crate) args: Vec<String>,
+    pub(crate) benches: Vec<String>,
+    pub(crate) backends: Vec<String>,
+    pub(crate) versions: Vec<String>,
+    /// Commits the branch versions resolved to at the start of the run.
+    pub(crate) burn_revisions: BTreeMap<String, String>,
+    pub(crate) dtypes: Vec<String>,
+    /// Variables set when running the benchmarks of every backend or of one of them.
+    pub(crate) envs: Vec<BackendEnv>,
+    /// Commit of the burn-bench workspace running the benchmarks.
+    pub(crate) burn_bench_git_hash: Option<String>,
+    pub(crate) system_info: BenchmarkSystemInfo,
+}
+
+impl RunManifest {
+    /// Write the manifest to the runs directory of the cache, next to the records.
+    pub(crate) fn save(&self) -> io::Result<PathBuf> {
+        let path = manifest_path(&self.run_id);
+        fs::create_dir_all(benchmarks_cache_dir().join("runs"))?;
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+/// Path of the manifest of the run.
+pub(crate) fn manifest_path(run_id: &str) -> PathBuf {
+    benchmarks_cache_dir()
+        .join("runs")
+        .join(format!("{run_id}.json"))
+}
@@ -1,9 +1,46 @@
+mod android;
+mod artifacts;
 pub(crate) mod auth;
+mod backend_env;
 mod base;
+mod binary_size;
+mod cache;
+mod calibration;
+mod clean;
+mod clocks;
+mod container;
 mod dependency;
+mod diff;
+mod disk_space;
+mod doctor;
+mod events;
+mod feature_mapping;
+mod flamegraph;
+mod html;
+mod interrupt;
+mod junit;
+mod lock;
+mod logging;
+mod manifest;
+mod ncu;
+mod normalization;
+mod notify;
+mod nsys;
+mod outliers;
+mod pinning;
+mod plan;
+mod preflight;
 mod processor;
 mod progressbar;
+mod remote;
 mod reports;
+mod shard;
+mod significance;
+mod state;
+mod threads;
+mod trend;
+mod tui;
+mod wasm;
 mod workflow;
 
 pub use base::*;
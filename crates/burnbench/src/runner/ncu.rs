@@ -0,0 +1,164 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::persistence::{BenchmarkRecord, KernelProfile, KernelTime, benchmarks_cache_dir};
+
+/// Number of kernels listed in the summary of a profile.
+const TOP_KERNELS: usize = 5;
+const KERNEL_NAME: &str = "Kernel Name";
+const DURATION: &str = "gpu__time_duration.sum";
+const OCCUPANCY: &str = "sm__warps_active.avg.pct_of_peak_sustained_active";
+const MEMORY_THROUGHPUT: &str = "gpu__compute_memory_throughput.avg.pct_of_peak_sustained_elapsed";
+const SM_THROUGHPUT: &str = "sm__throughput.avg.pct_of_peak_sustained_elapsed";
+
+/// Summarize the kernels of an `ncu` report, read back with `ncu --import`.
+pub(crate) fn kernel_profile(ncu_path: &str, report: &Path) -> Result<KernelProfile, String> {
+    let metrics = [DURATION, OCCUPANCY, MEMORY_THROUGHPUT, SM_THROUGHPUT].join(",");
+    let output = Command::new(ncu_path)
+        .arg("--import")
+        .arg(report)
+        .args(["--csv", "--page", "raw", "--metrics", &metrics])
+        .output()
+        .map_err(|e| format!("failed to run {ncu_path} ({e})"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{ncu_path} failed to import {} ({})",
+            report.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    summarize(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Attach the profile to the records of the files and of their copies in the cache directory.
+pub(crate) fn attach_kernel_profile(files: &[PathBuf], profile: &KernelProfile) -> io::Result<()> {
+    let cache_dir = benchmarks_cache_dir();
+    for file in files {
+        let mut record: BenchmarkRecord = serde_json::from_reader(fs::File::open(file)?)?;
+        record.kernel_profile = Some(profile.clone());
+        let cached = file.file_name().map(|name| cache_dir.join(name));
+        for file in std::iter::once(file.clone()).chain(cached.filter(|c| c.exists())) {
+            serde_json::to_writer_pretty(fs::File::create(file)?, &record)?;
+        }
+    }
+    Ok(())
+}
+
+/// Summarize the raw page of a report exported to CSV, with a row of units under the header and
+/// a row per kernel launch.
+fn summarize(csv: &str) -> Result<KernelProfile, String> {
+    let mut lines = csv
+        .lines()
+        .skip_while(|line| !line.starts_with('"'))
+        .map(parse_csv_line);
+    let header = lines.next().ok_or("empty ncu report")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| format!("missing column {name} in the ncu report"))
+    };
+    let (name, duration) = (column(KERNEL_NAME)?, column(DURATION)?);
+    let metrics = [
+        column(OCCUPANCY)?,
+        column(MEMORY_THROUGHPUT)?,
+        column(SM_THROUGHPUT)?,
+    ];
+
+    let mut kernels: BTreeMap<String, (u64, f64)> = BTreeMap::new();
+    let mut total_time = 0.0;
+    let mut weighted = [0.0; 3];
+    // The units row has no kernel name nor numeric values.
+    for row in lines {
+        let value = |index: usize| {
+            row.get(index)
+                .and_then(|value| value.replace(',', "").parse::<f64>().ok())
+        };
+        let Some(time) = value(duration) else {
+            continue;
+        };
+        let kernel = kernels
+            .entry(row.get(name).cloned().unwrap_or_default())
+            .or_default();
+        kernel.0 += 1;
+        kernel.1 += time;
+        total_time += time;
+        for (sum, index) in weighted.iter_mut().zip(metrics) {
+            *sum += value(index).unwrap_or_default() * time;
+        }
+    }
+    if kernels.is_empty() || total_time <= 0.0 {
+        return Err("no kernel found in the ncu report".to_string());
+    }
+
+    let mut top_kernels: Vec<KernelTime> = kernels
+        .into_iter()
+        .map(|(name, (calls, time))| KernelTime {
+            name,
+            calls,
+            time_percent: time / total_time * 100.0,
+        })
+        .collect();
+    top_kernels.sort_by(|a, b| b.time_percent.total_cmp(&a.time_percent));
+    top_kernels.truncate(TOP_KERNELS);
+    let [achieved_occupancy, memory_throughput, sm_utilization] =
+        weighted.map(|sum| sum / total_time);
+    Ok(KernelProfile {
+        top_kernels,
+        achieved_occupancy,
+        memory_throughput,
+        sm_utilization,
+    })
+}
+
+/// Fields of a CSV line exported by `ncu` or `nsys`, quoted or not.
+pub(crate) fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_raw_page() {
+        let csv = format!(
+            "==PROF== Disconnected from process 4242\n\
+            \"ID\",\"Kernel Name\",\"{DURATION}\",\"{OCCUPANCY}\",\"{MEMORY_THROUGHPUT}\",\"{SM_THROUGHPUT}\"\n\
+            \"\",\"\",\"nsecond\",\"%\",\"%\",\"%\"\n\
+            \"0\",\"matmul<f32, 4>\",\"3,000\",\"50\",\"60\",\"80\"\n\
+            \"1\",\"add\",\"1,000\",\"10\",\"20\",\"40\"\n\
+            \"2\",\"matmul<f32, 4>\",\"4,000\",\"50\",\"60\",\"80\"\n"
+        );
+
+        let profile = summarize(&csv).unwrap();
+
+        assert_eq!(profile.top_kernels[0].name, "matmul<f32, 4>");
+        assert_eq!(profile.top_kernels[0].calls, 2);
+        assert_eq!(profile.top_kernels[0].time_percent, 87.5);
+        assert_eq!(profile.top_kernels[1].name, "add");
+        assert_eq!(profile.achieved_occupancy, 45.0);
+        assert_eq!(profile.memory_throughput, 55.0);
+        assert_eq!(profile.sm_utilization, 75.0);
+    }
+}
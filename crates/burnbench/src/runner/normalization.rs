@@ -0,0 +1,115 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::persistence::{BenchmarkRecord, NormalizedScore, benchmarks_cache_dir};
+
+/// Set the normalized score of each record against the same benchmark run with the reference
+/// backend in the same run.
+///
+/// The records without a counterpart on the reference backend keep no score. A backend in a
+/// thread count sweep is compared to the reference with the same number of threads, or to the
+/// reference without a sweep.
+pub(crate) fn normalize_records(records: &mut [BenchmarkRecord], reference: &str) {
+    let references: Vec<BenchmarkRecord> = records
+        .iter()
+        .filter(|record| record.feature == reference)
+        .cloned()
+        .collect();
+    for record in records.iter_mut() {
+        let median = record.results.computed.median;
+        let reference_median = references
+            .iter()
+            .find(|r| {
+                r.run_id == record.run_id
+                    && r.burn_version == record.burn_version
+                    && r.results.name == record.results.name
+                    && r.results.shapes == record.results.shapes
+                    && r.dtype == record.dtype
+                    && (r.threads.is_none() || r.threads == record.threads)
+            })
+            .map(|r| r.results.computed.median);
+        record.normalized = match reference_median {
+            Some(reference_median) if !reference_median.is_zero() && !median.is_zero() => {
+                Some(NormalizedScore {
+                    reference: reference.to_string(),
+                    ratio: median.as_secs_f64() / reference_median.as_secs_f64(),
+                })
+            }
+            _ => None,
+        };
+    }
+}
+
+/// Normalize the records of the run in their files and in their copies in the cache directory,
+/// so that the reports, the shared results and the merged reports include the scores.
+pub(crate) fn normalize_record_files(files: &[PathBuf], reference: &str) -> io::Result<()> {
+    let mut records = files
+        .iter()
+        .map(|file| Ok(serde_json::from_reader(fs::File::open(file)?)?))
+        .collect::<io::Result<Vec<BenchmarkRecord>>>()?;
+    normalize_records(&mut records, reference);
+    let cache_dir = benchmarks_cache_dir();
+    for (file, record) in files.iter().zip(&records) {
+        let cached = file.file_name().map(|name| cache_dir.join(name));
+        for file in std::iter::once(file.clone()).chain(cached.filter(|c| c.exists())) {
+            serde_json::to_writer_pretty(fs::File::create(file)?, record)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn record(feature: &str, name: &str, median_ms: u64) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord {
+            feature: feature.to_string(),
+            run_id: Some("run".to_string()),
+            ..Default::default()
+        };
+        record.results.name = name.to_string();
+        record.results.computed.median = Duration::from_millis(median_ms);
+        record
+    }
+
+    #[test]
+    fn test_normalize_against_reference_backend() {
+        let mut records = vec![
+            record("tch-cpu", "matmul", 20),
+            record("ndarray", "matmul", 40),
+            record("ndarray", "conv2d", 10),
+        ];
+
+        normalize_records(&mut records, "tch-cpu");
+
+        let ratios: Vec<Option<f64>> = records
+            .iter()
+            .map(|r| r.normalized.as_ref().map(|n| n.ratio))
+            .collect();
+        assert_eq!(ratios, vec![Some(1.0), Some(2.0), None]);
+        assert_eq!(records[1].normalized.as_ref().unwrap().reference, "tch-cpu");
+    }
+
+    #[test]
+    fn test_normalize_against_reference_of_same_dtype() {
+        let with_dtype = |mut record: BenchmarkRecord, dtype: &str| {
+            record.dtype = Some(dtype.to_string());
+            record
+        };
+        let mut records = vec![
+            with_dtype(record("tch-cpu", "matmul", 20), "f32"),
+            with_dtype(record("tch-cpu", "matmul", 10), "f16"),
+            with_dtype(record("ndarray", "matmul", 40), "f32"),
+            with_dtype(record("ndarray", "matmul", 40), "f16"),
+        ];
+
+        normalize_records(&mut records, "tch-cpu");
+
+        let ratios: Vec<Option<f64>> = records
+            .iter()
+            .map(|r| r.normalized.as_ref().map(|n| n.ratio))
+            .collect();
+        assert_eq!(ratios, vec![Some(1.0), Some(1.0), Some(2.0), Some(4.0)]);
+    }
+}
@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+use serde_json::json;
+
+/// Maximum length of a Discord message.
+const DISCORD_MAX_LEN: usize = 2000;
+
+/// Destination of the notification sent when a run ends, given as `desktop` or as the URL of a
+/// Slack or Discord incoming webhook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Notifier {
+    Desktop,
+    Slack(String),
+    Discord(String),
+}
+
+impl FromStr for Notifier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "desktop" {
+            Ok(Self::Desktop)
+        } else if s.starts_with("https://discord.com/api/webhooks/")
+            || s.starts_with("https://discordapp.com/api/webhooks/")
+        {
+            Ok(Self::Discord(s.to_string()))
+        } else if s.starts_with("https://") || s.starts_with("http://") {
+            // Slack compatible webhooks, such as Mattermost or Rocket.Chat
+            Ok(Self::Slack(s.to_string()))
+        } else {
+            Err(format!(
+                "invalid notification target '{s}', expected desktop or a webhook URL"
+            ))
+        }
+    }
+}
+
+impl Notifier {
+    /// Send the notification, with the results table in the message of the webhooks.
+    pub(crate) fn notify(&self, title: &str, table: &str) -> Result<(), String> {
+        match self {
+            Self::Desktop => notify_rust::Notification::new()
+                .appname("burnbench")
+                .summary(title)
+                .body("See the results in the terminal.")
+                .show()
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Self::Slack(url) => post(url, json!({ "text": message(title, table, None) })),
+            Self::Discord(url) => post(
+                url,
+                json!({ "content": message(title, table, Some(DISCORD_MAX_LEN)) }),
+            ),
+        }
+    }
+}
+
+/// Message with the table in a code block, truncated to `max_len` characters.
+fn message(title: &str, table: &str, max_len: Option<usize>) -> String {
+    let message = format!("{title}\n```\n{table}\n```");
+    match max_len {
+        Some(max_len) if message.chars().count() > max_len => {
+            let suffix = "\n…\n```";
+            let head: String = format!("{title}\n```\n{table}")
+                .chars()
+                .take(max_len - suffix.chars().count())
+                .collect();
+            format!("{head}{suffix}")
+        }
+        _ => message,
+    }
+}
+
+fn post(url: &str, body: serde_json::Value) -> Result<(), String> {
+    let response = reqwest::blocking::Client::new()
+        .post(url)
+        .json(&body)
+        .send()
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("error {}", response.status()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_notifier() {
+        assert_eq!("desktop".parse::<Notifier>(), Ok(Notifier::Desktop));
+        assert_eq!(
+            "https://hooks.slack.com/services/T0/B0/x".parse::<Notifier>(),
+            Ok(Notifier::Slack(
+                "https://hooks.slack.com/services/T0/B0/x".to_string()
+            ))
+        );
+        assert_eq!(
+            "https://discord.com/api/webhooks/1/x".parse::<Notifier>(),
+            Ok(Notifier::Discord(
+                "https://discord.com/api/webhooks/1/x".to_string()
+            ))
+        );
+        assert!("slack".parse::<Notifier>().is_err());
+    }
+
+    #[test]
+    fn test_message_truncated() {
+        assert_eq!(message("Done", "| a |", None), "Done\n```\n| a |\n```");
+        let truncated = message("Done", &"x".repeat(100), Some(20));
+        assert_eq!(truncated.chars().count(), 20);
+        assert!(truncated.ends_with("\n…\n```"));
+    }
+}
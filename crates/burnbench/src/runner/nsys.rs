@@ -0,0 +1,166 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::UNIX_EPOCH,
+};
+
+use tempfile::TempDir;
+
+use super::ncu::parse_csv_line;
+use super::pinning::cargo_runner_env;
+use crate::persistence::{BenchmarkRecord, KernelLaunches, benchmarks_cache_dir};
+
+/// Report of `nsys stats` summarizing the launches of each kernel.
+const KERNEL_SUMMARY: &str = "cuda_gpu_kern_sum";
+
+/// Traces of the bench executables of a combination, run by cargo under `nsys`.
+///
+/// The executables are traced in the order cargo runs them, so each record belongs to the first
+/// trace completed after its timestamp.
+pub(crate) struct KernelTraces {
+    dir: TempDir,
+}
+
+impl KernelTraces {
+    pub(crate) fn new() -> io::Result<Self> {
+        Ok(Self {
+            dir: tempfile::tempdir()?,
+        })
+    }
+
+    /// Variable making cargo run the bench executables under `nsys`.
+    pub(crate) fn env(&self) -> Result<(String, String), String> {
+        let output = self.dir.path().join("trace-%n");
+        cargo_runner_env(format!(
+            "nsys profile --trace=cuda --force-overwrite=true --output={}",
+            output.display()
+        ))
+    }
+
+    /// Attach the kernel launches of the traces to the records of the files and of their copies
+    /// in the cache directory.
+    pub(crate) fn attach(&self, files: &[PathBuf]) -> Result<(), String> {
+        let mut traces = vec![];
+        for entry in fs::read_dir(self.dir.path()).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "nsys-rep")
+            {
+                traces.push((completion_time(&path)?, kernel_launches(&path)?));
+            }
+        }
+        traces.sort_by_key(|(completed, _)| *completed);
+
+        for file in files {
+            attach_to_record_file(file, &traces)
+                .map_err(|e| format!("{} ({e})", file.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn attach_to_record_file(file: &Path, traces: &[(u128, KernelLaunches)]) -> io::Result<()> {
+    let mut record: BenchmarkRecord = serde_json::from_reader(fs::File::open(file)?)?;
+    record.kernel_launches = traces
+        .iter()
+        .find(|(completed, _)| *completed >= record.results.timestamp)
+        .map(|(_, launches)| *launches);
+    let cached = file
+        .file_name()
+        .map(|name| benchmarks_cache_dir().join(name));
+    for file in std::iter::once(file.to_path_buf()).chain(cached.filter(|c| c.exists())) {
+        serde_json::to_writer_pretty(fs::File::create(file)?, &record)?;
+    }
+    Ok(())
+}
+
+/// Time the trace was written, in milliseconds since the epoch like the record timestamps.
+fn completion_time(trace: &Path) -> Result<u128, String> {
+    fs::metadata(trace)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("{} ({e})", trace.display()))
+        .map(|modified| {
+            modified
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+        })
+}
+
+fn kernel_launches(trace: &Path) -> Result<KernelLaunches, String> {
+    let output = Command::new("nsys")
+        .args([
+            "stats",
+            "--report",
+            KERNEL_SUMMARY,
+            "--format",
+            "csv",
+            "--output",
+            "-",
+        ])
+        .arg(trace)
+        .output()
+        .map_err(|e| format!("failed to run nsys ({e})"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "nsys failed to summarize {} ({})",
+            trace.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    summarize(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Count the launches in the kernel summary exported to CSV, with a row per distinct kernel
+/// under the header, after the messages of `nsys`.
+fn summarize(csv: &str) -> Result<KernelLaunches, String> {
+    let mut lines = csv
+        .lines()
+        .skip_while(|line| !line.contains("Instances"))
+        .map(parse_csv_line);
+    let header = lines.next().ok_or("no kernel summary in the nsys output")?;
+    let instances = header
+        .iter()
+        .position(|column| column == "Instances")
+        .ok_or("missing column Instances in the kernel summary")?;
+    let mut launches = KernelLaunches {
+        launches: 0,
+        distinct_kernels: 0,
+    };
+    for row in lines {
+        let Some(count) = row
+            .get(instances)
+            .and_then(|count| count.replace(',', "").parse::<u64>().ok())
+        else {
+            continue;
+        };
+        launches.launches += count;
+        launches.distinct_kernels += 1;
+    }
+    Ok(launches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_kernel_summary() {
+        let csv = "\
+Processing [trace-1.sqlite] with [cuda_gpu_kern_sum.py]...
+\"Time (%)\",\"Total Time (ns)\",\"Instances\",\"Avg (ns)\",\"Name\"
+\"70.0\",\"7000\",\"1,000\",\"7.0\",\"fused_elemwise<f32, 4>\"
+\"30.0\",\"3000\",\"200\",\"15.0\",\"matmul\"
+";
+
+        assert_eq!(
+            summarize(csv),
+            Ok(KernelLaunches {
+                launches: 1200,
+                distinct_kernels: 2,
+            })
+        );
+    }
+}
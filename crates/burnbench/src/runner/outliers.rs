@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use crate::persistence::BenchmarkRecord;
+
+/// Factor between a result and the recent history above which it is likely a misconfiguration,
+/// such as a debug build or a throttled machine, rather than a change of performance.
+pub(crate) const OUTLIER_FACTOR: f64 = 5.0;
+/// Number of the most recent records of the history a result is compared to.
+const RECENT_RECORDS: usize = 10;
+
+/// Result deviating from the recent history of the same benchmark on the same hardware.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Outlier {
+    pub(crate) name: String,
+    pub(crate) feature: String,
+    pub(crate) median: Duration,
+    /// Median of the medians of the recent history.
+    pub(crate) reference: Duration,
+}
+
+impl Outlier {
+    /// How many times the result is slower, or faster when below 1.
+    pub(crate) fn factor(&self) -> f64 {
+        self.median.as_secs_f64() / self.reference.as_secs_f64()
+    }
+}
+
+/// Results deviating by more than [OUTLIER_FACTOR] from the recent history of the same
+/// benchmark, backend and shapes on the same hardware.
+///
+/// The records of the same run in the history are ignored, they are the new records saved in the
+/// cache.
+pub(crate) fn find_outliers(
+    records: &[BenchmarkRecord],
+    history: &[BenchmarkRecord],
+) -> Vec<Outlier> {
+    records
+        .iter()
+        .filter_map(|record| {
+            let mut recent: Vec<&BenchmarkRecord> = history
+                .iter()
+                .filter(|past| {
+                    (record.run_id.is_none() || past.run_id != record.run_id)
+                        && past.results.timestamp < record.results.timestamp
+                        && past.results.name == record.results.name
+                        && past.results.shapes == record.results.shapes
+                        && past.feature == record.feature
+                        && past.system_info.hardware() == record.system_info.hardware()
+                })
+                .collect();
+            recent.sort_by_key(|past| std::cmp::Reverse(past.results.timestamp));
+            let mut medians: Vec<Duration> = recent
+                .iter()
+                .take(RECENT_RECORDS)
+                .map(|past| past.results.computed.median)
+                .collect();
+            medians.sort();
+            let reference = *medians.get(medians.len() / 2)?;
+            let median = record.results.computed.median;
+            if reference.is_zero() || median.is_zero() {
+                return None;
+            }
+            let outlier = Outlier {
+                name: record.results.name.clone(),
+                feature: record.feature.clone(),
+                median,
+                reference,
+            };
+            let factor = outlier.factor();
+            (factor > OUTLIER_FACTOR || factor < 1.0 / OUTLIER_FACTOR).then_some(outlier)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(run_id: &str, timestamp: u128, median_ms: u64) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord {
+            feature: "cuda".to_string(),
+            run_id: Some(run_id.to_string()),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        record.results.timestamp = timestamp;
+        record.results.computed.median = Duration::from_millis(median_ms);
+        record
+    }
+
+    #[test]
+    fn test_find_outliers_against_recent_history() {
+        let history = vec![
+            record("a", 1, 1000),
+            record("b", 2, 10),
+            record("c", 3, 12),
+            record("d", 4, 11),
+        ];
+        let records = vec![record("e", 5, 80), record("e", 6, 4)];
+        // The new records are also in the history, they are not compared to themselves.
+        let history = [history, records.clone()].concat();
+
+        let outliers = find_outliers(&records, &history);
+
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].reference, Duration::from_millis(12));
+        assert_eq!(outliers[0].median, Duration::from_millis(80));
+    }
+
+    #[test]
+    fn test_find_outliers_without_history() {
+        let records = vec![record("e", 5, 80)];
+        assert!(find_outliers(&records, &records).is_empty());
+    }
+}
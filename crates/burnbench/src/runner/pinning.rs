@@ -0,0 +1,121 @@
+use std::process::Command;
+
+use crate::system_info::PINNING_ENV;
+
+/// CPU cores or NUMA node the benches are pinned to, to reduce the variance caused by the
+/// migrations between cores and sockets on multi-socket machines.
+///
+/// Cargo runs the benches through `taskset` or `numactl`, set as the runner of the host target,
+/// so that only the benches are pinned and not their build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Pinning {
+    /// Cores given as a list such as `0-7` or `0,2,4-7`.
+    Cores(String),
+    /// NUMA node whose cores and memory are used.
+    NumaNode(u32),
+}
+
+impl Pinning {
+    /// Check that the tool pinning the benches is available.
+    pub(crate) fn new(cores: Option<&str>, numa_node: Option<u32>) -> Result<Option<Self>, String> {
+        let pinning = match (cores, numa_node) {
+            (Some(cores), _) => Self::Cores(cores.to_string()),
+            (None, Some(node)) => Self::NumaNode(node),
+            (None, None) => return Ok(None),
+        };
+        if !cfg!(target_os = "linux") {
+            return Err("pinning is only supported on Linux".to_string());
+        }
+        let tool = pinning.command()[0];
+        if Command::new(tool).arg("--help").output().is_err() {
+            return Err(format!("{tool} not found, install it to pin the benches"));
+        }
+        Ok(Some(pinning))
+    }
+
+    /// Pinning, as stored in the system info of the records.
+    pub(crate) fn description(&self) -> String {
+        match self {
+            Self::Cores(cores) => format!("cores {cores}"),
+            Self::NumaNode(node) => format!("NUMA node {node}"),
+        }
+    }
+
+    fn command(&self) -> Vec<&'static str> {
+        match self {
+            Self::Cores(_) => vec!["taskset", "-c"],
+            Self::NumaNode(_) => vec!["numactl"],
+        }
+    }
+
+    /// Variables making cargo run the benches pinned, with the one recording the pinning.
+    pub(crate) fn envs(&self) -> Result<Vec<(String, String)>, String> {
+        let runner = match self {
+            Self::Cores(cores) => format!("{} {cores}", self.command().join(" ")),
+            Self::NumaNode(node) => {
+                format!("numactl --cpunodebind={node} --membind={node}")
+            }
+        };
+        Ok(vec![
+            cargo_runner_env(runner)?,
+            (PINNING_ENV.to_string(), self.description()),
+        ])
+    }
+}
+
+/// Variable making cargo run the executables of the host through the runner command.
+pub(crate) fn cargo_runner_env(runner: String) -> Result<(String, String), String> {
+    let host = host_target()?;
+    Ok((
+        format!(
+            "CARGO_TARGET_{}_RUNNER",
+            host.replace(['-', '.'], "_").to_uppercase()
+        ),
+        runner,
+    ))
+}
+
+/// Parse a list of cores such as `0-7` or `0,2,4-7`.
+pub(crate) fn parse_cores(cores: &str) -> Result<String, String> {
+    let valid = cores.split(',').all(|range| {
+        let (start, end) = range.split_once('-').unwrap_or((range, range));
+        matches!(
+            (start.parse::<usize>(), end.parse::<usize>()),
+            (Ok(start), Ok(end)) if start <= end
+        )
+    });
+    if valid {
+        Ok(cores.to_string())
+    } else {
+        Err(format!(
+            "invalid cores '{cores}', expected a list such as 0-7 or 0,2,4-7"
+        ))
+    }
+}
+
+/// Target of the machine, such as `x86_64-unknown-linux-gnu`.
+fn host_target() -> Result<String, String> {
+    let output = Command::new("rustc")
+        .arg("-vV")
+        .output()
+        .map_err(|e| format!("failed to run rustc ({e})"))?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(|host| host.trim().to_string())
+        .ok_or_else(|| "failed to get the host target from rustc".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cores() {
+        assert_eq!(parse_cores("0-7"), Ok("0-7".to_string()));
+        assert_eq!(parse_cores("0,2,4-7"), Ok("0,2,4-7".to_string()));
+        assert!(parse_cores("7-0").is_err());
+        assert!(parse_cores("0-").is_err());
+        assert!(parse_cores("all").is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of jobs generated by a matrix in GitHub Actions.
+pub(crate) const GHA_MAX_JOBS: usize = 256;
+/// Label of the runners of the backends without one in the suite.
+const DEFAULT_RUNNER: &str = "ubuntu-latest";
+
+/// Benchmark suite run by the CI, read from a TOML file such as:
+///
+/// ```toml
+/// benches = ["unary", "matmul"]
+/// backends = ["cuda-fusion", "wgpu-fusion", "ndarray"]
+/// dtypes = ["f32"]
+///
+/// [runners]
+/// default = "ubuntu-latest"
+/// cuda-fusion = "gpu-nvidia"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Suite {
+    /// Benches of the suite, every bench of the crate when empty.
+    #[serde(default)]
+    pub(crate) benches: Vec<String>,
+    pub(crate) backends: Vec<String>,
+    /// Data types of the suite, `f32` when empty.
+    #[serde(default)]
+    pub(crate) dtypes: Vec<String>,
+    /// Label of the runner of each backend, the `default` one for the others.
+    #[serde(default)]
+    runners: BTreeMap<String, String>,
+}
+
+impl Suite {
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{} ({e})", path.display()))?;
+        toml::from_str(&content).map_err(|e| format!("invalid suite {} ({e})", path.display()))
+    }
+
+    /// Label of the runner of the backend.
+    pub(crate) fn runner(&self, backend: &str) -> &str {
+        self.runners
+            .get(backend)
+            .or_else(|| self.runners.get("default"))
+            .map_or(DEFAULT_RUNNER, String::as_str)
+    }
+}
+
+/// Job of the CI matrix, running a bench on a backend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct MatrixEntry {
+    pub(crate) backend: String,
+    pub(crate) bench: String,
+    pub(crate) dtype: String,
+    pub(crate) runner: String,
+    /// Cargo features of the benchmarks crate enabled by the job, comma separated.
+    pub(crate) features: String,
+}
+
+/// Matrix in the format of the `strategy.matrix` of GitHub Actions, on a single line so that it
+/// can be written to `$GITHUB_OUTPUT` and read with `fromJSON`.
+pub(crate) fn gha_matrix(entries: &[MatrixEntry]) -> String {
+    #[derive(Serialize)]
+    struct Matrix<'a> {
+        include: &'a [MatrixEntry],
+    }
+    serde_json::to_string(&Matrix { include: entries }).expect("Matrix should be serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_suite_runners() {
+        let suite: Suite = toml::from_str(
+            r#"
+            backends = ["cuda-fusion", "ndarray"]
+
+            [runners]
+            default = "cpu-large"
+            cuda-fusion = "gpu-nvidia"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(suite.runner("cuda-fusion"), "gpu-nvidia");
+        assert_eq!(suite.runner("ndarray"), "cpu-large");
+        assert_eq!(Suite::default().runner("ndarray"), DEFAULT_RUNNER);
+    }
+
+    #[test]
+    fn test_gha_matrix() {
+        let entries = [MatrixEntry {
+            backend: "cuda-fusion".to_string(),
+            bench: "matmul".to_string(),
+            dtype: "f32".to_string(),
+            runner: "gpu-nvidia".to_string(),
+            features: "burnbench/cuda-fusion,burnbench/f32".to_string(),
+        }];
+
+        assert_eq!(
+            gha_matrix(&entries),
+            r#"{"include":[{"backend":"cuda-fusion","bench":"matmul","dtype":"f32","runner":"gpu-nvidia","features":"burnbench/cuda-fusion,burnbench/f32"}]}"#
+        );
+    }
+}
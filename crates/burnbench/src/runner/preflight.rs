@@ -0,0 +1,274 @@
+use core::fmt;
+use regex::Regex;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use super::clean::TARGET_LOCK;
+use super::dependency::Dependency;
+use super::lock::FileLock;
+
+/// Name of the bench target added to the benchmarks crate to probe its compatibility.
+const PROBE_NAME: &str = "burnbench-preflight";
+
+/// Minimal benchmark exercising what the harness expects from Burn and from burnbench: the
+/// shape of the `Benchmark` trait and the backend types selected by `bench_on_backend!`.
+const PROBE_SOURCE: &str = r#"use burn::tensor::{Distribution, Tensor, backend::Backend};
+use burnbench::{Benchmark, BenchmarkResult, run_benchmark};
+
+struct ProbeBenchmark<B: Backend> {
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for ProbeBenchmark<B> {
+    type Input = Tensor<B, 1>;
+    type Output = Tensor<B, 1>;
+
+    fn name(&self) -> String {
+        "preflight".to_string()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![1]]
+    }
+
+    fn execute(&self, input: Self::Input) -> Self::Output {
+        input
+    }
+
+    fn prepare(&self) -> Self::Input {
+        Tensor::random([1], Distribution::Default, &self.device)
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device).unwrap();
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(device: &B::Device) -> Vec<BenchmarkResult> {
+    vec![run_benchmark(ProbeBenchmark::<B> {
+        device: device.clone(),
+    })]
+}
+
+fn main() {
+    burnbench::bench_on_backend!();
+}
+"#;
+
+/// Restore the manifest of the benchmarks crate without the probe target, even when the check
+/// fails or panics.
+struct ProbeTargetGuard {
+    manifest_path: PathBuf,
+    original_content: String,
+}
+
+impl Drop for ProbeTargetGuard {
+    fn drop(&mut self) {
+        if let Err(e) = fs::write(&self.manifest_path, &self.original_content) {
+            tracing::error!(
+                "Failed to remove the probe target from {} ({e})",
+                self.manifest_path.display()
+            );
+        }
+    }
+}
+
+/// Incompatibility between the harness and a Burn version found by the pre-flight check.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Mismatch {
+    /// A feature enabled by the harness does not exist in Burn.
+    Feature { krate: String, feature: String },
+    /// A backend type used by `bench_on_backend!` does not exist in Burn.
+    BackendType(String),
+    /// The `Benchmark` trait implemented by the benchmarks does not match the one of burnbench.
+    BenchmarkTrait(String),
+    /// Any other compilation error.
+    Other(String),
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Feature { krate, feature } => write!(
+                f,
+                "`{krate}` has no feature `{feature}`: update the feature mapping of the benchmarks crate for this version"
+            ),
+            Self::BackendType(name) => write!(
+                f,
+                "backend type `burn::backend::{name}` does not exist: it was likely renamed (e.g. CudaJit -> Cuda, HipJit -> Rocm), use the matching legacy feature"
+            ),
+            Self::BenchmarkTrait(error) => write!(
+                f,
+                "the `Benchmark` trait does not match the one expected by the benchmarks ({error}): update the benchmarks or burnbench"
+            ),
+            Self::Other(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// Compile the probe benchmark against the patched Burn version with the features of the
+/// selected backends, and return the mismatches found.
+pub(crate) fn check_compatibility(
+    crate_name: &str,
+    crate_path: &Path,
+    workspace: &Path,
+    version: &Dependency,
+    features: &str,
+) -> io::Result<Vec<Mismatch>> {
+    let probe_dir = Path::new(crate::BENCHMARKS_TARGET_DIR).join("preflight");
+    fs::create_dir_all(&probe_dir)?;
+    let probe_path = fs::canonicalize(&probe_dir)?.join("probe.rs");
+
+    // Concurrent runs would patch the same manifest and build in the same target directory.
+    let lock = FileLock::acquire(
+        &Path::new(crate::BENCHMARKS_TARGET_DIR).join(TARGET_LOCK),
+        "the benchmarks target directory",
+    )?;
+    fs::write(&probe_path, PROBE_SOURCE)?;
+    let guard = version.patch(crate_path, workspace)?;
+
+    // Register the probe as a bench target of the patched manifest.
+    let manifest_path = crate_path.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)?;
+    let probe_target = ProbeTargetGuard {
+        manifest_path: manifest_path.clone(),
+        original_content: manifest.clone(),
+    };
+    fs::write(
+        &manifest_path,
+        format!(
+            "{manifest}\n[[bench]]\nharness = false\nname = \"{PROBE_NAME}\"\npath = \"{}\"\n",
+            probe_path.to_string_lossy().replace('\\', "/")
+        ),
+    )?;
+    let output = Command::new("cargo")
+        .env("CARGO_TERM_COLOR", "never")
+        .args([
+            "check",
+            "--message-format",
+            "short",
+            "--manifest-path",
+            &manifest_path.to_string_lossy(),
+            "--package",
+            crate_name,
+            "--bench",
+            PROBE_NAME,
+            "--features",
+            features,
+            "--target-dir",
+            crate::BENCHMARKS_TARGET_DIR,
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output();
+    core::mem::drop(probe_target);
+    core::mem::drop(guard);
+    core::mem::drop(lock);
+
+    let output = output?;
+    if output.status.success() {
+        return Ok(vec![]);
+    }
+    let mismatches = parse_mismatches(&String::from_utf8_lossy(&output.stderr));
+    if mismatches.is_empty() {
+        // Failed without a recognizable error, e.g. the registry could not be reached.
+        return Ok(vec![Mismatch::Other(
+            "the probe benchmark failed to compile, rerun with --skip-preflight --verbose to see the error"
+                .to_string(),
+        )]);
+    }
+    Ok(mismatches)
+}
+
+/// Extract the mismatches from the errors of `cargo check --message-format short`.
+fn parse_mismatches(stderr: &str) -> Vec<Mismatch> {
+    let feature_re = Regex::new(
+        r"depends on `([\w-]+)` with feature `([\w-]+)` but `[\w-]+` does not have that feature",
+    )
+    .unwrap();
+    let backend_re = Regex::new(r"unresolved import `burn::backend::(\w+)`").unwrap();
+    let trait_re = Regex::new(
+        r"(?:(?:method|type) `\w+` is not a member of trait `Benchmark`|not all trait items implemented, missing: .+|method `\w+` has an incompatible type for trait)",
+    )
+    .unwrap();
+
+    let mut mismatches = vec![];
+    let mut push = |mismatch: Mismatch| {
+        if !mismatches.contains(&mismatch) {
+            mismatches.push(mismatch);
+        }
+    };
+    let mut others = vec![];
+
+    for line in stderr.lines() {
+        if let Some(captures) = feature_re.captures(line) {
+            push(Mismatch::Feature {
+                krate: captures[1].to_string(),
+                feature: captures[2].to_string(),
+            });
+        } else if let Some(captures) = backend_re.captures(line) {
+            push(Mismatch::BackendType(captures[1].to_string()));
+        } else if let Some(found) = trait_re.find(line) {
+            push(Mismatch::BenchmarkTrait(found.as_str().to_string()));
+        } else if line.contains("error") && !line.starts_with("error: could not compile") {
+            others.push(line.trim().to_string());
+        }
+    }
+    // Only report the remaining errors when nothing more specific was found, they are usually
+    // consequences of the mismatches.
+    if mismatches.is_empty() {
+        mismatches.extend(others.into_iter().map(Mismatch::Other));
+    }
+
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_feature_mismatch() {
+        let stderr = "error: failed to select a version for `burn`.\n    ... required by package `backend-comparison v0.1.0`\nversions that meet the requirements `=0.16.0` are: 0.16.0\n\nthe package `backend-comparison` depends on `burn`, with features: `cuda` but `burn` does not have these features.\npackage `backend-comparison` depends on `burn` with feature `cuda` but `burn` does not have that feature.";
+        assert_eq!(
+            parse_mismatches(stderr),
+            vec![Mismatch::Feature {
+                krate: "burn".to_string(),
+                feature: "cuda".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_backend_and_trait_mismatches() {
+        let stderr = "probe.rs:1:5: error[E0432]: unresolved import `burn::backend::CudaJit`: no `CudaJit` in `backend`\nprobe.rs:10:5: error[E0407]: method `prepare` is not a member of trait `Benchmark`\nprobe.rs:8:1: error[E0046]: not all trait items implemented, missing: `args`\nerror: could not compile `backend-comparison` (bench \"burnbench-preflight\") due to 3 previous errors";
+        assert_eq!(
+            parse_mismatches(stderr),
+            vec![
+                Mismatch::BackendType("CudaJit".to_string()),
+                Mismatch::BenchmarkTrait(
+                    "method `prepare` is not a member of trait `Benchmark`".to_string()
+                ),
+                Mismatch::BenchmarkTrait(
+                    "not all trait items implemented, missing: `args`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_errors() {
+        let stderr = "probe.rs:3:1: error[E0433]: failed to resolve: use of undeclared crate `foo`\nerror: could not compile `backend-comparison`";
+        assert_eq!(
+            parse_mismatches(stderr),
+            vec![Mismatch::Other(
+                "probe.rs:3:1: error[E0433]: failed to resolve: use of undeclared crate `foo`"
+                    .to_string()
+            )]
+        );
+    }
+}
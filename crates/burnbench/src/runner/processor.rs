@@ -1,11 +1,16 @@
+use super::container::Container;
+use super::flamegraph;
+use super::interrupt;
 use super::progressbar::RunnerProgressBar;
+use crate::benchmark::ProgressLine;
 use glob::glob;
 use std::collections::HashMap;
 use std::io::{self, BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 
 /// Processor for standard output of cargo process
 pub trait OutputProcessor: Send + Sync + 'static {
@@ -31,7 +36,6 @@ impl OutputProcessor for VerboseProcessor {
 
 /// A processor that just send the output into oblivion.
 #[derive(Default)]
-#[allow(dead_code)]
 pub struct SinkProcessor;
 
 impl OutputProcessor for SinkProcessor {
@@ -40,12 +44,30 @@ impl OutputProcessor for SinkProcessor {
     fn finish(&self) {}
 }
 
+/// Forward the output of a step of an invocation, such as the build of the benches before they
+/// run on another device, without finishing the processor.
+pub(crate) struct StepProcessor(pub(crate) Arc<dyn OutputProcessor>);
+
+impl OutputProcessor for StepProcessor {
+    fn process_line(&self, line: &str) {
+        self.0.process_line(line);
+    }
+    fn progress(&self) {
+        self.0.progress();
+    }
+    fn finish(&self) {}
+}
+
 /// A processor for a nice and compact output experience using a progress bar
 pub struct NiceProcessor {
     bench: String,
     backend: String,
     version: String,
     pb: Arc<Mutex<RunnerProgressBar>>,
+    /// Benchmark currently running, as reported by the benchmark binary.
+    current: Mutex<Option<String>>,
+    /// Last finished benchmark with its duration.
+    last: Mutex<Option<(String, Duration)>>,
 }
 
 pub(crate) enum NiceProcessorState {
@@ -67,6 +89,8 @@ impl NiceProcessor {
             backend,
             version,
             pb,
+            current: Mutex::new(None),
+            last: Mutex::new(None),
         }
     }
 
@@ -76,7 +100,18 @@ impl NiceProcessor {
                 format!("🔨 {} ▶ {} ▶ {}", self.version, self.backend, self.bench)
             }
             NiceProcessorState::Running => {
-                format!("🔥 {} ▶ {} ▶ {}", self.version, self.backend, self.bench)
+                let mut message =
+                    format!("🔥 {} ▶ {} ▶ {}", self.version, self.backend, self.bench);
+                if let Some(current) = self.current.lock().unwrap().as_ref() {
+                    message.push_str(&format!(" ▶ {current}"));
+                }
+                if let Some((name, duration)) = self.last.lock().unwrap().as_ref() {
+                    message.push_str(&format!(
+                        " (last: {name} in {:.1}s)",
+                        duration.as_secs_f64()
+                    ));
+                }
+                message
             }
             NiceProcessorState::Uploading => {
                 format!("💾 {} ▶ {} ▶ {}", self.version, self.backend, self.bench)
@@ -88,7 +123,16 @@ impl NiceProcessor {
 impl OutputProcessor for NiceProcessor {
     fn process_line(&self, line: &str) {
         let pb = self.pb.lock().unwrap();
-        let state = if line.contains("Compiling") {
+        let state = if let Some(progress) = ProgressLine::parse(line) {
+            match progress {
+                ProgressLine::Start { name } => *self.current.lock().unwrap() = Some(name),
+                ProgressLine::End { name, duration } => {
+                    *self.current.lock().unwrap() = None;
+                    *self.last.lock().unwrap() = Some((name, duration));
+                }
+            }
+            NiceProcessorState::Running
+        } else if line.contains("Compiling") {
             pb.stop_spinner();
             NiceProcessorState::Compiling
         } else if line.contains("Running") {
@@ -118,6 +162,8 @@ pub struct CargoRunner<'a> {
     envs: Vec<(String, String)>,
     processor: Arc<dyn OutputProcessor>,
     profiling: Profiling,
+    container: Option<Container>,
+    stacks: Option<PathBuf>,
 }
 
 #[derive(Clone)]
@@ -126,11 +172,21 @@ pub enum Profiling {
     Activated {
         ncu_path: String,
         ncu_ui_path: String,
+        /// Also sample the CPU stacks of the benches for the differential flamegraphs.
+        flamegraph: bool,
+        /// Run the benches after profiling them instead of opening the report in `ncu-ui`, so
+        /// that the runner attaches a summary of the report to their records.
+        headless: bool,
     },
 }
 
 impl<'a> CargoRunner<'a> {
-    fn run_profile(&self, ncu_path: &str, ncu_ui_path: &str) -> io::Result<ExitStatus> {
+    fn run_profile(
+        &self,
+        ncu_path: &str,
+        ncu_ui_path: &str,
+        headless: bool,
+    ) -> io::Result<ExitStatus> {
         let get_benches = |bench: &str| {
             let pattern = format!("./target/benchmarks/release/deps/{}-*", bench);
             let files: Vec<_> = glob(&pattern)
@@ -142,7 +198,7 @@ impl<'a> CargoRunner<'a> {
         };
 
         let bench = &self.params[1];
-        log::info!("Profiling benchmark {bench:?}");
+        tracing::info!("Profiling benchmark {bench:?}");
         get_benches(bench)
             .into_iter()
             .for_each(|f| std::fs::remove_file(f).unwrap());
@@ -196,6 +252,17 @@ impl<'a> CargoRunner<'a> {
             None,
         )?;
 
+        // Before opening the report, which blocks until ncu-ui is closed.
+        if let Some(stacks) = &self.stacks
+            && let Err(e) = flamegraph::capture_stacks(bin, &self.envs, stacks)
+        {
+            tracing::warn!("Failed to sample the stacks of {bench} ({e})");
+        }
+
+        // The timings under ncu, which replays each kernel, are not representative.
+        if headless {
+            return self.run_bench();
+        }
         let output = format!("{}.ncu-rep", file);
         run_process(ncu_ui_path, &[&output], None, None)
     }
@@ -205,60 +272,52 @@ impl<'a> CargoRunner<'a> {
         envs: Vec<(String, String)>,
         processor: Arc<dyn OutputProcessor>,
         profiling: Profiling,
+        container: Option<Container>,
     ) -> Self {
         Self {
             params,
             envs,
             processor,
             profiling,
+            container,
+            stacks: None,
         }
     }
 
+    /// Write the folded CPU stacks of the profiled bench to the file.
+    pub fn with_stacks(mut self, path: PathBuf) -> Self {
+        self.stacks = Some(path);
+        self
+    }
+
     pub fn run(&self) -> io::Result<ExitStatus> {
         match &self.profiling {
             Profiling::Deactivated => self.run_bench(),
             Profiling::Activated {
                 ncu_path,
                 ncu_ui_path,
-            } => self.run_profile(ncu_path, ncu_ui_path),
+                headless,
+                ..
+            } => self.run_profile(ncu_path, ncu_ui_path, *headless),
         }
     }
 
-    fn run_command(&self, mut cargo: Child) -> io::Result<ExitStatus> {
-        // stdout
-        let stdout = BufReader::new(cargo.stdout.take().expect("stdout should be captured"));
-        let stdout_processor = Arc::clone(&self.processor);
-        let stdout_thread = thread::spawn(move || {
-            for line in stdout.lines() {
-                let line = line.expect("A line from stdout should be read");
-                stdout_processor.process_line(&line);
-                stdout_processor.progress();
-            }
-        });
-        // stderr
-        let stderr = BufReader::new(cargo.stderr.take().expect("stderr should be captured"));
-        let stderr_processor = Arc::clone(&self.processor);
-        let stderr_thread = thread::spawn(move || {
-            for line in stderr.lines() {
-                let line = line.expect("A line from stderr should be read");
-                stderr_processor.process_line(&line);
-                stderr_processor.progress();
-            }
-        });
-        // wait for process completion
-        stdout_thread
-            .join()
-            .expect("The stderr thread should not panic");
-        stderr_thread
-            .join()
-            .expect("The stderr thread should not panic");
-        self.processor.finish();
-        cargo.wait()
+    fn run_command(&self, cargo: Child) -> io::Result<ExitStatus> {
+        process_output(cargo, &self.processor)
     }
+
     fn run_bench(&self) -> io::Result<ExitStatus> {
-        let cargo = Command::new("cargo")
-            .env("CARGO_TERM_COLOR", "always")
-            .envs(self.envs.iter().map(|(k, v)| (k, v)))
+        let mut envs = vec![("CARGO_TERM_COLOR".to_string(), "always".to_string())];
+        envs.extend(self.envs.iter().cloned());
+        let mut command = match &self.container {
+            Some(container) => container.cargo_command(&envs)?,
+            None => {
+                let mut command = Command::new("cargo");
+                command.envs(envs);
+                command
+            }
+        };
+        let cargo = command
             .arg("bench")
             .args(self.params)
             .stdout(Stdio::piped())
@@ -270,6 +329,48 @@ impl<'a> CargoRunner<'a> {
     }
 }
 
+/// Stream the output of the child process to the processor and wait for its completion.
+///
+/// The process is killed if the run is interrupted.
+pub(crate) fn process_output(
+    mut child: Child,
+    processor: &Arc<dyn OutputProcessor>,
+) -> io::Result<ExitStatus> {
+    // stdout
+    let stdout = BufReader::new(child.stdout.take().expect("stdout should be captured"));
+    let stdout_processor = Arc::clone(processor);
+    let stdout_thread = thread::spawn(move || {
+        for line in stdout.lines() {
+            let line = line.expect("A line from stdout should be read");
+            stdout_processor.process_line(&line);
+            stdout_processor.progress();
+        }
+    });
+    // stderr
+    let stderr = BufReader::new(child.stderr.take().expect("stderr should be captured"));
+    let stderr_processor = Arc::clone(processor);
+    let stderr_thread = thread::spawn(move || {
+        for line in stderr.lines() {
+            let line = line.expect("A line from stderr should be read");
+            stderr_processor.process_line(&line);
+            stderr_processor.progress();
+        }
+    });
+    // the process is killed if the run is interrupted
+    interrupt::register_child(child);
+    // wait for process completion
+    stdout_thread
+        .join()
+        .expect("The stderr thread should not panic");
+    stderr_thread
+        .join()
+        .expect("The stderr thread should not panic");
+    processor.finish();
+    interrupt::take_child()
+        .expect("The process should be registered")
+        .wait()
+}
+
 fn run_process(
     name: &str,
     args: &[&str],
@@ -277,7 +378,7 @@ fn run_process(
     path: Option<&Path>,
 ) -> io::Result<ExitStatus> {
     let joined_args = args.join(" ");
-    log::info!("Command line: {} {}", name, &joined_args);
+    tracing::info!("Command line: {} {}", name, &joined_args);
     let mut command = Command::new(name);
     if let Some(path) = path {
         command.current_dir(path);
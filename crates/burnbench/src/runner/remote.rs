@@ -0,0 +1,236 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    str::FromStr,
+    sync::Arc,
+};
+
+use super::processor::{OutputProcessor, process_output};
+
+/// Directory of the remote home receiving the synchronized workspaces.
+const REMOTE_WORKSPACES_DIR: &str = "burnbench-remote";
+
+/// Machine executing the benchmarks over SSH, given as `user@host[:dir]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Remote {
+    /// SSH destination.
+    host: String,
+    /// Directory receiving the workspace, relative to the remote home unless absolute.
+    dir: Option<String>,
+}
+
+impl FromStr for Remote {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (host, dir) = match s.split_once(':') {
+            Some((host, dir)) => (host, Some(dir.to_string())),
+            None => (s, None),
+        };
+        if host.is_empty() || dir.as_deref() == Some("") {
+            return Err(format!("invalid remote '{s}', expected user@host[:dir]"));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            dir,
+        })
+    }
+}
+
+impl Remote {
+    pub(crate) fn host(&self) -> &str {
+        &self.host
+    }
+
+    fn workspace_dir(&self, workspace: &Path) -> String {
+        self.dir.clone().unwrap_or_else(|| {
+            let name = fs::canonicalize(workspace)
+                .ok()
+                .and_then(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_else(|| "workspace".to_string());
+            format!("{REMOTE_WORKSPACES_DIR}/{name}")
+        })
+    }
+
+    /// Copy the workspace to the remote machine, leaving out the build artifacts.
+    pub(crate) fn sync(&self, workspace: &Path) -> io::Result<()> {
+        let dir = self.workspace_dir(workspace);
+        check_status(
+            Command::new("ssh")
+                .args([&self.host, &format!("mkdir -p {}", shell_quote(&dir))])
+                .status()?,
+            "ssh",
+        )?;
+        let source = format!("{}/", workspace.to_string_lossy());
+        check_status(
+            Command::new("rsync")
+                .args([
+                    "--archive",
+                    "--compress",
+                    "--delete",
+                    "--exclude",
+                    "/target",
+                    &source,
+                    &format!("{}:{dir}/", self.host),
+                ])
+                .status()?,
+            "rsync",
+        )
+    }
+
+    /// Run burnbench on the remote machine with the given arguments, streaming its output to
+    /// the processor.
+    pub(crate) fn run(
+        &self,
+        workspace: &Path,
+        bin: &str,
+        args: &[String],
+        processor: Arc<dyn OutputProcessor>,
+    ) -> io::Result<ExitStatus> {
+        let mut command = format!(
+            "cd {} && cargo run --release --bin {} --",
+            shell_quote(&self.workspace_dir(workspace)),
+            shell_quote(bin)
+        );
+        for arg in args {
+            command.push(' ');
+            command.push_str(&shell_quote(arg));
+        }
+        tracing::info!("Remote command line: {command}");
+
+        let ssh = Command::new("ssh")
+            .args([&self.host, &command])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        process_output(ssh, &processor)
+    }
+
+    /// Copy the records of the last remote run into the local directory and return their local
+    /// paths.
+    pub(crate) fn pull_records(&self, destination: &Path) -> io::Result<Vec<PathBuf>> {
+        let output = Command::new("ssh")
+            .args([
+                &self.host,
+                "cd ~/.cache/burn/burnbench/runs/\"$(cat ~/.cache/burn/burnbench/runs/latest)\" && find \"$PWD\" -name 'bench_*.json' | sort",
+            ])
+            .output()?;
+        check_status(output.status, "ssh")?;
+        let remote_files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        if remote_files.is_empty() {
+            return Ok(vec![]);
+        }
+
+        fs::create_dir_all(destination)?;
+        let mut rsync = Command::new("rsync")
+            .args([
+                "--archive",
+                "--no-relative",
+                "--files-from=-",
+                &format!("{}:/", self.host),
+                &format!("{}/", destination.to_string_lossy()),
+            ])
+            .stdin(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = rsync.stdin.take() {
+            stdin.write_all(remote_files.join("\n").as_bytes())?;
+        }
+        check_status(rsync.wait()?, "rsync")?;
+
+        Ok(remote_files
+            .iter()
+            .filter_map(|file| Path::new(file).file_name())
+            .map(|name| destination.join(name))
+            .collect())
+    }
+}
+
+fn check_status(status: ExitStatus, program: &str) -> io::Result<()> {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{program} exited with {status}")))
+    }
+}
+
+/// Arguments of the remote run: the current ones without the `--remote` option.
+pub(crate) fn forwarded_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut forwarded = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--remote" {
+            args.next();
+        } else if !arg.starts_with("--remote=") {
+            forwarded.push(arg);
+        }
+    }
+    forwarded
+}
+
+/// Quote the argument for the remote shell.
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=@:,+".contains(c))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote() {
+        assert_eq!(
+            "me@gpu-box".parse::<Remote>(),
+            Ok(Remote {
+                host: "me@gpu-box".to_string(),
+                dir: None,
+            })
+        );
+        assert_eq!(
+            "me@gpu-box:/data/burn-bench".parse::<Remote>(),
+            Ok(Remote {
+                host: "me@gpu-box".to_string(),
+                dir: Some("/data/burn-bench".to_string()),
+            })
+        );
+        assert!("me@gpu-box:".parse::<Remote>().is_err());
+        assert!(":dir".parse::<Remote>().is_err());
+    }
+
+    #[test]
+    fn test_forwarded_args_strip_remote() {
+        let args = [
+            "run",
+            "--remote",
+            "me@gpu-box",
+            "-b",
+            "cuda",
+            "--remote=me@other",
+        ];
+        assert_eq!(
+            forwarded_args(args.map(String::from)),
+            vec!["run", "-b", "cuda"]
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("cuda-fusion"), "cuda-fusion");
+        assert_eq!(shell_quote("name=^matmul"), "'name=^matmul'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
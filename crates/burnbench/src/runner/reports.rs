@@ -1,153 +1,1658 @@
 use comfy_table::{Cell, CellAlignment, Color, Table};
 use core::fmt;
 use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::Display,
-    fs,
-    io::{BufRead, BufReader},
-    path::PathBuf,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
 };
 
-use crate::persistence::BenchmarkRecord;
+use clap::ValueEnum;
+use glob::glob;
+use regex::Regex;
+use uuid::Uuid;
+
+use super::binary_size::{BinarySize, format_size};
+use super::significance::{SIGNIFICANCE_LEVEL, format_p_value, mann_whitney_p_value};
+use crate::BenchmarkId;
+use crate::persistence::{
+    BenchmarkRecord, BurnSource, KernelLaunches, KernelProfile, benchmarks_cache_dir,
+};
+
+/// File of the runs directory holding the id of the last run.
+const LATEST_RUN_FILE: &str = "latest";
+const NOISE_SCORE_FILE: &str = "noise_score.txt";
+const BINARY_SIZES_FILE: &str = "binary_sizes.json";
 
 pub(crate) struct FailedBenchmark {
     pub(crate) bench: String,
     pub(crate) backend: String,
+    pub(crate) version: String,
+    pub(crate) dtype: String,
+    /// File containing the full cargo output of the failed run.
+    pub(crate) log_path: Option<PathBuf>,
 }
 
 impl fmt::Display for FailedBenchmark {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.log_path {
+            return write!(f, "See the full output in {}", path.display());
+        }
         write!(
             f,
-            "Run the benchmark with verbose enabled to see the error:\ncargo run --bin burnbench -- run --benches {} --backends {} --verbose",
-            self.bench, self.backend
+            "Run the benchmark with verbose enabled to see the error:\ncargo run --bin burnbench -- run --benches {} --backends {} --versions {} --dtypes {} --verbose",
+            self.bench, self.backend, self.version, self.dtype
         )
     }
 }
 
+/// Combination of benchmarks, backend, version and dtype that was not run, e.g. because the
+/// time budget of the run was exhausted or the backend is not available on the machine.
+pub(crate) struct SkippedBenchmark {
+    pub(crate) bench: String,
+    pub(crate) backend: String,
+    pub(crate) version: String,
+    pub(crate) dtype: String,
+    pub(crate) reason: String,
+}
+
 pub(crate) struct BenchmarkCollection {
     failed_benchmarks: Vec<FailedBenchmark>,
-    results_file: PathBuf,
+    skipped_benchmarks: Vec<SkippedBenchmark>,
+    /// Directory of the run, with one directory of records per cargo invocation. `None` when
+    /// there is no previous run.
+    run_dir: Option<PathBuf>,
     successful_records: Vec<BenchmarkRecord>,
+    /// Noise score of the machine in percent, measured by the calibration of the run.
+    noise_score: Option<f64>,
+    /// Sizes of the executables of the benches built by the run.
+    binary_sizes: Vec<BinarySize>,
 }
 
 impl Default for BenchmarkCollection {
+    /// Collection of a new run with a random id.
     fn default() -> Self {
-        let results_file = dirs::home_dir()
-            .expect("Home directory should exist")
-            .join(".cache")
-            .join("burn")
-            .join("burnbench")
-            .join("benchmark_results.txt");
-        fs::remove_file(results_file.clone()).ok();
+        Self::new(&Uuid::new_v4().to_string())
+    }
+}
+
+impl BenchmarkCollection {
+    /// Create the collection of a run, which becomes the previous run for the next commands.
+    ///
+    /// The records of a resumed run are kept in its directory.
+    pub(crate) fn new(run_id: &str) -> Self {
+        let run_dir = run_dir(run_id);
+        if let Err(e) = fs::create_dir_all(&run_dir)
+            .and_then(|_| fs::write(runs_dir().join(LATEST_RUN_FILE), run_id))
+        {
+            tracing::warn!("Failed to create the directory of the run ({e})");
+        }
+        Self::with_run_dir(Some(run_dir))
+    }
+
+    /// Create a collection pointing to the results of the previous run without resetting them.
+    pub(crate) fn from_previous_run() -> Self {
+        Self::with_run_dir(latest_run_id().map(|run_id| run_dir(&run_id)))
+    }
+
+    /// Create a collection of records loaded from elsewhere than a run, such as the sets of
+    /// results compared by the diff command.
+    pub(crate) fn from_records(records: Vec<BenchmarkRecord>) -> Self {
+        let mut collection = Self::with_run_dir(None);
+        collection.successful_records = records;
+        collection
+    }
+
+    fn with_run_dir(run_dir: Option<PathBuf>) -> Self {
         Self {
             failed_benchmarks: vec![],
-            results_file,
+            skipped_benchmarks: vec![],
             successful_records: vec![],
+            noise_score: None,
+            // Kept when resuming so that the sizes of the completed combinations are not lost.
+            binary_sizes: run_dir
+                .as_deref()
+                .map(read_binary_sizes)
+                .unwrap_or_default(),
+            run_dir,
+        }
+    }
+
+    /// Attach the noise score of the machine to the run, the changes within it are not
+    /// highlighted in the reports.
+    pub(crate) fn set_noise_score(&mut self, score: f64) -> io::Result<()> {
+        self.noise_score = Some(score);
+        match &self.run_dir {
+            Some(run_dir) => fs::write(run_dir.join(NOISE_SCORE_FILE), score.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the change is too small to be told apart from the noise of the machine.
+    fn within_noise(&self, change: Option<f64>) -> bool {
+        matches!((change, self.noise_score), (Some(change), Some(noise)) if change.abs() <= noise)
+    }
+
+    /// Change to highlight, ignoring the changes within the noise of the machine and the ones
+    /// that are not statistically significant.
+    fn significant_change(&self, change: Option<f64>, p_value: Option<f64>) -> Option<f64> {
+        change
+            .filter(|_| !self.within_noise(change))
+            .filter(|_| p_value.is_none_or(|p| p < SIGNIFICANCE_LEVEL))
+    }
+
+    /// Change against the baseline, with its p-value when enough samples are available.
+    fn change_label(&self, change: f64, p_value: Option<f64>) -> String {
+        let approx = if self.within_noise(Some(change)) {
+            "≈ "
+        } else {
+            ""
+        };
+        match p_value {
+            Some(p_value) => format!("{approx}{change:+.1}%, {}", format_p_value(p_value)),
+            None => format!("{approx}{change:+.1}%"),
+        }
+    }
+
+    /// Footer of the tables explaining the annotation of the changes within the noise.
+    fn noise_footer(&self) -> String {
+        match self.noise_score {
+            Some(noise) => format!(
+                "\n\nNoise score: ±{noise:.1}%, smaller changes are marked with ≈ and not highlighted"
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// Add the sizes of the executables built by a cargo invocation of the run.
+    pub(crate) fn push_binary_sizes(&mut self, sizes: Vec<BinarySize>) -> io::Result<()> {
+        self.binary_sizes.extend(sizes);
+        match &self.run_dir {
+            Some(run_dir) => fs::write(
+                run_dir.join(BINARY_SIZES_FILE),
+                serde_json::to_string_pretty(&self.binary_sizes)?,
+            ),
+            None => Ok(()),
         }
     }
-}
 
-impl BenchmarkCollection {
     pub(crate) fn push_failed_benchmark(&mut self, benchmark: FailedBenchmark) {
         self.failed_benchmarks.push(benchmark);
     }
 
+    pub(crate) fn push_skipped_benchmark(&mut self, benchmark: SkippedBenchmark) {
+        self.skipped_benchmarks.push(benchmark);
+    }
+
+    pub(crate) fn records(&self) -> &[BenchmarkRecord] {
+        &self.successful_records
+    }
+
+    pub(crate) fn failed_benchmarks(&self) -> &[FailedBenchmark] {
+        &self.failed_benchmarks
+    }
+
+    pub(crate) fn skipped_benchmarks(&self) -> &[SkippedBenchmark] {
+        &self.skipped_benchmarks
+    }
+
+    /// Create the directory receiving the records of the next cargo invocation, passed to the
+    /// benchmarks with the `BURN_BENCH_RESULTS_DIR` environment variable.
+    pub(crate) fn invocation_dir(&self) -> io::Result<PathBuf> {
+        let run_dir = self
+            .run_dir
+            .as_ref()
+            .ok_or_else(|| io::Error::other("the collection has no run directory"))?;
+        fs::create_dir_all(run_dir)?;
+        // Zero padded so that the invocations are listed in order.
+        let count = sorted_entries(run_dir, |path| path.is_dir()).len();
+        let dir = run_dir.join(format!("{count:04}"));
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Files of the records written by the benchmarks in the directory of an invocation.
+    pub(crate) fn invocation_records(dir: &Path) -> Vec<PathBuf> {
+        sorted_entries(dir, |path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+    }
+
+    /// Files of the records produced by the run so far.
+    pub(crate) fn record_files(&self) -> Vec<PathBuf> {
+        let Some(run_dir) = &self.run_dir else {
+            return vec![];
+        };
+        sorted_entries(run_dir, |path| path.is_dir())
+            .iter()
+            .flat_map(|dir| Self::invocation_records(dir))
+            .collect()
+    }
+
+    /// Add existing record files to the results of the run.
+    pub(crate) fn push_record_files(&self, files: &[PathBuf]) -> io::Result<()> {
+        let dir = self.invocation_dir()?;
+        for file in files {
+            let name = file.file_name().ok_or_else(|| {
+                io::Error::other(format!("invalid record file {}", file.display()))
+            })?;
+            fs::copy(file, dir.join(name))?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn load_records(&mut self) -> &mut Self {
-        if let Ok(file) = fs::File::open(self.results_file.clone()) {
-            let file_reader = BufReader::new(file);
-            for file in file_reader.lines() {
-                let file_path = file.unwrap();
-                if let Ok(br_file) = fs::File::open(file_path.clone()) {
-                    let benchmarkrecord =
-                        serde_json::from_reader::<_, BenchmarkRecord>(br_file).unwrap();
-                    self.successful_records.push(benchmarkrecord)
-                } else {
-                    println!("Cannot find the benchmark-record file: {}", file_path);
-                };
+        for file_path in self.record_files() {
+            if let Ok(br_file) = fs::File::open(&file_path)
+                && let Ok(record) = serde_json::from_reader::<_, BenchmarkRecord>(br_file)
+            {
+                self.successful_records.push(record);
+            } else {
+                println!(
+                    "Cannot read the benchmark-record file: {}",
+                    file_path.display()
+                );
+            }
+        }
+        self.noise_score = self
+            .run_dir
+            .as_ref()
+            .and_then(|run_dir| fs::read_to_string(run_dir.join(NOISE_SCORE_FILE)).ok())
+            .and_then(|score| score.trim().parse().ok());
+        self.binary_sizes = self
+            .run_dir
+            .as_deref()
+            .map(read_binary_sizes)
+            .unwrap_or_default();
+
+        self
+    }
+
+    /// Load every benchmark record found in the cache directory, not only the ones of the
+    /// previous run.
+    pub(crate) fn load_all_records(&mut self) -> &mut Self {
+        let pattern = benchmarks_cache_dir().join("bench_*.json");
+        let files = glob(&pattern.to_string_lossy())
+            .into_iter()
+            .flat_map(|r| r.filter_map(|f| f.ok()));
+        for file_path in files {
+            if let Ok(br_file) = fs::File::open(&file_path)
+                && let Ok(record) = serde_json::from_reader::<_, BenchmarkRecord>(br_file)
+            {
+                self.successful_records.push(record);
+            } else {
+                println!(
+                    "Cannot read the benchmark-record file: {}",
+                    file_path.display()
+                );
             }
         }
 
         self
     }
 
-    pub(crate) fn get_ascii_table(&self) -> String {
+    /// Load the records exported from other machines, either record files or directories
+    /// containing `bench_*.json` record files such as a copy of the local cache.
+    ///
+    /// A file can hold a single record or an array of records. The records found several times
+    /// are only loaded once.
+    pub(crate) fn load_exported_records(&mut self, paths: &[PathBuf]) -> &mut Self {
+        let mut files = vec![];
+        for path in paths {
+            if path.is_dir() {
+                let pattern = path.join("bench_*.json");
+                files.extend(
+                    glob(&pattern.to_string_lossy())
+                        .into_iter()
+                        .flat_map(|r| r.filter_map(|f| f.ok())),
+                );
+            } else {
+                files.push(path.clone());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for file_path in files {
+            let Ok(content) = fs::read_to_string(&file_path) else {
+                println!(
+                    "Cannot read the benchmark-record file: {}",
+                    file_path.display()
+                );
+                continue;
+            };
+            let records = match serde_json::from_str::<BenchmarkRecord>(&content) {
+                Ok(record) => vec![record],
+                Err(_) => match serde_json::from_str::<Vec<BenchmarkRecord>>(&content) {
+                    Ok(records) => records,
+                    Err(_) => {
+                        println!("Not a benchmark-record file: {}", file_path.display());
+                        continue;
+                    }
+                },
+            };
+            for record in records {
+                if seen.insert((
+                    record.results.name.clone(),
+                    record.results.timestamp,
+                    record.system_info.hardware(),
+                )) {
+                    self.successful_records.push(record);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Apply the report filters, in order, to the loaded records.
+    pub(crate) fn apply_filters(&mut self, filters: &[ReportFilter]) -> &mut Self {
+        for filter in filters {
+            match filter {
+                ReportFilter::MinMedian(min) => self
+                    .successful_records
+                    .retain(|r| r.results.computed.median >= *min),
+                ReportFilter::Name(regex) => {
+                    self.successful_records
+                        .retain(|r| regex.is_match(&r.results.name));
+                    self.failed_benchmarks.retain(|b| regex.is_match(&b.bench));
+                    self.skipped_benchmarks.retain(|b| regex.is_match(&b.bench));
+                }
+                ReportFilter::OnlyRegressions(threshold) => {
+                    let regressed = self.regressed_benchmarks(*threshold);
+                    self.successful_records
+                        .retain(|r| regressed.contains(&regression_key(r)));
+                }
+                ReportFilter::Top(count) => {
+                    // Keep the slowest records while preserving the order of the run
+                    let mut order: Vec<usize> = (0..self.successful_records.len()).collect();
+                    order.sort_by(|a, b| {
+                        self.successful_records[*b]
+                            .results
+                            .computed
+                            .median
+                            .cmp(&self.successful_records[*a].results.computed.median)
+                    });
+                    let kept: HashSet<usize> = order.into_iter().take(*count).collect();
+                    let mut index = 0;
+                    self.successful_records.retain(|_| {
+                        let keep = kept.contains(&index);
+                        index += 1;
+                        keep
+                    });
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Benchmarks, identified by name, shapes, dtype and feature, for which a version is slower
+    /// than the baseline version by more than the threshold percentage.
+    fn regressed_benchmarks(&self, threshold: f64) -> HashSet<RegressionKey> {
+        let mut regressed = HashSet::new();
+        let Some(baselines) = self.baseline_medians() else {
+            return regressed;
+        };
+
+        for record in &self.successful_records {
+            if let Some(change) = baselines.change(record)
+                && change > threshold
+            {
+                regressed.insert(regression_key(record));
+            }
+        }
+
+        regressed
+    }
+
+    /// Medians of the records of the baseline version.
+    ///
+    /// The baseline version is the version of the first record, which is the first version of
+    /// the run.
+    fn baseline_medians(&self) -> Option<BaselineMedians<'_>> {
+        let version = &self.successful_records.first()?.burn_version;
+        let records = self
+            .successful_records
+            .iter()
+            .filter(|r| &r.burn_version == version)
+            .map(|r| (record_key(r), r))
+            .collect();
+
+        Some(BaselineMedians { version, records })
+    }
+
+    pub(crate) fn get_ascii_table(&self, thresholds: &ChangeThresholds, order: RowOrder) -> String {
+        let baselines = self.baseline_medians();
         let mut records = self.successful_records.clone();
 
-        // Sort by benchmark name, then shapes, then median
+        // The versions are ranked in the order of the run rather than alphabetically.
+        let mut versions: Vec<&str> = vec![];
+        for record in &self.successful_records {
+            if !versions.contains(&record.burn_version.as_str()) {
+                versions.push(&record.burn_version);
+            }
+        }
+        let version_rank = |record: &BenchmarkRecord| {
+            versions
+                .iter()
+                .position(|version| *version == record.burn_version)
+        };
+        let compare = |a: &BenchmarkRecord, b: &BenchmarkRecord, key: SortBy| match key {
+            SortBy::Median => a.results.computed.median.cmp(&b.results.computed.median),
+            SortBy::Name => benchmark_id(a)
+                .cmp(&benchmark_id(b))
+                .then_with(|| a.results.name.cmp(&b.results.name))
+                .then_with(|| a.results.shapes.cmp(&b.results.shapes)),
+            SortBy::Backend => a.feature.cmp(&b.feature),
+            SortBy::Version => version_rank(a).cmp(&version_rank(b)),
+        };
+        // Sort by group, then by the requested key, then by benchmark, dtype and median
         records.sort_by(|a, b| {
-            a.results
-                .name
-                .cmp(&b.results.name)
-                .then_with(|| a.results.shapes.cmp(&b.results.shapes))
-                .then_with(|| {
-                    a.results
-                        .computed
-                        .median
-                        .partial_cmp(&b.results.computed.median)
-                        .unwrap()
-                })
+            compare(a, b, order.group_by.sort_key())
+                .then_with(|| compare(a, b, order.sort_by))
+                .then_with(|| compare(a, b, SortBy::Name))
+                .then_with(|| a.dtype.cmp(&b.dtype))
+                .then_with(|| compare(a, b, SortBy::Median))
         });
 
         let mut table = Table::new();
         table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
-        table.set_header(vec![
-            "Benchmark",
-            "Burn Version",
-            "Shapes",
-            "Feature",
-            "Backend",
-            "Device",
-            "Median",
-        ]);
+        // Only display the tags when at least one run has been tagged
+        let tagged = records.iter().any(|r| r.tag.is_some());
+        // Only display the build configurations when at least one run has a custom one
+        let configured = records.iter().any(|r| build_config(r).is_some());
+        let mut header = vec!["Benchmark", "Burn Version"];
+        if tagged {
+            header.push("Tag");
+        }
+        if configured {
+            header.push("Build");
+        }
+        // Only display the numbers of threads in a thread count sweep
+        let swept = records.iter().any(|r| r.threads.is_some());
+        if swept {
+            header.push("Threads");
+        }
+        header.extend(["Shapes", "DType", "Feature", "Backend", "Device", "Median"]);
+        // Only display the changes when several versions have been run
+        let changes: Vec<Option<f64>> = records
+            .iter()
+            .map(|r| baselines.as_ref().and_then(|b| b.change(r)))
+            .collect();
+        let compared = changes.iter().any(Option::is_some);
+        if compared {
+            header.push("Change");
+        }
+        // Only display the fusion speedup when both variants of a backend have been run
+        let fusion_speedups: Vec<Option<f64>> =
+            records.iter().map(|r| self.fusion_speedup(r)).collect();
+        let fusion = fusion_speedups.iter().any(Option::is_some);
+        if fusion {
+            header.push("Fusion Speedup");
+        }
+        // Only display the energy when it has been measured
+        let energy = records.iter().any(|r| r.results.energy.is_some());
+        if energy {
+            header.extend(["Energy", "Avg Power"]);
+        }
+        let columns = header.len();
+        table.set_header(header);
 
-        let mut prev_benchmark = "";
-        let mut prev_shapes = vec![];
+        let mut prev_group = None;
 
         // success benchmarks
-        for record in &records {
-            if prev_benchmark != record.results.name || prev_shapes != record.results.shapes {
-                if !prev_benchmark.is_empty() {
-                    table.add_row(vec![
-                        Cell::new("----").fg(Color::DarkGrey),
-                        Cell::new("----").fg(Color::DarkGrey),
-                        Cell::new("----").fg(Color::DarkGrey),
-                        Cell::new("----").fg(Color::DarkGrey),
-                        Cell::new("----").fg(Color::DarkGrey),
-                        Cell::new("----").fg(Color::DarkGrey),
-                        Cell::new("----").fg(Color::DarkGrey),
-                    ]);
+        for ((record, change), fusion_speedup) in records.iter().zip(changes).zip(fusion_speedups) {
+            let group = order.group_by.group(record);
+            if prev_group.as_ref() != Some(&group) {
+                if prev_group.is_some() {
+                    table.add_row(
+                        (0..columns)
+                            .map(|_| Cell::new("----").fg(Color::DarkGrey))
+                            .collect::<Vec<_>>(),
+                    );
                 }
-                prev_benchmark = &record.results.name;
-                prev_shapes = record.results.shapes.clone();
+                prev_group = Some(group);
             }
 
-            table.add_row(vec![
+            let p_value = baselines.as_ref().and_then(|b| b.p_value(record));
+            let mut row = vec![
                 Cell::new(&record.results.name).fg(Color::Green),
                 Cell::new(&record.burn_version).fg(Color::Green),
+            ];
+            if tagged {
+                row.push(Cell::new(record.tag.as_deref().unwrap_or("-")).fg(Color::Green));
+            }
+            if configured {
+                let config = build_config(record).unwrap_or_else(|| "-".to_string());
+                row.push(Cell::new(config).fg(Color::Green));
+            }
+            if swept {
+                let threads = record.threads.map(|t| t.to_string());
+                row.push(Cell::new(threads.as_deref().unwrap_or("-")).fg(Color::Green));
+            }
+            row.extend([
                 Cell::new(format!("{}", ShapeFmt::new(&record.results.shapes))).fg(Color::Green),
+                Cell::new(record.dtype.as_deref().unwrap_or("-")).fg(Color::Green),
                 Cell::new(&record.feature).fg(Color::Green),
                 Cell::new(format!("`{}`", &record.backend)).fg(Color::Green),
                 Cell::new(&record.device).fg(Color::Green),
-                Cell::new(format!("{:.3?}", record.results.computed.median))
-                    .set_alignment(CellAlignment::Right),
+                thresholds.highlight(
+                    Cell::new(format!("{:.3?}", record.results.computed.median))
+                        .set_alignment(CellAlignment::Right),
+                    self.significant_change(change, p_value),
+                ),
             ]);
+            if compared {
+                let label = match change {
+                    Some(change) => self.change_label(change, p_value),
+                    None => "-".to_string(),
+                };
+                row.push(thresholds.highlight(
+                    Cell::new(label).set_alignment(CellAlignment::Right),
+                    self.significant_change(change, p_value),
+                ));
+            }
+            if fusion {
+                let speedup = match fusion_speedup {
+                    Some(speedup) => format!("{speedup:.2}×"),
+                    None => "-".to_string(),
+                };
+                row.push(Cell::new(speedup).set_alignment(CellAlignment::Right));
+            }
+            if energy {
+                let (joules, watts) = match record.results.energy {
+                    Some(energy) => (
+                        format!("{:.3} J", energy.joules),
+                        format!("{:.1} W", energy.average_watts),
+                    ),
+                    None => ("-".to_string(), "-".to_string()),
+                };
+                row.push(Cell::new(joules).set_alignment(CellAlignment::Right));
+                row.push(Cell::new(watts).set_alignment(CellAlignment::Right));
+            }
+            table.add_row(row);
         }
 
         // failed benchmarks
         for benchmark in &self.failed_benchmarks {
-            table.add_row(vec![
-                Cell::new(&benchmark.bench).fg(Color::Red),
-                Cell::new("-"),
+            let mut row = vec![Cell::new(&benchmark.bench).fg(Color::Red), Cell::new("-")];
+            if tagged {
+                row.push(Cell::new("-"));
+            }
+            if configured {
+                row.push(Cell::new("-"));
+            }
+            if swept {
+                row.push(Cell::new("-"));
+            }
+            row.extend([
                 Cell::new("-"),
+                Cell::new(&benchmark.dtype).fg(Color::Red),
                 Cell::new("-"),
                 Cell::new(format!("`{}`", &benchmark.backend)).fg(Color::Red),
                 Cell::new("-"),
                 Cell::new("FAILED").fg(Color::Red),
             ]);
+            if compared {
+                row.push(Cell::new("-"));
+            }
+            if fusion {
+                row.push(Cell::new("-"));
+            }
+            if energy {
+                row.extend([Cell::new("-"), Cell::new("-")]);
+            }
+            table.add_row(row);
+        }
+
+        // skipped benchmarks
+        for benchmark in &self.skipped_benchmarks {
+            let mut row = vec![
+                Cell::new(&benchmark.bench).fg(Color::Yellow),
+                Cell::new(&benchmark.version).fg(Color::Yellow),
+            ];
+            if tagged {
+                row.push(Cell::new("-"));
+            }
+            if configured {
+                row.push(Cell::new("-"));
+            }
+            if swept {
+                row.push(Cell::new("-"));
+            }
+            row.extend([
+                Cell::new("-"),
+                Cell::new(&benchmark.dtype).fg(Color::Yellow),
+                Cell::new("-"),
+                Cell::new(format!("`{}`", &benchmark.backend)).fg(Color::Yellow),
+                Cell::new("-"),
+                Cell::new("SKIPPED").fg(Color::Yellow),
+            ]);
+            if compared {
+                row.push(Cell::new("-"));
+            }
+            if fusion {
+                row.push(Cell::new("-"));
+            }
+            if energy {
+                row.extend([Cell::new("-"), Cell::new("-")]);
+            }
+            table.add_row(row);
+        }
+
+        format!("{table}{}", self.noise_footer())
+    }
+
+    /// Speedup of a record of a fusion backend over the same benchmark run on the backend
+    /// without fusion, e.g. `cuda-fusion` over `cuda`.
+    fn fusion_speedup(&self, record: &BenchmarkRecord) -> Option<f64> {
+        let feature = record.feature.strip_suffix("-fusion")?;
+        let unfused = self.successful_records.iter().find(|r| {
+            r.feature == feature
+                && r.results.name == record.results.name
+                && r.results.shapes == record.results.shapes
+                && r.dtype == record.dtype
+                && r.burn_version == record.burn_version
+                && r.tag == record.tag
+                && r.cargo_profile == record.cargo_profile
+                && r.rustflags == record.rustflags
+        })?;
+        let median = record.results.computed.median;
+        if median.is_zero() {
+            return None;
+        }
+        Some(unfused.results.computed.median.as_secs_f64() / median.as_secs_f64())
+    }
+
+    /// Pivoted view of the records with one row per benchmark and one column per backend and
+    /// version. Each cell shows the median and its relative change against the first column.
+    pub(crate) fn get_pivot_table(&self, thresholds: &ChangeThresholds) -> String {
+        self.pivot_table(thresholds, PivotColumns::Runs)
+    }
+
+    /// Pivoted view of the records with one column per backend, version and host, to compare
+    /// the records merged from several machines. The changes against the first column are
+    /// highlighted.
+    pub(crate) fn get_host_pivot_table(&self, thresholds: &ChangeThresholds) -> String {
+        self.pivot_table(thresholds, PivotColumns::Hosts)
+    }
+
+    fn pivot_table(&self, thresholds: &ChangeThresholds, pivot_columns: PivotColumns) -> String {
+        let baselines = match pivot_columns {
+            PivotColumns::Runs => self.baseline_medians(),
+            PivotColumns::Hosts => None,
+        };
+        let mut columns: Vec<String> = vec![];
+        let mut rows: BTreeMap<PivotRow, HashMap<String, PivotCell>> = BTreeMap::new();
+
+        let mut add_column = |column: &String| {
+            if !columns.contains(column) {
+                columns.push(column.clone());
+            }
+        };
+
+        for record in &self.successful_records {
+            let column = match pivot_columns {
+                PivotColumns::Runs => run_label(record),
+                PivotColumns::Hosts => {
+                    format!("{} on {}", run_label(record), record.system_info.host())
+                }
+            };
+            add_column(&column);
+            rows.entry((
+                benchmark_id(record),
+                record.results.name.clone(),
+                format!("{}", ShapeFmt::new(&record.results.shapes)),
+                record.dtype.clone().unwrap_or_else(|| "-".to_string()),
+            ))
+            .or_default()
+            .insert(
+                column,
+                PivotCell::Median(
+                    record,
+                    baselines.as_ref().and_then(|b| b.change(record)),
+                    baselines.as_ref().and_then(|b| b.p_value(record)),
+                ),
+            );
+        }
+        for benchmark in &self.failed_benchmarks {
+            let column = format!("{} @ {}", benchmark.backend, benchmark.version);
+            add_column(&column);
+            rows.entry((
+                BenchmarkId::from_name(&benchmark.bench),
+                benchmark.bench.clone(),
+                "-".to_string(),
+                benchmark.dtype.clone(),
+            ))
+            .or_default()
+            .insert(column, PivotCell::Failed);
+        }
+        for benchmark in &self.skipped_benchmarks {
+            let column = format!("{} @ {}", benchmark.backend, benchmark.version);
+            add_column(&column);
+            rows.entry((
+                BenchmarkId::from_name(&benchmark.bench),
+                benchmark.bench.clone(),
+                "-".to_string(),
+                benchmark.dtype.clone(),
+            ))
+            .or_default()
+            .insert(column, PivotCell::Skipped);
+        }
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        let mut header = vec![
+            "Benchmark".to_string(),
+            "Shapes".to_string(),
+            "DType".to_string(),
+        ];
+        header.extend(columns.iter().map(|c| format!("`{c}`")));
+        table.set_header(header);
+
+        for ((_, name, shapes, dtype), cells) in rows {
+            let baseline = columns.iter().find_map(|c| match cells.get(c) {
+                Some(PivotCell::Median(record, ..)) => Some(*record),
+                _ => None,
+            });
+            let mut row = vec![
+                Cell::new(name).fg(Color::Green),
+                Cell::new(shapes),
+                Cell::new(dtype),
+            ];
+            for column in &columns {
+                let cell = match (cells.get(column), baseline) {
+                    (Some(PivotCell::Median(record, change, p_value)), Some(baseline))
+                        if record.results.computed.median != baseline.results.computed.median =>
+                    {
+                        let median = record.results.computed.median;
+                        let delta = relative_change(median, baseline.results.computed.median);
+                        let delta_p_value = mann_whitney_p_value(
+                            &baseline.results.raw.durations,
+                            &record.results.raw.durations,
+                        );
+                        let cell = match delta {
+                            Some(delta) => Cell::new(format!(
+                                "{median:.3?} ({})",
+                                self.change_label(delta, delta_p_value)
+                            )),
+                            None => Cell::new(format!("{median:.3?}")),
+                        };
+                        let (change, p_value) = match pivot_columns {
+                            PivotColumns::Runs => (*change, *p_value),
+                            PivotColumns::Hosts => (delta, delta_p_value),
+                        };
+                        thresholds.highlight(cell, self.significant_change(change, p_value))
+                    }
+                    (Some(PivotCell::Median(record, change, p_value)), _) => thresholds.highlight(
+                        Cell::new(format!("{:.3?}", record.results.computed.median)),
+                        self.significant_change(*change, *p_value),
+                    ),
+                    (Some(PivotCell::Failed), _) => Cell::new("FAILED").fg(Color::Red),
+                    (Some(PivotCell::Skipped), _) => Cell::new("SKIPPED").fg(Color::Yellow),
+                    (None, _) => Cell::new("-"),
+                };
+                row.push(cell.set_alignment(CellAlignment::Right));
+            }
+            table.add_row(row);
+        }
+
+        format!("{table}{}", self.noise_footer())
+    }
+
+    /// Comparison of the records against the ones of a base set of results, with one row per
+    /// benchmark, shapes, feature and number of threads. The significant changes above the
+    /// thresholds are highlighted and counted in the footer.
+    ///
+    /// The latest record is kept when a set holds the same benchmark several times.
+    pub(crate) fn get_diff_table(
+        &self,
+        base: &BenchmarkCollection,
+        thresholds: &ChangeThresholds,
+    ) -> String {
+        let base_records = latest_records(&base.successful_records);
+        let records = latest_records(&self.successful_records);
+        let keys: BTreeSet<&RecordKey> = base_records.keys().chain(records.keys()).collect();
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        // Only display the numbers of threads in a thread count sweep
+        let swept = keys.iter().any(|(.., threads)| threads.is_some());
+        let mut header = vec!["Benchmark", "Shapes", "DType", "Feature"];
+        if swept {
+            header.push("Threads");
+        }
+        header.extend(["Base", "New", "Change"]);
+        table.set_header(header);
+
+        let (mut regressions, mut improvements, mut compared) = (0, 0, 0);
+        for key in keys {
+            let (name, shapes, dtype, feature, threads) = key;
+            let base_record = base_records.get(key);
+            let record = records.get(key);
+            let mut row = vec![
+                Cell::new(name).fg(Color::Green),
+                Cell::new(format!("{}", ShapeFmt::new(shapes))),
+                Cell::new(dtype.as_deref().unwrap_or("-")),
+                Cell::new(feature),
+            ];
+            if swept {
+                row.push(Cell::new(
+                    threads.map_or("-".to_string(), |t| t.to_string()),
+                ));
+            }
+            let median_cell = |record: Option<&&BenchmarkRecord>| {
+                let label = record.map_or("-".to_string(), |r| {
+                    format!("{:.3?}", r.results.computed.median)
+                });
+                Cell::new(label).set_alignment(CellAlignment::Right)
+            };
+            row.extend([median_cell(base_record), median_cell(record)]);
+            let change_cell = match (base_record, record) {
+                (Some(base_record), Some(record)) => {
+                    let change = relative_change(
+                        record.results.computed.median,
+                        base_record.results.computed.median,
+                    );
+                    let p_value = mann_whitney_p_value(
+                        &base_record.results.raw.durations,
+                        &record.results.raw.durations,
+                    );
+                    compared += 1;
+                    let significant = self.significant_change(change, p_value);
+                    match significant.and_then(|c| thresholds.color(c)) {
+                        Some(Color::Red) => regressions += 1,
+                        Some(_) => improvements += 1,
+                        None => {}
+                    }
+                    let cell = match change {
+                        Some(change) => Cell::new(self.change_label(change, p_value)),
+                        None => Cell::new("-"),
+                    };
+                    thresholds.highlight(cell, significant)
+                }
+                (None, _) => Cell::new("new").fg(Color::Yellow),
+                (_, None) => Cell::new("removed").fg(Color::Yellow),
+            };
+            row.push(change_cell.set_alignment(CellAlignment::Right));
+            table.add_row(row);
+        }
+
+        format!(
+            "{table}\n\nCompared benchmarks: {compared}, regressions: {regressions}, improvements: {improvements}{}",
+            self.noise_footer()
+        )
+    }
+
+    /// Summarize the performance of each backend relative to the reference backend.
+    ///
+    /// The score of a backend is the geometric mean of its median durations divided by the
+    /// median durations of the reference backend, over the benchmarks executed on both.
+    pub(crate) fn get_summary(&self, reference: &str) -> Option<String> {
+        let mut reference_medians = HashMap::new();
+        for record in self
+            .successful_records
+            .iter()
+            .filter(|r| r.feature == reference)
+        {
+            reference_medians.insert(
+                (
+                    &record.results.name,
+                    &record.results.shapes,
+                    &record.burn_version,
+                    &record.dtype,
+                    record.threads,
+                ),
+                record.results.computed.median,
+            );
+        }
+
+        let mut ratios: BTreeMap<(&str, &str), Vec<f64>> = BTreeMap::new();
+        for record in self
+            .successful_records
+            .iter()
+            .filter(|r| r.feature != reference)
+        {
+            let key = (
+                &record.results.name,
+                &record.results.shapes,
+                &record.burn_version,
+                &record.dtype,
+                record.threads,
+            );
+            let median = record.results.computed.median;
+            if let Some(reference_median) = reference_medians.get(&key)
+                && !reference_median.is_zero()
+                && !median.is_zero()
+            {
+                ratios
+                    .entry((record.feature.as_str(), record.burn_version.as_str()))
+                    .or_default()
+                    .push(median.as_secs_f64() / reference_median.as_secs_f64());
+            }
+        }
+        if ratios.is_empty() {
+            return None;
+        }
+
+        let versions: HashSet<&str> = ratios.keys().map(|(_, version)| *version).collect();
+        let mut summary = String::from("Summary (geometric mean of the median ratios):");
+        for ((feature, version), ratios) in ratios.iter() {
+            let score = geometric_mean(ratios);
+            let backend = if versions.len() > 1 {
+                format!("`{feature}` @ {version}")
+            } else {
+                format!("`{feature}`")
+            };
+            let comparison = if score >= 1.0 {
+                format!("{score:.2}× slower than")
+            } else {
+                format!("{:.2}× faster than", 1.0 / score)
+            };
+            summary.push_str(&format!(
+                "\n- {backend} is {comparison} `{reference}` overall ({} benchmarks)",
+                ratios.len()
+            ));
+        }
+
+        Some(summary)
+    }
+
+    /// Summarize the normalized scores of each backend per host, which compares the machines
+    /// despite their absolute differences, `None` when no record has been normalized.
+    ///
+    /// The score of a backend on a host is the geometric mean of the ratios of its medians to
+    /// the reference backend of the runs on the host.
+    pub(crate) fn get_normalized_summary(&self) -> Option<String> {
+        type ScoreKey<'a> = (&'a str, &'a str, String, &'a str);
+        let mut ratios: BTreeMap<ScoreKey, Vec<f64>> = BTreeMap::new();
+        for record in &self.successful_records {
+            let Some(normalized) = &record.normalized else {
+                continue;
+            };
+            if normalized.reference == record.feature {
+                continue;
+            }
+            ratios
+                .entry((
+                    &record.feature,
+                    &record.burn_version,
+                    record.system_info.host(),
+                    &normalized.reference,
+                ))
+                .or_default()
+                .push(normalized.ratio);
+        }
+        if ratios.is_empty() {
+            return None;
+        }
+
+        let mut summary =
+            String::from("Normalized scores (geometric mean of the median ratios on each host):");
+        for ((feature, version, host, reference), ratios) in ratios.iter() {
+            summary.push_str(&format!(
+                "\n- `{feature}` @ {version} on {host}: {:.2}× the time of `{reference}` ({} benchmarks)",
+                geometric_mean(ratios),
+                ratios.len()
+            ));
+        }
+
+        Some(summary)
+    }
+
+    /// "Binary size" section of the report, with the stripped size of the executable of each
+    /// bench per backend, `None` when no size was measured.
+    pub(crate) fn get_binary_size_table(&self) -> Option<String> {
+        if self.binary_sizes.is_empty() {
+            return None;
+        }
+        let backends: Vec<&str> = self
+            .binary_sizes
+            .iter()
+            .map(|size| size.backend.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let mut rows: BTreeMap<(&str, &str, &str), HashMap<&str, u64>> = BTreeMap::new();
+        for size in &self.binary_sizes {
+            rows.entry((&size.bench, &size.version, &size.dtype))
+                .or_default()
+                .insert(&size.backend, size.bytes);
+        }
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        let mut header = vec!["Benchmark", "Burn Version", "DType"];
+        header.extend(backends.iter());
+        table.set_header(header);
+        for ((bench, version, dtype), sizes) in rows {
+            let mut row = vec![Cell::new(bench), Cell::new(version), Cell::new(dtype)];
+            row.extend(backends.iter().map(|backend| {
+                let size = sizes.get(backend).map(|bytes| format_size(*bytes));
+                Cell::new(size.unwrap_or_else(|| "-".to_string()))
+                    .set_alignment(CellAlignment::Right)
+            }));
+            table.add_row(row);
+        }
+
+        Some(format!("Binary size (stripped):\n\n{table}"))
+    }
+
+    /// "Thread scaling" section of the report, with the median of each benchmark per number of
+    /// threads of a thread count sweep and its speedup over the fewest threads, `None` without a
+    /// sweep.
+    pub(crate) fn get_thread_scaling_table(&self) -> Option<String> {
+        let swept: Vec<&BenchmarkRecord> = self
+            .successful_records
+            .iter()
+            .filter(|record| record.threads.is_some())
+            .collect();
+        let counts: Vec<usize> = swept
+            .iter()
+            .filter_map(|record| record.threads)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if counts.len() < 2 {
+            return None;
+        }
+        type ScalingKey<'a> = (&'a str, &'a Vec<Vec<usize>>, &'a str, &'a str);
+        let mut rows: BTreeMap<ScalingKey, BTreeMap<usize, Duration>> = BTreeMap::new();
+        for record in swept {
+            rows.entry((
+                &record.results.name,
+                &record.results.shapes,
+                &record.feature,
+                &record.burn_version,
+            ))
+            .or_default()
+            .insert(
+                record.threads.unwrap_or_default(),
+                record.results.computed.median,
+            );
+        }
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        let mut header = vec![
+            "Benchmark".to_string(),
+            "Shapes".to_string(),
+            "Feature".to_string(),
+            "Burn Version".to_string(),
+        ];
+        header.extend(counts.iter().map(|count| match count {
+            1 => "1 thread".to_string(),
+            count => format!("{count} threads"),
+        }));
+        table.set_header(header);
+        for ((name, shapes, feature, version), medians) in rows {
+            // The speedups are relative to the fewest threads the benchmark ran with.
+            let reference = medians.values().next().copied().unwrap_or_default();
+            let mut row = vec![
+                Cell::new(name),
+                Cell::new(format!("{}", ShapeFmt::new(shapes))),
+                Cell::new(feature),
+                Cell::new(version),
+            ];
+            row.extend(counts.iter().map(|count| {
+                let label = match medians.get(count) {
+                    Some(median) if !median.is_zero() => format!(
+                        "{median:.3?} ({:.2}×)",
+                        reference.as_secs_f64() / median.as_secs_f64()
+                    ),
+                    Some(median) => format!("{median:.3?}"),
+                    None => "-".to_string(),
+                };
+                Cell::new(label).set_alignment(CellAlignment::Right)
+            }));
+            table.add_row(row);
+        }
+
+        Some(format!("Thread scaling:\n\n{table}"))
+    }
+
+    /// "Kernel profile" section of the report, with the summary of each profile attached to the
+    /// records with `--profile-headless`, `None` without one.
+    pub(crate) fn get_kernel_profile_table(&self) -> Option<String> {
+        // The records of a bench executable share the profile of the executable.
+        let mut profiles: Vec<(&str, &str, &KernelProfile, BTreeSet<&str>)> = vec![];
+        for record in &self.successful_records {
+            let Some(profile) = &record.kernel_profile else {
+                continue;
+            };
+            let existing = profiles.iter_mut().find(|(feature, version, p, _)| {
+                *feature == record.feature && *version == record.burn_version && *p == profile
+            });
+            match existing {
+                Some((_, _, _, names)) => {
+                    names.insert(&record.results.name);
+                }
+                None => profiles.push((
+                    &record.feature,
+                    &record.burn_version,
+                    profile,
+                    BTreeSet::from([record.results.name.as_str()]),
+                )),
+            }
+        }
+        if profiles.is_empty() {
+            return None;
+        }
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(vec![
+            "Benchmarks",
+            "Feature",
+            "Burn Version",
+            "Top Kernels",
+            "Occupancy",
+            "Memory Throughput",
+            "SM Utilization",
+        ]);
+        for (feature, version, profile, names) in profiles {
+            let kernels: Vec<String> = profile
+                .top_kernels
+                .iter()
+                .map(|kernel| {
+                    format!(
+                        "{} ({:.1}%, {} calls)",
+                        truncate_kernel_name(&kernel.name),
+                        kernel.time_percent,
+                        kernel.calls
+                    )
+                })
+                .collect();
+            table.add_row(vec![
+                Cell::new(names.into_iter().collect::<Vec<_>>().join(", ")),
+                Cell::new(feature),
+                Cell::new(version),
+                Cell::new(kernels.join("\n")),
+                Cell::new(format!("{:.1}%", profile.achieved_occupancy))
+                    .set_alignment(CellAlignment::Right),
+                Cell::new(format!("{:.1}%", profile.memory_throughput))
+                    .set_alignment(CellAlignment::Right),
+                Cell::new(format!("{:.1}%", profile.sm_utilization))
+                    .set_alignment(CellAlignment::Right),
+            ]);
+        }
+
+        Some(format!("Kernel profile:\n\n{table}"))
+    }
+
+    /// "Kernel launches" section of the report, with the kernels launched by each bench
+    /// executable traced with `--kernel-stats`, `None` without a trace.
+    pub(crate) fn get_kernel_launches_table(&self) -> Option<String> {
+        let mut rows: BTreeMap<(&str, &str, &str), KernelLaunches> = BTreeMap::new();
+        for record in &self.successful_records {
+            if let Some(launches) = record.kernel_launches {
+                rows.insert(
+                    (&record.results.name, &record.feature, &record.burn_version),
+                    launches,
+                );
+            }
+        }
+        if rows.is_empty() {
+            return None;
+        }
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(vec![
+            "Benchmark",
+            "Feature",
+            "Burn Version",
+            "Launches",
+            "Distinct Kernels",
+        ]);
+        for ((name, feature, version), launches) in rows {
+            table.add_row(vec![
+                Cell::new(name),
+                Cell::new(feature),
+                Cell::new(version),
+                Cell::new(launches.launches).set_alignment(CellAlignment::Right),
+                Cell::new(launches.distinct_kernels).set_alignment(CellAlignment::Right),
+            ]);
+        }
+
+        Some(format!("Kernel launches:\n\n{table}"))
+    }
+
+    /// "Host allocations" section of the report, with the allocations per execution of each
+    /// benchmark run with `--count-allocations`, `None` when they were not counted.
+    pub(crate) fn get_allocation_table(&self) -> Option<String> {
+        let counted: Vec<&BenchmarkRecord> = self
+            .successful_records
+            .iter()
+            .filter(|record| record.results.allocations.is_some())
+            .collect();
+        if counted.is_empty() {
+            return None;
+        }
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(vec![
+            "Benchmark",
+            "Shapes",
+            "Feature",
+            "Burn Version",
+            "Allocations",
+            "Allocated",
+            "Peak",
+        ]);
+        for record in counted {
+            let Some(allocations) = record.results.allocations else {
+                continue;
+            };
+            table.add_row(vec![
+                Cell::new(&record.results.name),
+                Cell::new(format!("{}", ShapeFmt::new(&record.results.shapes))),
+                Cell::new(&record.feature),
+                Cell::new(&record.burn_version),
+                Cell::new(format!("{:.1}", allocations.allocations))
+                    .set_alignment(CellAlignment::Right),
+                Cell::new(format_size(allocations.bytes.round() as u64))
+                    .set_alignment(CellAlignment::Right),
+                Cell::new(format_size(allocations.peak_bytes)).set_alignment(CellAlignment::Right),
+            ]);
         }
 
-        table.to_string()
+        Some(format!("Host allocations per execution:\n\n{table}"))
     }
+
+    /// Table of the throughput of the benchmarks reporting their work.
+    pub(crate) fn get_throughput_table(&self) -> Option<String> {
+        let reported: Vec<&BenchmarkRecord> = self
+            .successful_records
+            .iter()
+            .filter(|record| record.results.throughput.is_some())
+            .collect();
+        if reported.is_empty() {
+            return None;
+        }
+
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(vec![
+            "Benchmark",
+            "Shapes",
+            "Feature",
+            "Burn Version",
+            "Throughput",
+        ]);
+        for record in reported {
+            let Some(throughput) = &record.results.throughput else {
+                continue;
+            };
+            table.add_row(vec![
+                Cell::new(&record.results.name),
+                Cell::new(format!("{}", ShapeFmt::new(&record.results.shapes))),
+                Cell::new(&record.feature),
+                Cell::new(&record.burn_version),
+                Cell::new(throughput).set_alignment(CellAlignment::Right),
+            ]);
+        }
+
+        Some(format!("Throughput over the median duration:\n\n{table}"))
+    }
+}
+
+/// Kernel names with their template arguments can span hundreds of characters.
+fn truncate_kernel_name(name: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    if name.chars().count() <= MAX_CHARS {
+        return name.to_string();
+    }
+    let truncated: String = name.chars().take(MAX_CHARS - 1).collect();
+    format!("{truncated}…")
+}
+
+/// Filter applied to the benchmark records before rendering the report.
+#[derive(Debug, Clone)]
+pub(crate) enum ReportFilter {
+    /// Keep the records with a median greater or equal to the duration.
+    MinMedian(Duration),
+    /// Keep the records with a benchmark name matching the regex.
+    Name(Regex),
+    /// Keep the benchmarks slower than in the baseline version by more than the percentage.
+    OnlyRegressions(f64),
+    /// Keep the N slowest records.
+    Top(usize),
+}
+
+impl FromStr for ReportFilter {
+    type Err = String;
+
+    fn from_str(filter: &str) -> Result<Self, Self::Err> {
+        let (key, value) = match filter.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (filter, None),
+        };
+        match (key, value) {
+            ("min-median", Some(value)) => parse_duration(value).map(Self::MinMedian),
+            ("name", Some(value)) => Regex::new(value)
+                .map(Self::Name)
+                .map_err(|e| format!("invalid name regex ({e})")),
+            ("only-regressions", None) => Ok(Self::OnlyRegressions(0.0)),
+            ("only-regressions", Some(value)) => value
+                .parse()
+                .map(Self::OnlyRegressions)
+                .map_err(|_| format!("invalid regression threshold '{value}'")),
+            ("top", Some(value)) => value
+                .parse()
+                .map(Self::Top)
+                .map_err(|_| format!("invalid number of records '{value}'")),
+            _ => Err(format!(
+                "unknown report filter '{filter}', expected one of: min-median=<duration>, name=<regex>, only-regressions[=<percent>], top=<count>"
+            )),
+        }
+    }
+}
+
+/// Parse a duration with a unit suffix such as `500us`, `1.5ms`, `2s` or `30m`.
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{value}'"))?;
+    let secs = match unit {
+        "ns" => number / 1e9,
+        "us" | "µs" => number / 1e6,
+        "ms" => number / 1e3,
+        "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => {
+            return Err(format!(
+                "invalid duration unit in '{value}', expected ns, us, ms, s, m or h"
+            ));
+        }
+    };
+    Duration::try_from_secs_f64(secs).map_err(|e| format!("invalid duration '{value}' ({e})"))
+}
+
+/// Directory of the runs, holding their manifest and records.
+fn runs_dir() -> PathBuf {
+    benchmarks_cache_dir().join("runs")
+}
+
+/// Directory of the records and state of the run, so that concurrent runs don't overwrite each
+/// other's results.
+pub(crate) fn run_dir(run_id: &str) -> PathBuf {
+    runs_dir().join(run_id)
+}
+
+/// Id of the last run started.
+pub(crate) fn latest_run_id() -> Option<String> {
+    fs::read_to_string(runs_dir().join(LATEST_RUN_FILE))
+        .ok()
+        .map(|run_id| run_id.trim().to_string())
+        .filter(|run_id| !run_id.is_empty())
+}
+
+/// Sizes of the executables saved in the run directory.
+fn read_binary_sizes(run_dir: &Path) -> Vec<BinarySize> {
+    fs::read_to_string(run_dir.join(BINARY_SIZES_FILE))
+        .ok()
+        .and_then(|sizes| serde_json::from_str(&sizes).ok())
+        .unwrap_or_default()
+}
+
+/// Entries of the directory matching the predicate, sorted by name.
+fn sorted_entries(dir: &Path, predicate: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| predicate(path))
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Relative change in percent of the median against the baseline median.
+fn relative_change(median: Duration, baseline: Duration) -> Option<f64> {
+    if baseline.is_zero() {
+        return None;
+    }
+    Some((median.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0)
+}
+
+/// Key sorting the rows of the results table within their group.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, strum::Display)]
+pub(crate) enum SortBy {
+    /// Fastest first
+    #[default]
+    #[strum(to_string = "median")]
+    Median,
+    /// By benchmark, then shapes
+    #[strum(to_string = "name")]
+    Name,
+    /// By backend feature
+    #[strum(to_string = "backend")]
+    Backend,
+    /// By Burn version, in the order of the run
+    #[strum(to_string = "version")]
+    Version,
+}
+
+/// Rows of the results table kept together, separated by a line from the other groups.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, strum::Display)]
+pub(crate) enum GroupBy {
+    /// A group per benchmark and shapes
+    #[default]
+    #[strum(to_string = "bench")]
+    Bench,
+    /// A group per backend feature
+    #[strum(to_string = "backend")]
+    Backend,
+    /// A group per Burn version
+    #[strum(to_string = "version")]
+    Version,
+}
+
+impl GroupBy {
+    fn sort_key(&self) -> SortBy {
+        match self {
+            Self::Bench => SortBy::Name,
+            Self::Backend => SortBy::Backend,
+            Self::Version => SortBy::Version,
+        }
+    }
+
+    fn group<'a>(&self, record: &'a BenchmarkRecord) -> (&'a str, Option<&'a Vec<Vec<usize>>>) {
+        match self {
+            Self::Bench => (&record.results.name, Some(&record.results.shapes)),
+            Self::Backend => (&record.feature, None),
+            Self::Version => (&record.burn_version, None),
+        }
+    }
+}
+
+/// Order of the rows of the results table, by benchmark and fastest first by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct RowOrder {
+    pub(crate) sort_by: SortBy,
+    pub(crate) group_by: GroupBy,
+}
+
+/// Thresholds in percent above which a change against the baseline version is highlighted.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChangeThresholds {
+    /// Slowdown highlighted in red.
+    pub(crate) regression: f64,
+    /// Speedup highlighted in green.
+    pub(crate) improvement: f64,
+}
+
+impl ChangeThresholds {
+    fn color(&self, change: f64) -> Option<Color> {
+        if change > self.regression {
+            Some(Color::Red)
+        } else if change < -self.improvement {
+            Some(Color::Green)
+        } else {
+            None
+        }
+    }
+
+    fn highlight(&self, cell: Cell, change: Option<f64>) -> Cell {
+        match change.and_then(|c| self.color(c)) {
+            Some(color) => cell.fg(color),
+            None => cell,
+        }
+    }
+}
+
+/// Label identifying the run of a record: its feature, version and tag if any.
+pub(crate) fn run_label(record: &BenchmarkRecord) -> String {
+    // Local checkouts change over time, show the commit they were benchmarked at.
+    let version = match record
+        .burn_source
+        .as_ref()
+        .and_then(|source| source.local_revision())
+    {
+        Some(revision) => format!("{} ({revision})", record.burn_version),
+        None => record.burn_version.clone(),
+    };
+    let feature = match record.threads {
+        Some(threads) => format!("{} ×{threads}", record.feature),
+        None => record.feature.clone(),
+    };
+    let label = match &record.tag {
+        Some(tag) => format!("{feature} @ {version} [{tag}]"),
+        None => format!("{feature} @ {version}"),
+    };
+    match build_config(record) {
+        Some(config) => format!("{label} {{{config}}}"),
+        None => label,
+    }
+}
+
+/// Build configuration of the record when not the default one, such as
+/// `release-lto, -C target-cpu=native`.
+pub(crate) fn build_config(record: &BenchmarkRecord) -> Option<String> {
+    let config: Vec<&str> = [record.cargo_profile.as_deref(), record.rustflags.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect();
+    (!config.is_empty()).then(|| config.join(", "))
+}
+
+/// Identity of the benchmark of the record, derived from its name for the older records.
+pub(crate) fn benchmark_id(record: &BenchmarkRecord) -> BenchmarkId {
+    record
+        .results
+        .id
+        .clone()
+        .unwrap_or_else(|| BenchmarkId::from_name(&record.results.name))
+}
+
+/// Row of the pivot table: benchmark identity, name, shapes and dtype.
+type PivotRow = (BenchmarkId, String, String, String);
+
+type RecordKey<'a> = (
+    &'a String,
+    &'a Vec<Vec<usize>>,
+    &'a Option<String>,
+    &'a String,
+    Option<usize>,
+);
+
+fn record_key(record: &BenchmarkRecord) -> RecordKey<'_> {
+    (
+        &record.results.name,
+        &record.results.shapes,
+        &record.dtype,
+        &record.feature,
+        record.threads,
+    )
+}
+
+/// Latest record of each benchmark, shapes, dtype, feature and number of threads.
+fn latest_records(records: &[BenchmarkRecord]) -> BTreeMap<RecordKey<'_>, &BenchmarkRecord> {
+    let mut latest: BTreeMap<RecordKey, &BenchmarkRecord> = BTreeMap::new();
+    for record in records {
+        let entry = latest.entry(record_key(record)).or_insert(record);
+        if record.results.timestamp > entry.results.timestamp {
+            *entry = record;
+        }
+    }
+    latest
+}
+
+type RegressionKey = (String, Vec<Vec<usize>>, Option<String>, String);
+
+fn regression_key(record: &BenchmarkRecord) -> RegressionKey {
+    (
+        record.results.name.clone(),
+        record.results.shapes.clone(),
+        record.dtype.clone(),
+        record.feature.clone(),
+    )
+}
+
+struct BaselineMedians<'a> {
+    version: &'a str,
+    records: HashMap<RecordKey<'a>, &'a BenchmarkRecord>,
+}
+
+impl BaselineMedians<'_> {
+    fn baseline(&self, record: &BenchmarkRecord) -> Option<&BenchmarkRecord> {
+        if record.burn_version == self.version {
+            return None;
+        }
+        self.records.get(&record_key(record)).copied()
+    }
+
+    /// Relative change in percent of the record median against the same benchmark and feature
+    /// in the baseline version.
+    fn change(&self, record: &BenchmarkRecord) -> Option<f64> {
+        let baseline = self.baseline(record)?;
+        relative_change(
+            record.results.computed.median,
+            baseline.results.computed.median,
+        )
+    }
+
+    /// p-value of the change of the record against the baseline version.
+    fn p_value(&self, record: &BenchmarkRecord) -> Option<f64> {
+        let baseline = self.baseline(record)?;
+        mann_whitney_p_value(
+            &baseline.results.raw.durations,
+            &record.results.raw.durations,
+        )
+    }
+}
+
+/// Columns of a pivoted table.
+#[derive(Clone, Copy)]
+enum PivotColumns {
+    /// One column per backend and version, highlighting the changes against the first version.
+    Runs,
+    /// One column per backend, version and host, highlighting the changes against the first
+    /// column.
+    Hosts,
+}
+
+enum PivotCell<'a> {
+    /// Record with its change against the baseline version and the p-value of the change.
+    Median(&'a BenchmarkRecord, Option<f64>, Option<f64>),
+    Failed,
+    Skipped,
+}
+
+/// Geometric mean of the given ratios.
+fn geometric_mean(ratios: &[f64]) -> f64 {
+    let log_sum: f64 = ratios.iter().map(|r| r.ln()).sum();
+    (log_sum / ratios.len() as f64).exp()
 }
 
 pub struct ShapeFmt<'a> {
@@ -189,3 +1694,795 @@ impl Display for ShapeFmt<'_> {
         Ok(())
     }
 }
+
+/// Escape the characters that have a special meaning in XML and HTML documents.
+pub(crate) fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::{KernelTime, NormalizedScore};
+    use crate::{AllocationMeasurement, Throughput};
+
+    #[test]
+    fn test_run_label_of_dirty_local_checkout() {
+        let record = BenchmarkRecord {
+            feature: "wgpu".to_string(),
+            burn_version: "local".to_string(),
+            burn_source: Some(BurnSource::Path {
+                path: "/home/me/burn".to_string(),
+                git_hash: Some("0123abcd4567".to_string()),
+                dirty: true,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(run_label(&record), "wgpu @ local (0123abc+dirty)");
+    }
+
+    #[test]
+    fn test_run_label_of_build_config() {
+        let record = BenchmarkRecord {
+            feature: "ndarray-simd".to_string(),
+            burn_version: "main".to_string(),
+            rustflags: Some("-C target-cpu=native".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            run_label(&record),
+            "ndarray-simd @ main {-C target-cpu=native}"
+        );
+    }
+
+    #[test]
+    fn test_escape_markup_special_characters() {
+        assert_eq!(
+            escape_markup("a<b> & \"c\" 'd'"),
+            "a&lt;b&gt; &amp; &quot;c&quot; &apos;d&apos;"
+        );
+    }
+
+    #[test]
+    fn test_parse_report_filters() {
+        assert!(matches!(
+            "min-median=1.5ms".parse::<ReportFilter>(),
+            Ok(ReportFilter::MinMedian(d)) if d == Duration::from_micros(1500)
+        ));
+        assert!(matches!(
+            "only-regressions".parse::<ReportFilter>(),
+            Ok(ReportFilter::OnlyRegressions(t)) if t == 0.0
+        ));
+        assert!(matches!(
+            "only-regressions=5".parse::<ReportFilter>(),
+            Ok(ReportFilter::OnlyRegressions(t)) if t == 5.0
+        ));
+        assert!(matches!(
+            "top=10".parse::<ReportFilter>(),
+            Ok(ReportFilter::Top(10))
+        ));
+        assert!(matches!(
+            "name=^matmul".parse::<ReportFilter>(),
+            Ok(ReportFilter::Name(re)) if re.is_match("matmul-f32")
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_report_filters() {
+        assert!("min-median=10".parse::<ReportFilter>().is_err());
+        assert!("top=many".parse::<ReportFilter>().is_err());
+        assert!("slowest".parse::<ReportFilter>().is_err());
+    }
+
+    fn filter_record(name: &str, version: &str, median_ms: u64) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord {
+            feature: "cuda".to_string(),
+            burn_version: version.to_string(),
+            ..Default::default()
+        };
+        record.results.name = name.to_string();
+        record.results.computed.median = Duration::from_millis(median_ms);
+        record
+    }
+
+    fn names_and_versions(collection: &BenchmarkCollection) -> Vec<(&str, &str)> {
+        collection
+            .records()
+            .iter()
+            .map(|r| (r.results.name.as_str(), r.burn_version.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_only_regressions_filter_keeps_benchmarks_above_threshold() {
+        let mut collection = BenchmarkCollection::from_records(vec![
+            filter_record("matmul", "0.17.0", 10),
+            filter_record("conv2d", "0.17.0", 100),
+            filter_record("reduce", "0.17.0", 10),
+            filter_record("matmul", "main", 20),
+            filter_record("conv2d", "main", 105),
+            filter_record("reduce", "main", 5),
+        ]);
+
+        collection.apply_filters(&[ReportFilter::OnlyRegressions(10.0)]);
+
+        // matmul: +100%, conv2d: +5%, reduce: -50%
+        assert_eq!(
+            names_and_versions(&collection),
+            vec![("matmul", "0.17.0"), ("matmul", "main")]
+        );
+    }
+
+    #[test]
+    fn test_top_filter_keeps_slowest_records_in_run_order() {
+        let mut collection = BenchmarkCollection::from_records(vec![
+            filter_record("matmul", "main", 20),
+            filter_record("conv2d", "main", 5),
+            filter_record("reduce", "main", 30),
+            filter_record("unary", "main", 10),
+        ]);
+
+        collection.apply_filters(&[ReportFilter::Top(2)]);
+
+        assert_eq!(
+            names_and_versions(&collection),
+            vec![("matmul", "main"), ("reduce", "main")]
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("500us"), Ok(Duration::from_micros(500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("1.5h"), Ok(Duration::from_secs(90 * 60)));
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration(&format!("{}h", "9".repeat(400))).is_err());
+    }
+
+    #[test]
+    fn test_change_thresholds_colors() {
+        let thresholds = ChangeThresholds {
+            regression: 5.0,
+            improvement: 10.0,
+        };
+        assert_eq!(thresholds.color(6.0), Some(Color::Red));
+        assert_eq!(thresholds.color(-11.0), Some(Color::Green));
+        assert_eq!(thresholds.color(4.0), None);
+        assert_eq!(thresholds.color(-9.0), None);
+    }
+
+    #[test]
+    fn test_geometric_mean_of_ratios() {
+        let score = geometric_mean(&[2.0, 8.0]);
+        assert!((score - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_geometric_mean_of_inverse_ratios_is_one() {
+        let score = geometric_mean(&[0.5, 2.0]);
+        assert!((score - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_record_files_of_invocations_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let collection = BenchmarkCollection::with_run_dir(Some(dir.path().to_path_buf()));
+        let first = collection.invocation_dir().unwrap();
+        fs::write(first.join("bench_matmul_1.json"), "{}").unwrap();
+        let second = collection.invocation_dir().unwrap();
+        fs::write(second.join("bench_conv2d_2.json"), "{}").unwrap();
+        fs::write(second.join("notes.txt"), "").unwrap();
+
+        assert_eq!(
+            BenchmarkCollection::invocation_records(&second),
+            vec![second.join("bench_conv2d_2.json")]
+        );
+        assert_eq!(
+            collection.record_files(),
+            vec![
+                first.join("bench_matmul_1.json"),
+                second.join("bench_conv2d_2.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_exported_records_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let record = |name: &str| {
+            let mut record = BenchmarkRecord::default();
+            record.results.name = name.to_string();
+            record
+        };
+        let single = dir.path().join("bench_matmul_0.json");
+        fs::write(&single, serde_json::to_string(&record("matmul")).unwrap()).unwrap();
+        let array = dir.path().join("export.json");
+        let records = vec![record("matmul"), record("conv2d")];
+        fs::write(&array, serde_json::to_string(&records).unwrap()).unwrap();
+
+        let mut collection = BenchmarkCollection::from_previous_run();
+        collection.load_exported_records(&[dir.path().to_path_buf(), array]);
+
+        let mut names: Vec<&str> = collection
+            .records()
+            .iter()
+            .map(|r| r.results.name.as_str())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["conv2d", "matmul"]);
+    }
+
+    #[test]
+    fn test_thread_scaling_table_speedups() {
+        let record = |threads: usize, median_ms: u64| {
+            let mut record = BenchmarkRecord {
+                feature: "ndarray".to_string(),
+                burn_version: "main".to_string(),
+                threads: Some(threads),
+                ..Default::default()
+            };
+            record.results.name = "matmul".to_string();
+            record.results.computed.median = Duration::from_millis(median_ms);
+            record
+        };
+        let mut collection = BenchmarkCollection::with_run_dir(None);
+        collection.successful_records = vec![record(4, 25), record(1, 100)];
+
+        let table = collection.get_thread_scaling_table().unwrap();
+
+        assert!(table.contains("| 1 thread "), "{table}");
+        assert!(table.contains("100.000ms (1.00×)"), "{table}");
+        assert!(table.contains("25.000ms (4.00×)"), "{table}");
+    }
+
+    #[test]
+    fn test_kernel_profile_table_groups_the_records_of_a_profile() {
+        let profile = KernelProfile {
+            top_kernels: vec![KernelTime {
+                name: "matmul_kernel".to_string(),
+                calls: 12,
+                time_percent: 80.0,
+            }],
+            achieved_occupancy: 42.0,
+            memory_throughput: 61.5,
+            sm_utilization: 73.0,
+        };
+        let record = |name: &str| {
+            let mut record = BenchmarkRecord {
+                feature: "cuda-fusion".to_string(),
+                burn_version: "main".to_string(),
+                kernel_profile: Some(profile.clone()),
+                ..Default::default()
+            };
+            record.results.name = name.to_string();
+            record
+        };
+        let mut collection = BenchmarkCollection::with_run_dir(None);
+        collection.successful_records = vec![record("matmul"), record("matmul_transposed")];
+
+        let table = collection.get_kernel_profile_table().unwrap();
+
+        assert!(table.contains("matmul, matmul_transposed"), "{table}");
+        assert!(table.contains("matmul_kernel (80.0%, 12 calls)"), "{table}");
+        assert!(table.contains("42.0%"), "{table}");
+        assert!(table.contains("73.0%"), "{table}");
+    }
+
+    #[test]
+    fn test_kernel_launches_table() {
+        let record = |feature: &str, launches: u64, distinct_kernels: u64| {
+            let mut record = BenchmarkRecord {
+                feature: feature.to_string(),
+                burn_version: "main".to_string(),
+                kernel_launches: Some(KernelLaunches {
+                    launches,
+                    distinct_kernels,
+                }),
+                ..Default::default()
+            };
+            record.results.name = "unary".to_string();
+            record
+        };
+        let mut collection = BenchmarkCollection::with_run_dir(None);
+        collection.successful_records =
+            vec![record("cuda", 3000, 3), record("cuda-fusion", 1000, 1)];
+
+        let table = collection.get_kernel_launches_table().unwrap();
+
+        let row = |feature: &str| {
+            table
+                .lines()
+                .find(|line| line.contains(&format!(" {feature} ")))
+                .unwrap()
+        };
+        assert!(row("cuda").contains(" 3000 "), "{table}");
+        assert!(row("cuda-fusion").contains(" 1000 "), "{table}");
+    }
+
+    #[test]
+    fn test_allocation_table() {
+        let mut record = BenchmarkRecord {
+            feature: "ndarray".to_string(),
+            burn_version: "main".to_string(),
+            ..Default::default()
+        };
+        record.results.name = "unary".to_string();
+        record.results.allocations = Some(AllocationMeasurement {
+            allocations: 3.0,
+            bytes: 2048.0,
+            peak_bytes: 0,
+        });
+        let mut collection = BenchmarkCollection::with_run_dir(None);
+        collection.successful_records = vec![record, BenchmarkRecord::default()];
+
+        let table = collection.get_allocation_table().unwrap();
+
+        assert!(table.contains("| unary "), "{table}");
+        assert!(table.contains(" 3.0 "), "{table}");
+        assert_eq!(table.lines().filter(|l| l.starts_with("| ")).count(), 2);
+    }
+
+    #[test]
+    fn test_throughput_table() {
+        let mut record = BenchmarkRecord {
+            feature: "wgpu".to_string(),
+            burn_version: "main".to_string(),
+            ..Default::default()
+        };
+        record.results.name = "transformer-decoder".to_string();
+        record.results.throughput = Some(Throughput::new(128.0, "tokens", Duration::from_secs(2)));
+        let mut collection = BenchmarkCollection::with_run_dir(None);
+        collection.successful_records = vec![record, BenchmarkRecord::default()];
+
+        let table = collection.get_throughput_table().unwrap();
+
+        assert!(table.contains("| transformer-decoder "), "{table}");
+        assert!(table.contains(" 64.00 tokens/s "), "{table}");
+        assert_eq!(table.lines().filter(|l| l.starts_with("| ")).count(), 2);
+    }
+
+    #[test]
+    fn test_normalized_summary_geometric_mean() {
+        let record = |feature: &str, ratio: f64| BenchmarkRecord {
+            feature: feature.to_string(),
+            burn_version: "main".to_string(),
+            normalized: Some(NormalizedScore {
+                reference: "tch-cpu".to_string(),
+                ratio,
+            }),
+            ..Default::default()
+        };
+        let mut collection = BenchmarkCollection::with_run_dir(None);
+        collection.successful_records = vec![
+            record("tch-cpu", 1.0),
+            record("ndarray", 2.0),
+            record("ndarray", 8.0),
+        ];
+
+        let summary = collection.get_normalized_summary().unwrap();
+
+        assert!(
+            summary.contains("4.00× the time of `tch-cpu` (2 benchmarks)"),
+            "{summary}"
+        );
+        assert!(!summary.contains("`tch-cpu` @"), "{summary}");
+    }
+
+    #[test]
+    fn test_ascii_table_dtype_column() {
+        let record = |dtype: &str, median_ms: u64| {
+            let mut record = BenchmarkRecord {
+                feature: "cuda".to_string(),
+                dtype: Some(dtype.to_string()),
+                burn_version: "main".to_string(),
+                ..Default::default()
+            };
+            record.results.name = "matmul".to_string();
+            record.results.computed.median = Duration::from_millis(median_ms);
+            record
+        };
+        let collection =
+            BenchmarkCollection::from_records(vec![record("f32", 20), record("f16", 10)]);
+
+        let table = collection.get_ascii_table(
+            &ChangeThresholds {
+                regression: 5.0,
+                improvement: 5.0,
+            },
+            RowOrder::default(),
+        );
+
+        let rows: Vec<&str> = table.lines().filter(|l| l.contains("matmul")).collect();
+        assert_eq!(rows.len(), 2, "{table}");
+        assert!(rows[0].contains("f16"), "{table}");
+        assert!(rows[1].contains("f32"), "{table}");
+    }
+
+    /// Cells of the rows of a rendered table, the header included, without their colors.
+    fn table_cells(table: &str) -> Vec<Vec<String>> {
+        let colors = Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        colors
+            .replace_all(table, "")
+            .lines()
+            .filter(|l| l.starts_with("| "))
+            .map(|l| {
+                l.trim_matches('|')
+                    .split('|')
+                    .map(|cell| cell.trim().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ascii_table_failed_row_next_to_configured_record() {
+        let mut record = BenchmarkRecord {
+            feature: "ndarray".to_string(),
+            dtype: Some("f32".to_string()),
+            burn_version: "main".to_string(),
+            rustflags: Some("-C target-cpu=native".to_string()),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        let mut collection = BenchmarkCollection::from_records(vec![record]);
+        collection.push_failed_benchmark(FailedBenchmark {
+            bench: "conv2d".to_string(),
+            backend: "ndarray".to_string(),
+            version: "main".to_string(),
+            dtype: "f16".to_string(),
+            log_path: None,
+        });
+
+        let table = collection.get_ascii_table(
+            &ChangeThresholds {
+                regression: 5.0,
+                improvement: 5.0,
+            },
+            RowOrder::default(),
+        );
+
+        let cells = table_cells(&table);
+        let dtype = cells[0].iter().position(|c| c == "DType").unwrap();
+        let failed = cells.iter().find(|row| row[0] == "conv2d").unwrap();
+        assert_eq!(failed.len(), cells[0].len(), "{table}");
+        assert_eq!(failed[dtype], "f16", "{table}");
+    }
+
+    #[test]
+    fn test_ascii_table_skipped_row_in_thread_sweep() {
+        let mut record = BenchmarkRecord {
+            feature: "ndarray".to_string(),
+            dtype: Some("f32".to_string()),
+            burn_version: "main".to_string(),
+            threads: Some(4),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        let mut collection = BenchmarkCollection::from_records(vec![record]);
+        collection.push_skipped_benchmark(SkippedBenchmark {
+            bench: "conv2d".to_string(),
+            backend: "ndarray".to_string(),
+            version: "main".to_string(),
+            dtype: "f16".to_string(),
+            reason: "time budget exhausted".to_string(),
+        });
+
+        let table = collection.get_ascii_table(
+            &ChangeThresholds {
+                regression: 5.0,
+                improvement: 5.0,
+            },
+            RowOrder::default(),
+        );
+
+        let cells = table_cells(&table);
+        let dtype = cells[0].iter().position(|c| c == "DType").unwrap();
+        let skipped = cells.iter().find(|row| row[0] == "conv2d").unwrap();
+        assert_eq!(skipped.len(), cells[0].len(), "{table}");
+        assert_eq!(skipped[dtype], "f16", "{table}");
+    }
+
+    #[test]
+    fn test_summary_matches_thread_counts() {
+        let record = |feature: &str, threads: usize, median_ms: u64| {
+            let mut record = BenchmarkRecord {
+                feature: feature.to_string(),
+                burn_version: "main".to_string(),
+                threads: Some(threads),
+                ..Default::default()
+            };
+            record.results.name = "matmul".to_string();
+            record.results.computed.median = Duration::from_millis(median_ms);
+            record
+        };
+        let collection = BenchmarkCollection::from_records(vec![
+            record("tch-cpu", 1, 40),
+            record("tch-cpu", 8, 10),
+            record("ndarray", 1, 80),
+            record("ndarray", 8, 20),
+        ]);
+
+        let summary = collection.get_summary("tch-cpu").unwrap();
+
+        assert!(
+            summary.contains("`ndarray` is 2.00× slower than `tch-cpu` overall (2 benchmarks)"),
+            "{summary}"
+        );
+    }
+
+    #[test]
+    fn test_summary_and_fusion_speedup_match_dtypes() {
+        let record = |feature: &str, dtype: &str, median_ms: u64| {
+            let mut record = BenchmarkRecord {
+                feature: feature.to_string(),
+                dtype: Some(dtype.to_string()),
+                burn_version: "main".to_string(),
+                ..Default::default()
+            };
+            record.results.name = "matmul".to_string();
+            record.results.computed.median = Duration::from_millis(median_ms);
+            record
+        };
+        let collection = BenchmarkCollection::from_records(vec![
+            record("cuda", "f32", 40),
+            record("cuda", "f16", 10),
+            record("cuda-fusion", "f32", 20),
+            record("cuda-fusion", "f16", 10),
+        ]);
+
+        let summary = collection.get_summary("cuda").unwrap();
+        let speedups: Vec<Option<f64>> = collection
+            .records()
+            .iter()
+            .map(|r| collection.fusion_speedup(r))
+            .collect();
+
+        // f32: 20 / 40, f16: 10 / 10
+        assert!(
+            summary.contains("`cuda-fusion` is 1.41× faster than `cuda` overall (2 benchmarks)"),
+            "{summary}"
+        );
+        assert_eq!(speedups, vec![None, None, Some(2.0), Some(1.0)]);
+    }
+
+    fn fusion_record(
+        feature: &str,
+        version: &str,
+        tag: Option<&str>,
+        median_ms: u64,
+    ) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord {
+            feature: feature.to_string(),
+            burn_version: version.to_string(),
+            tag: tag.map(str::to_string),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        record.results.computed.median = Duration::from_millis(median_ms);
+        record
+    }
+
+    #[test]
+    fn test_fusion_speedup_over_unfused_backend() {
+        let collection = BenchmarkCollection::from_records(vec![
+            fusion_record("cuda", "main", None, 30),
+            fusion_record("cuda-fusion", "main", None, 10),
+        ]);
+
+        let speedups: Vec<Option<f64>> = collection
+            .records()
+            .iter()
+            .map(|r| collection.fusion_speedup(r))
+            .collect();
+
+        assert_eq!(speedups, vec![None, Some(3.0)]);
+    }
+
+    #[test]
+    fn test_fusion_speedup_ignores_other_versions_and_tags() {
+        let collection = BenchmarkCollection::from_records(vec![
+            fusion_record("cuda", "0.17.0", None, 30),
+            fusion_record("cuda", "main", Some("before"), 30),
+            fusion_record("cuda-fusion", "main", None, 10),
+        ]);
+
+        let fused = &collection.records()[2];
+
+        assert_eq!(collection.fusion_speedup(fused), None);
+    }
+
+    #[test]
+    fn test_pivot_table_of_two_backends() {
+        let record = |name: &str, feature: &str, median_ms: u64| {
+            let mut record = BenchmarkRecord {
+                feature: feature.to_string(),
+                burn_version: "main".to_string(),
+                ..Default::default()
+            };
+            record.results.name = name.to_string();
+            record.results.computed.median = Duration::from_millis(median_ms);
+            record
+        };
+        let collection = BenchmarkCollection::from_records(vec![
+            record("matmul", "cuda", 20),
+            record("conv2d", "cuda", 10),
+            record("matmul", "wgpu", 10),
+            record("conv2d", "wgpu", 10),
+        ]);
+
+        let table = collection.get_pivot_table(&ChangeThresholds {
+            regression: 5.0,
+            improvement: 5.0,
+        });
+
+        assert_eq!(
+            table_cells(&table),
+            vec![
+                vec![
+                    "Benchmark",
+                    "Shapes",
+                    "DType",
+                    "`cuda @ main`",
+                    "`wgpu @ main`"
+                ],
+                vec!["conv2d", "()", "-", "10.000ms", "10.000ms"],
+                vec!["matmul", "()", "-", "20.000ms", "10.000ms (-50.0%)"],
+            ],
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn test_pivot_table_failed_cell() {
+        let mut record = BenchmarkRecord {
+            feature: "cuda".to_string(),
+            dtype: Some("f32".to_string()),
+            burn_version: "main".to_string(),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        record.results.computed.median = Duration::from_millis(20);
+        let mut collection = BenchmarkCollection::from_records(vec![record]);
+        collection.push_failed_benchmark(FailedBenchmark {
+            bench: "matmul".to_string(),
+            backend: "wgpu".to_string(),
+            version: "main".to_string(),
+            dtype: "f32".to_string(),
+            log_path: None,
+        });
+
+        let table = collection.get_pivot_table(&ChangeThresholds {
+            regression: 5.0,
+            improvement: 5.0,
+        });
+
+        // The failed benchmark has no shapes, it gets a row of its own
+        assert_eq!(
+            table_cells(&table),
+            vec![
+                vec![
+                    "Benchmark",
+                    "Shapes",
+                    "DType",
+                    "`cuda @ main`",
+                    "`wgpu @ main`"
+                ],
+                vec!["matmul", "()", "f32", "20.000ms", "-"],
+                vec!["matmul", "-", "f32", "-", "FAILED"],
+            ],
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn test_ascii_table_grouped_by_version() {
+        let record = |name: &str, version: &str, median_ms: u64| {
+            let mut record = BenchmarkRecord {
+                feature: "cuda".to_string(),
+                burn_version: version.to_string(),
+                ..Default::default()
+            };
+            record.results.name = name.to_string();
+            record.results.computed.median = Duration::from_millis(median_ms);
+            record
+        };
+        let collection = BenchmarkCollection::from_records(vec![
+            record("matmul", "main", 20),
+            record("conv2d", "main", 10),
+            record("matmul", "0.17.0", 30),
+            record("conv2d", "0.17.0", 5),
+        ]);
+
+        let table = collection.get_ascii_table(
+            &ChangeThresholds {
+                regression: 5.0,
+                improvement: 5.0,
+            },
+            RowOrder {
+                sort_by: SortBy::Name,
+                group_by: GroupBy::Version,
+            },
+        );
+
+        let rows: Vec<&str> = table
+            .lines()
+            .filter(|l| l.starts_with("| "))
+            .skip(1)
+            .collect();
+        assert_eq!(rows.len(), 5, "{table}");
+        assert!(
+            rows[0].contains("conv2d") && rows[0].contains("main"),
+            "{table}"
+        );
+        assert!(
+            rows[1].contains("matmul") && rows[1].contains("main"),
+            "{table}"
+        );
+        assert!(rows[2].contains("----"), "{table}");
+        assert!(
+            rows[3].contains("conv2d") && rows[3].contains("0.17.0"),
+            "{table}"
+        );
+        assert!(
+            rows[4].contains("matmul") && rows[4].contains("0.17.0"),
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn test_diff_table_counts_regressions() {
+        let record = |name: &str, timestamp: u128, median_ms: u64| {
+            let mut record = BenchmarkRecord {
+                feature: "cuda".to_string(),
+                ..Default::default()
+            };
+            record.results.name = name.to_string();
+            record.results.timestamp = timestamp;
+            record.results.computed.median = Duration::from_millis(median_ms);
+            record
+        };
+        let base = BenchmarkCollection::from_records(vec![
+            record("matmul", 1, 30),
+            record("matmul", 2, 10),
+            record("conv2d", 2, 10),
+        ]);
+        let new = BenchmarkCollection::from_records(vec![
+            record("matmul", 3, 20),
+            record("reduce", 3, 5),
+        ]);
+
+        let table = new.get_diff_table(
+            &base,
+            &ChangeThresholds {
+                regression: 5.0,
+                improvement: 5.0,
+            },
+        );
+
+        assert!(table.contains("+100.0%"), "{table}");
+        assert!(table.contains("removed"), "{table}");
+        assert!(table.contains("new"), "{table}");
+        assert!(
+            table.contains("Compared benchmarks: 1, regressions: 1, improvements: 0"),
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn test_escape_markup_leaves_plain_text_untouched() {
+        assert_eq!(escape_markup("matmul-f32"), "matmul-f32");
+    }
+}
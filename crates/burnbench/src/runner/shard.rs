@@ -0,0 +1,63 @@
+use std::{fmt, str::FromStr};
+
+/// Part of the version × backend × bench × dtype matrix run by one of several runners, given as
+/// `<index>/<count>` with the index starting at 1.
+///
+/// The matrix is enumerated in the same order on every runner and its entries are dealt to the
+/// shards in turn, so that the runners given the same arguments run disjoint parts of similar
+/// sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Shard {
+    index: usize,
+    count: usize,
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid shard '{s}', expected <index>/<count> such as 2/5");
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index: usize = index.parse().map_err(|_| invalid())?;
+        let count: usize = count.parse().map_err(|_| invalid())?;
+        if index == 0 || index > count {
+            return Err(invalid());
+        }
+        Ok(Self { index, count })
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+impl Shard {
+    /// Whether the entry at the position in the enumerated matrix belongs to the shard.
+    pub(crate) fn contains(&self, position: usize) -> bool {
+        position % self.count == self.index - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shard() {
+        assert_eq!("2/5".parse::<Shard>(), Ok(Shard { index: 2, count: 5 }));
+        assert!("0/5".parse::<Shard>().is_err());
+        assert!("6/5".parse::<Shard>().is_err());
+        assert!("2".parse::<Shard>().is_err());
+    }
+
+    #[test]
+    fn test_shards_are_disjoint_and_complete() {
+        let shards: Vec<Shard> = (1..=3).map(|i| Shard { index: i, count: 3 }).collect();
+        for position in 0..10 {
+            let owners = shards.iter().filter(|s| s.contains(position)).count();
+            assert_eq!(owners, 1);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+/// p-value below which a change between two versions is considered significant.
+pub(crate) const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Minimum number of samples per side for the normal approximation of the test to hold.
+const MIN_SAMPLES: usize = 5;
+
+/// Two-sided p-value of the Mann-Whitney U test comparing the raw durations of two records.
+///
+/// The test makes no assumption on the distribution of the durations, which are usually skewed
+/// by outliers. Return `None` when there are too few samples.
+pub(crate) fn mann_whitney_p_value(a: &[Duration], b: &[Duration]) -> Option<f64> {
+    let (n1, n2) = (a.len(), b.len());
+    if n1 < MIN_SAMPLES || n2 < MIN_SAMPLES {
+        return None;
+    }
+
+    let mut samples: Vec<(Duration, bool)> = a
+        .iter()
+        .map(|d| (*d, true))
+        .chain(b.iter().map(|d| (*d, false)))
+        .collect();
+    samples.sort_by_key(|(d, _)| *d);
+
+    // Ranks start at 1, tied samples share the average of their ranks.
+    let n = samples.len();
+    let mut rank_sum_a = 0.0;
+    let mut tie_correction = 0.0;
+    let mut start = 0;
+    while start < n {
+        let mut end = start + 1;
+        while end < n && samples[end].0 == samples[start].0 {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        rank_sum_a += rank * samples[start..end].iter().filter(|(_, a)| *a).count() as f64;
+        let ties = (end - start) as f64;
+        tie_correction += ties.powi(3) - ties;
+        start = end;
+    }
+
+    let (n1, n2, n) = (n1 as f64, n2 as f64, n as f64);
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        // All the samples are equal.
+        return Some(1.0);
+    }
+    // Continuity correction of the normal approximation.
+    let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+
+    Some(erfc(z / std::f64::consts::SQRT_2).min(1.0))
+}
+
+/// Significance of the p-value, as displayed next to the changes.
+pub(crate) fn format_p_value(p_value: f64) -> String {
+    if p_value < 0.001 {
+        "p<0.001".to_string()
+    } else {
+        format!("p={p_value:.3}")
+    }
+}
+
+/// Complementary error function, with a fractional error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let poly = -1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let value = t * (-x * x + poly).exp();
+    if x >= 0.0 { value } else { 2.0 - value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: impl IntoIterator<Item = u64>) -> Vec<Duration> {
+        values.into_iter().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_mann_whitney_separated_samples() {
+        let p_value = mann_whitney_p_value(&millis(1..=5), &millis(6..=10)).unwrap();
+        // Asymptotic p-value with continuity correction.
+        assert!((p_value - 0.0122).abs() < 1e-3, "{p_value}");
+    }
+
+    #[test]
+    fn test_mann_whitney_identical_samples() {
+        let samples = millis([10, 11, 12, 11, 10, 12]);
+        let p_value = mann_whitney_p_value(&samples, &samples).unwrap();
+        assert!(p_value > 0.9, "{p_value}");
+        assert_eq!(
+            mann_whitney_p_value(&millis([5; 8]), &millis([5; 8])),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_mann_whitney_requires_enough_samples() {
+        assert_eq!(mann_whitney_p_value(&millis(1..=3), &millis(1..=10)), None);
+    }
+
+    #[test]
+    fn test_format_p_value() {
+        assert_eq!(format_p_value(0.0001), "p<0.001");
+        assert_eq!(format_p_value(0.0123), "p=0.012");
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use super::reports::run_dir;
+
+/// Combination of benchmarks, backend, version and dtype executed by a single cargo invocation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct Combination {
+    pub(crate) bench: String,
+    pub(crate) backend: String,
+    pub(crate) version: String,
+    pub(crate) dtype: String,
+    /// Number of threads of a CPU backend in a thread count sweep.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) threads: Option<usize>,
+}
+
+/// Completion state of a run, persisted after every combination so that an interrupted run can
+/// be resumed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct RunState {
+    completed: Vec<Combination>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl RunState {
+    /// Start the state of a new run in its directory, leaving the state of the other runs as
+    /// is.
+    pub(crate) fn new(run_id: &str) -> Self {
+        Self {
+            completed: vec![],
+            path: Self::path(run_id),
+        }
+    }
+
+    /// Load the state of the run to resume it, or start its state when it has none.
+    pub(crate) fn resume(run_id: &str) -> Self {
+        let path = Self::path(run_id);
+        let mut state = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .unwrap_or_default();
+        state.path = path;
+        state
+    }
+
+    fn path(run_id: &str) -> PathBuf {
+        run_dir(run_id).join("run_state.json")
+    }
+
+    pub(crate) fn is_completed(&self, combination: &Combination) -> bool {
+        self.completed.contains(combination)
+    }
+
+    pub(crate) fn num_completed(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Record the combination as completed and persist the state on disk.
+    pub(crate) fn mark_completed(&mut self, combination: Combination) -> io::Result<()> {
+        if !self.is_completed(&combination) {
+            self.completed.push(combination);
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combination(backend: &str) -> Combination {
+        Combination {
+            bench: "matmul".to_string(),
+            backend: backend.to_string(),
+            version: "main".to_string(),
+            dtype: "f32".to_string(),
+            threads: None,
+        }
+    }
+
+    #[test]
+    fn test_completed_combinations_survive_serialization() {
+        let state = RunState {
+            completed: vec![combination("wgpu")],
+            path: PathBuf::new(),
+        };
+        let content = serde_json::to_string(&state).unwrap();
+        let state: RunState = serde_json::from_str(&content).unwrap();
+
+        assert!(state.is_completed(&combination("wgpu")));
+        assert!(!state.is_completed(&combination("cuda")));
+    }
+}
@@ -0,0 +1,81 @@
+use std::{str::FromStr, thread::available_parallelism};
+
+/// Variables setting the number of threads of the thread pools used by the CPU backends: rayon
+/// for ndarray and candle, OpenMP and the BLAS libraries for libtorch and ndarray.
+const THREAD_ENVS: [&str; 4] = [
+    "RAYON_NUM_THREADS",
+    "OMP_NUM_THREADS",
+    "MKL_NUM_THREADS",
+    "OPENBLAS_NUM_THREADS",
+];
+
+/// Number of threads of a CPU backend in a thread count sweep, `max` being the available
+/// parallelism of the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ThreadCount {
+    Count(usize),
+    Max,
+}
+
+impl FromStr for ThreadCount {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "max" => Ok(Self::Max),
+            count => match count.parse() {
+                Ok(count) if count > 0 => Ok(Self::Count(count)),
+                _ => Err(format!(
+                    "invalid thread count '{s}', expected a positive number or max"
+                )),
+            },
+        }
+    }
+}
+
+/// Sorted and deduplicated numbers of threads of the sweep.
+pub(crate) fn resolve_thread_counts(counts: &[ThreadCount]) -> Vec<usize> {
+    let max = available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let mut counts: Vec<usize> = counts
+        .iter()
+        .map(|count| match count {
+            ThreadCount::Count(count) => *count,
+            ThreadCount::Max => max,
+        })
+        .collect();
+    counts.sort();
+    counts.dedup();
+    counts
+}
+
+/// Variables limiting the backend to the number of threads, with the one recording it in the
+/// results.
+pub(crate) fn thread_envs(threads: usize) -> Vec<(String, String)> {
+    THREAD_ENVS
+        .iter()
+        .chain(["BURN_BENCH_THREADS"].iter())
+        .map(|key| (key.to_string(), threads.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_thread_counts() {
+        assert_eq!("4".parse::<ThreadCount>(), Ok(ThreadCount::Count(4)));
+        assert_eq!("max".parse::<ThreadCount>(), Ok(ThreadCount::Max));
+        assert!("0".parse::<ThreadCount>().is_err());
+        assert!("all".parse::<ThreadCount>().is_err());
+
+        assert_eq!(
+            resolve_thread_counts(&[
+                ThreadCount::Count(16),
+                ThreadCount::Count(1),
+                ThreadCount::Count(16)
+            ]),
+            vec![1, 16]
+        );
+    }
+}
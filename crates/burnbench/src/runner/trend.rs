@@ -0,0 +1,470 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use comfy_table::{Cell, CellAlignment, Color, Table};
+use strum::Display;
+
+use super::reports::ShapeFmt;
+use super::significance::{SIGNIFICANCE_LEVEL, mann_whitney_p_value};
+use crate::persistence::BenchmarkRecord;
+
+/// Default threshold in percent below which the shifts are not reported.
+pub(crate) const DEFAULT_SHIFT_THRESHOLD: f64 = 10.0;
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Minimum number of points at the level of a series before a shift from it can be detected.
+const MIN_LEVEL_POINTS: usize = 5;
+/// Deviation, in standard deviations of the level, ignored by the CUSUM at each point.
+const CUSUM_SLACK: f64 = 0.5;
+/// Cumulated deviation, in standard deviations of the level, above which a shift is detected.
+const CUSUM_THRESHOLD: f64 = 5.0;
+/// Minimum standard deviation of the log of the medians, about 1%, so that a perfectly stable
+/// level doesn't turn every small change into a shift.
+const MIN_LOG_STD: f64 = 0.01;
+
+/// Points of the trend of a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Display)]
+pub(crate) enum TrendGrouping {
+    /// One point per Burn version, in the order they were first benchmarked
+    #[strum(to_string = "version")]
+    Version,
+    /// One point per day
+    #[strum(to_string = "date")]
+    Date,
+    /// One point per run
+    #[strum(to_string = "run")]
+    Run,
+}
+
+/// Median of a benchmark over the records of a version or a day.
+#[derive(Debug, Clone)]
+pub(crate) struct TrendPoint {
+    /// Version or day of the point.
+    pub(crate) label: String,
+    /// Median of the medians of the records.
+    pub(crate) median: Duration,
+    /// Raw durations of the records, to test the significance of the changes.
+    pub(crate) durations: Vec<Duration>,
+}
+
+/// Medians over time of a benchmark, shapes and feature on a host.
+#[derive(Debug, Clone)]
+pub(crate) struct Trend {
+    pub(crate) name: String,
+    pub(crate) shapes: Vec<Vec<usize>>,
+    pub(crate) feature: String,
+    pub(crate) host: String,
+    pub(crate) points: Vec<TrendPoint>,
+}
+
+/// Shift of the level of a trend.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ChangePoint {
+    /// Index of the first point at the new level.
+    pub(crate) index: usize,
+    /// Index of the point at which the shift was detected, the shifts detected at the last
+    /// point being the new ones.
+    pub(crate) detected: usize,
+    /// Relative change in percent of the new level against the previous one.
+    pub(crate) change: f64,
+}
+
+/// Trends of the records, keeping the last points of each.
+pub(crate) fn trends(
+    records: &[BenchmarkRecord],
+    grouping: TrendGrouping,
+    last: usize,
+) -> Vec<Trend> {
+    let mut records: Vec<&BenchmarkRecord> = records.iter().collect();
+    records.sort_by_key(|record| record.results.timestamp);
+
+    type TrendKey = (String, Vec<Vec<usize>>, String, String);
+    let mut series: BTreeMap<TrendKey, Vec<(String, String, Vec<&BenchmarkRecord>)>> =
+        BTreeMap::new();
+    for record in records {
+        let date = |format| {
+            chrono::DateTime::from_timestamp_millis(record.results.timestamp as i64)
+                .map(|date| date.format(format).to_string())
+                .unwrap_or_default()
+        };
+        let (group, label) = match grouping {
+            TrendGrouping::Version => (record.burn_version.clone(), record.burn_version.clone()),
+            TrendGrouping::Date => (date("%Y-%m-%d"), date("%Y-%m-%d")),
+            // The records of a run are labeled with the time of its first record.
+            TrendGrouping::Run => (
+                record
+                    .run_id
+                    .clone()
+                    .unwrap_or_else(|| record.results.timestamp.to_string()),
+                date("%Y-%m-%d %H:%M"),
+            ),
+        };
+        let groups = series
+            .entry((
+                record.results.name.clone(),
+                record.results.shapes.clone(),
+                record.feature.clone(),
+                record.system_info.host(),
+            ))
+            .or_default();
+        // The points are ordered by the first record of their version, day or run.
+        match groups.iter_mut().find(|(key, ..)| *key == group) {
+            Some((.., group)) => group.push(record),
+            None => groups.push((group, label, vec![record])),
+        }
+    }
+
+    series
+        .into_iter()
+        .map(|((name, shapes, feature, host), groups)| {
+            let skipped = groups.len().saturating_sub(last);
+            let points = groups
+                .into_iter()
+                .skip(skipped)
+                .map(|(_, label, records)| {
+                    let mut medians: Vec<Duration> = records
+                        .iter()
+                        .map(|record| record.results.computed.median)
+                        .collect();
+                    medians.sort();
+                    TrendPoint {
+                        label,
+                        median: medians[medians.len() / 2],
+                        durations: records
+                            .iter()
+                            .flat_map(|record| record.results.raw.durations.iter().copied())
+                            .collect(),
+                    }
+                })
+                .collect();
+            Trend {
+                name,
+                shapes,
+                feature,
+                host,
+                points,
+            }
+        })
+        .collect()
+}
+
+/// Shifts of the level of the medians, detected by a two-sided CUSUM on their logarithm.
+///
+/// The deviations of the points are measured in standard deviations of the current level, made
+/// of the points since the previous shift, and a shift is reported when they accumulate beyond
+/// [CUSUM_THRESHOLD]. It must also be larger than the threshold in percent, and statistically
+/// significant when enough samples are available.
+pub(crate) fn change_points(points: &[TrendPoint], threshold: f64) -> Vec<ChangePoint> {
+    let logs: Vec<f64> = points
+        .iter()
+        .map(|point| point.median.as_secs_f64().max(f64::MIN_POSITIVE).ln())
+        .collect();
+    let mut change_points = vec![];
+    // Start of the current level, and of the current excursion from it.
+    let (mut start, mut onset) = (0, 0);
+    let (mut high, mut low) = (0.0, 0.0);
+    for (i, log) in logs.iter().enumerate() {
+        if high == 0.0 && low == 0.0 {
+            onset = i;
+        }
+        if onset - start < MIN_LEVEL_POINTS {
+            continue;
+        }
+        let (mean, std) = mean_std(&logs[start..onset]);
+        let deviation = (log - mean) / std.max(MIN_LOG_STD);
+        high = f64::max(0.0, high + deviation - CUSUM_SLACK);
+        low = f64::max(0.0, low - deviation - CUSUM_SLACK);
+        if high <= CUSUM_THRESHOLD && low <= CUSUM_THRESHOLD {
+            continue;
+        }
+
+        let (shifted, _) = mean_std(&logs[onset..=i]);
+        let change = ((shifted - mean).exp() - 1.0) * 100.0;
+        let durations = |points: &[TrendPoint]| -> Vec<Duration> {
+            points
+                .iter()
+                .flat_map(|point| point.durations.iter().copied())
+                .collect()
+        };
+        let p_value = mann_whitney_p_value(
+            &durations(&points[start..onset]),
+            &durations(&points[onset..=i]),
+        );
+        if change.abs() > threshold && p_value.is_none_or(|p| p < SIGNIFICANCE_LEVEL) {
+            change_points.push(ChangePoint {
+                index: onset,
+                detected: i,
+                change,
+            });
+            start = onset;
+        }
+        (high, low) = (0.0, 0.0);
+    }
+    change_points
+}
+
+fn mean_std(values: &[f64]) -> (f64, f64) {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    (mean, variance.sqrt())
+}
+
+/// Sparkline of the medians, from the fastest to the slowest.
+pub(crate) fn sparkline(medians: &[Duration]) -> String {
+    let (Some(min), Some(max)) = (medians.iter().min(), medians.iter().max()) else {
+        return String::new();
+    };
+    let range = (*max - *min).as_secs_f64();
+    medians
+        .iter()
+        .map(|median| {
+            if range == 0.0 {
+                return SPARKLINE_LEVELS[SPARKLINE_LEVELS.len() / 2];
+            }
+            let level =
+                (*median - *min).as_secs_f64() / range * (SPARKLINE_LEVELS.len() - 1) as f64;
+            SPARKLINE_LEVELS[level.round() as usize]
+        })
+        .collect()
+}
+
+/// Table of the trends with their sparkline, first and last medians, and change-points.
+pub(crate) fn get_trend_table(trends: &[Trend], threshold: f64) -> String {
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Benchmark",
+        "Shapes",
+        "Feature",
+        "Host",
+        "Trend",
+        "First",
+        "Last",
+        "Change-points",
+    ]);
+    for trend in trends {
+        let (Some(first), Some(last)) = (trend.points.first(), trend.points.last()) else {
+            continue;
+        };
+        let medians: Vec<Duration> = trend.points.iter().map(|point| point.median).collect();
+        let changes = change_points(&trend.points, threshold);
+        let labels: Vec<String> = changes
+            .iter()
+            .map(|change| {
+                let arrow = if change.change > 0.0 { "↑" } else { "↓" };
+                format!(
+                    "{arrow} {:+.1}% at {}",
+                    change.change, trend.points[change.index].label
+                )
+            })
+            .collect();
+        // Colored after the last change-point, the current state of the benchmark.
+        let color = changes.last().map(|change| {
+            if change.change > 0.0 {
+                Color::Red
+            } else {
+                Color::Green
+            }
+        });
+        let mut changes_cell = Cell::new(if labels.is_empty() {
+            "-".to_string()
+        } else {
+            labels.join(", ")
+        });
+        if let Some(color) = color {
+            changes_cell = changes_cell.fg(color);
+        }
+        table.add_row(vec![
+            Cell::new(&trend.name).fg(Color::Green),
+            Cell::new(format!("{}", ShapeFmt::new(&trend.shapes))),
+            Cell::new(&trend.feature),
+            Cell::new(&trend.host),
+            Cell::new(sparkline(&medians)),
+            Cell::new(format!("{:.3?} ({})", first.median, first.label))
+                .set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.3?} ({})", last.median, last.label))
+                .set_alignment(CellAlignment::Right),
+            changes_cell,
+        ]);
+    }
+
+    format!("{table}")
+}
+
+/// Alert listing the shifts detected at the last point of the trends, the ones that are new,
+/// `None` when there is none.
+pub(crate) fn get_shift_alert(trends: &[Trend], threshold: f64) -> Option<String> {
+    let lines: Vec<String> = trends
+        .iter()
+        .flat_map(|trend| {
+            change_points(&trend.points, threshold)
+                .into_iter()
+                .filter(|change| change.detected + 1 == trend.points.len())
+                .map(|change| {
+                    format!(
+                        "- {} {} `{}` on {}: {:+.1}% since {}",
+                        trend.name,
+                        ShapeFmt::new(&trend.shapes),
+                        trend.feature,
+                        trend.host,
+                        change.change,
+                        trend.points[change.index].label
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (!lines.is_empty()).then(|| format!("Performance shifts detected:\n{}", lines.join("\n")))
+}
+
+/// Alert for the shifts detected at the records of a run, with one point per run in the history
+/// of their benchmarks.
+pub(crate) fn get_run_shift_alert(
+    records: &[BenchmarkRecord],
+    history: &[BenchmarkRecord],
+) -> Option<String> {
+    let benchmarks: HashSet<(&String, &Vec<Vec<usize>>, &String)> = records
+        .iter()
+        .map(|record| {
+            (
+                &record.results.name,
+                &record.results.shapes,
+                &record.feature,
+            )
+        })
+        .collect();
+    let history: Vec<BenchmarkRecord> = history
+        .iter()
+        .filter(|record| {
+            benchmarks.contains(&(
+                &record.results.name,
+                &record.results.shapes,
+                &record.feature,
+            ))
+        })
+        .cloned()
+        .collect();
+    get_shift_alert(
+        &trends(&history, TrendGrouping::Run, usize::MAX),
+        DEFAULT_SHIFT_THRESHOLD,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(version: &str, timestamp: u128, median_ms: u64) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord {
+            feature: "cuda".to_string(),
+            burn_version: version.to_string(),
+            ..Default::default()
+        };
+        record.results.name = "matmul".to_string();
+        record.results.timestamp = timestamp;
+        record.results.computed.median = Duration::from_millis(median_ms);
+        record
+    }
+
+    #[test]
+    fn test_trend_per_version() {
+        let records = vec![
+            record("0.17.0", 1, 10),
+            record("0.18.0", 3, 20),
+            record("0.17.0", 2, 12),
+            record("main", 4, 21),
+        ];
+
+        let trends = trends(&records, TrendGrouping::Version, 10);
+
+        assert_eq!(trends.len(), 1);
+        let points: Vec<(&str, Duration)> = trends[0]
+            .points
+            .iter()
+            .map(|point| (point.label.as_str(), point.median))
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                ("0.17.0", Duration::from_millis(12)),
+                ("0.18.0", Duration::from_millis(20)),
+                ("main", Duration::from_millis(21)),
+            ]
+        );
+    }
+
+    fn points(medians_us: &[u64]) -> Vec<TrendPoint> {
+        medians_us
+            .iter()
+            .enumerate()
+            .map(|(i, median)| TrendPoint {
+                label: i.to_string(),
+                median: Duration::from_micros(*median),
+                durations: vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_change_points_detect_shift() {
+        let points = points(&[1000, 1020, 990, 1010, 1000, 1000, 1500, 1510, 1490]);
+
+        let changes = change_points(&points, DEFAULT_SHIFT_THRESHOLD);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].index, changes[0].detected), (6, 6));
+        assert!((changes[0].change - 50.0).abs() < 1.0, "{changes:?}");
+    }
+
+    #[test]
+    fn test_change_points_ignore_small_and_early_changes() {
+        // Too few points to know the level before the change.
+        assert!(change_points(&points(&[1000, 1000, 2000, 2000]), 10.0).is_empty());
+        // Detected by the CUSUM, but below the threshold.
+        assert!(
+            change_points(&points(&[1000, 1000, 1000, 1000, 1000, 1050, 1050]), 10.0).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_shift_alert_only_reports_new_shifts() {
+        let trend = |medians: &[u64]| Trend {
+            name: "matmul".to_string(),
+            shapes: vec![],
+            feature: "cuda".to_string(),
+            host: "RTX 4090".to_string(),
+            points: points(medians),
+        };
+        let old = trend(&[1000, 1000, 1000, 1000, 1000, 2000, 2000]);
+        let new = trend(&[1000, 1000, 1000, 1000, 1000, 2000]);
+
+        assert!(get_shift_alert(&[old], 10.0).is_none());
+        let alert = get_shift_alert(&[new], 10.0).unwrap();
+        assert!(
+            alert.contains("`cuda` on RTX 4090: +100.0% since 5"),
+            "{alert}"
+        );
+    }
+
+    #[test]
+    fn test_trend_keeps_last_points() {
+        let records = vec![
+            record("0.16.0", 1, 10),
+            record("0.17.0", 2, 10),
+            record("0.18.0", 3, 10),
+        ];
+
+        let trends = trends(&records, TrendGrouping::Version, 2);
+
+        assert_eq!(trends[0].points[0].label, "0.17.0");
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let medians = [10, 20, 15, 10].map(Duration::from_millis);
+        assert_eq!(sparkline(&medians), "▁█▅▁");
+        assert_eq!(sparkline(&[Duration::from_millis(5); 2]), "▅▅");
+    }
+}
@@ -0,0 +1,371 @@
+use std::{
+    io,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table},
+};
+
+use super::interrupt;
+
+/// Refresh period of the monitoring screen.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Benches, backends and dtypes selected in the TUI.
+pub(crate) struct Selection {
+    pub(crate) benches: Vec<String>,
+    pub(crate) backends: Vec<String>,
+    pub(crate) dtypes: Vec<String>,
+}
+
+/// State of a version, backend and dtype combination of the run.
+#[derive(Clone)]
+pub(crate) enum CombinationState {
+    Queued,
+    Running(Instant),
+    Succeeded(Duration),
+    Failed(Duration),
+}
+
+pub(crate) struct CombinationStatus {
+    pub(crate) version: String,
+    pub(crate) backend: String,
+    pub(crate) dtype: String,
+    pub(crate) state: CombinationState,
+}
+
+struct Checklist {
+    title: &'static str,
+    items: Vec<(String, bool)>,
+    state: ListState,
+}
+
+impl Checklist {
+    fn new(title: &'static str, items: Vec<String>, checked: &[&str]) -> Self {
+        let items = items
+            .into_iter()
+            .map(|item| {
+                let is_checked = checked.contains(&item.as_str());
+                (item, is_checked)
+            })
+            .collect();
+
+        Self {
+            title,
+            items,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn toggle(&mut self) {
+        if let Some((_, checked)) = self
+            .state
+            .selected()
+            .and_then(|index| self.items.get_mut(index))
+        {
+            *checked = !*checked;
+        }
+    }
+
+    fn toggle_all(&mut self) {
+        let check = self.items.iter().any(|(_, checked)| !checked);
+        for (_, checked) in self.items.iter_mut() {
+            *checked = check;
+        }
+    }
+
+    fn checked(&self) -> Vec<String> {
+        self.items
+            .iter()
+            .filter(|(_, checked)| *checked)
+            .map(|(item, _)| item.clone())
+            .collect()
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|(item, checked)| {
+                let mark = if *checked { "[x]" } else { "[ ]" };
+                ListItem::new(format!("{mark} {item}"))
+            })
+            .collect();
+        let border_style = if focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(format!(
+                        "{} ({}/{})",
+                        self.title,
+                        self.checked().len(),
+                        self.items.len()
+                    )),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
+
+/// Let the user check the benches, backends and dtypes to run.
+///
+/// Return `None` when the selection is cancelled.
+pub(crate) fn select(
+    benches: Vec<String>,
+    backends: Vec<String>,
+    dtypes: Vec<String>,
+) -> io::Result<Option<Selection>> {
+    let mut checklists = [
+        Checklist::new("Benches", benches, &[]),
+        Checklist::new("Backends", backends, &[]),
+        Checklist::new("Dtypes", dtypes, &["f32"]),
+    ];
+
+    let mut terminal = ratatui::init();
+    let result = run_selection(&mut terminal, &mut checklists);
+    ratatui::restore();
+
+    Ok(result?.then(|| {
+        let [benches, backends, dtypes] = checklists.map(|checklist| checklist.checked());
+        Selection {
+            benches,
+            backends,
+            dtypes,
+        }
+    }))
+}
+
+fn run_selection(
+    terminal: &mut DefaultTerminal,
+    checklists: &mut [Checklist; 3],
+) -> io::Result<bool> {
+    let mut focus = 0;
+    loop {
+        let ready = checklists.iter().all(|c| !c.checked().is_empty());
+        terminal.draw(|frame| {
+            let [lists, help_line] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            let columns = Layout::horizontal([Constraint::Ratio(1, 3); 3]).split(lists);
+            for (index, checklist) in checklists.iter_mut().enumerate() {
+                checklist.render(frame, columns[index], index == focus);
+            }
+            let help = if ready {
+                "←/→ column · ↑/↓ move · space toggle · a toggle all · enter run · q quit"
+            } else {
+                "←/→ column · ↑/↓ move · space toggle · a toggle all · q quit (check at least one item per column)"
+            };
+            frame.render_widget(Paragraph::new(help), help_line);
+        })?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let checklist = &mut checklists[focus];
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Enter if ready => return Ok(true),
+            KeyCode::Left | KeyCode::BackTab => focus = (focus + 2) % 3,
+            KeyCode::Right | KeyCode::Tab => focus = (focus + 1) % 3,
+            KeyCode::Up => checklist.state.select_previous(),
+            KeyCode::Down => checklist.state.select_next(),
+            KeyCode::Char(' ') => checklist.toggle(),
+            KeyCode::Char('a') => checklist.toggle_all(),
+            _ => {}
+        }
+    }
+}
+
+/// Monitor the combinations of the run until the user quits, then display the final report once
+/// it is available.
+///
+/// Quitting while the benchmarks are running stops them.
+pub(crate) fn monitor(
+    statuses: &Mutex<Vec<CombinationStatus>>,
+    report: &Mutex<Option<String>>,
+) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = run_monitor(&mut terminal, statuses, report);
+    ratatui::restore();
+    result
+}
+
+fn run_monitor(
+    terminal: &mut DefaultTerminal,
+    statuses: &Mutex<Vec<CombinationStatus>>,
+    report: &Mutex<Option<String>>,
+) -> io::Result<()> {
+    let mut scroll: u16 = 0;
+    loop {
+        let report_text = report.lock().unwrap().clone();
+        let done = report_text.is_some();
+        terminal.draw(|frame| {
+            let statuses = statuses.lock().unwrap();
+            let [title, grid, table] = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Percentage(40),
+                Constraint::Min(0),
+            ])
+            .areas(frame.area());
+
+            let finished = statuses
+                .iter()
+                .filter(|s| {
+                    matches!(
+                        s.state,
+                        CombinationState::Succeeded(_) | CombinationState::Failed(_)
+                    )
+                })
+                .count();
+            let title_text = if done {
+                format!(
+                    "Done: {finished}/{} combinations · ↑/↓ scroll · q quit",
+                    statuses.len()
+                )
+            } else if interrupt::is_interrupted() {
+                "Stopping the benchmarks...".to_string()
+            } else {
+                format!(
+                    "Running: {finished}/{} combinations · q stop",
+                    statuses.len()
+                )
+            };
+            frame.render_widget(Paragraph::new(title_text), title);
+            frame.render_widget(grid_table(&statuses), grid);
+
+            let text = report_text
+                .as_deref()
+                .unwrap_or("The comparison table is displayed once the benchmarks are done.");
+            frame.render_widget(
+                Paragraph::new(text)
+                    .scroll((scroll, 0))
+                    .block(Block::default().borders(Borders::ALL).title("Results")),
+                table,
+            );
+        })?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc if done => return Ok(()),
+            KeyCode::Char('q') | KeyCode::Esc => interrupt::interrupt(),
+            KeyCode::Up => scroll = scroll.saturating_sub(1),
+            KeyCode::Down => scroll = scroll.saturating_add(1),
+            _ => {}
+        }
+    }
+}
+
+/// Grid of the combinations with one row per version and backend, and one column per dtype.
+fn grid_table(statuses: &[CombinationStatus]) -> Table<'static> {
+    let mut dtypes: Vec<&str> = vec![];
+    let mut rows: Vec<(&str, &str)> = vec![];
+    for status in statuses {
+        if !dtypes.contains(&status.dtype.as_str()) {
+            dtypes.push(&status.dtype);
+        }
+        let row = (status.version.as_str(), status.backend.as_str());
+        if !rows.contains(&row) {
+            rows.push(row);
+        }
+    }
+
+    let header = Row::new(
+        ["Version", "Backend"]
+            .into_iter()
+            .chain(dtypes.iter().copied())
+            .map(|title| Cell::from(title.to_string()))
+            .collect::<Vec<_>>(),
+    )
+    .style(Style::default().add_modifier(Modifier::BOLD));
+    let table_rows = rows.iter().map(|(version, backend)| {
+        let mut cells = vec![
+            Cell::from(version.to_string()),
+            Cell::from(backend.to_string()),
+        ];
+        for dtype in &dtypes {
+            let state = statuses
+                .iter()
+                .find(|s| s.version == *version && s.backend == *backend && s.dtype == *dtype)
+                .map(|s| s.state.clone());
+            cells.push(state_cell(state));
+        }
+        Row::new(cells)
+    });
+
+    let mut widths = vec![Constraint::Length(16), Constraint::Length(24)];
+    widths.extend(dtypes.iter().map(|_| Constraint::Length(14)));
+    Table::new(table_rows.collect::<Vec<_>>(), widths)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title("Combinations"))
+}
+
+fn state_cell(state: Option<CombinationState>) -> Cell<'static> {
+    match state {
+        None => Cell::from("-"),
+        Some(CombinationState::Queued) => {
+            Cell::from("queued").style(Style::default().fg(Color::DarkGray))
+        }
+        Some(CombinationState::Running(start)) => {
+            Cell::from(format!("🔥 {}", format_duration(start.elapsed())))
+                .style(Style::default().fg(Color::Yellow))
+        }
+        Some(CombinationState::Succeeded(duration)) => {
+            Cell::from(format!("✅ {}", format_duration(duration)))
+                .style(Style::default().fg(Color::Green))
+        }
+        Some(CombinationState::Failed(duration)) => {
+            Cell::from(format!("❌ {}", format_duration(duration)))
+                .style(Style::default().fg(Color::Red))
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}m{:02}s", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(5)), "0m05s");
+        assert_eq!(format_duration(Duration::from_secs(3725)), "62m05s");
+    }
+
+    #[test]
+    fn test_checklist_toggle_all() {
+        let mut checklist = Checklist::new("Dtypes", vec!["f32".into(), "f16".into()], &["f32"]);
+        checklist.toggle_all();
+        assert_eq!(
+            checklist.checked(),
+            vec!["f32".to_string(), "f16".to_string()]
+        );
+        checklist.toggle_all();
+        assert!(checklist.checked().is_empty());
+    }
+}
@@ -0,0 +1,468 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        Arc,
+        mpsc::{self, RecvTimeoutError, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use glob::glob;
+
+use super::doctor::check_rust_target;
+use super::interrupt;
+use super::processor::{OutputProcessor, StepProcessor, process_output};
+use crate::persistence::{BenchmarkRecord, benchmarks_cache_dir, save_validated_records};
+
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+/// Browsers looked up in the `PATH` when `--browser` isn't given.
+const BROWSERS: [&str; 5] = [
+    "google-chrome",
+    "google-chrome-stable",
+    "chromium",
+    "chromium-browser",
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+];
+/// Time given to the benchmarks of a bench to report their results.
+const BROWSER_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Outcome of the benches run in the browser, which has no exit status of its own.
+#[derive(Debug)]
+pub(crate) enum WasmOutcome {
+    /// Building or binding the benches failed with the status of the tool.
+    BuildFailed(ExitStatus),
+    /// A bench failed in the browser or its records could not be saved.
+    Failed(String),
+    /// Every bench completed and its records were saved.
+    Completed,
+}
+
+impl WasmOutcome {
+    pub(crate) fn success(&self) -> bool {
+        matches!(self, Self::Completed)
+    }
+}
+
+/// Headless browser running the benches compiled to WebAssembly on the WebGPU backend of wgpu.
+///
+/// Each bench is built for `wasm32-unknown-unknown`, bound with `wasm-bindgen` and served on a
+/// local HTTP endpoint, to which the page sends the output and the records of the benchmarks.
+#[derive(Debug, Clone)]
+pub(crate) struct WasmTarget {
+    browser: PathBuf,
+}
+
+impl WasmTarget {
+    /// Check the tools required to build and run the benches, and find the browser.
+    pub(crate) fn new(browser: Option<&Path>) -> Result<Self, String> {
+        check_rust_target(WASM_TARGET)?;
+        if !is_available(Path::new("wasm-bindgen")) {
+            return Err(
+                "wasm-bindgen is not installed, run `cargo install wasm-bindgen-cli` with the \
+                 version of the wasm-bindgen crate in Cargo.lock"
+                    .to_string(),
+            );
+        }
+        let browser = match browser {
+            Some(browser) if is_available(browser) => browser.to_path_buf(),
+            Some(browser) => return Err(format!("cannot run the browser {}", browser.display())),
+            None => BROWSERS
+                .iter()
+                .map(PathBuf::from)
+                .find(|browser| is_available(browser))
+                .ok_or("no Chrome or Chromium browser found, give one with --browser")?,
+        };
+        Ok(Self { browser })
+    }
+
+    /// Build the benches and run them in the browser, writing their records in `results_dir`.
+    pub(crate) fn run(
+        &self,
+        benches: &[String],
+        params: &[&str],
+        envs: &[(String, String)],
+        processor: Arc<dyn OutputProcessor>,
+        results_dir: &Path,
+    ) -> io::Result<WasmOutcome> {
+        // The stale artifacts are removed to find the ones of this build.
+        let patterns: Vec<String> = if benches[0] == "all" {
+            vec!["*".to_string()]
+        } else {
+            benches
+                .iter()
+                .map(|bench| bench.replace('-', "_"))
+                .collect()
+        };
+        for artifact in artifacts(&patterns) {
+            fs::remove_file(artifact)?;
+        }
+
+        let status = build(params, envs, &processor)?;
+        if !status.success() {
+            processor.finish();
+            return Ok(WasmOutcome::BuildFailed(status));
+        }
+
+        let mut outcome = WasmOutcome::Completed;
+        for artifact in artifacts(&patterns) {
+            outcome = self.run_artifact(&artifact, envs, &processor, results_dir)?;
+            if !outcome.success() || interrupt::is_interrupted() {
+                break;
+            }
+        }
+        processor.finish();
+        Ok(outcome)
+    }
+
+    fn run_artifact(
+        &self,
+        artifact: &Path,
+        envs: &[(String, String)],
+        processor: &Arc<dyn OutputProcessor>,
+        results_dir: &Path,
+    ) -> io::Result<WasmOutcome> {
+        let bench = bench_name(artifact);
+        let dir = Path::new(crate::BENCHMARKS_TARGET_DIR)
+            .join("wasm")
+            .join(&bench);
+        let status = Command::new("wasm-bindgen")
+            .args(["--target", "web", "--no-typescript", "--out-name", "bench"])
+            .arg("--out-dir")
+            .arg(&dir)
+            .arg(artifact)
+            .status()?;
+        if !status.success() {
+            return Ok(WasmOutcome::BuildFailed(status));
+        }
+        fs::write(dir.join("index.html"), index_html(envs))?;
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/", listener.local_addr()?);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || serve(listener, dir, sender));
+
+        processor.process_line(&format!("Running {bench} in {}", self.browser.display()));
+        let profile = tempfile::tempdir()?;
+        let mut browser = self.launch(&url, profile.path())?;
+
+        let deadline = Instant::now() + BROWSER_TIMEOUT;
+        let mut records = vec![];
+        let outcome = loop {
+            if interrupt::is_interrupted() {
+                break Err("interrupted".to_string());
+            }
+            if let Some(status) = browser.try_wait()? {
+                break Err(format!("the browser exited with {status}"));
+            }
+            match receiver.recv_timeout(Duration::from_millis(200)) {
+                Ok(Message::Output(line)) => {
+                    processor.process_line(&line);
+                    processor.progress();
+                }
+                Ok(Message::Records(batch)) => records.extend(batch),
+                Ok(Message::Done(None)) => break Ok(()),
+                Ok(Message::Done(Some(error))) => break Err(error),
+                Err(RecvTimeoutError::Timeout) if Instant::now() > deadline => {
+                    break Err(format!("no results after {BROWSER_TIMEOUT:?}"));
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    break Err("the server stopped".to_string());
+                }
+            }
+        };
+
+        browser.kill().ok();
+        browser.wait()?;
+        // The records of the benchmarks that completed are kept even if a later one failed.
+        let saved = save_validated_records(
+            &records,
+            &[benchmarks_cache_dir(), results_dir.to_path_buf()],
+        )
+        .map_err(|e| format!("failed to save the records ({e})"));
+        match outcome.and(saved) {
+            Ok(()) => Ok(WasmOutcome::Completed),
+            Err(error) => {
+                processor.process_line(&format!("❌ {bench} failed in the browser: {error}"));
+                Ok(WasmOutcome::Failed(error))
+            }
+        }
+    }
+
+    fn launch(&self, url: &str, profile: &Path) -> io::Result<Child> {
+        let mut command = Command::new(&self.browser);
+        command
+            .args([
+                "--headless=new",
+                "--no-first-run",
+                "--no-default-browser-check",
+                "--enable-unsafe-webgpu",
+            ])
+            .arg(format!("--user-data-dir={}", profile.display()));
+        // WebGPU is only exposed on top of Vulkan on Linux.
+        if cfg!(target_os = "linux") {
+            command.arg("--enable-features=Vulkan");
+        }
+        command
+            .arg(url)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    }
+}
+
+/// Build the benches for the browser with the arguments of `cargo bench`.
+fn build(
+    params: &[&str],
+    envs: &[(String, String)],
+    processor: &Arc<dyn OutputProcessor>,
+) -> io::Result<ExitStatus> {
+    // getrandom needs to be told to use the random generator of the browser.
+    let mut rustflags = std::env::var("RUSTFLAGS").unwrap_or_default();
+    rustflags.push_str(" --cfg getrandom_backend=\"wasm_js\"");
+    let cargo = Command::new("cargo")
+        .env("CARGO_TERM_COLOR", "always")
+        .env("RUSTFLAGS", rustflags.trim())
+        .envs(envs.iter().map(|(k, v)| (k, v)))
+        .args(["build", "--release", "--target", WASM_TARGET])
+        .args(params)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // The processor is finished once the benches have run in the browser.
+    let build_processor: Arc<dyn OutputProcessor> = Arc::new(StepProcessor(processor.clone()));
+    process_output(cargo, &build_processor)
+}
+
+fn is_available(program: &Path) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Artifacts of the benches matching the patterns, such as `unary_sweep`.
+fn artifacts(patterns: &[String]) -> Vec<PathBuf> {
+    let deps = Path::new(crate::BENCHMARKS_TARGET_DIR)
+        .join(WASM_TARGET)
+        .join("release")
+        .join("deps");
+    patterns
+        .iter()
+        .flat_map(|pattern| {
+            let pattern = deps.join(format!("{pattern}-*.wasm"));
+            glob(&pattern.to_string_lossy())
+                .into_iter()
+                .flat_map(|paths| paths.filter_map(Result::ok))
+        })
+        .collect()
+}
+
+/// Name of the bench of an artifact such as `unary_sweep-0123456789abcdef.wasm`.
+fn bench_name(artifact: &Path) -> String {
+    let stem = artifact
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    match stem.rsplit_once('-') {
+        Some((name, _hash)) => name.replace('_', "-"),
+        None => stem,
+    }
+}
+
+/// Page loading the bench, with the environment of the run read by the benchmarks.
+fn index_html(envs: &[(String, String)]) -> String {
+    let envs: BTreeMap<&str, &str> = envs
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    // The JSON must not close the script element.
+    let envs = serde_json::to_string(&envs).unwrap().replace("</", "<\\/");
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>burnbench</title></head>
+<body>
+<script>window.burnBenchEnv = {envs};</script>
+<script type="module">
+import init from "./bench.js";
+
+const done = (error) => fetch("/done", {{ method: "POST", body: String(error) }});
+if (navigator.gpu) {{
+    init().catch(done);
+}} else {{
+    done("WebGPU is not available in the browser");
+}}
+</script>
+</body>
+</html>
+"#
+    )
+}
+
+/// Message sent by the page to the runner.
+enum Message {
+    Output(String),
+    Records(Vec<BenchmarkRecord>),
+    Done(Option<String>),
+}
+
+/// Request of the page, read until the end of its body.
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+impl Request {
+    fn read(reader: &mut impl BufRead) -> io::Result<Self> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid request line {line:?}"),
+            ));
+        };
+        let (method, path) = (method.to_string(), path.to_string());
+
+        let mut content_length = 0;
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Self {
+            method,
+            path,
+            body: String::from_utf8_lossy(&body).to_string(),
+        })
+    }
+}
+
+/// Serve the files of the bench and receive the messages of the page, until the runner is gone.
+fn serve(listener: TcpListener, dir: PathBuf, sender: Sender<Message>) {
+    for stream in listener.incoming().filter_map(Result::ok) {
+        // The browser keeps idle connections open, each one is handled in its own thread.
+        let dir = dir.clone();
+        let sender = sender.clone();
+        thread::spawn(move || {
+            if let Err(e) = handle(stream, &dir, &sender) {
+                tracing::warn!("Failed to handle a request of the browser ({e})");
+            }
+        });
+    }
+}
+
+fn handle(stream: TcpStream, dir: &Path, sender: &Sender<Message>) -> io::Result<()> {
+    let request = Request::read(&mut BufReader::new(&stream))?;
+    let message = match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/output") => Some(Message::Output(request.body)),
+        ("POST", "/results") => match serde_json::from_str(&request.body) {
+            Ok(records) => Some(Message::Records(records)),
+            Err(e) => return respond(stream, "400 Bad Request", "text/plain", e.to_string()),
+        },
+        ("POST", "/done") if request.body.is_empty() => Some(Message::Done(None)),
+        ("POST", "/done") => Some(Message::Done(Some(request.body))),
+        ("GET", path) => {
+            let file = match path.trim_start_matches('/') {
+                "" => "index.html",
+                file => file,
+            };
+            // Only the files of the bench are served.
+            let content = match file.contains("..") {
+                true => None,
+                false => fs::read(dir.join(file)).ok(),
+            };
+            return match content {
+                Some(content) => respond(stream, "200 OK", content_type(file), content),
+                None => respond(stream, "404 Not Found", "text/plain", "Not found"),
+            };
+        }
+        _ => None,
+    };
+    match message {
+        Some(message) => {
+            // The runner is gone once the benchmarks are done.
+            sender.send(message).ok();
+            respond(stream, "200 OK", "text/plain", "")
+        }
+        None => respond(stream, "404 Not Found", "text/plain", "Not found"),
+    }
+}
+
+fn respond(
+    mut stream: TcpStream,
+    status: &str,
+    content_type: &str,
+    body: impl AsRef<[u8]>,
+) -> io::Result<()> {
+    let body = body.as_ref();
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+fn content_type(file: &str) -> &'static str {
+    match Path::new(file).extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("js") => "text/javascript",
+        Some("wasm") => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_request_with_body() {
+        let raw = "POST /output HTTP/1.1\r\nHost: 127.0.0.1\r\ncontent-length: 31\r\n\r\n##burnbench:start name=matmul\r\n";
+        let request = Request::read(&mut raw.as_bytes()).unwrap();
+
+        assert_eq!(
+            request,
+            Request {
+                method: "POST".to_string(),
+                path: "/output".to_string(),
+                body: "##burnbench:start name=matmul\r\n".to_string(),
+            }
+        );
+        assert!(Request::read(&mut "\r\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_bench_name_of_artifact() {
+        assert_eq!(
+            bench_name(Path::new("deps/unary_sweep-0123456789abcdef.wasm")),
+            "unary-sweep"
+        );
+    }
+}
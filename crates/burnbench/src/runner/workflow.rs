@@ -1,70 +1,162 @@
 use regex::Regex;
-use reqwest::{
-    blocking::{Client, Response},
-    header::CONTENT_TYPE,
-};
 use serde_json::{Map as JsonMap, Value};
-use std::{env, fs::File, io::BufReader};
+use std::{env, fs::File, io::BufReader, str::FromStr};
 
-use hmac_sha256::HMAC;
 use uuid::Uuid;
 
-use crate::{TRACEL_CI_SERVER_BASE_URL, ci_errorln};
+use crate::ci_errorln;
+use crate::server_client::ServerClient;
 
-fn get_webhook_url() -> String {
-    format!("{TRACEL_CI_SERVER_BASE_URL}burn_bench/webhook/benchmark")
+/// Version of the JSON shape of the webhook payloads, bumped on breaking changes.
+const WEBHOOK_SCHEMA_VERSION: u64 = 1;
+
+/// Algorithm of the HMAC signature of the webhook payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SignatureAlgorithm {
+    Sha256,
+    Sha512,
+    /// The payloads are not signed, for services authenticating the requests otherwise.
+    None,
 }
 
-pub(crate) fn send_output_results(inputs_file: &str, table: &str, share_link: Option<&str>) {
-    if let Some((json, pr_number)) = load_inputs(inputs_file)
-        && let Some(cleaned_table) = clean_output(table, share_link)
-        && let Some(payload) = serialize_result(json, pr_number, cleaned_table, "complete")
-    {
-        send_event("complete", payload);
+impl FromStr for SignatureAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(Self::Sha256),
+            "sha512" => Ok(Self::Sha512),
+            "none" => Ok(Self::None),
+            _ => Err(format!(
+                "unknown signature algorithm '{s}', expected sha256, sha512 or none"
+            )),
+        }
     }
 }
 
-fn load_inputs(inputs_file: &str) -> Option<(Value, i64)> {
-    let json = read_inputs(inputs_file).ok()?;
-    let pr_number = get_pr_number(&json)?;
-    Some((json, pr_number))
-}
+impl SignatureAlgorithm {
+    /// Signature of the payload in the `<algorithm>=<hex digest>` format of GitHub.
+    fn sign(&self, payload: &[u8], secret: &[u8]) -> Option<String> {
+        match self {
+            Self::Sha256 => Some(format!(
+                "sha256={}",
+                hex::encode(hmac_sha256::HMAC::mac(payload, secret))
+            )),
+            Self::Sha512 => Some(format!(
+                "sha512={}",
+                hex::encode(hmac_sha512::HMAC::mac(payload, secret))
+            )),
+            Self::None => None,
+        }
+    }
 
-fn send_event(action: &str, payload: Vec<u8>) {
-    let (signature, delivery_id) = match build_req_body(&payload) {
-        Ok(t) => t,
-        Err(e) => {
-            ci_errorln!("❌ {e}");
-            return;
+    fn default_header(&self) -> &'static str {
+        match self {
+            Self::Sha512 => "X-Hub-Signature-512",
+            _ => "X-Hub-Signature-256",
         }
-    };
+    }
+}
 
-    let post_url = get_webhook_url();
-    match send_result(&post_url, payload, &signature, &delivery_id) {
-        Ok(resp) => {
-            let status = resp.status();
-            if status.is_success() {
-                println!("✅ Sent '{action}' webhook to server at '{post_url}'.");
-            } else {
-                let body = resp
-                    .text()
-                    .unwrap_or_else(|_| "<could not read body>".into());
-                ci_errorln!("❌ Webhook failed with status: {status} ({body})");
+/// Destination and signing of the webhook, configured by environment variables so that CI
+/// services other than the Tracel server can consume the events:
+///
+/// - `WEBHOOK_SIGNATURE_ALGORITHM`: `sha256` (default), `sha512` or `none`;
+/// - `WEBHOOK_PAYLOAD_SECRET`: secret of the HMAC signature;
+/// - `WEBHOOK_SIGNATURE_HEADER`: header of the signature, `X-Hub-Signature-256` or
+///   `X-Hub-Signature-512` by default;
+/// - `WEBHOOK_DELIVERY_HEADER`: header of the unique delivery id, `X-GitHub-Delivery` by default.
+struct WebhookConfig {
+    url: Option<String>,
+    algorithm: SignatureAlgorithm,
+    signature_header: String,
+    delivery_header: String,
+}
+
+impl WebhookConfig {
+    fn from_env(url: Option<&str>) -> Result<Self, String> {
+        let algorithm = match env::var("WEBHOOK_SIGNATURE_ALGORITHM") {
+            Ok(algorithm) => algorithm.parse()?,
+            Err(_) => SignatureAlgorithm::Sha256,
+        };
+        Ok(Self {
+            url: url.map(str::to_string),
+            algorithm,
+            signature_header: env::var("WEBHOOK_SIGNATURE_HEADER")
+                .unwrap_or_else(|_| algorithm.default_header().to_string()),
+            delivery_header: env::var("WEBHOOK_DELIVERY_HEADER")
+                .unwrap_or_else(|_| "X-GitHub-Delivery".to_string()),
+        })
+    }
+
+    /// Headers of the request carrying the payload.
+    fn headers(&self, payload: &[u8]) -> Result<Vec<(String, String)>, String> {
+        let mut headers = vec![(self.delivery_header.clone(), Uuid::new_v4().to_string())];
+        if self.algorithm != SignatureAlgorithm::None {
+            let secret = env::var("WEBHOOK_PAYLOAD_SECRET")
+                .map_err(|_| "Missing WEBHOOK_PAYLOAD_SECRET".to_string())?;
+            if let Some(signature) = self.algorithm.sign(payload, secret.as_bytes()) {
+                headers.push((self.signature_header.clone(), signature));
             }
         }
+        Ok(headers)
+    }
+}
+
+/// Send the results table to the webhook, the Tracel server unless `webhook_url` is provided.
+///
+/// The Tracel server only receives the runs of the pull request workflows, with their inputs
+/// file and PR number. Another webhook receives every run, the inputs and the PR number being
+/// included when available.
+pub(crate) fn send_output_results(
+    inputs_file: Option<&str>,
+    table: &str,
+    share_link: Option<&str>,
+    webhook_url: Option<&str>,
+) {
+    if inputs_file.is_none() && webhook_url.is_none() {
+        return;
+    }
+    let config = match WebhookConfig::from_env(webhook_url) {
+        Ok(config) => config,
         Err(e) => {
-            ci_errorln!("❌ Error sending webhook: {e}");
+            ci_errorln!("❌ {e}");
+            return;
         }
+    };
+    let json = match inputs_file {
+        Some(inputs_file) => match read_inputs(inputs_file) {
+            Ok(json) => json,
+            Err(()) => return,
+        },
+        None => Value::Object(JsonMap::new()),
+    };
+    let pr_number = json["pr_number"].as_i64();
+    if pr_number.is_none() && webhook_url.is_none() {
+        println!("ℹ️ No valid 'pr_number' found. Skipping webhook.");
+        return;
+    }
+    if let Some(cleaned_table) = clean_output(table, share_link)
+        && let Some(payload) = serialize_result(json, pr_number, cleaned_table, "complete")
+    {
+        send_event(&config, "complete", payload);
     }
 }
 
-fn get_pr_number(json: &Value) -> Option<i64> {
-    match json["pr_number"].as_i64() {
-        Some(n) => Some(n),
-        None => {
-            println!("ℹ️ No valid 'pr_number' found. Skipping webhook.");
-            None
+fn send_event(config: &WebhookConfig, action: &str, payload: Vec<u8>) {
+    let headers = match config.headers(&payload) {
+        Ok(headers) => headers,
+        Err(e) => {
+            ci_errorln!("❌ {e}");
+            return;
         }
+    };
+
+    let client = ServerClient::default();
+    let post_url = config.url.clone().unwrap_or_else(|| client.webhook_url());
+    match client.send_webhook(&post_url, &payload, &headers) {
+        Ok(()) => println!("✅ Sent '{action}' webhook to server at '{post_url}'."),
+        Err(e) => ci_errorln!("❌ Webhook failed ({e})"),
     }
 }
 
@@ -94,13 +186,16 @@ fn clean_output(table: &str, share_link: Option<&str>) -> Option<JsonMap<String,
 
 fn serialize_result(
     mut json: Value,
-    pr_number: i64,
+    pr_number: Option<i64>,
     table: JsonMap<String, Value>,
     action: &str,
 ) -> Option<Vec<u8>> {
     json["results"] = Value::Object(table);
     json["action"] = Value::String(action.to_string());
-    json["pr_number"] = Value::Number(serde_json::Number::from(pr_number));
+    if let Some(pr_number) = pr_number {
+        json["pr_number"] = Value::Number(serde_json::Number::from(pr_number));
+    }
+    json["schema_version"] = Value::Number(serde_json::Number::from(WEBHOOK_SCHEMA_VERSION));
     serde_json::to_vec(&json)
         .map_err(|e| {
             ci_errorln!("❌ Failed to serialize result: {e}");
@@ -108,26 +203,55 @@ fn serialize_result(
         .ok()
 }
 
-fn build_req_body(payload: &[u8]) -> Result<(String, String), String> {
-    let secret = env::var("WEBHOOK_PAYLOAD_SECRET")
-        .map_err(|_| "Missing WEBHOOK_PAYLOAD_SECRET".to_string())?;
-    let mac = HMAC::mac(payload, secret.as_bytes());
-    let signature = format!("sha256={}", hex::encode(mac));
-    let delivery_id = Uuid::new_v4().to_string();
-    Ok((signature, delivery_id))
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signature_algorithm() {
+        assert_eq!("sha256".parse(), Ok(SignatureAlgorithm::Sha256));
+        assert_eq!("sha512".parse(), Ok(SignatureAlgorithm::Sha512));
+        assert_eq!("none".parse(), Ok(SignatureAlgorithm::None));
+        assert!("md5".parse::<SignatureAlgorithm>().is_err());
+    }
 
-fn send_result(
-    url: &str,
-    payload: Vec<u8>,
-    signature: &str,
-    delivery_id: &str,
-) -> Result<Response, reqwest::Error> {
-    Client::new()
-        .post(url)
-        .header(CONTENT_TYPE, "application/json")
-        .header("X-GitHub-Delivery", delivery_id)
-        .header("X-Hub-Signature-256", signature)
-        .body(payload)
-        .send()
+    #[test]
+    fn test_sign_payload() {
+        let signature = SignatureAlgorithm::Sha256.sign(b"{}", b"secret").unwrap();
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        let signature = SignatureAlgorithm::Sha512.sign(b"{}", b"secret").unwrap();
+        assert!(signature.starts_with("sha512="));
+        assert_eq!(signature.len(), "sha512=".len() + 128);
+        assert_eq!(SignatureAlgorithm::None.sign(b"{}", b"secret"), None);
+    }
+
+    #[test]
+    fn test_serialize_result_with_schema_version() {
+        let payload = serialize_result(
+            serde_json::json!({ "pr_number": 42 }),
+            Some(42),
+            JsonMap::new(),
+            "complete",
+        )
+        .unwrap();
+        let json: Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(json["schema_version"], WEBHOOK_SCHEMA_VERSION);
+        assert_eq!(json["action"], "complete");
+    }
+
+    #[test]
+    fn test_serialize_result_without_pull_request() {
+        let payload = serialize_result(
+            Value::Object(JsonMap::new()),
+            None,
+            JsonMap::new(),
+            "complete",
+        )
+        .unwrap();
+        let json: Value = serde_json::from_slice(&payload).unwrap();
+        assert!(json.get("pr_number").is_none(), "{json}");
+        assert_eq!(json["action"], "complete");
+        assert_eq!(json["results"], serde_json::json!({}));
+    }
 }
@@ -0,0 +1,110 @@
+use std::sync::OnceLock;
+
+const SERVER_URL_ENV: &str = "BURN_BENCH_SERVER_URL";
+const WEBSITE_URL_ENV: &str = "BURN_BENCH_WEBSITE_URL";
+
+const TRACEL_CI_SERVER_BASE_URL: &str = if cfg!(debug_assertions) {
+    // development
+    "http://localhost:8000/v1/"
+} else {
+    // production
+    "https://user-benchmark-server-812794505978.northamerica-northeast1.run.app/v1/"
+};
+
+const BENCHMARK_WEBSITE_URL: &str = if cfg!(debug_assertions) || cfg!(test) {
+    "http://localhost:4321/"
+} else {
+    "https://burn.dev/"
+};
+
+static SERVER_URL: OnceLock<String> = OnceLock::new();
+static WEBSITE_URL: OnceLock<String> = OnceLock::new();
+
+/// Override the benchmark server and the website showing its results, for organizations
+/// running their own results server.
+///
+/// Must be called before the URLs are first read, the `BURN_BENCH_SERVER_URL` and
+/// `BURN_BENCH_WEBSITE_URL` environment variables are used when the URLs are not provided.
+pub(crate) fn init_server_urls(server_url: Option<String>, website_url: Option<String>) {
+    if let Some(url) = server_url {
+        let _ = SERVER_URL.set(with_trailing_slash(url));
+    }
+    if let Some(url) = website_url {
+        let _ = WEBSITE_URL.set(with_trailing_slash(url));
+    }
+}
+
+/// Base URL of the benchmark server, ending with a slash.
+pub(crate) fn server_base_url() -> &'static str {
+    SERVER_URL.get_or_init(|| {
+        std::env::var(SERVER_URL_ENV)
+            .map(with_trailing_slash)
+            .unwrap_or_else(|_| TRACEL_CI_SERVER_BASE_URL.to_string())
+    })
+}
+
+/// Base URL of the website showing the shared results, ending with a slash.
+pub(crate) fn website_url() -> &'static str {
+    WEBSITE_URL.get_or_init(|| {
+        std::env::var(WEBSITE_URL_ENV)
+            .map(with_trailing_slash)
+            .unwrap_or_else(|_| BENCHMARK_WEBSITE_URL.to_string())
+    })
+}
+
+/// Name identifying a self-hosted benchmark server, used to store its tokens separately.
+///
+/// Returns `None` for the Tracel benchmark server.
+pub(crate) fn server_key() -> Option<String> {
+    let url = server_base_url();
+    (url != TRACEL_CI_SERVER_BASE_URL).then(|| url_key(url))
+}
+
+/// Host and port of the URL with the characters unfit for a file name replaced.
+fn url_key(url: &str) -> String {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = url.split('/').next().unwrap_or(url);
+    host.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn with_trailing_slash(url: String) -> String {
+    if url.ends_with('/') {
+        url
+    } else {
+        format!("{url}/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_trailing_slash() {
+        assert_eq!(
+            with_trailing_slash("https://bench.example.com/v1".to_string()),
+            "https://bench.example.com/v1/"
+        );
+        assert_eq!(
+            with_trailing_slash("https://bench.example.com/v1/".to_string()),
+            "https://bench.example.com/v1/"
+        );
+    }
+
+    #[test]
+    fn test_url_key() {
+        assert_eq!(
+            url_key("https://bench.example.com/v1/"),
+            "bench.example.com"
+        );
+        assert_eq!(url_key("http://localhost:8000/v1/"), "localhost_8000");
+    }
+}
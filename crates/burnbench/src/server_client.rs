@@ -0,0 +1,318 @@
+use std::{fmt, io::Write, thread, time::Duration};
+
+use flate2::{Compression, write::GzEncoder};
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use reqwest::{
+    StatusCode,
+    blocking::{Client, RequestBuilder, Response},
+    header::{ACCEPT, AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE, USER_AGENT},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::BenchmarkRecord;
+use crate::runner::auth::{Tokens, get_auth_header_value};
+use crate::server::server_base_url;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+/// Characters encoded in the identifiers used as path segments.
+const ID_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_');
+
+/// Authenticated user, as returned by the `users/me` endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct UserInfo {
+    pub nickname: String,
+}
+
+/// Body of a batch upload of benchmark records.
+#[derive(Serialize)]
+struct RecordBatch<'a> {
+    manifest: BatchManifest<'a>,
+    records: &'a [BenchmarkRecord],
+}
+
+/// Description of a batch, checked by the server against the records it received.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchManifest<'a> {
+    record_count: usize,
+    run_id: Option<&'a str>,
+}
+
+/// Error returned by a request to the benchmark server.
+#[derive(Debug)]
+pub(crate) enum ServerError {
+    /// The request could not be sent, for instance because the server is unreachable.
+    Request(reqwest::Error),
+    /// The server answered with an error status.
+    Status { status: StatusCode, body: String },
+    /// The response doesn't match the expected type.
+    Decode(reqwest::Error),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerError::Request(e) => write!(f, "request failed: {e}"),
+            ServerError::Status { status, body } if body.is_empty() => write!(f, "error {status}"),
+            ServerError::Status { status, body } => write!(f, "error {status} ({body})"),
+            ServerError::Decode(e) => write!(f, "invalid response: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+/// Client of the benchmark server.
+///
+/// Requests time out after 30 seconds and are retried when the server is unreachable, or when
+/// they time out or the server answers with an error and they can safely be sent again.
+pub(crate) struct ServerClient {
+    client: Client,
+    base_url: String,
+}
+
+impl Default for ServerClient {
+    /// Client of the benchmark server selected with `--server-url`.
+    fn default() -> Self {
+        Self::new(server_base_url())
+    }
+}
+
+impl ServerClient {
+    /// Client of the benchmark server at the given base URL, ending with a slash.
+    pub(crate) fn new(base_url: &str) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("HTTP client should be created");
+        Self {
+            client,
+            base_url: base_url.to_string(),
+        }
+    }
+
+    /// Returns the user authenticated by the access token.
+    pub(crate) fn get_user(&self, access_token: &str) -> Result<UserInfo, ServerError> {
+        let response = self.send(|| {
+            self.request(self.client.get(self.url("users/me")))
+                .header(CONTENT_TYPE, "application/json")
+                .header(AUTHORIZATION, get_auth_header_value(access_token))
+        })?;
+        response.json().map_err(ServerError::Decode)
+    }
+
+    /// Issue new tokens from a refresh token.
+    pub(crate) fn refresh_tokens(&self, refresh_token: &str) -> Result<Tokens, ServerError> {
+        let response = self.send(|| {
+            self.request(self.client.post(self.url("auth/refresh-token")))
+                .header(CONTENT_TYPE, "application/json")
+                .header(AUTHORIZATION, format!("Bearer-Refresh {refresh_token}"))
+                // it is important to explicitly add an empty body otherwise
+                // reqwest won't send the request in release build
+                .body("")
+        })?;
+        response.json().map_err(ServerError::Decode)
+    }
+
+    /// Share the benchmark records of a cargo invocation in a single gzip-compressed request.
+    pub(crate) fn upload_records(
+        &self,
+        access_token: &str,
+        records: &[BenchmarkRecord],
+    ) -> Result<(), ServerError> {
+        let batch = RecordBatch {
+            manifest: BatchManifest {
+                record_count: records.len(),
+                run_id: records.first().and_then(|record| record.run_id.as_deref()),
+            },
+            records,
+        };
+        let json = serde_json::to_vec(&batch).expect("Records should be serializable");
+        let body = gzip(&json).expect("Records should be compressed");
+        self.send(|| {
+            self.request(self.client.post(self.url("benchmarks/batch")))
+                .header(ACCEPT, "application/json")
+                .header(CONTENT_TYPE, "application/json")
+                .header(CONTENT_ENCODING, "gzip")
+                .header(AUTHORIZATION, get_auth_header_value(access_token))
+                .body(body.clone())
+        })
+        .map(|_| ())
+    }
+
+    /// Shared benchmark records of a run.
+    pub(crate) fn get_run_records(
+        &self,
+        run_id: &str,
+    ) -> Result<Vec<BenchmarkRecord>, ServerError> {
+        let url = self.url(&format!("benchmarks/runs/{}", encode(run_id)));
+        let response = self.send(|| {
+            self.request(self.client.get(&url))
+                .header(ACCEPT, "application/json")
+        })?;
+        response.json().map_err(ServerError::Decode)
+    }
+
+    /// Retract a shared benchmark record.
+    pub(crate) fn delete_record(
+        &self,
+        access_token: &str,
+        record_id: &str,
+    ) -> Result<(), ServerError> {
+        let url = self.url(&format!("benchmarks/{}", encode(record_id)));
+        self.send(|| {
+            self.request(self.client.delete(&url))
+                .header(AUTHORIZATION, get_auth_header_value(access_token))
+        })
+        .map(|_| ())
+    }
+
+    /// Retract all the shared benchmark records of a run.
+    pub(crate) fn delete_run(&self, access_token: &str, run_id: &str) -> Result<(), ServerError> {
+        let url = self.url(&format!("benchmarks/runs/{}", encode(run_id)));
+        self.send(|| {
+            self.request(self.client.delete(&url))
+                .header(AUTHORIZATION, get_auth_header_value(access_token))
+        })
+        .map(|_| ())
+    }
+
+    /// URL of the webhook receiving the GitHub workflow events.
+    pub(crate) fn webhook_url(&self) -> String {
+        self.url("burn_bench/webhook/benchmark")
+    }
+
+    /// Send a GitHub workflow event to the webhook with its signature headers.
+    pub(crate) fn send_webhook(
+        &self,
+        url: &str,
+        payload: &[u8],
+        headers: &[(String, String)],
+    ) -> Result<(), ServerError> {
+        self.send(|| {
+            let mut request = self
+                .request(self.client.post(url))
+                .header(CONTENT_TYPE, "application/json");
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            request.body(payload.to_vec())
+        })
+        .map(|_| ())
+    }
+
+    /// Returns true if the server answers, whatever the status of the response.
+    pub(crate) fn is_reachable(&self) -> bool {
+        self.request(self.client.get(&self.base_url))
+            .timeout(Duration::from_secs(5))
+            .send()
+            .is_ok()
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!("{}{endpoint}", self.base_url)
+    }
+
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder.header(USER_AGENT, "burnbench")
+    }
+
+    /// Send the request built by `build`, retrying transient failures.
+    ///
+    /// The requests which are not idempotent, such as the uploads and the webhooks, may have been
+    /// processed when they time out or fail with a server error, they are only sent again when
+    /// the connection could not be established.
+    fn send(&self, build: impl Fn() -> RequestBuilder) -> Result<Response, ServerError> {
+        let mut attempt = 1;
+        loop {
+            let request = build().build().map_err(ServerError::Request)?;
+            let idempotent = request.method().is_idempotent();
+            let result = self.client.execute(request);
+            let retry = match &result {
+                Ok(response) => idempotent && response.status().is_server_error(),
+                Err(e) => e.is_connect() || (idempotent && e.is_timeout()),
+            };
+            if !retry || attempt == MAX_ATTEMPTS {
+                return into_result(result);
+            }
+            thread::sleep(RETRY_DELAY * attempt);
+            attempt += 1;
+        }
+    }
+}
+
+/// Encode an identifier to be used as a path segment.
+fn encode(id: &str) -> String {
+    utf8_percent_encode(id, ID_ENCODE_SET).to_string()
+}
+
+fn gzip(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+fn into_result(result: Result<Response, reqwest::Error>) -> Result<Response, ServerError> {
+    let response = result.map_err(ServerError::Request)?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        let body = response.text().unwrap_or_default();
+        Err(ServerError::Status { status, body })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let client = ServerClient::new("https://bench.example.com/v1/");
+        assert_eq!(
+            client.url("users/me"),
+            "https://bench.example.com/v1/users/me"
+        );
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let json = br#"{"manifest":{"recordCount":0,"runId":null},"records":[]}"#;
+        let compressed = gzip(json).unwrap();
+        let mut decompressed = vec![];
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, json);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            encode("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            "67e55044-10b1-426f-9247-bb680e5fe0c8"
+        );
+        assert_eq!(encode("../runs"), "%2E%2E%2Fruns");
+    }
+
+    #[test]
+    fn test_display_status_error() {
+        let error = ServerError::Status {
+            status: StatusCode::UNAUTHORIZED,
+            body: String::new(),
+        };
+        assert_eq!(error.to_string(), "error 401 Unauthorized");
+        let error = ServerError::Status {
+            status: StatusCode::BAD_REQUEST,
+            body: "missing field".to_string(),
+        };
+        assert_eq!(error.to_string(), "error 400 Bad Request (missing field)");
+    }
+}
@@ -1,12 +1,31 @@
 use serde::{Deserialize, Serialize};
+#[cfg(not(target_family = "wasm"))]
 use std::collections::HashSet;
+#[cfg(not(target_family = "wasm"))]
 use wgpu::{self};
 
+/// Environment variable passing the GPU clocks locked by the runner to the benchmarks.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) const LOCKED_CLOCKS_ENV: &str = "BURN_BENCH_LOCKED_CLOCKS";
+/// Environment variable passing the CPU cores or NUMA node the benchmarks are pinned to.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) const PINNING_ENV: &str = "BURN_BENCH_PINNING";
+
 #[derive(Default, Clone, Serialize, Deserialize)]
 pub struct BenchmarkSystemInfo {
     cpus: Vec<String>,
     gpus: Vec<String>,
     pub os: BenchmarkOSInfo,
+    /// GPU clocks locked during the run, if any.
+    #[serde(
+        rename = "lockedClocks",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub locked_clocks: Option<String>,
+    /// CPU cores or NUMA node the benchmarks were pinned to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinning: Option<String>,
 }
 
 #[derive(Default, Clone, Serialize, Deserialize)]
@@ -16,6 +35,7 @@ pub struct BenchmarkOSInfo {
     windows_linux_subsystem: bool,
 }
 
+#[cfg(not(target_family = "wasm"))]
 impl From<os_info::Info> for BenchmarkOSInfo {
     fn from(info: os_info::Info) -> Self {
         BenchmarkOSInfo {
@@ -26,14 +46,56 @@ impl From<os_info::Info> for BenchmarkOSInfo {
 }
 
 impl BenchmarkSystemInfo {
+    #[cfg(not(target_family = "wasm"))]
     pub fn new() -> Self {
         Self {
             cpus: BenchmarkSystemInfo::enumerate_cpus(),
             gpus: BenchmarkSystemInfo::enumerate_gpus(),
             os: BenchmarkOSInfo::from(os_info::get()),
+            locked_clocks: std::env::var(LOCKED_CLOCKS_ENV).ok(),
+            pinning: std::env::var(PINNING_ENV).ok(),
+        }
+    }
+
+    /// The browser doesn't expose the hardware, and the adapters can't be enumerated without
+    /// blocking, so the machine is identified by the user agent of the browser.
+    #[cfg(target_family = "wasm")]
+    pub fn new() -> Self {
+        Self {
+            cpus: vec![],
+            gpus: vec![],
+            os: BenchmarkOSInfo {
+                name: crate::browser::user_agent(),
+                windows_linux_subsystem: false,
+            },
+            locked_clocks: None,
+            pinning: None,
         }
     }
 
+    /// CPUs and GPUs of the machine, in a stable order.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) fn hardware(&self) -> String {
+        let mut cpus = self.cpus.clone();
+        cpus.sort();
+        let mut gpus = self.gpus.clone();
+        gpus.sort();
+        format!("{} | {}", cpus.join(", "), gpus.join(", "))
+    }
+
+    /// Short name of the machine in the reports: its GPUs, or its CPUs when it has none.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) fn host(&self) -> String {
+        let mut names = if self.gpus.is_empty() {
+            self.cpus.clone()
+        } else {
+            self.gpus.clone()
+        };
+        names.sort();
+        names.join(" + ")
+    }
+
+    #[cfg(not(target_family = "wasm"))]
     fn enumerate_cpus() -> Vec<String> {
         let system = sysinfo::System::new_with_specifics(
             sysinfo::RefreshKind::nothing().with_cpu(sysinfo::CpuRefreshKind::everything()),
@@ -46,6 +108,7 @@ impl BenchmarkSystemInfo {
         cpu_names.into_iter().collect()
     }
 
+    #[cfg(not(target_family = "wasm"))]
     fn enumerate_gpus() -> Vec<String> {
         let instance = wgpu::Instance::default();
         let adapters: Vec<wgpu::Adapter> =